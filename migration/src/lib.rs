@@ -3,10 +3,32 @@ pub use sea_orm_migration::prelude::*;
 pub struct Migrator;
 
 mod m20250811_014756_create_users_table;
+mod m20261018_000001_add_users_email_verified;
+mod m20261018_000002_create_identities_table;
+mod m20261018_000003_create_refresh_tokens_table;
+mod m20261018_000004_create_clients_table;
+mod m20261018_000005_add_clients_jwks;
+mod m20261018_000006_create_roles_tables;
+mod m20261018_000007_create_organizations_tables;
+mod m20261018_000008_add_users_profile;
+mod m20261018_000009_add_identities_idp_username;
+mod m20261018_000010_add_refresh_tokens_scope;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20250811_014756_create_users_table::Migration)]
+        vec![
+            Box::new(m20250811_014756_create_users_table::Migration),
+            Box::new(m20261018_000001_add_users_email_verified::Migration),
+            Box::new(m20261018_000002_create_identities_table::Migration),
+            Box::new(m20261018_000003_create_refresh_tokens_table::Migration),
+            Box::new(m20261018_000004_create_clients_table::Migration),
+            Box::new(m20261018_000005_add_clients_jwks::Migration),
+            Box::new(m20261018_000006_create_roles_tables::Migration),
+            Box::new(m20261018_000007_create_organizations_tables::Migration),
+            Box::new(m20261018_000008_add_users_profile::Migration),
+            Box::new(m20261018_000009_add_identities_idp_username::Migration),
+            Box::new(m20261018_000010_add_refresh_tokens_scope::Migration),
+        ]
     }
 }
@@ -0,0 +1,152 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Identities::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Identities::Id).uuid().primary_key())
+                    .col(ColumnDef::new(Identities::UserId).uuid().not_null())
+                    .col(ColumnDef::new(Identities::Idp).string().not_null())
+                    .col(ColumnDef::new(Identities::IdpUid).string().not_null())
+                    .col(
+                        ColumnDef::new(Identities::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_identities_user_id")
+                            .from(Identities::Table, Identities::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_identities_idp_uid_unique")
+                    .table(Identities::Table)
+                    .col(Identities::Idp)
+                    .col(Identities::IdpUid)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_identities_user_id")
+                    .table(Identities::Table)
+                    .col(Identities::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // every existing user keeps its single identity
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO identities (id, user_id, idp, idp_uid, created_at) \
+                 SELECT gen_random_uuid(), id, idp, idp_uid, created_at FROM users",
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_users_idp_uid_unique")
+                    .table(Users::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::Idp)
+                    .drop_column(Users::IdpUid)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(ColumnDef::new(Users::Idp).string())
+                    .add_column(ColumnDef::new(Users::IdpUid).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        // a user only keeps the identity it was created with, linked ones are dropped
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE users SET idp = first.idp, idp_uid = first.idp_uid \
+                 FROM (SELECT DISTINCT ON (user_id) user_id, idp, idp_uid FROM identities \
+                       ORDER BY user_id, created_at, id) AS first \
+                 WHERE users.id = first.user_id",
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .modify_column(ColumnDef::new(Users::Idp).string().not_null())
+                    .modify_column(ColumnDef::new(Users::IdpUid).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_users_idp_uid_unique")
+                    .table(Users::Table)
+                    .col(Users::Idp)
+                    .col(Users::IdpUid)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Identities::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+    Idp,
+    IdpUid,
+}
+
+#[derive(DeriveIden)]
+enum Identities {
+    Table,
+    Id,
+    UserId,
+    Idp,
+    IdpUid,
+    CreatedAt,
+}
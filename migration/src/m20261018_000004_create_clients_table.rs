@@ -0,0 +1,113 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Clients::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Clients::ClientId).string().primary_key())
+                    .col(ColumnDef::new(Clients::SecretHash).string())
+                    .col(
+                        ColumnDef::new(Clients::RedirectUris)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Clients::AllowedScopes)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Clients::AllowedGrantTypes)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Clients::AccessTokenTtl).big_integer())
+                    .col(ColumnDef::new(Clients::RefreshTokenTtl).big_integer())
+                    .col(ColumnDef::new(Clients::Audience).string().not_null())
+                    .col(
+                        ColumnDef::new(Clients::IsActive)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(Clients::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Clients::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // refresh tokens issued through a client stay bound to it
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RefreshTokens::Table)
+                    .add_column(ColumnDef::new(RefreshTokens::ClientId).string())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_refresh_tokens_client_id")
+                            .from_tbl(RefreshTokens::Table)
+                            .from_col(RefreshTokens::ClientId)
+                            .to_tbl(Clients::Table)
+                            .to_col(Clients::ClientId)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RefreshTokens::Table)
+                    .drop_foreign_key(Alias::new("fk_refresh_tokens_client_id"))
+                    .drop_column(RefreshTokens::ClientId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Clients::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Clients {
+    Table,
+    ClientId,
+    SecretHash,
+    RedirectUris,
+    AllowedScopes,
+    AllowedGrantTypes,
+    AccessTokenTtl,
+    RefreshTokenTtl,
+    Audience,
+    IsActive,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum RefreshTokens {
+    Table,
+    ClientId,
+}
@@ -1,2 +1,12 @@
 pub mod auth_session_repository;
+pub mod authorization_code_repository;
+pub mod client_assertion_repository;
+pub mod device_authorization_repository;
+pub mod link_ticket_repository;
+pub mod login_session_repository;
+pub mod oidc_client_repository;
+pub mod organization_repository;
+pub mod refresh_token_repository;
+pub mod revoked_token_repository;
+pub mod role_repository;
 pub mod sau_user_repository;
@@ -4,14 +4,21 @@ use crate::domain::oauth::auth_session::AuthSession;
 
 #[async_trait::async_trait]
 pub trait AuthSessionCacheRepo: Send + Sync {
+    // `ttl` in seconds, the lifetime of the auth session cookie
     async fn set_auth_session(
         &self,
         auth_session: &AuthSession,
+        ttl: u64,
     ) -> Result<(), AuthSessionCacheRepoError>;
     async fn get_auth_session(
         &self,
         session_id: Uuid,
     ) -> Result<AuthSession, AuthSessionCacheRepoError>;
+    // removes the session while reading it, only one caller ever gets it
+    async fn take_auth_session(
+        &self,
+        session_id: Uuid,
+    ) -> Result<AuthSession, AuthSessionCacheRepoError>;
 }
 
 #[derive(thiserror::Error, Debug)]
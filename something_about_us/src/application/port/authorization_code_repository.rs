@@ -0,0 +1,30 @@
+use crate::domain::oauth::authorization_code::AuthorizationCode;
+
+// codes are keyed by their hash and live for `ttl` seconds at most.
+#[async_trait::async_trait]
+pub trait AuthorizationCodeCacheRepo: Send + Sync {
+    async fn set_authorization_code(
+        &self,
+        code_hash: &str,
+        code: &AuthorizationCode,
+        ttl: u64,
+    ) -> Result<(), AuthorizationCodeCacheRepoError>;
+
+    // removes the code while reading it, only one caller can ever get it.
+    async fn take_authorization_code(
+        &self,
+        code_hash: &str,
+    ) -> Result<Option<AuthorizationCode>, AuthorizationCodeCacheRepoError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum AuthorizationCodeCacheRepoError {
+    #[error("cache server connection error : {0}")]
+    CacheConnectionError(String),
+
+    #[error("failed to set authorization code: {0}")]
+    SetAuthorizationCodeError(String),
+
+    #[error("invalid authorization code entry: {0}")]
+    InvalidEntry(String),
+}
@@ -0,0 +1,18 @@
+// `jti` values of the `private_key_jwt` assertions already used, kept for `ttl` seconds
+// which covers the remaining lifetime of the assertion.
+#[async_trait::async_trait]
+pub trait ClientAssertionCacheRepo: Send + Sync {
+    // false when the `jti` of the client was already used, only one caller can ever claim it.
+    async fn claim_assertion_jti(
+        &self,
+        client_id: &str,
+        jti: &str,
+        ttl: u64,
+    ) -> Result<bool, ClientAssertionCacheRepoError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ClientAssertionCacheRepoError {
+    #[error("cache server connection error : {0}")]
+    CacheConnectionError(String),
+}
@@ -0,0 +1,74 @@
+use crate::domain::oauth::device_authorization::DeviceAuthorization;
+
+// device authorizations are keyed by the hash of their device code,
+// the user code only points to that hash until a user approves the device.
+#[async_trait::async_trait]
+pub trait DeviceAuthorizationCacheRepo: Send + Sync {
+    async fn set_device_authorization(
+        &self,
+        device_code_hash: &str,
+        authorization: &DeviceAuthorization,
+        ttl: u64,
+    ) -> Result<(), DeviceAuthorizationCacheRepoError>;
+
+    async fn get_device_authorization(
+        &self,
+        device_code_hash: &str,
+    ) -> Result<Option<DeviceAuthorization>, DeviceAuthorizationCacheRepoError>;
+
+    // removes the authorization while reading it, only one poll can ever get the tokens.
+    async fn take_device_authorization(
+        &self,
+        device_code_hash: &str,
+    ) -> Result<Option<DeviceAuthorization>, DeviceAuthorizationCacheRepoError>;
+
+    async fn set_user_code(
+        &self,
+        user_code: &str,
+        device_code_hash: &str,
+        ttl: u64,
+    ) -> Result<(), DeviceAuthorizationCacheRepoError>;
+
+    async fn get_user_code(
+        &self,
+        user_code: &str,
+    ) -> Result<Option<String>, DeviceAuthorizationCacheRepoError>;
+
+    // removes the user code while reading it, only one login can approve the device.
+    async fn take_user_code(
+        &self,
+        user_code: &str,
+    ) -> Result<Option<String>, DeviceAuthorizationCacheRepoError>;
+
+    // wrong user codes entered by anyone within the last window, counted up to `limit`
+    async fn user_code_failures(
+        &self,
+        limit: u64,
+    ) -> Result<u64, DeviceAuthorizationCacheRepoError>;
+
+    // counts a wrong user code for `window` seconds, nothing more is counted past `limit`
+    async fn count_user_code_failure(
+        &self,
+        limit: u64,
+        window: u64,
+    ) -> Result<(), DeviceAuthorizationCacheRepoError>;
+
+    // `false` when the device already polled within the last `interval` seconds
+    async fn claim_device_poll(
+        &self,
+        device_code_hash: &str,
+        interval: u64,
+    ) -> Result<bool, DeviceAuthorizationCacheRepoError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeviceAuthorizationCacheRepoError {
+    #[error("cache server connection error : {0}")]
+    CacheConnectionError(String),
+
+    #[error("failed to set device authorization: {0}")]
+    SetDeviceAuthorizationError(String),
+
+    #[error("invalid device authorization entry: {0}")]
+    InvalidEntry(String),
+}
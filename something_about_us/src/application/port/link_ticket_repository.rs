@@ -0,0 +1,29 @@
+use crate::domain::oauth::link_ticket::LinkTicket;
+
+// tickets live for `ttl` seconds at most and are handed out once.
+#[async_trait::async_trait]
+pub trait LinkTicketCacheRepo: Send + Sync {
+    async fn set_link_ticket(
+        &self,
+        ticket: &LinkTicket,
+        ttl: u64,
+    ) -> Result<(), LinkTicketCacheRepoError>;
+
+    // removes the ticket while reading it, only one caller ever gets it
+    async fn take_link_ticket(
+        &self,
+        ticket: &str,
+    ) -> Result<Option<LinkTicket>, LinkTicketCacheRepoError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LinkTicketCacheRepoError {
+    #[error("cache server connection error : {0}")]
+    CacheConnectionError(String),
+
+    #[error("failed to set link ticket: {0}")]
+    SetLinkTicketError(String),
+
+    #[error("invalid link ticket entry: {0}")]
+    InvalidEntry(String),
+}
@@ -0,0 +1,35 @@
+use uuid::Uuid;
+
+use crate::domain::oauth::login_session::LoginSession;
+
+// sessions live for `ttl` seconds at most, a deleted session is gone for every instance.
+#[async_trait::async_trait]
+pub trait LoginSessionCacheRepo: Send + Sync {
+    async fn set_login_session(
+        &self,
+        session: &LoginSession,
+        ttl: u64,
+    ) -> Result<(), LoginSessionCacheRepoError>;
+
+    async fn get_login_session(
+        &self,
+        session_id: Uuid,
+    ) -> Result<Option<LoginSession>, LoginSessionCacheRepoError>;
+
+    async fn delete_login_session(
+        &self,
+        session_id: Uuid,
+    ) -> Result<(), LoginSessionCacheRepoError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LoginSessionCacheRepoError {
+    #[error("cache server connection error : {0}")]
+    CacheConnectionError(String),
+
+    #[error("failed to set login session: {0}")]
+    SetLoginSessionError(String),
+
+    #[error("invalid login session entry: {0}")]
+    InvalidEntry(String),
+}
@@ -0,0 +1,31 @@
+use crate::domain::oauth::oidc_client::OidcClient;
+
+#[async_trait::async_trait]
+pub trait OidcClientRepo: Send + Sync {
+    async fn get_client_by_id(
+        &self,
+        client_id: &str,
+    ) -> Result<Option<OidcClient>, OidcClientRepoError>;
+
+    async fn list_clients(&self) -> Result<Vec<OidcClient>, OidcClientRepoError>;
+
+    async fn create_client(&self, client: &OidcClient) -> Result<(), OidcClientRepoError>;
+
+    // replaces every mutable field of an existing client
+    async fn update_client(&self, client: &OidcClient) -> Result<(), OidcClientRepoError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum OidcClientRepoError {
+    #[error("database error : {0}")]
+    DatabaseError(String),
+
+    #[error("casting error : {0}")]
+    CastingError(String),
+
+    #[error("client already exists")]
+    ClientAlreadyExists,
+
+    #[error("client not found")]
+    ClientNotFound,
+}
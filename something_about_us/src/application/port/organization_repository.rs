@@ -0,0 +1,150 @@
+use uuid::Uuid;
+
+use crate::domain::organization::{
+    invitation::Invitation,
+    sau_organization::{MemberRole, Organization, OrganizationMember, Team, TeamMember},
+};
+
+#[async_trait::async_trait]
+pub trait OrganizationRepo: Send + Sync {
+    // creates the organization together with its first owner
+    async fn create_organization(
+        &self,
+        organization: &Organization,
+        owner: &OrganizationMember,
+    ) -> Result<(), OrganizationRepoError>;
+
+    async fn get_organization_by_id(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Option<Organization>, OrganizationRepoError>;
+
+    async fn get_organization_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<Organization>, OrganizationRepoError>;
+
+    // organizations the user is a member of, with its role in each
+    async fn list_organizations_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<(Organization, MemberRole)>, OrganizationRepoError>;
+
+    // `None` when the organization does not exist
+    async fn update_organization_name(
+        &self,
+        organization_id: Uuid,
+        name: &str,
+    ) -> Result<Option<Organization>, OrganizationRepoError>;
+
+    // members, teams and invitations go with it, returns false when it did not exist
+    async fn delete_organization(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<bool, OrganizationRepoError>;
+
+    async fn get_member(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<OrganizationMember>, OrganizationRepoError>;
+
+    async fn list_members(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<OrganizationMember>, OrganizationRepoError>;
+
+    // fails with `LastOwner` instead of leaving the organization without an owner,
+    // returns false when the user is not a member
+    async fn update_member_role(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+        role: MemberRole,
+    ) -> Result<bool, OrganizationRepoError>;
+
+    // the team memberships of the user in the organization go with it, fails with `LastOwner`
+    // like `update_member_role`, returns false when the user is not a member
+    async fn remove_member(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, OrganizationRepoError>;
+
+    async fn create_team(&self, team: &Team) -> Result<(), OrganizationRepoError>;
+
+    async fn list_teams(&self, organization_id: Uuid) -> Result<Vec<Team>, OrganizationRepoError>;
+
+    async fn get_team_by_slug(
+        &self,
+        organization_id: Uuid,
+        slug: &str,
+    ) -> Result<Option<Team>, OrganizationRepoError>;
+
+    // returns false when the team did not exist
+    async fn delete_team(&self, team_id: Uuid) -> Result<bool, OrganizationRepoError>;
+
+    async fn list_team_members(
+        &self,
+        team_id: Uuid,
+    ) -> Result<Vec<TeamMember>, OrganizationRepoError>;
+
+    // adding a user already in the team is a no-op
+    async fn add_team_member(&self, member: &TeamMember) -> Result<(), OrganizationRepoError>;
+
+    // returns false when the user was not in the team
+    async fn remove_team_member(
+        &self,
+        team_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, OrganizationRepoError>;
+
+    async fn create_invitation(&self, invitation: &Invitation)
+        -> Result<(), OrganizationRepoError>;
+
+    async fn list_invitations(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<Invitation>, OrganizationRepoError>;
+
+    async fn get_invitation_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Invitation>, OrganizationRepoError>;
+
+    // deletes the invitation and adds the member at once, false when the invitation was
+    // already used. a user who is a member already keeps its role.
+    async fn accept_invitation(
+        &self,
+        invitation_id: Uuid,
+        member: &OrganizationMember,
+    ) -> Result<bool, OrganizationRepoError>;
+
+    // returns false when the invitation did not exist
+    async fn delete_invitation(
+        &self,
+        organization_id: Uuid,
+        invitation_id: Uuid,
+    ) -> Result<bool, OrganizationRepoError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum OrganizationRepoError {
+    #[error("database error : {0}")]
+    DatabaseError(String),
+
+    #[error("casting error : {0}")]
+    CastingError(String),
+
+    #[error("organization slug is already taken")]
+    SlugTaken,
+
+    #[error("team slug is already taken in the organization")]
+    TeamSlugTaken,
+
+    #[error("an organization needs at least one owner")]
+    LastOwner,
+
+    #[error("user not found")]
+    UserNotFound,
+}
@@ -0,0 +1,37 @@
+use uuid::Uuid;
+
+use crate::domain::oauth::refresh_token::RefreshToken;
+
+#[async_trait::async_trait]
+pub trait RefreshTokenRepo: Send + Sync {
+    async fn save_refresh_token(&self, token: &RefreshToken) -> Result<(), RefreshTokenRepoError>;
+
+    async fn get_refresh_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshToken>, RefreshTokenRepoError>;
+
+    // marks `used_id` as used and stores `successor` atomically.
+    // returns false without storing anything when `used_id` was already used or revoked.
+    async fn rotate_refresh_token(
+        &self,
+        used_id: Uuid,
+        successor: &RefreshToken,
+    ) -> Result<bool, RefreshTokenRepoError>;
+
+    async fn revoke_refresh_token_family(
+        &self,
+        family_id: Uuid,
+    ) -> Result<(), RefreshTokenRepoError>;
+
+    async fn revoke_refresh_tokens_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<(), RefreshTokenRepoError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RefreshTokenRepoError {
+    #[error("database error : {0}")]
+    DatabaseError(String),
+}
@@ -0,0 +1,35 @@
+use uuid::Uuid;
+
+// entries only have to outlive the access tokens they revoke, so `ttl` is in seconds
+// and stale entries simply expire from the cache.
+#[async_trait::async_trait]
+pub trait RevokedTokenCacheRepo: Send + Sync {
+    async fn set_revoked_jti(&self, jti: Uuid, ttl: u64) -> Result<(), RevokedTokenCacheRepoError>;
+
+    async fn is_revoked_jti(&self, jti: Uuid) -> Result<bool, RevokedTokenCacheRepoError>;
+
+    // every token of `user_id` issued at or before `revoked_before` (unix milliseconds) is revoked.
+    async fn set_user_revoked_before(
+        &self,
+        user_id: Uuid,
+        revoked_before: i64,
+        ttl: u64,
+    ) -> Result<(), RevokedTokenCacheRepoError>;
+
+    async fn get_user_revoked_before(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<i64>, RevokedTokenCacheRepoError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RevokedTokenCacheRepoError {
+    #[error("cache server connection error : {0}")]
+    CacheConnectionError(String),
+
+    #[error("failed to set revoked token: {0}")]
+    SetRevokedTokenError(String),
+
+    #[error("invalid revoked token entry: {0}")]
+    InvalidEntry(String),
+}
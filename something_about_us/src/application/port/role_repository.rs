@@ -0,0 +1,42 @@
+use uuid::Uuid;
+
+use crate::domain::user::role::Role;
+
+#[async_trait::async_trait]
+pub trait RoleRepo: Send + Sync {
+    async fn list_roles(&self) -> Result<Vec<Role>, RoleRepoError>;
+
+    async fn get_role_by_name(&self, name: &str) -> Result<Option<Role>, RoleRepoError>;
+
+    // creates the role together with its permissions
+    async fn create_role(&self, role: &Role) -> Result<(), RoleRepoError>;
+
+    // replaces the description and every permission of an existing role
+    async fn update_role(&self, role: &Role) -> Result<(), RoleRepoError>;
+
+    // assignments of the role are removed with it, returns false when it did not exist
+    async fn delete_role(&self, role_id: Uuid) -> Result<bool, RoleRepoError>;
+
+    // assigning a role the user already has is a no-op
+    async fn assign_role(&self, user_id: Uuid, role_id: Uuid) -> Result<(), RoleRepoError>;
+
+    // returns false when the role was not assigned
+    async fn unassign_role(&self, user_id: Uuid, role_id: Uuid) -> Result<bool, RoleRepoError>;
+
+    async fn get_roles_by_user_id(&self, user_id: Uuid) -> Result<Vec<Role>, RoleRepoError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RoleRepoError {
+    #[error("database error : {0}")]
+    DatabaseError(String),
+
+    #[error("role already exists")]
+    RoleAlreadyExists,
+
+    #[error("role not found")]
+    RoleNotFound,
+
+    #[error("user not found")]
+    UserNotFound,
+}
@@ -1,18 +1,105 @@
-use crate::domain::{idp::supported_idp::SupportIdp, user::sau_user::SAUUser};
+use uuid::Uuid;
+
+use crate::domain::{
+    idp::supported_idp::SupportIdp,
+    user::{
+        sau_user::{Email, SAUUser, Username},
+        user_filter::UserFilter,
+        user_identity::UserIdentity,
+        user_profile::UserProfile,
+    },
+};
 
 #[async_trait::async_trait]
 pub trait SAUUserRepo: Send + Sync {
+    async fn get_user_by_id(&self, user_id: Uuid) -> Result<Option<SAUUser>, SAUUserRepoError>;
+
+    // resolves any identity linked to the user
     async fn get_user_by_idp_and_idp_id(
         &self,
         idp: &SupportIdp,
-        idp_id: &String,
+        idp_id: &str,
     ) -> Result<Option<SAUUser>, SAUUserRepoError>;
 
+    // creates the user together with its first identity, seeded with the idp profile.
+    // fails with `UsernameTaken` / `EmailTaken` when another user holds one of them
     async fn create_user_by_idp_and_idp_id(
         &self,
         idp: &SupportIdp,
-        idp_id: &String,
+        idp_id: &str,
+        idp_username: Option<&str>,
+        profile: &UserProfile,
     ) -> Result<SAUUser, SAUUserRepoError>;
+
+    // records the idp login asserted at a sign-in on the identity
+    async fn update_identity_username(
+        &self,
+        idp: &SupportIdp,
+        idp_id: &str,
+        idp_username: Option<&str>,
+    ) -> Result<(), SAUUserRepoError>;
+
+    // only the given fields are written, a new email is stored unverified.
+    // `None` when the user does not exist
+    async fn update_user_profile(
+        &self,
+        user_id: Uuid,
+        username: Option<&Username>,
+        email: Option<&Email>,
+    ) -> Result<Option<SAUUser>, SAUUserRepoError>;
+
+    // writes the fields of `profile` that are set, `email_verified` goes with the email.
+    // `None` when the user does not exist
+    async fn update_user_idp_profile(
+        &self,
+        user_id: Uuid,
+        profile: &UserProfile,
+    ) -> Result<Option<SAUUser>, SAUUserRepoError>;
+
+    // up to `limit` users matching `filter` with an id greater than `after`, ordered by id
+    async fn list_users(
+        &self,
+        filter: &UserFilter,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<Vec<SAUUser>, SAUUserRepoError>;
+
+    // `None` when the user does not exist
+    async fn set_user_active(
+        &self,
+        user_id: Uuid,
+        is_active: bool,
+    ) -> Result<Option<SAUUser>, SAUUserRepoError>;
+
+    // identities, refresh tokens, roles and memberships go with it, fails with
+    // `LastOrganizationOwner` instead of leaving an organization without an owner.
+    // returns false when the user did not exist
+    async fn delete_user(&self, user_id: Uuid) -> Result<bool, SAUUserRepoError>;
+
+    async fn exists_user_by_email(&self, email: &Email) -> Result<bool, SAUUserRepoError>;
+
+    async fn exists_user_by_username(&self, username: &Username) -> Result<bool, SAUUserRepoError>;
+
+    async fn get_identities_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<UserIdentity>, SAUUserRepoError>;
+
+    // linking an identity the user already owns returns it unchanged
+    async fn link_identity(
+        &self,
+        user_id: Uuid,
+        idp: &SupportIdp,
+        idp_id: &str,
+        idp_username: Option<&str>,
+    ) -> Result<UserIdentity, SAUUserRepoError>;
+
+    // the last identity of a user can not be unlinked
+    async fn unlink_identity(
+        &self,
+        user_id: Uuid,
+        identity_id: Uuid,
+    ) -> Result<(), SAUUserRepoError>;
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -22,4 +109,22 @@ pub enum SAUUserRepoError {
 
     #[error("casting error : {0}")]
     CastingError(String),
+
+    #[error("username is already taken")]
+    UsernameTaken,
+
+    #[error("email is already used by another user")]
+    EmailTaken,
+
+    #[error("identity is already linked to another user")]
+    IdentityAlreadyLinked,
+
+    #[error("identity not found")]
+    IdentityNotFound,
+
+    #[error("last identity of a user can not be unlinked")]
+    LastIdentity,
+
+    #[error("user is the last owner of an organization")]
+    LastOrganizationOwner,
 }
@@ -1,3 +1,12 @@
 pub mod jwt_service;
+pub mod login_session_service;
 pub mod oauth_service;
+pub mod oidc_client_service;
+pub mod oidc_service;
+pub mod organization_service;
+pub mod refresh_token_service;
+pub mod role_service;
+#[cfg(test)]
+pub mod token_repo_test;
+pub mod token_revocation_service;
 pub mod user_service;
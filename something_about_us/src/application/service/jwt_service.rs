@@ -1,36 +1,146 @@
-use std::{ops::Deref, sync::Arc};
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
 use jsonwebtoken::jwk::JwkSet;
 use uuid::Uuid;
 
-use crate::domain::oauth::{sau_jwt::SAUJwt, sau_jwt_issuer::JwtIssue};
+use crate::domain::{
+    oauth::{
+        id_token::IdToken,
+        sau_jwt::{SAUClaims, SAUJwt, ServiceClaims},
+        sau_jwt_issuer::JwtIssue,
+    },
+    user::role::UserGrants,
+};
 
+// the key set can be replaced at runtime by key rotation,
+// clones of the service share it.
 #[derive(Clone)]
 pub struct JwtService<I: JwtIssue> {
-    jwks: Arc<JwkSet>,
+    keys: Arc<RwLock<Arc<SigningKeys<I>>>>,
+}
+
+struct SigningKeys<I: JwtIssue> {
+    jwks: JwkSet,
     current_kid: Uuid,
     jwt_issuer: Arc<I>,
 }
 
+impl<I: JwtIssue> SigningKeys<I> {
+    fn new(jwt_issuer: Arc<I>, kid: Uuid) -> Self {
+        Self {
+            jwks: jwt_issuer.create_jwks(),
+            current_kid: kid,
+            jwt_issuer,
+        }
+    }
+}
+
 impl<I: JwtIssue> JwtService<I> {
     pub fn new(jwt_issuer: Arc<I>, kid: Uuid) -> Self {
-        let jwks = Arc::new(jwt_issuer.create_jwks());
         Self {
-            jwt_issuer,
-            jwks,
-            current_kid: kid,
+            keys: Arc::new(RwLock::new(Arc::new(SigningKeys::new(jwt_issuer, kid)))),
         }
     }
 
+    // publishes the keys of `jwt_issuer` and signs with `kid` from now on
+    pub fn replace_keys(&self, jwt_issuer: Arc<I>, kid: Uuid) {
+        let keys = Arc::new(SigningKeys::new(jwt_issuer, kid));
+        *self.keys.write().unwrap_or_else(|e| e.into_inner()) = keys;
+    }
+
+    fn keys(&self) -> Arc<SigningKeys<I>> {
+        self.keys.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn current_kid(&self) -> Uuid {
+        self.keys().current_kid
+    }
+
     pub fn get_jwks(&self) -> JwkSet {
-        self.jwks.clone().deref().clone()
+        self.keys().jwks.clone()
     }
 
-    pub fn issue_with_id(&self, uid: &Uuid) -> Result<SAUJwt, JwtIssuerServiceError> {
-        Ok(self
+    pub fn issue_with_id(
+        &self,
+        uid: &Uuid,
+        grants: &UserGrants,
+    ) -> Result<SAUJwt, JwtIssuerServiceError> {
+        let keys = self.keys();
+        keys.jwt_issuer
+            .issue_with_id(&keys.current_kid, uid, grants)
+            .map_err(|e| JwtIssuerServiceError::JwtIssueError(e.to_string()))
+    }
+
+    // `ttl` in seconds, never longer than `jwt.access_token_ttl`
+    // since retiring keys are only published for that long.
+    pub fn issue_for_audience(
+        &self,
+        uid: &Uuid,
+        aud: &str,
+        ttl: u64,
+        grants: &UserGrants,
+    ) -> Result<SAUJwt, JwtIssuerServiceError> {
+        let keys = self.keys();
+        keys.jwt_issuer
+            .issue_for_audience(
+                &keys.current_kid,
+                uid,
+                aud,
+                Duration::from_secs(ttl),
+                grants,
+            )
+            .map_err(|e| JwtIssuerServiceError::JwtIssueError(e.to_string()))
+    }
+
+    // `ttl` in seconds, `jwt.client_credentials_ttl`
+    pub fn issue_service_token(
+        &self,
+        client_id: &str,
+        aud: &str,
+        scope: &str,
+        ttl: u64,
+    ) -> Result<SAUJwt, JwtIssuerServiceError> {
+        let keys = self.keys();
+        keys.jwt_issuer
+            .issue_service_token(
+                &keys.current_kid,
+                client_id,
+                aud,
+                scope,
+                Duration::from_secs(ttl),
+            )
+            .map_err(|e| JwtIssuerServiceError::JwtIssueError(e.to_string()))
+    }
+
+    pub fn issue_id_token(&self, id_token: &IdToken) -> Result<SAUJwt, JwtIssuerServiceError> {
+        let keys = self.keys();
+        keys.jwt_issuer
+            .issue_id_token(&keys.current_kid, id_token)
+            .map_err(|e| JwtIssuerServiceError::JwtIssueError(e.to_string()))
+    }
+
+    pub fn verify(&self, jwt: &str) -> Result<SAUClaims, JwtIssuerServiceError> {
+        self.keys()
             .jwt_issuer
-            .issue_with_id(&self.current_kid, uid)
-            .map_err(|e| JwtIssuerServiceError::JwtIssueError(e.to_string()))?)
+            .verify(jwt)
+            .map_err(|e| JwtIssuerServiceError::InvalidJwt(e.to_string()))
+    }
+
+    pub fn verify_any_audience(&self, jwt: &str) -> Result<SAUClaims, JwtIssuerServiceError> {
+        self.keys()
+            .jwt_issuer
+            .verify_any_audience(jwt)
+            .map_err(|e| JwtIssuerServiceError::InvalidJwt(e.to_string()))
+    }
+
+    pub fn verify_service_token(&self, jwt: &str) -> Result<ServiceClaims, JwtIssuerServiceError> {
+        self.keys()
+            .jwt_issuer
+            .verify_service_token(jwt)
+            .map_err(|e| JwtIssuerServiceError::InvalidJwt(e.to_string()))
     }
 }
 
@@ -38,4 +148,12 @@ impl<I: JwtIssue> JwtService<I> {
 pub enum JwtIssuerServiceError {
     #[error("jwt issue error : {0}")]
     JwtIssueError(String),
+
+    #[error("invalid jwt : {0}")]
+    InvalidJwt(String),
+}
+
+#[cfg(test)]
+mod tests {
+    include!("jwt_service_test.rs");
 }
@@ -0,0 +1,114 @@
+use super::JwtService;
+use crate::domain::{
+    oauth::sau_jwt_issuer::{KeyPair, PublicKeyParameters, SAUJwtIssuer},
+    user::role::UserGrants,
+};
+use jsonwebtoken::{DecodingKey, EncodingKey};
+use std::{collections::HashMap, sync::Arc};
+use uuid::Uuid;
+
+fn generate_key_pair() -> KeyPair {
+    use ring::signature::KeyPair as _;
+    let pkcs8 =
+        ring::signature::Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+    let key_pair = ring::signature::Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    KeyPair {
+        private_key: EncodingKey::from_ed_der(pkcs8.as_ref()),
+        public_key: DecodingKey::from_ed_der(key_pair.public_key().as_ref()),
+        parameters: PublicKeyParameters::Ed25519 { x: String::new() },
+    }
+}
+
+fn issuer(kids: &[Uuid]) -> Arc<SAUJwtIssuer> {
+    let key_pairs = kids
+        .iter()
+        .map(|kid| (*kid, generate_key_pair()))
+        .collect::<HashMap<_, _>>();
+    Arc::new(SAUJwtIssuer::new(
+        "test-issuer".to_string(),
+        "test-audience".to_string(),
+        3600,
+        key_pairs,
+    ))
+}
+
+fn kid_of(jwt: &str) -> String {
+    jsonwebtoken::decode_header(jwt).unwrap().kid.unwrap()
+}
+
+#[test]
+fn test_replace_keys_switches_signing_key() {
+    let first = Uuid::now_v7();
+    let second = Uuid::now_v7();
+    let jwt_service = JwtService::new(issuer(&[first]), first);
+    assert_eq!(
+        kid_of(
+            &jwt_service
+                .issue_with_id(&Uuid::now_v7(), &UserGrants::default())
+                .unwrap()
+        ),
+        first.to_string()
+    );
+
+    jwt_service.replace_keys(issuer(&[second]), second);
+    assert_eq!(jwt_service.current_kid(), second);
+    assert_eq!(
+        kid_of(
+            &jwt_service
+                .issue_with_id(&Uuid::now_v7(), &UserGrants::default())
+                .unwrap()
+        ),
+        second.to_string()
+    );
+}
+
+#[test]
+fn test_replace_keys_is_shared_by_clones() {
+    let first = Uuid::now_v7();
+    let second = Uuid::now_v7();
+    let jwt_service = JwtService::new(issuer(&[first]), first);
+    let cloned = jwt_service.clone();
+
+    jwt_service.replace_keys(issuer(&[first, second]), second);
+    assert_eq!(cloned.current_kid(), second);
+    assert_eq!(cloned.get_jwks().keys.len(), 2);
+}
+
+#[test]
+fn test_client_audience_is_verified_only_by_any_audience() {
+    let kid = Uuid::now_v7();
+    let jwt_service = JwtService::new(issuer(&[kid]), kid);
+    let uid = Uuid::now_v7();
+    let jwt = jwt_service
+        .issue_for_audience(&uid, "orders-api", 60, &UserGrants::default())
+        .unwrap();
+
+    assert!(jwt_service.verify(&jwt).is_err());
+    let claims = jwt_service.verify_any_audience(&jwt).unwrap();
+    assert_eq!(claims.sub, uid);
+    assert_eq!(claims.aud, "orders-api");
+    assert_eq!(claims.exp - claims.iat, 60);
+}
+
+#[test]
+fn test_service_token_is_not_a_user_token() {
+    let kid = Uuid::now_v7();
+    let jwt_service = JwtService::new(issuer(&[kid]), kid);
+    // a client id shaped like a user id must still not pass as that user
+    let client_id = Uuid::now_v7().to_string();
+    let jwt = jwt_service
+        .issue_service_token(&client_id, "test-audience", "orders:read", 60)
+        .unwrap();
+
+    assert!(jwt_service.verify(&jwt).is_err());
+    assert!(jwt_service.verify_any_audience(&jwt).is_err());
+    let claims = jwt_service.verify_service_token(&jwt).unwrap();
+    assert_eq!(claims.sub, client_id);
+    assert_eq!(claims.scope, "orders:read");
+    assert_eq!(claims.exp - claims.iat, 60);
+
+    let user_jwt = jwt_service
+        .issue_with_id(&Uuid::now_v7(), &UserGrants::default())
+        .unwrap();
+    assert!(jwt_service.verify_service_token(&user_jwt).is_err());
+}
@@ -0,0 +1,95 @@
+use uuid::Uuid;
+
+use crate::{
+    application::port::login_session_repository::LoginSessionCacheRepo,
+    domain::oauth::login_session::LoginSession,
+};
+
+#[derive(Clone)]
+pub struct LoginSessionService<C: LoginSessionCacheRepo> {
+    cache_repo: C,
+    enabled: bool,
+    ttl: u64,
+    token_ttl: u64,
+    token_audience: String,
+}
+
+impl<C: LoginSessionCacheRepo> LoginSessionService<C> {
+    // `ttl` and `token_ttl` in seconds, tokens are issued for `token_audience`
+    pub fn new(
+        cache_repo: C,
+        enabled: bool,
+        ttl: u64,
+        token_ttl: u64,
+        token_audience: String,
+    ) -> Self {
+        Self {
+            cache_repo,
+            enabled,
+            ttl,
+            token_ttl,
+            token_audience,
+        }
+    }
+
+    // whether browser logins returning to a frontend get a session instead of tokens
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn ttl(&self) -> u64 {
+        self.ttl
+    }
+
+    pub fn token_ttl(&self) -> u64 {
+        self.token_ttl
+    }
+
+    pub fn token_audience(&self) -> &str {
+        &self.token_audience
+    }
+
+    pub async fn create(&self, user_id: Uuid) -> Result<LoginSession, LoginSessionServiceError> {
+        let session = LoginSession::new(user_id, self.ttl)
+            .map_err(|e| LoginSessionServiceError::Issue(e.to_string()))?;
+        self.cache_repo
+            .set_login_session(&session, self.ttl)
+            .await
+            .map_err(|e| LoginSessionServiceError::Cache(e.to_string()))?;
+        Ok(session)
+    }
+
+    // `None` when the session is unknown or expired
+    pub async fn get(
+        &self,
+        session_id: Uuid,
+    ) -> Result<Option<LoginSession>, LoginSessionServiceError> {
+        let session = self
+            .cache_repo
+            .get_login_session(session_id)
+            .await
+            .map_err(|e| LoginSessionServiceError::Cache(e.to_string()))?;
+        Ok(session.filter(|session| session.remaining_ttl().is_some()))
+    }
+
+    pub async fn delete(&self, session_id: Uuid) -> Result<(), LoginSessionServiceError> {
+        self.cache_repo
+            .delete_login_session(session_id)
+            .await
+            .map_err(|e| LoginSessionServiceError::Cache(e.to_string()))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LoginSessionServiceError {
+    #[error("login session issue error : {0}")]
+    Issue(String),
+
+    #[error("login session cache error : {0}")]
+    Cache(String),
+}
+
+#[cfg(test)]
+mod tests {
+    include!("login_session_service_test.rs");
+}
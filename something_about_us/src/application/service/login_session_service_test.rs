@@ -0,0 +1,78 @@
+use super::LoginSessionService;
+use crate::{
+    application::port::login_session_repository::{
+        LoginSessionCacheRepo, LoginSessionCacheRepoError,
+    },
+    domain::oauth::login_session::LoginSession,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use uuid::Uuid;
+
+// ttl is ignored, entries never expire during a test
+#[derive(Clone, Default)]
+struct MemoryLoginSessionRepo {
+    sessions: Arc<Mutex<HashMap<Uuid, LoginSession>>>,
+}
+
+#[async_trait::async_trait]
+impl LoginSessionCacheRepo for MemoryLoginSessionRepo {
+    async fn set_login_session(
+        &self,
+        session: &LoginSession,
+        _ttl: u64,
+    ) -> Result<(), LoginSessionCacheRepoError> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session.id, session.clone());
+        Ok(())
+    }
+
+    async fn get_login_session(
+        &self,
+        session_id: Uuid,
+    ) -> Result<Option<LoginSession>, LoginSessionCacheRepoError> {
+        Ok(self.sessions.lock().unwrap().get(&session_id).cloned())
+    }
+
+    async fn delete_login_session(
+        &self,
+        session_id: Uuid,
+    ) -> Result<(), LoginSessionCacheRepoError> {
+        self.sessions.lock().unwrap().remove(&session_id);
+        Ok(())
+    }
+}
+
+fn service(repo: MemoryLoginSessionRepo) -> LoginSessionService<MemoryLoginSessionRepo> {
+    LoginSessionService::new(repo, true, 3600, 300, "sau".to_string())
+}
+
+#[tokio::test]
+async fn test_create_get_and_delete() {
+    let service = service(MemoryLoginSessionRepo::default());
+    let user_id = Uuid::now_v7();
+
+    let session = service.create(user_id).await.unwrap();
+    let found = service.get(session.id).await.unwrap().unwrap();
+    assert_eq!(found, session);
+    assert_eq!(found.user_id, user_id);
+
+    service.delete(session.id).await.unwrap();
+    assert!(service.get(session.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_expired_session_is_not_found() {
+    let repo = MemoryLoginSessionRepo::default();
+    let service = service(repo.clone());
+
+    let mut session = LoginSession::new(Uuid::now_v7(), 3600).unwrap();
+    session.expires_at = chrono::Utc::now().timestamp() - 1;
+    repo.set_login_session(&session, 3600).await.unwrap();
+
+    assert!(service.get(session.id).await.unwrap().is_none());
+}
@@ -5,34 +5,83 @@ use crate::{
     domain::{
         idp::supported_idp::SupportIdp,
         oauth::{
-            auth_session::AuthSession, error::SAUOAuthDomainError, oauth_provider::OAuthRequest,
-            sau_jwt::OAuthAccessToken,
+            auth_session::AuthSession,
+            error::SAUOAuthDomainError,
+            oauth_provider::{IdpIdentity, OAuthRequest},
+        },
+    },
+    infrastructure::{
+        config::types::{ProviderConfig, ProviderKindConfig},
+        provider::{
+            github::GithubOAuthClient, gitlab::GitlabOAuthClient, google::GoogleOAuthClient,
+            microsoft::MicrosoftOAuthClient, oidc::OidcOAuthClient,
         },
     },
-    infrastructure::{config::types::Config, provider::github::GithubOAuthClient},
 };
 
+// provider listed in `[[providers]]`, in config order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredIdp {
+    pub idp: SupportIdp,
+    pub kind: &'static str,
+}
+
 #[derive(Clone)]
 pub struct OAuthService {
     oauth_client: Arc<HashMap<SupportIdp, Box<dyn OAuthRequest>>>,
+    registered_idps: Arc<Vec<RegisteredIdp>>,
 }
 
 impl OAuthService {
-    pub fn new(cfg: &Config) -> Self {
-        let mut clients = HashMap::with_capacity(1);
-        let github_client = Box::new(GithubOAuthClient::from(&cfg.oidc.github));
-        clients.insert(SupportIdp::Github, github_client as _);
-        Self {
-            oauth_client: Arc::new(clients),
+    pub async fn new(providers: &[ProviderConfig]) -> Result<Self, OAuthServiceError> {
+        let mut clients = HashMap::with_capacity(providers.len());
+        let mut registered_idps = Vec::with_capacity(providers.len());
+
+        for provider in providers {
+            let idp = SupportIdp::try_from(provider.name.as_str())
+                .map_err(|e| OAuthServiceError::ProviderInit(e.to_string()))?;
+            let name = idp.as_str();
+
+            let oauth_client: Box<dyn OAuthRequest> = match &provider.kind {
+                ProviderKindConfig::Github(config) => Box::new(GithubOAuthClient::from(config)),
+                ProviderKindConfig::Gitlab(config) => Box::new(GitlabOAuthClient::from(config)),
+                ProviderKindConfig::Google(config) => {
+                    Box::new(GoogleOAuthClient::discover(name, config).await?)
+                }
+                ProviderKindConfig::Microsoft(config) => {
+                    Box::new(MicrosoftOAuthClient::discover(name, config).await?)
+                }
+                ProviderKindConfig::Oidc(config) => {
+                    Box::new(OidcOAuthClient::discover(name, config).await?)
+                }
+            };
+
+            if clients.insert(idp.clone(), oauth_client).is_some() {
+                return Err(OAuthServiceError::ProviderInit(format!(
+                    "duplicated provider name {}",
+                    idp.as_str()
+                )));
+            }
+            registered_idps.push(RegisteredIdp {
+                idp,
+                kind: provider.kind.as_str(),
+            });
         }
+
+        Ok(Self {
+            oauth_client: Arc::new(clients),
+            registered_idps: Arc::new(registered_idps),
+        })
     }
 
-    fn get_oauth_client(
-        &self,
-        idp: SupportIdp,
-    ) -> Result<&Box<dyn OAuthRequest>, OAuthServiceError> {
+    pub fn registered_idps(&self) -> &[RegisteredIdp] {
+        self.registered_idps.as_slice()
+    }
+
+    fn get_oauth_client(&self, idp: SupportIdp) -> Result<&dyn OAuthRequest, OAuthServiceError> {
         self.oauth_client
             .get(&idp)
+            .map(|client| client.as_ref())
             .ok_or(OAuthServiceError::NotSupportedIdp)
     }
 
@@ -41,33 +90,18 @@ impl OAuthService {
         idp: SupportIdp,
     ) -> Result<(Url, AuthSession), OAuthServiceError> {
         let oauth_client = self.get_oauth_client(idp)?;
-        Ok(oauth_client
-            .login()
-            .await
-            .map_err(OAuthServiceError::from)?)
+        oauth_client.login().await.map_err(OAuthServiceError::from)
     }
 
-    pub async fn callback_call(
+    pub async fn authenticate_call(
         &self,
         idp: SupportIdp,
         code: String,
-        pkce_verifier: String,
-    ) -> Result<OAuthAccessToken, OAuthServiceError> {
-        let oauth_client = self.get_oauth_client(idp)?;
-        Ok(oauth_client
-            .callback(code, pkce_verifier)
-            .await
-            .map_err(OAuthServiceError::from)?)
-    }
-
-    pub async fn get_user_id_call(
-        &self,
-        idp: SupportIdp,
-        access_token: String,
-    ) -> Result<String, OAuthServiceError> {
+        auth_session: &AuthSession,
+    ) -> Result<IdpIdentity, OAuthServiceError> {
         let oauth_client = self.get_oauth_client(idp)?;
-        let user_id = oauth_client.get_user_id(access_token).await?;
-        Ok(user_id)
+        let identity = oauth_client.authenticate(code, auth_session).await?;
+        Ok(identity)
     }
 }
 
@@ -76,6 +110,9 @@ pub enum OAuthServiceError {
     #[error("not supported oauth provider")]
     NotSupportedIdp,
 
+    #[error("oauth provider init fail : {0}")]
+    ProviderInit(String),
+
     #[error("oauth login fail : {0}")]
     OAuthLoginFail(#[from] SAUOAuthDomainError),
 }
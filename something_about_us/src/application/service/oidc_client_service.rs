@@ -0,0 +1,124 @@
+use crate::{
+    application::port::oidc_client_repository::{OidcClientRepo, OidcClientRepoError},
+    domain::oauth::oidc_client::{ClientRegistration, OidcClient, OpaqueClientSecret},
+};
+
+// registry of the clients allowed to use SAU as their OpenID provider,
+// managed through the admin endpoints.
+#[derive(Clone)]
+pub struct OidcClientService<R: OidcClientRepo> {
+    client_repo: R,
+    // `jwt.access_token_ttl`, a retiring key is only published that long after its last token
+    max_access_token_ttl: u64,
+}
+
+impl<R: OidcClientRepo> OidcClientService<R> {
+    pub fn new(client_repo: R, max_access_token_ttl: u64) -> Self {
+        Self {
+            client_repo,
+            max_access_token_ttl,
+        }
+    }
+
+    // the secret of a confidential client is only returned here and on rotation
+    pub async fn register(
+        &self,
+        registration: ClientRegistration,
+    ) -> Result<(OidcClient, Option<OpaqueClientSecret>), OidcClientServiceError> {
+        if registration
+            .access_token_ttl
+            .is_some_and(|ttl| ttl == 0 || ttl > self.max_access_token_ttl)
+        {
+            return Err(OidcClientServiceError::InvalidClient(format!(
+                "access_token_ttl must be between 1 and {} seconds",
+                self.max_access_token_ttl
+            )));
+        }
+        if registration.refresh_token_ttl == Some(0) {
+            return Err(OidcClientServiceError::InvalidClient(
+                "refresh_token_ttl must be positive".to_string(),
+            ));
+        }
+
+        let (client, secret) = OidcClient::register(registration)
+            .map_err(|e| OidcClientServiceError::InvalidClient(e.to_string()))?;
+        self.client_repo
+            .create_client(&client)
+            .await
+            .map_err(repository_error)?;
+        Ok((client, secret))
+    }
+
+    pub async fn list(&self) -> Result<Vec<OidcClient>, OidcClientServiceError> {
+        self.client_repo
+            .list_clients()
+            .await
+            .map_err(repository_error)
+    }
+
+    pub async fn get(&self, client_id: &str) -> Result<OidcClient, OidcClientServiceError> {
+        self.client_repo
+            .get_client_by_id(client_id)
+            .await
+            .map_err(repository_error)?
+            .ok_or(OidcClientServiceError::ClientNotFound)
+    }
+
+    pub async fn rotate_secret(
+        &self,
+        client_id: &str,
+    ) -> Result<(OidcClient, OpaqueClientSecret), OidcClientServiceError> {
+        let mut client = self.get(client_id).await?;
+        let secret = client
+            .rotate_secret()
+            .map_err(|e| OidcClientServiceError::InvalidClient(e.to_string()))?;
+        self.client_repo
+            .update_client(&client)
+            .await
+            .map_err(repository_error)?;
+        Ok((client, secret))
+    }
+
+    // a disabled client can neither start a login nor use its codes and refresh tokens
+    pub async fn set_active(
+        &self,
+        client_id: &str,
+        is_active: bool,
+    ) -> Result<OidcClient, OidcClientServiceError> {
+        let mut client = self.get(client_id).await?;
+        client.set_active(is_active);
+        self.client_repo
+            .update_client(&client)
+            .await
+            .map_err(repository_error)?;
+        Ok(client)
+    }
+}
+
+fn repository_error(e: OidcClientRepoError) -> OidcClientServiceError {
+    match e {
+        OidcClientRepoError::ClientAlreadyExists => OidcClientServiceError::ClientAlreadyExists,
+        OidcClientRepoError::ClientNotFound => OidcClientServiceError::ClientNotFound,
+        e => OidcClientServiceError::Repository(e.to_string()),
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum OidcClientServiceError {
+    #[error("invalid client : {0}")]
+    InvalidClient(String),
+
+    #[error("client already exists")]
+    ClientAlreadyExists,
+
+    #[error("client not found")]
+    ClientNotFound,
+
+    #[error("client repository error : {0}")]
+    Repository(String),
+}
+
+#[cfg(test)]
+mod tests {
+    include!("oidc_client_service_test.rs");
+}
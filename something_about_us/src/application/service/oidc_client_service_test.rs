@@ -0,0 +1,140 @@
+use super::{OidcClientService, OidcClientServiceError};
+use crate::{
+    application::port::oidc_client_repository::{OidcClientRepo, OidcClientRepoError},
+    domain::oauth::oidc_client::{ClientRegistration, GrantType, OidcClient},
+};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct MemoryOidcClientRepo {
+    clients: Arc<Mutex<Vec<OidcClient>>>,
+}
+
+#[async_trait::async_trait]
+impl OidcClientRepo for MemoryOidcClientRepo {
+    async fn get_client_by_id(
+        &self,
+        client_id: &str,
+    ) -> Result<Option<OidcClient>, OidcClientRepoError> {
+        let clients = self.clients.lock().unwrap();
+        Ok(clients
+            .iter()
+            .find(|client| client.client_id == client_id)
+            .cloned())
+    }
+
+    async fn list_clients(&self) -> Result<Vec<OidcClient>, OidcClientRepoError> {
+        Ok(self.clients.lock().unwrap().clone())
+    }
+
+    async fn create_client(&self, client: &OidcClient) -> Result<(), OidcClientRepoError> {
+        let mut clients = self.clients.lock().unwrap();
+        if clients.iter().any(|c| c.client_id == client.client_id) {
+            return Err(OidcClientRepoError::ClientAlreadyExists);
+        }
+        clients.push(client.clone());
+        Ok(())
+    }
+
+    async fn update_client(&self, client: &OidcClient) -> Result<(), OidcClientRepoError> {
+        let mut clients = self.clients.lock().unwrap();
+        let stored = clients
+            .iter_mut()
+            .find(|c| c.client_id == client.client_id)
+            .ok_or(OidcClientRepoError::ClientNotFound)?;
+        *stored = client.clone();
+        Ok(())
+    }
+}
+
+fn service() -> OidcClientService<MemoryOidcClientRepo> {
+    OidcClientService::new(MemoryOidcClientRepo::default(), 3600)
+}
+
+fn registration(client_id: &str) -> ClientRegistration {
+    ClientRegistration {
+        client_id: client_id.to_string(),
+        confidential: true,
+        redirect_uris: vec!["https://app.example.com/callback".to_string()],
+        allowed_scopes: vec!["openid".to_string()],
+        allowed_grant_types: vec![GrantType::AuthorizationCode],
+        access_token_ttl: None,
+        refresh_token_ttl: None,
+        audience: None,
+        jwks: None,
+    }
+}
+
+#[tokio::test]
+async fn test_register_and_get() {
+    let service = service();
+    let (client, secret) = service.register(registration("web-app")).await.unwrap();
+    assert!(secret.is_some());
+
+    let stored = service.get("web-app").await.unwrap();
+    assert_eq!(stored, client);
+    assert_eq!(service.list().await.unwrap().len(), 1);
+    assert!(matches!(
+        service.get("unknown").await,
+        Err(OidcClientServiceError::ClientNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_register_duplicated_client() {
+    let service = service();
+    service.register(registration("web-app")).await.unwrap();
+
+    let result = service.register(registration("web-app")).await;
+    assert!(matches!(
+        result,
+        Err(OidcClientServiceError::ClientAlreadyExists)
+    ));
+}
+
+#[tokio::test]
+async fn test_register_rejects_access_token_ttl_over_limit() {
+    let service = service();
+    for ttl in [0, 3601] {
+        let mut too_long = registration("web-app");
+        too_long.access_token_ttl = Some(ttl);
+        assert!(matches!(
+            service.register(too_long).await,
+            Err(OidcClientServiceError::InvalidClient(_))
+        ));
+    }
+
+    let mut allowed = registration("web-app");
+    allowed.access_token_ttl = Some(600);
+    assert!(service.register(allowed).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_rotate_secret() {
+    let service = service();
+    let (_, secret) = service.register(registration("web-app")).await.unwrap();
+
+    let (_, rotated) = service.rotate_secret("web-app").await.unwrap();
+    let stored = service.get("web-app").await.unwrap();
+    assert!(stored.authenticate(Some(&rotated)));
+    assert!(!stored.authenticate(secret.as_deref()));
+}
+
+#[tokio::test]
+async fn test_disable_client() {
+    let service = service();
+    let (_, secret) = service.register(registration("web-app")).await.unwrap();
+
+    let disabled = service.set_active("web-app", false).await.unwrap();
+    assert!(!disabled.is_active);
+    assert!(!service
+        .get("web-app")
+        .await
+        .unwrap()
+        .authenticate(secret.as_deref()));
+
+    assert!(matches!(
+        service.set_active("unknown", false).await,
+        Err(OidcClientServiceError::ClientNotFound)
+    ));
+}
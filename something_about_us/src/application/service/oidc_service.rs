@@ -0,0 +1,540 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    application::port::{
+        authorization_code_repository::AuthorizationCodeCacheRepo,
+        client_assertion_repository::ClientAssertionCacheRepo,
+        device_authorization_repository::DeviceAuthorizationCacheRepo,
+        oidc_client_repository::OidcClientRepo,
+    },
+    domain::oauth::{
+        authorization_code::{
+            AuthorizationCode, AuthorizationRequest, OpaqueAuthorizationCode, OPENID_SCOPE,
+        },
+        client_assertion::{ClientAssertion, CLIENT_ASSERTION_TYPE},
+        device_authorization::{DeviceAuthorization, DeviceAuthorizationStatus, OpaqueDeviceCode},
+        oidc_client::{GrantType, OidcClient},
+    },
+};
+
+// seconds, RFC 6749 recommends at most 10 minutes
+pub const AUTHORIZATION_CODE_TTL: u64 = 60;
+
+// seconds a device has to get its user logged in
+pub const DEVICE_CODE_TTL: u64 = 600;
+// seconds between two polls of a device, the RFC 8628 default
+pub const DEVICE_POLL_INTERVAL: u64 = 5;
+// RFC 8628 5.1, wrong user codes entered within a window of `USER_CODE_FAILURE_WINDOW` seconds
+// before every user code is refused until the window ends
+pub const MAX_USER_CODE_FAILURES: u64 = 100;
+pub const USER_CODE_FAILURE_WINDOW: u64 = 60;
+
+pub const CODE_RESPONSE_TYPE: &str = "code";
+pub const PKCE_METHOD_S256: &str = "S256";
+
+// query of `/oidc/authorize` before it is checked
+pub struct AuthorizeParams {
+    pub response_type: Option<String>,
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub scope: Option<String>,
+    pub state: Option<String>,
+    pub nonce: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+}
+
+// SAU as an OpenID provider for the clients registered in the `clients` table.
+#[derive(Clone)]
+pub struct OidcService<C, R>
+where
+    C: AuthorizationCodeCacheRepo + ClientAssertionCacheRepo + DeviceAuthorizationCacheRepo,
+    R: OidcClientRepo,
+{
+    // authorization codes, device authorizations and the `jti` of used client assertions
+    cache_repo: C,
+    client_repo: R,
+    // `jwt.iss`, the base url every endpoint is published under
+    issuer: String,
+    access_token_ttl: u64,
+    client_credentials_ttl: u64,
+}
+
+impl<C, R> OidcService<C, R>
+where
+    C: AuthorizationCodeCacheRepo + ClientAssertionCacheRepo + DeviceAuthorizationCacheRepo,
+    R: OidcClientRepo,
+{
+    pub fn new(
+        cache_repo: C,
+        client_repo: R,
+        issuer: String,
+        access_token_ttl: u64,
+        client_credentials_ttl: u64,
+    ) -> Self {
+        Self {
+            cache_repo,
+            client_repo,
+            issuer,
+            access_token_ttl,
+            client_credentials_ttl,
+        }
+    }
+
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    pub fn token_endpoint(&self) -> String {
+        format!("{}/api/v1/oidc/token", self.issuer.trim_end_matches('/'))
+    }
+
+    // page a user types the user code of a device in
+    pub fn device_verification_uri(&self) -> String {
+        format!("{}/api/v1/oidc/device", self.issuer.trim_end_matches('/'))
+    }
+
+    // lifetime in seconds of the service tokens of the `client_credentials` grant
+    pub fn client_credentials_ttl(&self) -> u64 {
+        self.client_credentials_ttl
+    }
+
+    // lifetime in seconds of the access tokens issued to `client`
+    pub fn access_token_ttl(&self, client: &OidcClient) -> u64 {
+        client.access_token_ttl.unwrap_or(self.access_token_ttl)
+    }
+
+    // errors about the client or its redirect uri must be shown to the user,
+    // every other error can be sent back to the redirect uri.
+    pub async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<AuthorizationRequest, OidcServiceError> {
+        let client = self
+            .get_client(&params.client_id)
+            .await?
+            .filter(|client| client.is_active)
+            .ok_or(OidcServiceError::UnknownClient)?;
+        if !client.allows_redirect_uri(&params.redirect_uri) {
+            return Err(OidcServiceError::InvalidRedirectUri);
+        }
+        if !client.allows_grant_type(GrantType::AuthorizationCode) {
+            return Err(OidcServiceError::UnauthorizedClient);
+        }
+
+        if params.response_type.as_deref() != Some(CODE_RESPONSE_TYPE) {
+            return Err(OidcServiceError::UnsupportedResponseType);
+        }
+        let scope = params.scope.unwrap_or_default();
+        if !scope.split_whitespace().any(|scope| scope == OPENID_SCOPE)
+            || !scope
+                .split_whitespace()
+                .all(|scope| client.allows_scope(scope))
+        {
+            return Err(OidcServiceError::InvalidScope);
+        }
+        let code_challenge = params
+            .code_challenge
+            .filter(|challenge| !challenge.is_empty())
+            .ok_or(OidcServiceError::InvalidRequest(
+                "code_challenge is required".to_string(),
+            ))?;
+        if params.code_challenge_method.as_deref() != Some(PKCE_METHOD_S256) {
+            return Err(OidcServiceError::InvalidRequest(
+                "code_challenge_method must be S256".to_string(),
+            ));
+        }
+
+        Ok(AuthorizationRequest {
+            client_id: params.client_id,
+            redirect_uri: params.redirect_uri,
+            scope,
+            state: params.state,
+            nonce: params.nonce,
+            code_challenge,
+        })
+    }
+
+    pub async fn issue_code(
+        &self,
+        user_id: Uuid,
+        request: AuthorizationRequest,
+    ) -> Result<OpaqueAuthorizationCode, OidcServiceError> {
+        let code =
+            AuthorizationCode::generate().map_err(|e| OidcServiceError::Issue(e.to_string()))?;
+        let authorization_code = AuthorizationCode {
+            user_id,
+            request,
+            auth_time: Utc::now().timestamp(),
+        };
+        self.cache_repo
+            .set_authorization_code(
+                &AuthorizationCode::hash(&code),
+                &authorization_code,
+                AUTHORIZATION_CODE_TTL,
+            )
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?;
+        Ok(code)
+    }
+
+    // authenticates a client at the token endpoint for `grant_type`
+    pub async fn authenticate_client(
+        &self,
+        client_id: &str,
+        client_secret: Option<&str>,
+        grant_type: GrantType,
+    ) -> Result<OidcClient, OidcServiceError> {
+        let client = self
+            .get_client(client_id)
+            .await?
+            .filter(|client| client.authenticate(client_secret))
+            .ok_or(OidcServiceError::InvalidClient)?;
+        if !client.allows_grant_type(grant_type) {
+            return Err(OidcServiceError::UnauthorizedClient);
+        }
+        Ok(client)
+    }
+
+    // `private_key_jwt` (RFC 7523), `client_id` may be left out since the assertion names the client.
+    // the `jti` is claimed only after the signature checked out, so forged assertions burn nothing.
+    pub async fn authenticate_client_assertion(
+        &self,
+        client_id: Option<&str>,
+        assertion_type: &str,
+        assertion: &str,
+        grant_type: GrantType,
+    ) -> Result<OidcClient, OidcServiceError> {
+        if assertion_type != CLIENT_ASSERTION_TYPE {
+            return Err(OidcServiceError::InvalidClient);
+        }
+        let client_id = match client_id {
+            Some(client_id) => client_id.to_string(),
+            None => {
+                ClientAssertion::peek_client_id(assertion).ok_or(OidcServiceError::InvalidClient)?
+            }
+        };
+        let client = self
+            .get_client(&client_id)
+            .await?
+            .filter(|client| client.is_active)
+            .ok_or(OidcServiceError::InvalidClient)?;
+
+        let token_endpoint = self.token_endpoint();
+        let claims = ClientAssertion::verify(assertion, &client, &[&token_endpoint, &self.issuer])
+            .map_err(|_| OidcServiceError::InvalidClient)?;
+        let claimed = self
+            .cache_repo
+            .claim_assertion_jti(&client.client_id, &claims.jti, claims.replay_window())
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?;
+        if !claimed {
+            return Err(OidcServiceError::InvalidClient);
+        }
+
+        if !client.allows_grant_type(grant_type) {
+            return Err(OidcServiceError::UnauthorizedClient);
+        }
+        Ok(client)
+    }
+
+    // scopes granted to an authenticated client for a service token,
+    // all of its allowed scopes when `scope` is left out.
+    pub fn client_credentials_scope(
+        &self,
+        client: &OidcClient,
+        scope: Option<&str>,
+    ) -> Result<String, OidcServiceError> {
+        // a public client could be anyone
+        if !client.is_confidential() {
+            return Err(OidcServiceError::UnauthorizedClient);
+        }
+        Self::granted_scope(client, scope)
+    }
+
+    // RFC 8628 device authorization request, the device shows the user code and polls with the device code
+    pub async fn start_device_authorization(
+        &self,
+        client_id: &str,
+        client_secret: Option<&str>,
+        scope: Option<&str>,
+    ) -> Result<(OpaqueDeviceCode, DeviceAuthorization), OidcServiceError> {
+        let client = self
+            .authenticate_client(client_id, client_secret, GrantType::DeviceCode)
+            .await?;
+        let scope = Self::granted_scope(&client, scope)?;
+
+        let device_code = DeviceAuthorization::generate_device_code()
+            .map_err(|e| OidcServiceError::Issue(e.to_string()))?;
+        let authorization = DeviceAuthorization::new(
+            client.client_id,
+            scope,
+            DEVICE_CODE_TTL,
+            DEVICE_POLL_INTERVAL,
+        )
+        .map_err(|e| OidcServiceError::Issue(e.to_string()))?;
+        let device_code_hash = DeviceAuthorization::hash(&device_code);
+        self.cache_repo
+            .set_device_authorization(&device_code_hash, &authorization, DEVICE_CODE_TTL)
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?;
+        self.cache_repo
+            .set_user_code(&authorization.user_code, &device_code_hash, DEVICE_CODE_TTL)
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?;
+        Ok((device_code, authorization))
+    }
+
+    // pending authorization of a user code, checked before the user is sent to log in.
+    // the counter is shared by everyone, a guessing client can not switch to a fresh one,
+    // at the price of locking out the users typing a code while it is exceeded.
+    pub async fn find_device_authorization(
+        &self,
+        user_code: &str,
+    ) -> Result<DeviceAuthorization, OidcServiceError> {
+        let failures = self
+            .cache_repo
+            .user_code_failures(MAX_USER_CODE_FAILURES)
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?;
+        if failures >= MAX_USER_CODE_FAILURES {
+            return Err(OidcServiceError::TooManyUserCodeAttempts);
+        }
+
+        let found = self.lookup_user_code(user_code).await;
+        if matches!(found, Err(OidcServiceError::InvalidUserCode)) {
+            self.cache_repo
+                .count_user_code_failure(MAX_USER_CODE_FAILURES, USER_CODE_FAILURE_WINDOW)
+                .await
+                .map_err(|e| OidcServiceError::Cache(e.to_string()))?;
+        }
+        found
+    }
+
+    async fn lookup_user_code(
+        &self,
+        user_code: &str,
+    ) -> Result<DeviceAuthorization, OidcServiceError> {
+        let user_code = DeviceAuthorization::normalize_user_code(user_code)
+            .ok_or(OidcServiceError::InvalidUserCode)?;
+        let device_code_hash = self
+            .cache_repo
+            .get_user_code(&user_code)
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?
+            .ok_or(OidcServiceError::InvalidUserCode)?;
+        self.pending_device_authorization(&device_code_hash).await
+    }
+
+    // binds the device to the logged in user, the user code is consumed so only one login can approve it
+    pub async fn approve_device(
+        &self,
+        user_code: &str,
+        user_id: Uuid,
+    ) -> Result<DeviceAuthorization, OidcServiceError> {
+        let user_code = DeviceAuthorization::normalize_user_code(user_code)
+            .ok_or(OidcServiceError::InvalidUserCode)?;
+        let device_code_hash = self
+            .cache_repo
+            .take_user_code(&user_code)
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?
+            .ok_or(OidcServiceError::InvalidUserCode)?;
+        let mut authorization = self.pending_device_authorization(&device_code_hash).await?;
+        let ttl = authorization
+            .remaining_ttl()
+            .ok_or(OidcServiceError::InvalidUserCode)?;
+
+        authorization.status = DeviceAuthorizationStatus::Approved {
+            user_id,
+            auth_time: Utc::now().timestamp(),
+        };
+        self.cache_repo
+            .set_device_authorization(&device_code_hash, &authorization, ttl)
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?;
+        Ok(authorization)
+    }
+
+    // a poll of the device, the approved authorization is consumed so the tokens are issued once.
+    pub async fn exchange_device_code(
+        &self,
+        device_code: &str,
+        client_id: &str,
+        client_secret: Option<&str>,
+    ) -> Result<(Uuid, DeviceAuthorization, OidcClient), OidcServiceError> {
+        let client = self
+            .authenticate_client(client_id, client_secret, GrantType::DeviceCode)
+            .await?;
+
+        let device_code_hash = DeviceAuthorization::hash(device_code);
+        let authorization = self
+            .cache_repo
+            .get_device_authorization(&device_code_hash)
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?
+            .ok_or(OidcServiceError::ExpiredToken)?;
+        if authorization.client_id != client.client_id {
+            return Err(OidcServiceError::InvalidGrant(
+                "device code was issued to another client".to_string(),
+            ));
+        }
+        let on_time = self
+            .cache_repo
+            .claim_device_poll(&device_code_hash, authorization.interval)
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?;
+        if !on_time {
+            return Err(OidcServiceError::SlowDown);
+        }
+        if authorization.is_pending() {
+            return Err(OidcServiceError::AuthorizationPending);
+        }
+
+        let authorization = self
+            .cache_repo
+            .take_device_authorization(&device_code_hash)
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?
+            .ok_or(OidcServiceError::ExpiredToken)?;
+        match authorization.status {
+            DeviceAuthorizationStatus::Approved { user_id, .. } => {
+                Ok((user_id, authorization, client))
+            }
+            DeviceAuthorizationStatus::Pending => Err(OidcServiceError::AuthorizationPending),
+        }
+    }
+
+    async fn pending_device_authorization(
+        &self,
+        device_code_hash: &str,
+    ) -> Result<DeviceAuthorization, OidcServiceError> {
+        self.cache_repo
+            .get_device_authorization(device_code_hash)
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?
+            .filter(DeviceAuthorization::is_pending)
+            .ok_or(OidcServiceError::InvalidUserCode)
+    }
+
+    // `scope` must be allowed for the client, all of its allowed scopes when left out
+    fn granted_scope(client: &OidcClient, scope: Option<&str>) -> Result<String, OidcServiceError> {
+        match scope.filter(|scope| !scope.trim().is_empty()) {
+            Some(scope) => {
+                if !scope
+                    .split_whitespace()
+                    .all(|scope| client.allows_scope(scope))
+                {
+                    return Err(OidcServiceError::InvalidScope);
+                }
+                Ok(scope.split_whitespace().collect::<Vec<_>>().join(" "))
+            }
+            None => Ok(client.allowed_scopes.join(" ")),
+        }
+    }
+
+    // the code is consumed before it is checked, a wrong verifier burns it as well.
+    pub async fn exchange_code(
+        &self,
+        code: &str,
+        client_id: &str,
+        client_secret: Option<&str>,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> Result<(AuthorizationCode, OidcClient), OidcServiceError> {
+        let client = self
+            .authenticate_client(client_id, client_secret, GrantType::AuthorizationCode)
+            .await?;
+
+        let authorization_code = self
+            .cache_repo
+            .take_authorization_code(&AuthorizationCode::hash(code))
+            .await
+            .map_err(|e| OidcServiceError::Cache(e.to_string()))?
+            .ok_or(OidcServiceError::InvalidGrant(
+                "authorization code is invalid, expired or already used".to_string(),
+            ))?;
+
+        let request = &authorization_code.request;
+        if request.client_id != client.client_id {
+            return Err(OidcServiceError::InvalidGrant(
+                "authorization code was issued to another client".to_string(),
+            ));
+        }
+        if request.redirect_uri != redirect_uri {
+            return Err(OidcServiceError::InvalidGrant(
+                "redirect_uri does not match the authorization request".to_string(),
+            ));
+        }
+        if !authorization_code.verify_pkce(code_verifier) {
+            return Err(OidcServiceError::InvalidGrant(
+                "code_verifier does not match the code_challenge".to_string(),
+            ));
+        }
+        Ok((authorization_code, client))
+    }
+
+    async fn get_client(&self, client_id: &str) -> Result<Option<OidcClient>, OidcServiceError> {
+        self.client_repo
+            .get_client_by_id(client_id)
+            .await
+            .map_err(|e| OidcServiceError::Repository(e.to_string()))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum OidcServiceError {
+    #[error("unknown client")]
+    UnknownClient,
+
+    #[error("redirect_uri is not registered for the client")]
+    InvalidRedirectUri,
+
+    #[error("only the `code` response type is supported")]
+    UnsupportedResponseType,
+
+    #[error("the scope is missing `openid` or not allowed for the client")]
+    InvalidScope,
+
+    #[error("invalid request : {0}")]
+    InvalidRequest(String),
+
+    #[error("client authentication failed")]
+    InvalidClient,
+
+    #[error("the client is not allowed to use this grant type")]
+    UnauthorizedClient,
+
+    #[error("invalid grant : {0}")]
+    InvalidGrant(String),
+
+    #[error("the user code is invalid, expired or already used")]
+    InvalidUserCode,
+
+    #[error("too many wrong user codes, try again in a minute")]
+    TooManyUserCodeAttempts,
+
+    #[error("the user has not approved the device yet")]
+    AuthorizationPending,
+
+    #[error("the device polls faster than its interval")]
+    SlowDown,
+
+    #[error("the device code is expired")]
+    ExpiredToken,
+
+    #[error("authorization code issue error : {0}")]
+    Issue(String),
+
+    #[error("cache error : {0}")]
+    Cache(String),
+
+    #[error("client repository error : {0}")]
+    Repository(String),
+}
+
+#[cfg(test)]
+mod tests {
+    include!("oidc_service_test.rs");
+}
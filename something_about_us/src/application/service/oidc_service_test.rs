@@ -0,0 +1,770 @@
+use super::{
+    AuthorizeParams, OidcService, OidcServiceError, AUTHORIZATION_CODE_TTL, DEVICE_POLL_INTERVAL,
+    MAX_USER_CODE_FAILURES,
+};
+use crate::{
+    application::port::{
+        authorization_code_repository::{
+            AuthorizationCodeCacheRepo, AuthorizationCodeCacheRepoError,
+        },
+        client_assertion_repository::{ClientAssertionCacheRepo, ClientAssertionCacheRepoError},
+        device_authorization_repository::{
+            DeviceAuthorizationCacheRepo, DeviceAuthorizationCacheRepoError,
+        },
+        oidc_client_repository::{OidcClientRepo, OidcClientRepoError},
+    },
+    domain::oauth::{
+        authorization_code::AuthorizationCode,
+        client_assertion::CLIENT_ASSERTION_TYPE,
+        device_authorization::DeviceAuthorization,
+        oidc_client::{ClientRegistration, GrantType, OidcClient},
+    },
+};
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use ring::{
+    digest::{digest, SHA256},
+    rand::SystemRandom,
+    signature::{Ed25519KeyPair, KeyPair},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+use uuid::Uuid;
+
+const REDIRECT_URI: &str = "https://app.example.com/callback";
+const VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+#[derive(Clone, Default)]
+struct MemoryAuthorizationCodeRepo {
+    codes: Arc<Mutex<HashMap<String, (AuthorizationCode, u64)>>>,
+    assertion_jtis: Arc<Mutex<HashSet<String>>>,
+    device_authorizations: Arc<Mutex<HashMap<String, DeviceAuthorization>>>,
+    user_codes: Arc<Mutex<HashMap<String, String>>>,
+    // cleared by a test to let the poll interval pass
+    device_polls: Arc<Mutex<HashSet<String>>>,
+    user_code_failures: Arc<Mutex<u64>>,
+}
+
+#[async_trait::async_trait]
+impl AuthorizationCodeCacheRepo for MemoryAuthorizationCodeRepo {
+    async fn set_authorization_code(
+        &self,
+        code_hash: &str,
+        code: &AuthorizationCode,
+        ttl: u64,
+    ) -> Result<(), AuthorizationCodeCacheRepoError> {
+        self.codes
+            .lock()
+            .unwrap()
+            .insert(code_hash.to_string(), (code.clone(), ttl));
+        Ok(())
+    }
+
+    async fn take_authorization_code(
+        &self,
+        code_hash: &str,
+    ) -> Result<Option<AuthorizationCode>, AuthorizationCodeCacheRepoError> {
+        Ok(self
+            .codes
+            .lock()
+            .unwrap()
+            .remove(code_hash)
+            .map(|(code, _)| code))
+    }
+}
+
+#[async_trait::async_trait]
+impl ClientAssertionCacheRepo for MemoryAuthorizationCodeRepo {
+    async fn claim_assertion_jti(
+        &self,
+        client_id: &str,
+        jti: &str,
+        _ttl: u64,
+    ) -> Result<bool, ClientAssertionCacheRepoError> {
+        Ok(self
+            .assertion_jtis
+            .lock()
+            .unwrap()
+            .insert(format!("{}:{}", client_id, jti)))
+    }
+}
+
+#[async_trait::async_trait]
+impl DeviceAuthorizationCacheRepo for MemoryAuthorizationCodeRepo {
+    async fn set_device_authorization(
+        &self,
+        device_code_hash: &str,
+        authorization: &DeviceAuthorization,
+        _ttl: u64,
+    ) -> Result<(), DeviceAuthorizationCacheRepoError> {
+        self.device_authorizations
+            .lock()
+            .unwrap()
+            .insert(device_code_hash.to_string(), authorization.clone());
+        Ok(())
+    }
+
+    async fn get_device_authorization(
+        &self,
+        device_code_hash: &str,
+    ) -> Result<Option<DeviceAuthorization>, DeviceAuthorizationCacheRepoError> {
+        Ok(self
+            .device_authorizations
+            .lock()
+            .unwrap()
+            .get(device_code_hash)
+            .cloned())
+    }
+
+    async fn take_device_authorization(
+        &self,
+        device_code_hash: &str,
+    ) -> Result<Option<DeviceAuthorization>, DeviceAuthorizationCacheRepoError> {
+        Ok(self
+            .device_authorizations
+            .lock()
+            .unwrap()
+            .remove(device_code_hash))
+    }
+
+    async fn set_user_code(
+        &self,
+        user_code: &str,
+        device_code_hash: &str,
+        _ttl: u64,
+    ) -> Result<(), DeviceAuthorizationCacheRepoError> {
+        self.user_codes
+            .lock()
+            .unwrap()
+            .insert(user_code.to_string(), device_code_hash.to_string());
+        Ok(())
+    }
+
+    async fn get_user_code(
+        &self,
+        user_code: &str,
+    ) -> Result<Option<String>, DeviceAuthorizationCacheRepoError> {
+        Ok(self.user_codes.lock().unwrap().get(user_code).cloned())
+    }
+
+    async fn take_user_code(
+        &self,
+        user_code: &str,
+    ) -> Result<Option<String>, DeviceAuthorizationCacheRepoError> {
+        Ok(self.user_codes.lock().unwrap().remove(user_code))
+    }
+
+    async fn user_code_failures(
+        &self,
+        limit: u64,
+    ) -> Result<u64, DeviceAuthorizationCacheRepoError> {
+        Ok((*self.user_code_failures.lock().unwrap()).min(limit))
+    }
+
+    async fn count_user_code_failure(
+        &self,
+        _limit: u64,
+        _window: u64,
+    ) -> Result<(), DeviceAuthorizationCacheRepoError> {
+        *self.user_code_failures.lock().unwrap() += 1;
+        Ok(())
+    }
+
+    async fn claim_device_poll(
+        &self,
+        device_code_hash: &str,
+        _interval: u64,
+    ) -> Result<bool, DeviceAuthorizationCacheRepoError> {
+        Ok(self
+            .device_polls
+            .lock()
+            .unwrap()
+            .insert(device_code_hash.to_string()))
+    }
+}
+
+#[derive(Clone, Default)]
+struct MemoryOidcClientRepo {
+    clients: Arc<Mutex<Vec<OidcClient>>>,
+}
+
+#[async_trait::async_trait]
+impl OidcClientRepo for MemoryOidcClientRepo {
+    async fn get_client_by_id(
+        &self,
+        client_id: &str,
+    ) -> Result<Option<OidcClient>, OidcClientRepoError> {
+        let clients = self.clients.lock().unwrap();
+        Ok(clients
+            .iter()
+            .find(|client| client.client_id == client_id)
+            .cloned())
+    }
+
+    async fn list_clients(&self) -> Result<Vec<OidcClient>, OidcClientRepoError> {
+        Ok(self.clients.lock().unwrap().clone())
+    }
+
+    async fn create_client(&self, client: &OidcClient) -> Result<(), OidcClientRepoError> {
+        self.clients.lock().unwrap().push(client.clone());
+        Ok(())
+    }
+
+    async fn update_client(&self, client: &OidcClient) -> Result<(), OidcClientRepoError> {
+        let mut clients = self.clients.lock().unwrap();
+        let stored = clients
+            .iter_mut()
+            .find(|c| c.client_id == client.client_id)
+            .ok_or(OidcClientRepoError::ClientNotFound)?;
+        *stored = client.clone();
+        Ok(())
+    }
+}
+
+type TestOidcService = OidcService<MemoryAuthorizationCodeRepo, MemoryOidcClientRepo>;
+
+// a confidential `web-app`, whose secret is returned, and a public `spa`
+fn register(clients: &MemoryOidcClientRepo, client_id: &str, confidential: bool) -> Option<String> {
+    let (client, secret) = OidcClient::register(ClientRegistration {
+        client_id: client_id.to_string(),
+        confidential,
+        redirect_uris: vec![REDIRECT_URI.to_string()],
+        allowed_scopes: vec!["openid".to_string(), "email".to_string()],
+        allowed_grant_types: vec![GrantType::AuthorizationCode, GrantType::RefreshToken],
+        access_token_ttl: None,
+        refresh_token_ttl: None,
+        audience: None,
+        jwks: None,
+    })
+    .unwrap();
+    clients.clients.lock().unwrap().push(client);
+    secret
+}
+
+fn service() -> (TestOidcService, MemoryAuthorizationCodeRepo, String) {
+    let repo = MemoryAuthorizationCodeRepo::default();
+    let clients = MemoryOidcClientRepo::default();
+    let secret = register(&clients, "web-app", true).unwrap();
+    register(&clients, "spa", false);
+    (
+        OidcService::new(
+            repo.clone(),
+            clients,
+            "https://auth.example.com".to_string(),
+            3600,
+            900,
+        ),
+        repo,
+        secret,
+    )
+}
+
+fn params(client_id: &str) -> AuthorizeParams {
+    AuthorizeParams {
+        response_type: Some("code".to_string()),
+        client_id: client_id.to_string(),
+        redirect_uri: REDIRECT_URI.to_string(),
+        scope: Some("openid email".to_string()),
+        state: Some("af0ifjsldkj".to_string()),
+        nonce: Some("n-0S6_WzA2Mj".to_string()),
+        code_challenge: Some(BASE64_URL_SAFE_NO_PAD.encode(digest(&SHA256, VERIFIER.as_bytes()))),
+        code_challenge_method: Some("S256".to_string()),
+    }
+}
+
+async fn issued_code(service: &TestOidcService, client_id: &str) -> String {
+    let request = service.authorize(params(client_id)).await.unwrap();
+    service.issue_code(Uuid::now_v7(), request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_authorize_valid_request() {
+    let (service, _, _) = service();
+    let request = service.authorize(params("web-app")).await.unwrap();
+    assert_eq!(request.client_id, "web-app");
+    assert_eq!(request.state.as_deref(), Some("af0ifjsldkj"));
+    assert!(request.has_scope("email"));
+}
+
+#[tokio::test]
+async fn test_authorize_rejects_unknown_client_and_redirect_uri() {
+    let (service, _, _) = service();
+    assert!(matches!(
+        service.authorize(params("unknown")).await,
+        Err(OidcServiceError::UnknownClient)
+    ));
+
+    let mut other_uri = params("web-app");
+    other_uri.redirect_uri = "https://evil.example.com/callback".to_string();
+    assert!(matches!(
+        service.authorize(other_uri).await,
+        Err(OidcServiceError::InvalidRedirectUri)
+    ));
+}
+
+#[tokio::test]
+async fn test_authorize_requires_openid_code_and_pkce() {
+    let (service, _, _) = service();
+
+    let mut token = params("web-app");
+    token.response_type = Some("token".to_string());
+    assert!(matches!(
+        service.authorize(token).await,
+        Err(OidcServiceError::UnsupportedResponseType)
+    ));
+
+    let mut no_openid = params("web-app");
+    no_openid.scope = Some("email".to_string());
+    assert!(matches!(
+        service.authorize(no_openid).await,
+        Err(OidcServiceError::InvalidScope)
+    ));
+
+    let mut plain = params("web-app");
+    plain.code_challenge_method = Some("plain".to_string());
+    assert!(matches!(
+        service.authorize(plain).await,
+        Err(OidcServiceError::InvalidRequest(_))
+    ));
+
+    let mut no_challenge = params("web-app");
+    no_challenge.code_challenge = None;
+    assert!(matches!(
+        service.authorize(no_challenge).await,
+        Err(OidcServiceError::InvalidRequest(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_issue_code_stores_hash_only() {
+    let (service, repo, _) = service();
+    let code = issued_code(&service, "web-app").await;
+
+    let codes = repo.codes.lock().unwrap();
+    let (_, ttl) = codes.get(&AuthorizationCode::hash(&code)).unwrap();
+    assert_eq!(*ttl, AUTHORIZATION_CODE_TTL);
+    assert!(!codes.contains_key(&code));
+}
+
+#[tokio::test]
+async fn test_exchange_code_once() {
+    let (service, _, secret) = service();
+    let code = issued_code(&service, "web-app").await;
+
+    let (exchanged, client) = service
+        .exchange_code(
+            &code,
+            "web-app",
+            Some(secret.as_str()),
+            REDIRECT_URI,
+            VERIFIER,
+        )
+        .await
+        .unwrap();
+    assert_eq!(exchanged.request.nonce.as_deref(), Some("n-0S6_WzA2Mj"));
+    assert_eq!(client.client_id, "web-app");
+
+    let replay = service
+        .exchange_code(
+            &code,
+            "web-app",
+            Some(secret.as_str()),
+            REDIRECT_URI,
+            VERIFIER,
+        )
+        .await;
+    assert!(matches!(replay, Err(OidcServiceError::InvalidGrant(_))));
+}
+
+#[tokio::test]
+async fn test_exchange_code_public_client() {
+    let (service, _, _) = service();
+    let code = issued_code(&service, "spa").await;
+
+    assert!(service
+        .exchange_code(&code, "spa", None, REDIRECT_URI, VERIFIER)
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_exchange_code_rejects_wrong_client_secret() {
+    let (service, _, _) = service();
+    let code = issued_code(&service, "web-app").await;
+
+    for secret in [None, Some("wrong")] {
+        let result = service
+            .exchange_code(&code, "web-app", secret, REDIRECT_URI, VERIFIER)
+            .await;
+        assert!(matches!(result, Err(OidcServiceError::InvalidClient)));
+    }
+}
+
+#[tokio::test]
+async fn test_exchange_code_of_other_client() {
+    let (service, _, _) = service();
+    let code = issued_code(&service, "web-app").await;
+
+    let result = service
+        .exchange_code(&code, "spa", None, REDIRECT_URI, VERIFIER)
+        .await;
+    assert!(matches!(result, Err(OidcServiceError::InvalidGrant(_))));
+}
+
+#[tokio::test]
+async fn test_exchange_code_checks_redirect_uri_and_verifier() {
+    let (service, _, secret) = service();
+
+    let code = issued_code(&service, "web-app").await;
+    let result = service
+        .exchange_code(
+            &code,
+            "web-app",
+            Some(secret.as_str()),
+            "https://app.example.com/other",
+            VERIFIER,
+        )
+        .await;
+    assert!(matches!(result, Err(OidcServiceError::InvalidGrant(_))));
+
+    let code = issued_code(&service, "web-app").await;
+    let result = service
+        .exchange_code(
+            &code,
+            "web-app",
+            Some(secret.as_str()),
+            REDIRECT_URI,
+            &"a".repeat(43),
+        )
+        .await;
+    assert!(matches!(result, Err(OidcServiceError::InvalidGrant(_))));
+}
+
+#[tokio::test]
+async fn test_authorize_rejects_scope_not_allowed_for_client() {
+    let (service, _, _) = service();
+    let mut profile = params("web-app");
+    profile.scope = Some("openid profile".to_string());
+    assert!(matches!(
+        service.authorize(profile).await,
+        Err(OidcServiceError::InvalidScope)
+    ));
+}
+
+#[tokio::test]
+async fn test_disabled_client_is_unknown() {
+    let (service, _, secret) = service();
+    let code = issued_code(&service, "web-app").await;
+    service
+        .client_repo
+        .clients
+        .lock()
+        .unwrap()
+        .iter_mut()
+        .for_each(|client| client.set_active(false));
+
+    assert!(matches!(
+        service.authorize(params("web-app")).await,
+        Err(OidcServiceError::UnknownClient)
+    ));
+    let result = service
+        .exchange_code(
+            &code,
+            "web-app",
+            Some(secret.as_str()),
+            REDIRECT_URI,
+            VERIFIER,
+        )
+        .await;
+    assert!(matches!(result, Err(OidcServiceError::InvalidClient)));
+}
+
+#[tokio::test]
+async fn test_grant_type_not_allowed_for_client() {
+    let (service, _, secret) = service();
+    service
+        .client_repo
+        .clients
+        .lock()
+        .unwrap()
+        .iter_mut()
+        .for_each(|client| client.allowed_grant_types = vec![GrantType::RefreshToken]);
+
+    assert!(matches!(
+        service.authorize(params("web-app")).await,
+        Err(OidcServiceError::UnauthorizedClient)
+    ));
+    let result = service
+        .authenticate_client(
+            "web-app",
+            Some(secret.as_str()),
+            GrantType::AuthorizationCode,
+        )
+        .await;
+    assert!(matches!(result, Err(OidcServiceError::UnauthorizedClient)));
+    assert!(service
+        .authenticate_client("web-app", Some(secret.as_str()), GrantType::RefreshToken)
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_client_credentials_scope() {
+    let (service, _, _) = service();
+    let client = service.get_client("web-app").await.unwrap().unwrap();
+
+    assert_eq!(
+        service.client_credentials_scope(&client, None).unwrap(),
+        "openid email"
+    );
+    assert_eq!(
+        service
+            .client_credentials_scope(&client, Some("email"))
+            .unwrap(),
+        "email"
+    );
+    assert!(matches!(
+        service.client_credentials_scope(&client, Some("email profile")),
+        Err(OidcServiceError::InvalidScope)
+    ));
+
+    let public = service.get_client("spa").await.unwrap().unwrap();
+    assert!(matches!(
+        service.client_credentials_scope(&public, None),
+        Err(OidcServiceError::UnauthorizedClient)
+    ));
+}
+
+// registers a public `client_id` that may only use the device code grant
+fn register_device_client(service: &TestOidcService, client_id: &str) {
+    let (client, _) = OidcClient::register(ClientRegistration {
+        client_id: client_id.to_string(),
+        confidential: false,
+        redirect_uris: Vec::new(),
+        allowed_scopes: vec!["openid".to_string(), "email".to_string()],
+        allowed_grant_types: vec![GrantType::DeviceCode],
+        access_token_ttl: None,
+        refresh_token_ttl: None,
+        audience: None,
+        jwks: None,
+    })
+    .unwrap();
+    service.client_repo.clients.lock().unwrap().push(client);
+}
+
+#[tokio::test]
+async fn test_device_authorization_flow() {
+    let (service, repo, _) = service();
+    register_device_client(&service, "cli");
+    let user_id = Uuid::now_v7();
+
+    let (device_code, authorization) = service
+        .start_device_authorization("cli", None, None)
+        .await
+        .unwrap();
+    assert_eq!(authorization.scope, "openid email");
+    assert_eq!(authorization.interval, DEVICE_POLL_INTERVAL);
+
+    assert!(matches!(
+        service
+            .exchange_device_code(&device_code, "cli", None)
+            .await,
+        Err(OidcServiceError::AuthorizationPending)
+    ));
+    assert!(matches!(
+        service
+            .exchange_device_code(&device_code, "cli", None)
+            .await,
+        Err(OidcServiceError::SlowDown)
+    ));
+
+    // typed in lower case and without the dash
+    let typed = authorization.user_code.replace('-', "").to_lowercase();
+    let found = service.find_device_authorization(&typed).await.unwrap();
+    assert_eq!(found.client_id, "cli");
+    service.approve_device(&typed, user_id).await.unwrap();
+    assert!(matches!(
+        service.approve_device(&typed, Uuid::now_v7()).await,
+        Err(OidcServiceError::InvalidUserCode)
+    ));
+
+    repo.device_polls.lock().unwrap().clear();
+    let (approved_user, approved, client) = service
+        .exchange_device_code(&device_code, "cli", None)
+        .await
+        .unwrap();
+    assert_eq!(approved_user, user_id);
+    assert_eq!(approved.scope, "openid email");
+    assert_eq!(client.client_id, "cli");
+
+    repo.device_polls.lock().unwrap().clear();
+    assert!(matches!(
+        service
+            .exchange_device_code(&device_code, "cli", None)
+            .await,
+        Err(OidcServiceError::ExpiredToken)
+    ));
+}
+
+#[tokio::test]
+async fn test_device_code_is_bound_to_its_client() {
+    let (service, _, _) = service();
+    register_device_client(&service, "cli");
+    register_device_client(&service, "other-cli");
+
+    assert!(matches!(
+        service.start_device_authorization("spa", None, None).await,
+        Err(OidcServiceError::UnauthorizedClient)
+    ));
+    assert!(matches!(
+        service
+            .start_device_authorization("cli", None, Some("profile"))
+            .await,
+        Err(OidcServiceError::InvalidScope)
+    ));
+    assert!(matches!(
+        service.find_device_authorization("BCDF-GHJK").await,
+        Err(OidcServiceError::InvalidUserCode)
+    ));
+
+    let (device_code, _) = service
+        .start_device_authorization("cli", None, Some("openid"))
+        .await
+        .unwrap();
+    assert!(matches!(
+        service
+            .exchange_device_code(&device_code, "other-cli", None)
+            .await,
+        Err(OidcServiceError::InvalidGrant(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_wrong_user_codes_lock_out_user_code_entry() {
+    let (service, repo, _) = service();
+    register_device_client(&service, "cli");
+    let (_, authorization) = service
+        .start_device_authorization("cli", None, None)
+        .await
+        .unwrap();
+
+    for _ in 0..MAX_USER_CODE_FAILURES {
+        assert!(matches!(
+            service.find_device_authorization("BCDF-GHJK").await,
+            Err(OidcServiceError::InvalidUserCode)
+        ));
+    }
+    assert_eq!(*repo.user_code_failures.lock().unwrap(), MAX_USER_CODE_FAILURES);
+
+    // even the right code is refused until the window ends
+    assert!(matches!(
+        service
+            .find_device_authorization(&authorization.user_code)
+            .await,
+        Err(OidcServiceError::TooManyUserCodeAttempts)
+    ));
+    *repo.user_code_failures.lock().unwrap() = 0;
+    assert!(service
+        .find_device_authorization(&authorization.user_code)
+        .await
+        .is_ok());
+}
+
+// registers `billing-worker` with an Ed25519 key and returns an assertion signed with it
+fn register_jwks_client(service: &TestOidcService) -> String {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let jwks = serde_json::from_value(serde_json::json!({
+        "keys": [{
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "kid": "worker-key",
+            "x": BASE64_URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+        }]
+    }))
+    .unwrap();
+    let (client, _) = OidcClient::register(ClientRegistration {
+        client_id: "billing-worker".to_string(),
+        confidential: false,
+        redirect_uris: Vec::new(),
+        allowed_scopes: vec!["orders:read".to_string()],
+        allowed_grant_types: vec![GrantType::ClientCredentials],
+        access_token_ttl: None,
+        refresh_token_ttl: None,
+        audience: None,
+        jwks: Some(jwks),
+    })
+    .unwrap();
+    service.client_repo.clients.lock().unwrap().push(client);
+
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some("worker-key".to_string());
+    let claims = serde_json::json!({
+        "iss": "billing-worker",
+        "sub": "billing-worker",
+        "aud": service.token_endpoint(),
+        "exp": chrono::Utc::now().timestamp() + 60,
+        "jti": Uuid::now_v7().to_string(),
+    });
+    jsonwebtoken::encode(&header, &claims, &EncodingKey::from_ed_der(pkcs8.as_ref())).unwrap()
+}
+
+#[tokio::test]
+async fn test_client_assertion_authentication() {
+    let (service, _, _) = service();
+    let assertion = register_jwks_client(&service);
+
+    let wrong_type = service
+        .authenticate_client_assertion(
+            None,
+            "urn:example:other",
+            &assertion,
+            GrantType::ClientCredentials,
+        )
+        .await;
+    assert!(matches!(wrong_type, Err(OidcServiceError::InvalidClient)));
+
+    let client = service
+        .authenticate_client_assertion(
+            None,
+            CLIENT_ASSERTION_TYPE,
+            &assertion,
+            GrantType::ClientCredentials,
+        )
+        .await
+        .unwrap();
+    assert_eq!(client.client_id, "billing-worker");
+    assert_eq!(
+        service.client_credentials_scope(&client, None).unwrap(),
+        "orders:read"
+    );
+
+    // an assertion is single use
+    let replay = service
+        .authenticate_client_assertion(
+            Some("billing-worker"),
+            CLIENT_ASSERTION_TYPE,
+            &assertion,
+            GrantType::ClientCredentials,
+        )
+        .await;
+    assert!(matches!(replay, Err(OidcServiceError::InvalidClient)));
+}
+
+#[tokio::test]
+async fn test_client_assertion_of_other_client() {
+    let (service, _, _) = service();
+    let assertion = register_jwks_client(&service);
+
+    let result = service
+        .authenticate_client_assertion(
+            Some("web-app"),
+            CLIENT_ASSERTION_TYPE,
+            &assertion,
+            GrantType::ClientCredentials,
+        )
+        .await;
+    assert!(matches!(result, Err(OidcServiceError::InvalidClient)));
+}
@@ -0,0 +1,503 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    application::port::organization_repository::{OrganizationRepo, OrganizationRepoError},
+    domain::{
+        oauth::sau_jwt::OrgClaim,
+        organization::{
+            invitation::{Invitation, InvitationTarget, OpaqueInvitationToken},
+            sau_organization::{
+                validate_name, MemberRole, Organization, OrganizationMember, Team, TeamMember,
+            },
+        },
+        user::{sau_user::SAUUser, user_identity::UserIdentity},
+    },
+};
+
+const INVITATION_TTL_DAYS: i64 = 7;
+
+// every method acting for a user checks its role in the organization,
+// an organization the user is not a member of is reported as not found.
+#[derive(Clone)]
+pub struct OrganizationService<O: OrganizationRepo> {
+    organization_repo: O,
+    orgs_claim: bool,
+}
+
+impl<O: OrganizationRepo> OrganizationService<O> {
+    pub fn new(organization_repo: O, orgs_claim: bool) -> Self {
+        Self {
+            organization_repo,
+            orgs_claim,
+        }
+    }
+
+    // the creator becomes its first owner
+    pub async fn create_organization(
+        &self,
+        actor: Uuid,
+        slug: String,
+        name: String,
+    ) -> Result<Organization, OrganizationServiceError> {
+        let organization = Organization::new(slug, name)
+            .map_err(|e| OrganizationServiceError::InvalidOrganization(e.to_string()))?;
+        let owner = OrganizationMember::new(organization.id, actor, MemberRole::Owner);
+        self.organization_repo
+            .create_organization(&organization, &owner)
+            .await
+            .map_err(repository_error)?;
+        Ok(organization)
+    }
+
+    pub async fn list_organizations(
+        &self,
+        actor: Uuid,
+    ) -> Result<Vec<(Organization, MemberRole)>, OrganizationServiceError> {
+        self.organization_repo
+            .list_organizations_by_user_id(actor)
+            .await
+            .map_err(repository_error)
+    }
+
+    pub async fn get_organization(
+        &self,
+        actor: Uuid,
+        slug: &str,
+    ) -> Result<(Organization, MemberRole), OrganizationServiceError> {
+        self.membership(actor, slug).await
+    }
+
+    pub async fn rename_organization(
+        &self,
+        actor: Uuid,
+        slug: &str,
+        name: String,
+    ) -> Result<(Organization, MemberRole), OrganizationServiceError> {
+        let (organization, role) = self.managed(actor, slug).await?;
+        let name = validate_name(name)
+            .map_err(|e| OrganizationServiceError::InvalidOrganization(e.to_string()))?;
+        let organization = self
+            .organization_repo
+            .update_organization_name(organization.id, &name)
+            .await
+            .map_err(repository_error)?
+            .ok_or(OrganizationServiceError::OrganizationNotFound)?;
+        Ok((organization, role))
+    }
+
+    pub async fn delete_organization(
+        &self,
+        actor: Uuid,
+        slug: &str,
+    ) -> Result<(), OrganizationServiceError> {
+        let (organization, role) = self.membership(actor, slug).await?;
+        if role != MemberRole::Owner {
+            return Err(OrganizationServiceError::Forbidden);
+        }
+        let deleted = self
+            .organization_repo
+            .delete_organization(organization.id)
+            .await
+            .map_err(repository_error)?;
+        if !deleted {
+            return Err(OrganizationServiceError::OrganizationNotFound);
+        }
+        Ok(())
+    }
+
+    pub async fn list_members(
+        &self,
+        actor: Uuid,
+        slug: &str,
+    ) -> Result<Vec<OrganizationMember>, OrganizationServiceError> {
+        let (organization, _) = self.membership(actor, slug).await?;
+        self.organization_repo
+            .list_members(organization.id)
+            .await
+            .map_err(repository_error)
+    }
+
+    // admins manage members and admins, only an owner can make or unmake owners
+    pub async fn change_member_role(
+        &self,
+        actor: Uuid,
+        slug: &str,
+        user_id: Uuid,
+        role: MemberRole,
+    ) -> Result<OrganizationMember, OrganizationServiceError> {
+        let (organization, actor_role) = self.managed(actor, slug).await?;
+        let member = self.member(organization.id, user_id).await?;
+        if (member.role == MemberRole::Owner || role == MemberRole::Owner)
+            && actor_role != MemberRole::Owner
+        {
+            return Err(OrganizationServiceError::Forbidden);
+        }
+
+        let updated = self
+            .organization_repo
+            .update_member_role(organization.id, user_id, role)
+            .await
+            .map_err(repository_error)?;
+        if !updated {
+            return Err(OrganizationServiceError::MemberNotFound);
+        }
+        Ok(OrganizationMember { role, ..member })
+    }
+
+    // any member may leave, removing someone else needs an admin or an owner for an owner
+    pub async fn remove_member(
+        &self,
+        actor: Uuid,
+        slug: &str,
+        user_id: Uuid,
+    ) -> Result<(), OrganizationServiceError> {
+        let (organization, actor_role) = self.membership(actor, slug).await?;
+        if actor != user_id {
+            let member = self.member(organization.id, user_id).await?;
+            let allowed = actor_role.can_manage()
+                && (member.role != MemberRole::Owner || actor_role == MemberRole::Owner);
+            if !allowed {
+                return Err(OrganizationServiceError::Forbidden);
+            }
+        }
+
+        let removed = self
+            .organization_repo
+            .remove_member(organization.id, user_id)
+            .await
+            .map_err(repository_error)?;
+        if !removed {
+            return Err(OrganizationServiceError::MemberNotFound);
+        }
+        Ok(())
+    }
+
+    pub async fn create_team(
+        &self,
+        actor: Uuid,
+        slug: &str,
+        team_slug: String,
+        name: String,
+    ) -> Result<Team, OrganizationServiceError> {
+        let (organization, _) = self.managed(actor, slug).await?;
+        let team = Team::new(organization.id, team_slug, name)
+            .map_err(|e| OrganizationServiceError::InvalidOrganization(e.to_string()))?;
+        self.organization_repo
+            .create_team(&team)
+            .await
+            .map_err(repository_error)?;
+        Ok(team)
+    }
+
+    pub async fn list_teams(
+        &self,
+        actor: Uuid,
+        slug: &str,
+    ) -> Result<Vec<Team>, OrganizationServiceError> {
+        let (organization, _) = self.membership(actor, slug).await?;
+        self.organization_repo
+            .list_teams(organization.id)
+            .await
+            .map_err(repository_error)
+    }
+
+    pub async fn get_team(
+        &self,
+        actor: Uuid,
+        slug: &str,
+        team_slug: &str,
+    ) -> Result<(Team, Vec<TeamMember>), OrganizationServiceError> {
+        let (organization, _) = self.membership(actor, slug).await?;
+        let team = self.team(organization.id, team_slug).await?;
+        let members = self
+            .organization_repo
+            .list_team_members(team.id)
+            .await
+            .map_err(repository_error)?;
+        Ok((team, members))
+    }
+
+    pub async fn delete_team(
+        &self,
+        actor: Uuid,
+        slug: &str,
+        team_slug: &str,
+    ) -> Result<(), OrganizationServiceError> {
+        let (organization, _) = self.managed(actor, slug).await?;
+        let team = self.team(organization.id, team_slug).await?;
+        let deleted = self
+            .organization_repo
+            .delete_team(team.id)
+            .await
+            .map_err(repository_error)?;
+        if !deleted {
+            return Err(OrganizationServiceError::TeamNotFound);
+        }
+        Ok(())
+    }
+
+    // only members of the organization can join its teams
+    pub async fn add_team_member(
+        &self,
+        actor: Uuid,
+        slug: &str,
+        team_slug: &str,
+        user_id: Uuid,
+    ) -> Result<(), OrganizationServiceError> {
+        let (organization, _) = self.managed(actor, slug).await?;
+        let team = self.team(organization.id, team_slug).await?;
+        self.member(organization.id, user_id).await?;
+        self.organization_repo
+            .add_team_member(&TeamMember {
+                team_id: team.id,
+                user_id,
+                created_at: Utc::now(),
+            })
+            .await
+            .map_err(repository_error)
+    }
+
+    pub async fn remove_team_member(
+        &self,
+        actor: Uuid,
+        slug: &str,
+        team_slug: &str,
+        user_id: Uuid,
+    ) -> Result<(), OrganizationServiceError> {
+        let (organization, _) = self.managed(actor, slug).await?;
+        let team = self.team(organization.id, team_slug).await?;
+        let removed = self
+            .organization_repo
+            .remove_team_member(team.id, user_id)
+            .await
+            .map_err(repository_error)?;
+        if !removed {
+            return Err(OrganizationServiceError::MemberNotFound);
+        }
+        Ok(())
+    }
+
+    // the token is only returned here, the inviter hands it to the invitee
+    pub async fn invite(
+        &self,
+        actor: Uuid,
+        slug: &str,
+        target: InvitationTarget,
+        role: MemberRole,
+    ) -> Result<(OpaqueInvitationToken, Invitation), OrganizationServiceError> {
+        let (organization, actor_role) = self.managed(actor, slug).await?;
+        if role == MemberRole::Owner && actor_role != MemberRole::Owner {
+            return Err(OrganizationServiceError::Forbidden);
+        }
+
+        let (token, invitation) = Invitation::new(
+            organization.id,
+            target,
+            role,
+            actor,
+            Duration::days(INVITATION_TTL_DAYS),
+        )
+        .map_err(|e| OrganizationServiceError::InvalidOrganization(e.to_string()))?;
+        self.organization_repo
+            .create_invitation(&invitation)
+            .await
+            .map_err(repository_error)?;
+        Ok((token, invitation))
+    }
+
+    pub async fn list_invitations(
+        &self,
+        actor: Uuid,
+        slug: &str,
+    ) -> Result<Vec<Invitation>, OrganizationServiceError> {
+        let (organization, _) = self.managed(actor, slug).await?;
+        self.organization_repo
+            .list_invitations(organization.id)
+            .await
+            .map_err(repository_error)
+    }
+
+    pub async fn revoke_invitation(
+        &self,
+        actor: Uuid,
+        slug: &str,
+        invitation_id: Uuid,
+    ) -> Result<(), OrganizationServiceError> {
+        let (organization, _) = self.managed(actor, slug).await?;
+        let deleted = self
+            .organization_repo
+            .delete_invitation(organization.id, invitation_id)
+            .await
+            .map_err(repository_error)?;
+        if !deleted {
+            return Err(OrganizationServiceError::InvitationNotFound);
+        }
+        Ok(())
+    }
+
+    // `identities` are the ones linked to `user`, an invitation by idp username needs one of them
+    pub async fn accept_invitation(
+        &self,
+        token: &str,
+        user: &SAUUser,
+        identities: &[UserIdentity],
+    ) -> Result<(Organization, MemberRole), OrganizationServiceError> {
+        let invitation = self
+            .organization_repo
+            .get_invitation_by_token_hash(&Invitation::hash(token))
+            .await
+            .map_err(repository_error)?
+            .filter(|invitation| !invitation.is_expired(Utc::now()))
+            .ok_or(OrganizationServiceError::InvitationNotFound)?;
+        if !invitation.is_for(user, identities) {
+            return Err(OrganizationServiceError::InvitationNotForUser);
+        }
+
+        let member = OrganizationMember::new(invitation.organization_id, user.id, invitation.role);
+        let accepted = self
+            .organization_repo
+            .accept_invitation(invitation.id, &member)
+            .await
+            .map_err(repository_error)?;
+        if !accepted {
+            return Err(OrganizationServiceError::InvitationNotFound);
+        }
+
+        let organization = self
+            .organization_repo
+            .get_organization_by_id(invitation.organization_id)
+            .await
+            .map_err(repository_error)?
+            .ok_or(OrganizationServiceError::OrganizationNotFound)?;
+        let member = self.member(organization.id, user.id).await?;
+        Ok((organization, member.role))
+    }
+
+    // empty unless `jwt.orgs_claim` is enabled
+    pub async fn org_claims(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<OrgClaim>, OrganizationServiceError> {
+        if !self.orgs_claim {
+            return Ok(vec![]);
+        }
+        Ok(self
+            .list_organizations(user_id)
+            .await?
+            .into_iter()
+            .map(|(organization, role)| OrgClaim {
+                id: organization.id,
+                slug: organization.slug,
+                role,
+            })
+            .collect())
+    }
+
+    async fn membership(
+        &self,
+        actor: Uuid,
+        slug: &str,
+    ) -> Result<(Organization, MemberRole), OrganizationServiceError> {
+        let organization = self
+            .organization_repo
+            .get_organization_by_slug(slug)
+            .await
+            .map_err(repository_error)?
+            .ok_or(OrganizationServiceError::OrganizationNotFound)?;
+        let member = self
+            .organization_repo
+            .get_member(organization.id, actor)
+            .await
+            .map_err(repository_error)?
+            .ok_or(OrganizationServiceError::OrganizationNotFound)?;
+        Ok((organization, member.role))
+    }
+
+    async fn managed(
+        &self,
+        actor: Uuid,
+        slug: &str,
+    ) -> Result<(Organization, MemberRole), OrganizationServiceError> {
+        let (organization, role) = self.membership(actor, slug).await?;
+        if !role.can_manage() {
+            return Err(OrganizationServiceError::Forbidden);
+        }
+        Ok((organization, role))
+    }
+
+    async fn member(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<OrganizationMember, OrganizationServiceError> {
+        self.organization_repo
+            .get_member(organization_id, user_id)
+            .await
+            .map_err(repository_error)?
+            .ok_or(OrganizationServiceError::MemberNotFound)
+    }
+
+    async fn team(
+        &self,
+        organization_id: Uuid,
+        slug: &str,
+    ) -> Result<Team, OrganizationServiceError> {
+        self.organization_repo
+            .get_team_by_slug(organization_id, slug)
+            .await
+            .map_err(repository_error)?
+            .ok_or(OrganizationServiceError::TeamNotFound)
+    }
+}
+
+fn repository_error(e: OrganizationRepoError) -> OrganizationServiceError {
+    match e {
+        OrganizationRepoError::SlugTaken => OrganizationServiceError::SlugTaken,
+        OrganizationRepoError::TeamSlugTaken => OrganizationServiceError::TeamSlugTaken,
+        OrganizationRepoError::LastOwner => OrganizationServiceError::LastOwner,
+        OrganizationRepoError::UserNotFound => OrganizationServiceError::MemberNotFound,
+        e => OrganizationServiceError::Repository(e.to_string()),
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum OrganizationServiceError {
+    #[error("organization repository error : {0}")]
+    Repository(String),
+
+    #[error("{0}")]
+    InvalidOrganization(String),
+
+    #[error("organization slug is already taken")]
+    SlugTaken,
+
+    #[error("team slug is already taken in the organization")]
+    TeamSlugTaken,
+
+    #[error("organization not found")]
+    OrganizationNotFound,
+
+    #[error("team not found")]
+    TeamNotFound,
+
+    #[error("member not found")]
+    MemberNotFound,
+
+    #[error("role in the organization does not allow this")]
+    Forbidden,
+
+    #[error("an organization needs at least one owner")]
+    LastOwner,
+
+    #[error("invitation is invalid, expired or already used")]
+    InvitationNotFound,
+
+    #[error("invitation is for another user")]
+    InvitationNotForUser,
+}
+
+#[cfg(test)]
+mod tests {
+    include!("organization_service_test.rs");
+}
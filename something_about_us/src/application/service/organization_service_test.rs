@@ -0,0 +1,464 @@
+use super::{OrganizationService, OrganizationServiceError};
+use crate::{
+    application::port::organization_repository::{OrganizationRepo, OrganizationRepoError},
+    domain::{
+        organization::{
+            invitation::{Invitation, InvitationTarget},
+            sau_organization::{MemberRole, Organization, OrganizationMember, Team, TeamMember},
+        },
+        user::sau_user::{Email, SAUUser},
+    },
+};
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+// in-memory repository, every user id is treated as an existing user
+#[derive(Clone, Default)]
+struct MemoryOrganizationRepo {
+    organizations: Arc<Mutex<Vec<Organization>>>,
+    members: Arc<Mutex<Vec<OrganizationMember>>>,
+    teams: Arc<Mutex<Vec<Team>>>,
+    team_members: Arc<Mutex<Vec<TeamMember>>>,
+    invitations: Arc<Mutex<Vec<Invitation>>>,
+}
+
+impl MemoryOrganizationRepo {
+    fn owners_left(&self, organization_id: Uuid, without: Uuid) -> bool {
+        self.members.lock().unwrap().iter().any(|member| {
+            member.organization_id == organization_id
+                && member.user_id != without
+                && member.role == MemberRole::Owner
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl OrganizationRepo for MemoryOrganizationRepo {
+    async fn create_organization(
+        &self,
+        organization: &Organization,
+        owner: &OrganizationMember,
+    ) -> Result<(), OrganizationRepoError> {
+        let mut organizations = self.organizations.lock().unwrap();
+        if organizations
+            .iter()
+            .any(|existing| existing.slug == organization.slug)
+        {
+            return Err(OrganizationRepoError::SlugTaken);
+        }
+        organizations.push(organization.clone());
+        self.members.lock().unwrap().push(owner.clone());
+        Ok(())
+    }
+
+    async fn get_organization_by_id(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Option<Organization>, OrganizationRepoError> {
+        let organizations = self.organizations.lock().unwrap();
+        Ok(organizations
+            .iter()
+            .find(|organization| organization.id == organization_id)
+            .cloned())
+    }
+
+    async fn get_organization_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<Organization>, OrganizationRepoError> {
+        let organizations = self.organizations.lock().unwrap();
+        Ok(organizations
+            .iter()
+            .find(|organization| organization.slug == slug)
+            .cloned())
+    }
+
+    async fn list_organizations_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<(Organization, MemberRole)>, OrganizationRepoError> {
+        let organizations = self.organizations.lock().unwrap();
+        let members = self.members.lock().unwrap();
+        Ok(members
+            .iter()
+            .filter(|member| member.user_id == user_id)
+            .filter_map(|member| {
+                organizations
+                    .iter()
+                    .find(|organization| organization.id == member.organization_id)
+                    .map(|organization| (organization.clone(), member.role))
+            })
+            .collect())
+    }
+
+    async fn update_organization_name(
+        &self,
+        organization_id: Uuid,
+        name: &str,
+    ) -> Result<Option<Organization>, OrganizationRepoError> {
+        let mut organizations = self.organizations.lock().unwrap();
+        Ok(organizations
+            .iter_mut()
+            .find(|organization| organization.id == organization_id)
+            .map(|organization| {
+                organization.name = name.to_string();
+                organization.clone()
+            }))
+    }
+
+    async fn delete_organization(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<bool, OrganizationRepoError> {
+        let mut organizations = self.organizations.lock().unwrap();
+        let before = organizations.len();
+        organizations.retain(|organization| organization.id != organization_id);
+        self.members
+            .lock()
+            .unwrap()
+            .retain(|member| member.organization_id != organization_id);
+        Ok(organizations.len() < before)
+    }
+
+    async fn get_member(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<OrganizationMember>, OrganizationRepoError> {
+        let members = self.members.lock().unwrap();
+        Ok(members
+            .iter()
+            .find(|member| member.organization_id == organization_id && member.user_id == user_id)
+            .cloned())
+    }
+
+    async fn list_members(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<OrganizationMember>, OrganizationRepoError> {
+        let members = self.members.lock().unwrap();
+        Ok(members
+            .iter()
+            .filter(|member| member.organization_id == organization_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn update_member_role(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+        role: MemberRole,
+    ) -> Result<bool, OrganizationRepoError> {
+        if role != MemberRole::Owner && !self.owners_left(organization_id, user_id) {
+            return Err(OrganizationRepoError::LastOwner);
+        }
+        let mut members = self.members.lock().unwrap();
+        Ok(members
+            .iter_mut()
+            .find(|member| member.organization_id == organization_id && member.user_id == user_id)
+            .map(|member| member.role = role)
+            .is_some())
+    }
+
+    async fn remove_member(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, OrganizationRepoError> {
+        if !self.owners_left(organization_id, user_id) {
+            return Err(OrganizationRepoError::LastOwner);
+        }
+        let mut members = self.members.lock().unwrap();
+        let before = members.len();
+        members.retain(|member| {
+            member.organization_id != organization_id || member.user_id != user_id
+        });
+        Ok(members.len() < before)
+    }
+
+    async fn create_team(&self, team: &Team) -> Result<(), OrganizationRepoError> {
+        let mut teams = self.teams.lock().unwrap();
+        if teams.iter().any(|existing| {
+            existing.organization_id == team.organization_id && existing.slug == team.slug
+        }) {
+            return Err(OrganizationRepoError::TeamSlugTaken);
+        }
+        teams.push(team.clone());
+        Ok(())
+    }
+
+    async fn list_teams(&self, organization_id: Uuid) -> Result<Vec<Team>, OrganizationRepoError> {
+        let teams = self.teams.lock().unwrap();
+        Ok(teams
+            .iter()
+            .filter(|team| team.organization_id == organization_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_team_by_slug(
+        &self,
+        organization_id: Uuid,
+        slug: &str,
+    ) -> Result<Option<Team>, OrganizationRepoError> {
+        let teams = self.teams.lock().unwrap();
+        Ok(teams
+            .iter()
+            .find(|team| team.organization_id == organization_id && team.slug == slug)
+            .cloned())
+    }
+
+    async fn delete_team(&self, team_id: Uuid) -> Result<bool, OrganizationRepoError> {
+        let mut teams = self.teams.lock().unwrap();
+        let before = teams.len();
+        teams.retain(|team| team.id != team_id);
+        Ok(teams.len() < before)
+    }
+
+    async fn list_team_members(
+        &self,
+        team_id: Uuid,
+    ) -> Result<Vec<TeamMember>, OrganizationRepoError> {
+        let team_members = self.team_members.lock().unwrap();
+        Ok(team_members
+            .iter()
+            .filter(|member| member.team_id == team_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn add_team_member(&self, member: &TeamMember) -> Result<(), OrganizationRepoError> {
+        let mut team_members = self.team_members.lock().unwrap();
+        if !team_members.iter().any(|existing| {
+            existing.team_id == member.team_id && existing.user_id == member.user_id
+        }) {
+            team_members.push(member.clone());
+        }
+        Ok(())
+    }
+
+    async fn remove_team_member(
+        &self,
+        team_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, OrganizationRepoError> {
+        let mut team_members = self.team_members.lock().unwrap();
+        let before = team_members.len();
+        team_members.retain(|member| member.team_id != team_id || member.user_id != user_id);
+        Ok(team_members.len() < before)
+    }
+
+    async fn create_invitation(
+        &self,
+        invitation: &Invitation,
+    ) -> Result<(), OrganizationRepoError> {
+        self.invitations.lock().unwrap().push(invitation.clone());
+        Ok(())
+    }
+
+    async fn list_invitations(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<Invitation>, OrganizationRepoError> {
+        let invitations = self.invitations.lock().unwrap();
+        Ok(invitations
+            .iter()
+            .filter(|invitation| invitation.organization_id == organization_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_invitation_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Invitation>, OrganizationRepoError> {
+        let invitations = self.invitations.lock().unwrap();
+        Ok(invitations
+            .iter()
+            .find(|invitation| invitation.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn accept_invitation(
+        &self,
+        invitation_id: Uuid,
+        member: &OrganizationMember,
+    ) -> Result<bool, OrganizationRepoError> {
+        let mut invitations = self.invitations.lock().unwrap();
+        let before = invitations.len();
+        invitations.retain(|invitation| invitation.id != invitation_id);
+        if invitations.len() == before {
+            return Ok(false);
+        }
+        let mut members = self.members.lock().unwrap();
+        if !members.iter().any(|existing| {
+            existing.organization_id == member.organization_id && existing.user_id == member.user_id
+        }) {
+            members.push(member.clone());
+        }
+        Ok(true)
+    }
+
+    async fn delete_invitation(
+        &self,
+        organization_id: Uuid,
+        invitation_id: Uuid,
+    ) -> Result<bool, OrganizationRepoError> {
+        let mut invitations = self.invitations.lock().unwrap();
+        let before = invitations.len();
+        invitations.retain(|invitation| {
+            invitation.organization_id != organization_id || invitation.id != invitation_id
+        });
+        Ok(invitations.len() < before)
+    }
+}
+
+fn user(email: &str) -> SAUUser {
+    SAUUser {
+        id: Uuid::now_v7(),
+        username: None,
+        email: Some(Email::new(email.to_string()).unwrap()),
+        email_verified: true,
+        display_name: None,
+        avatar_url: None,
+        is_active: true,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+async fn service_with_organization() -> (OrganizationService<MemoryOrganizationRepo>, Uuid) {
+    let service = OrganizationService::new(MemoryOrganizationRepo::default(), true);
+    let owner = Uuid::now_v7();
+    service
+        .create_organization(owner, "acme".to_string(), "Acme".to_string())
+        .await
+        .unwrap();
+    (service, owner)
+}
+
+#[tokio::test]
+async fn test_create_organization() {
+    let (service, owner) = service_with_organization().await;
+
+    let (organization, role) = service.get_organization(owner, "acme").await.unwrap();
+    assert_eq!(organization.name, "Acme");
+    assert_eq!(role, MemberRole::Owner);
+    let claims = service.org_claims(owner).await.unwrap();
+    assert_eq!(claims.len(), 1);
+    assert_eq!(claims[0].slug, "acme");
+
+    let result = service
+        .create_organization(Uuid::now_v7(), "acme".to_string(), "Other".to_string())
+        .await;
+    assert!(matches!(result, Err(OrganizationServiceError::SlugTaken)));
+    let result = service.get_organization(Uuid::now_v7(), "acme").await;
+    assert!(matches!(
+        result,
+        Err(OrganizationServiceError::OrganizationNotFound)
+    ));
+    let result = service.remove_member(owner, "acme", owner).await;
+    assert!(matches!(result, Err(OrganizationServiceError::LastOwner)));
+}
+
+#[tokio::test]
+async fn test_invitation_flow() {
+    let (service, owner) = service_with_organization().await;
+    let invitee = user("invitee@example.com");
+    let target = InvitationTarget::Email(Email::new("invitee@example.com".to_string()).unwrap());
+
+    let (token, _) = service
+        .invite(owner, "acme", target, MemberRole::Admin)
+        .await
+        .unwrap();
+    let result = service
+        .accept_invitation(&token, &user("other@example.com"), &[])
+        .await;
+    assert!(matches!(
+        result,
+        Err(OrganizationServiceError::InvitationNotForUser)
+    ));
+
+    let (organization, role) = service
+        .accept_invitation(&token, &invitee, &[])
+        .await
+        .unwrap();
+    assert_eq!(organization.slug, "acme");
+    assert_eq!(role, MemberRole::Admin);
+    let result = service.accept_invitation(&token, &invitee, &[]).await;
+    assert!(matches!(
+        result,
+        Err(OrganizationServiceError::InvitationNotFound)
+    ));
+
+    // an admin cannot hand out ownership
+    let target = InvitationTarget::Email(Email::new("third@example.com".to_string()).unwrap());
+    let result = service
+        .invite(invitee.id, "acme", target, MemberRole::Owner)
+        .await;
+    assert!(matches!(result, Err(OrganizationServiceError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_teams_and_member_roles() {
+    let (service, owner) = service_with_organization().await;
+    let (token, _) = service
+        .invite(
+            owner,
+            "acme",
+            InvitationTarget::Email(Email::new("member@example.com".to_string()).unwrap()),
+            MemberRole::Member,
+        )
+        .await
+        .unwrap();
+    let member = user("member@example.com");
+    service
+        .accept_invitation(&token, &member, &[])
+        .await
+        .unwrap();
+
+    service
+        .create_team(owner, "acme", "backend".to_string(), "Backend".to_string())
+        .await
+        .unwrap();
+    let result = service
+        .create_team(
+            member.id,
+            "acme",
+            "frontend".to_string(),
+            "Frontend".to_string(),
+        )
+        .await;
+    assert!(matches!(result, Err(OrganizationServiceError::Forbidden)));
+    let result = service
+        .add_team_member(owner, "acme", "backend", Uuid::now_v7())
+        .await;
+    assert!(matches!(
+        result,
+        Err(OrganizationServiceError::MemberNotFound)
+    ));
+
+    service
+        .add_team_member(owner, "acme", "backend", member.id)
+        .await
+        .unwrap();
+    let (_, members) = service
+        .get_team(member.id, "acme", "backend")
+        .await
+        .unwrap();
+    assert_eq!(members.len(), 1);
+
+    service
+        .change_member_role(owner, "acme", member.id, MemberRole::Owner)
+        .await
+        .unwrap();
+    service
+        .change_member_role(member.id, "acme", owner, MemberRole::Member)
+        .await
+        .unwrap();
+    let result = service.remove_member(member.id, "acme", member.id).await;
+    assert!(matches!(result, Err(OrganizationServiceError::LastOwner)));
+    service.remove_member(owner, "acme", owner).await.unwrap();
+}
@@ -0,0 +1,165 @@
+use chrono::{Duration, Utc};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{
+    application::port::refresh_token_repository::RefreshTokenRepo,
+    domain::oauth::{
+        error::SAUOAuthDomainError,
+        oidc_client::{GrantType, OidcClient},
+        refresh_token::{OpaqueRefreshToken, RefreshToken},
+    },
+};
+
+#[derive(Clone)]
+pub struct RefreshTokenService<R: RefreshTokenRepo> {
+    refresh_token_repo: R,
+    refresh_token_ttl: Duration,
+}
+
+impl<R: RefreshTokenRepo> RefreshTokenService<R> {
+    pub fn new(refresh_token_repo: R, refresh_token_ttl: u64) -> Self {
+        Self {
+            refresh_token_repo,
+            refresh_token_ttl: Duration::seconds(refresh_token_ttl as i64),
+        }
+    }
+
+    // starts a new token family, called once per sign-in.
+    pub async fn issue(
+        &self,
+        user_id: Uuid,
+    ) -> Result<OpaqueRefreshToken, RefreshTokenServiceError> {
+        let issued = RefreshToken::new_family(user_id, self.refresh_token_ttl);
+        self.save_family(issued).await
+    }
+
+    // a family bound to `client`, only that client can rotate it.
+    // `scope` is what the client was granted, every refresh answers it again.
+    // `None` for a client not allowed the `refresh_token` grant, nothing is stored then.
+    pub async fn issue_for_client(
+        &self,
+        user_id: Uuid,
+        client: &OidcClient,
+        scope: &str,
+    ) -> Result<Option<OpaqueRefreshToken>, RefreshTokenServiceError> {
+        if !client.allows_grant_type(GrantType::RefreshToken) {
+            return Ok(None);
+        }
+        let ttl = client
+            .refresh_token_ttl
+            .map(|ttl| Duration::seconds(ttl as i64))
+            .unwrap_or(self.refresh_token_ttl);
+        let issued = RefreshToken::new_client_family(
+            user_id,
+            client.client_id.clone(),
+            scope.to_string(),
+            ttl,
+        );
+        self.save_family(issued).await.map(Some)
+    }
+
+    async fn save_family(
+        &self,
+        issued: Result<(OpaqueRefreshToken, RefreshToken), SAUOAuthDomainError>,
+    ) -> Result<OpaqueRefreshToken, RefreshTokenServiceError> {
+        let (token, refresh_token) =
+            issued.map_err(|e| RefreshTokenServiceError::Issue(e.to_string()))?;
+        self.refresh_token_repo
+            .save_refresh_token(&refresh_token)
+            .await
+            .map_err(|e| RefreshTokenServiceError::Repository(e.to_string()))?;
+        Ok(token)
+    }
+
+    // exchanges a refresh token for its successor, returned with its opaque value.
+    // a token is single use, replaying one revokes every token of its family.
+    // `client_id` is the client presenting the token, `None` for first-party logins.
+    pub async fn rotate(
+        &self,
+        token: &str,
+        client_id: Option<&str>,
+    ) -> Result<(RefreshToken, OpaqueRefreshToken), RefreshTokenServiceError> {
+        let refresh_token = self
+            .refresh_token_repo
+            .get_refresh_token_by_hash(&RefreshToken::hash(token))
+            .await
+            .map_err(|e| RefreshTokenServiceError::Repository(e.to_string()))?
+            .ok_or(RefreshTokenServiceError::InvalidToken)?;
+        // checked before anything else, a token presented by the wrong client is not burnt
+        if refresh_token.client_id.as_deref() != client_id {
+            return Err(RefreshTokenServiceError::InvalidToken);
+        }
+
+        if refresh_token.revoked_at.is_some() {
+            return Err(RefreshTokenServiceError::Revoked);
+        }
+        if refresh_token.used_at.is_some() {
+            return Err(self.revoke_reused_family(&refresh_token).await);
+        }
+        if refresh_token.is_expired(Utc::now()) {
+            return Err(RefreshTokenServiceError::Expired);
+        }
+
+        // a client family keeps the ttl it was issued with
+        let ttl = match refresh_token.client_id {
+            Some(_) => refresh_token.lifetime(),
+            None => self.refresh_token_ttl,
+        };
+        let (successor_token, successor) = refresh_token
+            .rotate(ttl)
+            .map_err(|e| RefreshTokenServiceError::Issue(e.to_string()))?;
+        let rotated = self
+            .refresh_token_repo
+            .rotate_refresh_token(refresh_token.id, &successor)
+            .await
+            .map_err(|e| RefreshTokenServiceError::Repository(e.to_string()))?;
+        if !rotated {
+            // lost a race against another use of the same token
+            return Err(self.revoke_reused_family(&refresh_token).await);
+        }
+
+        Ok((successor, successor_token))
+    }
+
+    async fn revoke_reused_family(&self, refresh_token: &RefreshToken) -> RefreshTokenServiceError {
+        warn!(
+            "refresh token reuse detected, revoke family {} of user {}",
+            refresh_token.family_id, refresh_token.user_id
+        );
+        match self
+            .refresh_token_repo
+            .revoke_refresh_token_family(refresh_token.family_id)
+            .await
+        {
+            Ok(()) => RefreshTokenServiceError::ReuseDetected,
+            Err(e) => RefreshTokenServiceError::Repository(e.to_string()),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RefreshTokenServiceError {
+    #[error("refresh token is invalid")]
+    InvalidToken,
+
+    #[error("refresh token is expired")]
+    Expired,
+
+    #[error("refresh token is revoked")]
+    Revoked,
+
+    #[error("refresh token reuse detected")]
+    ReuseDetected,
+
+    #[error("refresh token issue error : {0}")]
+    Issue(String),
+
+    #[error("refresh token repository error : {0}")]
+    Repository(String),
+}
+
+#[cfg(test)]
+mod tests {
+    include!("refresh_token_service_test.rs");
+}
@@ -0,0 +1,161 @@
+use super::{RefreshTokenService, RefreshTokenServiceError};
+use crate::{
+    application::service::token_repo_test::MemoryRefreshTokenRepo,
+    domain::oauth::oidc_client::{ClientRegistration, GrantType, OidcClient},
+};
+use uuid::Uuid;
+
+fn service(ttl: u64) -> RefreshTokenService<MemoryRefreshTokenRepo> {
+    RefreshTokenService::new(MemoryRefreshTokenRepo::default(), ttl)
+}
+
+#[tokio::test]
+async fn test_rotate_returns_user_and_new_token() {
+    let service = service(3600);
+    let user_id = Uuid::now_v7();
+    let token = service.issue(user_id).await.unwrap();
+
+    let (successor, rotated) = service.rotate(&token, None).await.unwrap();
+    assert_eq!(successor.user_id, user_id);
+    assert_ne!(rotated, token);
+
+    let (_, rotated_again) = service.rotate(&rotated, None).await.unwrap();
+    assert_ne!(rotated_again, rotated);
+}
+
+#[tokio::test]
+async fn test_reuse_revokes_family() {
+    let service = service(3600);
+    let token = service.issue(Uuid::now_v7()).await.unwrap();
+    let (_, successor) = service.rotate(&token, None).await.unwrap();
+
+    let reuse = service.rotate(&token, None).await;
+    assert!(matches!(reuse, Err(RefreshTokenServiceError::ReuseDetected)));
+
+    // the legitimate successor is revoked together with the replayed token
+    let result = service.rotate(&successor, None).await;
+    assert!(matches!(result, Err(RefreshTokenServiceError::Revoked)));
+}
+
+#[tokio::test]
+async fn test_reuse_keeps_other_families() {
+    let service = service(3600);
+    let user_id = Uuid::now_v7();
+    let token = service.issue(user_id).await.unwrap();
+    let other_session = service.issue(user_id).await.unwrap();
+    service.rotate(&token, None).await.unwrap();
+
+    assert!(service.rotate(&token, None).await.is_err());
+    assert!(service.rotate(&other_session, None).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_expired_token() {
+    let service = service(0);
+    let token = service.issue(Uuid::now_v7()).await.unwrap();
+
+    let result = service.rotate(&token, None).await;
+    assert!(matches!(result, Err(RefreshTokenServiceError::Expired)));
+}
+
+#[tokio::test]
+async fn test_unknown_token() {
+    let service = service(3600);
+
+    let result = service.rotate("not-issued", None).await;
+    assert!(matches!(result, Err(RefreshTokenServiceError::InvalidToken)));
+}
+
+fn client(refresh_token_ttl: Option<u64>) -> OidcClient {
+    client_with_grants(
+        refresh_token_ttl,
+        vec![GrantType::AuthorizationCode, GrantType::RefreshToken],
+    )
+}
+
+fn client_with_grants(refresh_token_ttl: Option<u64>, grant_types: Vec<GrantType>) -> OidcClient {
+    let (client, _) = OidcClient::register(ClientRegistration {
+        client_id: "web-app".to_string(),
+        confidential: true,
+        redirect_uris: vec!["https://app.example.com/callback".to_string()],
+        allowed_scopes: vec!["openid".to_string()],
+        allowed_grant_types: grant_types,
+        access_token_ttl: None,
+        refresh_token_ttl,
+        audience: None,
+        jwks: None,
+    })
+    .unwrap();
+    client
+}
+
+#[tokio::test]
+async fn test_client_token_is_bound_to_client() {
+    let service = service(3600);
+    let token = service
+        .issue_for_client(Uuid::now_v7(), &client(None), "openid")
+        .await
+        .unwrap()
+        .unwrap();
+
+    let first_party = service.rotate(&token, None).await;
+    assert!(matches!(
+        first_party,
+        Err(RefreshTokenServiceError::InvalidToken)
+    ));
+    let other_client = service.rotate(&token, Some("other-app")).await;
+    assert!(matches!(
+        other_client,
+        Err(RefreshTokenServiceError::InvalidToken)
+    ));
+
+    // the mismatches above did not use the token up
+    assert!(service.rotate(&token, Some("web-app")).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_client_ttl_override_survives_rotation() {
+    let service = service(3600);
+    let repo = service.refresh_token_repo.clone();
+    let token = service
+        .issue_for_client(Uuid::now_v7(), &client(Some(60)), "openid")
+        .await
+        .unwrap()
+        .unwrap();
+    service.rotate(&token, Some("web-app")).await.unwrap();
+
+    let tokens = repo.tokens.lock().unwrap();
+    assert_eq!(tokens.len(), 2);
+    assert!(tokens
+        .iter()
+        .all(|token| token.lifetime() == chrono::Duration::seconds(60)));
+}
+
+#[tokio::test]
+async fn test_client_scope_survives_rotation() {
+    let service = service(3600);
+    let token = service
+        .issue_for_client(Uuid::now_v7(), &client(None), "openid profile")
+        .await
+        .unwrap()
+        .unwrap();
+
+    let (successor, rotated) = service.rotate(&token, Some("web-app")).await.unwrap();
+    assert_eq!(successor.scope.as_deref(), Some("openid profile"));
+    let (successor, _) = service.rotate(&rotated, Some("web-app")).await.unwrap();
+    assert_eq!(successor.scope.as_deref(), Some("openid profile"));
+}
+
+#[tokio::test]
+async fn test_client_without_refresh_grant_gets_no_token() {
+    let service = service(3600);
+    let repo = service.refresh_token_repo.clone();
+    let client = client_with_grants(None, vec![GrantType::AuthorizationCode]);
+
+    let token = service
+        .issue_for_client(Uuid::now_v7(), &client, "openid")
+        .await
+        .unwrap();
+    assert!(token.is_none());
+    assert!(repo.tokens.lock().unwrap().is_empty());
+}
@@ -0,0 +1,148 @@
+use uuid::Uuid;
+
+use crate::{
+    application::port::role_repository::{RoleRepo, RoleRepoError},
+    domain::user::role::{Role, UserGrants},
+};
+
+#[derive(Clone)]
+pub struct RoleService<R: RoleRepo> {
+    role_repo: R,
+}
+
+impl<R: RoleRepo> RoleService<R> {
+    pub fn new(role_repo: R) -> Self {
+        Self { role_repo }
+    }
+
+    pub async fn create_role(
+        &self,
+        name: String,
+        description: Option<String>,
+        permissions: Vec<String>,
+    ) -> Result<Role, RoleServiceError> {
+        let role = Role::new(name, description, permissions)
+            .map_err(|e| RoleServiceError::InvalidRole(e.to_string()))?;
+        self.role_repo
+            .create_role(&role)
+            .await
+            .map_err(|e| match e {
+                RoleRepoError::RoleAlreadyExists => RoleServiceError::RoleAlreadyExists,
+                e => RoleServiceError::Repository(e.to_string()),
+            })?;
+        Ok(role)
+    }
+
+    pub async fn list_roles(&self) -> Result<Vec<Role>, RoleServiceError> {
+        self.role_repo
+            .list_roles()
+            .await
+            .map_err(|e| RoleServiceError::Repository(e.to_string()))
+    }
+
+    pub async fn get_role(&self, name: &str) -> Result<Role, RoleServiceError> {
+        self.role_repo
+            .get_role_by_name(name)
+            .await
+            .map_err(|e| RoleServiceError::Repository(e.to_string()))?
+            .ok_or(RoleServiceError::RoleNotFound)
+    }
+
+    // the new permissions reach a user with its next access token
+    pub async fn update_role(
+        &self,
+        name: &str,
+        description: Option<String>,
+        permissions: Vec<String>,
+    ) -> Result<Role, RoleServiceError> {
+        let mut role = self.get_role(name).await?;
+        role.description = description.filter(|description| !description.trim().is_empty());
+        role.permissions = Role::normalize_permissions(permissions)
+            .map_err(|e| RoleServiceError::InvalidRole(e.to_string()))?;
+        self.role_repo
+            .update_role(&role)
+            .await
+            .map_err(|e| match e {
+                RoleRepoError::RoleNotFound => RoleServiceError::RoleNotFound,
+                e => RoleServiceError::Repository(e.to_string()),
+            })?;
+        Ok(role)
+    }
+
+    pub async fn delete_role(&self, name: &str) -> Result<(), RoleServiceError> {
+        let role = self.get_role(name).await?;
+        let deleted = self
+            .role_repo
+            .delete_role(role.id)
+            .await
+            .map_err(|e| RoleServiceError::Repository(e.to_string()))?;
+        if !deleted {
+            return Err(RoleServiceError::RoleNotFound);
+        }
+        Ok(())
+    }
+
+    pub async fn assign_role(&self, user_id: Uuid, name: &str) -> Result<(), RoleServiceError> {
+        let role = self.get_role(name).await?;
+        self.role_repo
+            .assign_role(user_id, role.id)
+            .await
+            .map_err(|e| match e {
+                RoleRepoError::RoleNotFound => RoleServiceError::RoleNotFound,
+                RoleRepoError::UserNotFound => RoleServiceError::UserNotFound,
+                e => RoleServiceError::Repository(e.to_string()),
+            })
+    }
+
+    pub async fn unassign_role(&self, user_id: Uuid, name: &str) -> Result<(), RoleServiceError> {
+        let role = self.get_role(name).await?;
+        let unassigned = self
+            .role_repo
+            .unassign_role(user_id, role.id)
+            .await
+            .map_err(|e| RoleServiceError::Repository(e.to_string()))?;
+        if !unassigned {
+            return Err(RoleServiceError::RoleNotAssigned);
+        }
+        Ok(())
+    }
+
+    pub async fn get_user_roles(&self, user_id: Uuid) -> Result<Vec<Role>, RoleServiceError> {
+        self.role_repo
+            .get_roles_by_user_id(user_id)
+            .await
+            .map_err(|e| RoleServiceError::Repository(e.to_string()))
+    }
+
+    // roles and permissions put into the access tokens of the user
+    pub async fn grants(&self, user_id: Uuid) -> Result<UserGrants, RoleServiceError> {
+        let roles = self.get_user_roles(user_id).await?;
+        Ok(UserGrants::from_roles(&roles))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RoleServiceError {
+    #[error("role repository error : {0}")]
+    Repository(String),
+
+    #[error("{0}")]
+    InvalidRole(String),
+
+    #[error("role already exists")]
+    RoleAlreadyExists,
+
+    #[error("role not found")]
+    RoleNotFound,
+
+    #[error("role is not assigned to the user")]
+    RoleNotAssigned,
+
+    #[error("user not found")]
+    UserNotFound,
+}
+
+#[cfg(test)]
+mod tests {
+    include!("role_service_test.rs");
+}
@@ -0,0 +1,170 @@
+use super::{RoleService, RoleServiceError};
+use crate::{
+    application::port::role_repository::{RoleRepo, RoleRepoError},
+    domain::user::role::Role,
+};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+// in-memory repository, every user id is treated as an existing user
+#[derive(Clone, Default)]
+struct MemoryRoleRepo {
+    roles: Arc<Mutex<Vec<Role>>>,
+    assignments: Arc<Mutex<Vec<(Uuid, Uuid)>>>,
+}
+
+#[async_trait::async_trait]
+impl RoleRepo for MemoryRoleRepo {
+    async fn list_roles(&self) -> Result<Vec<Role>, RoleRepoError> {
+        Ok(self.roles.lock().unwrap().clone())
+    }
+
+    async fn get_role_by_name(&self, name: &str) -> Result<Option<Role>, RoleRepoError> {
+        let roles = self.roles.lock().unwrap();
+        Ok(roles.iter().find(|role| role.name == name).cloned())
+    }
+
+    async fn create_role(&self, role: &Role) -> Result<(), RoleRepoError> {
+        let mut roles = self.roles.lock().unwrap();
+        if roles.iter().any(|existing| existing.name == role.name) {
+            return Err(RoleRepoError::RoleAlreadyExists);
+        }
+        roles.push(role.clone());
+        Ok(())
+    }
+
+    async fn update_role(&self, role: &Role) -> Result<(), RoleRepoError> {
+        let mut roles = self.roles.lock().unwrap();
+        let existing = roles
+            .iter_mut()
+            .find(|existing| existing.id == role.id)
+            .ok_or(RoleRepoError::RoleNotFound)?;
+        *existing = role.clone();
+        Ok(())
+    }
+
+    async fn delete_role(&self, role_id: Uuid) -> Result<bool, RoleRepoError> {
+        let mut roles = self.roles.lock().unwrap();
+        let before = roles.len();
+        roles.retain(|role| role.id != role_id);
+        self.assignments
+            .lock()
+            .unwrap()
+            .retain(|(_, assigned)| *assigned != role_id);
+        Ok(roles.len() < before)
+    }
+
+    async fn assign_role(&self, user_id: Uuid, role_id: Uuid) -> Result<(), RoleRepoError> {
+        let mut assignments = self.assignments.lock().unwrap();
+        if !assignments.contains(&(user_id, role_id)) {
+            assignments.push((user_id, role_id));
+        }
+        Ok(())
+    }
+
+    async fn unassign_role(&self, user_id: Uuid, role_id: Uuid) -> Result<bool, RoleRepoError> {
+        let mut assignments = self.assignments.lock().unwrap();
+        let before = assignments.len();
+        assignments.retain(|assignment| *assignment != (user_id, role_id));
+        Ok(assignments.len() < before)
+    }
+
+    async fn get_roles_by_user_id(&self, user_id: Uuid) -> Result<Vec<Role>, RoleRepoError> {
+        let assignments = self.assignments.lock().unwrap();
+        let roles = self.roles.lock().unwrap();
+        Ok(roles
+            .iter()
+            .filter(|role| assignments.contains(&(user_id, role.id)))
+            .cloned()
+            .collect())
+    }
+}
+
+fn permissions(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[tokio::test]
+async fn test_create_role() {
+    let service = RoleService::new(MemoryRoleRepo::default());
+
+    let role = service
+        .create_role(
+            "support".to_string(),
+            None,
+            permissions(&["users:read", "clients:read"]),
+        )
+        .await
+        .unwrap();
+    assert_eq!(role.permissions, vec!["clients:read", "users:read"]);
+
+    let result = service
+        .create_role("support".to_string(), None, vec![])
+        .await;
+    assert!(matches!(result, Err(RoleServiceError::RoleAlreadyExists)));
+    let result = service
+        .create_role("Support Team".to_string(), None, vec![])
+        .await;
+    assert!(matches!(result, Err(RoleServiceError::InvalidRole(_))));
+}
+
+#[tokio::test]
+async fn test_grants_follow_assigned_roles() {
+    let service = RoleService::new(MemoryRoleRepo::default());
+    service
+        .create_role("support".to_string(), None, permissions(&["users:read"]))
+        .await
+        .unwrap();
+    service
+        .create_role(
+            "auditor".to_string(),
+            None,
+            permissions(&["users:read", "audit:read"]),
+        )
+        .await
+        .unwrap();
+    let user_id = Uuid::now_v7();
+    assert!(service.grants(user_id).await.unwrap().roles.is_empty());
+
+    service.assign_role(user_id, "support").await.unwrap();
+    service.assign_role(user_id, "auditor").await.unwrap();
+    service.assign_role(user_id, "auditor").await.unwrap();
+    let grants = service.grants(user_id).await.unwrap();
+    assert_eq!(grants.roles, vec!["auditor", "support"]);
+    assert_eq!(grants.scope().as_deref(), Some("audit:read users:read"));
+
+    service.unassign_role(user_id, "auditor").await.unwrap();
+    let result = service.unassign_role(user_id, "auditor").await;
+    assert!(matches!(result, Err(RoleServiceError::RoleNotAssigned)));
+    assert_eq!(
+        service.grants(user_id).await.unwrap().roles,
+        vec!["support"]
+    );
+}
+
+#[tokio::test]
+async fn test_update_and_delete_role() {
+    let service = RoleService::new(MemoryRoleRepo::default());
+    service
+        .create_role("support".to_string(), None, permissions(&["users:read"]))
+        .await
+        .unwrap();
+    let user_id = Uuid::now_v7();
+    service.assign_role(user_id, "support").await.unwrap();
+
+    service
+        .update_role(
+            "support",
+            Some("first line".to_string()),
+            permissions(&["users:write"]),
+        )
+        .await
+        .unwrap();
+    let grants = service.grants(user_id).await.unwrap();
+    assert_eq!(grants.permissions, vec!["users:write"]);
+
+    service.delete_role("support").await.unwrap();
+    assert!(service.grants(user_id).await.unwrap().roles.is_empty());
+    let result = service.assign_role(user_id, "support").await;
+    assert!(matches!(result, Err(RoleServiceError::RoleNotFound)));
+}
@@ -0,0 +1,123 @@
+// in-memory token repositories shared by the service tests
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use uuid::Uuid;
+
+use crate::{
+    application::port::{
+        refresh_token_repository::{RefreshTokenRepo, RefreshTokenRepoError},
+        revoked_token_repository::{RevokedTokenCacheRepo, RevokedTokenCacheRepoError},
+    },
+    domain::oauth::refresh_token::RefreshToken,
+};
+
+// ttls are recorded but entries never expire during a test
+#[derive(Clone, Default)]
+pub struct MemoryRevokedTokenRepo {
+    pub jtis: Arc<Mutex<HashMap<Uuid, u64>>>,
+    pub users: Arc<Mutex<HashMap<Uuid, (i64, u64)>>>,
+}
+
+#[async_trait::async_trait]
+impl RevokedTokenCacheRepo for MemoryRevokedTokenRepo {
+    async fn set_revoked_jti(&self, jti: Uuid, ttl: u64) -> Result<(), RevokedTokenCacheRepoError> {
+        self.jtis.lock().unwrap().insert(jti, ttl);
+        Ok(())
+    }
+
+    async fn is_revoked_jti(&self, jti: Uuid) -> Result<bool, RevokedTokenCacheRepoError> {
+        Ok(self.jtis.lock().unwrap().contains_key(&jti))
+    }
+
+    async fn set_user_revoked_before(
+        &self,
+        user_id: Uuid,
+        revoked_before: i64,
+        ttl: u64,
+    ) -> Result<(), RevokedTokenCacheRepoError> {
+        self.users
+            .lock()
+            .unwrap()
+            .insert(user_id, (revoked_before, ttl));
+        Ok(())
+    }
+
+    async fn get_user_revoked_before(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<i64>, RevokedTokenCacheRepoError> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .get(&user_id)
+            .map(|(revoked_before, _)| *revoked_before))
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct MemoryRefreshTokenRepo {
+    pub tokens: Arc<Mutex<Vec<RefreshToken>>>,
+}
+
+#[async_trait::async_trait]
+impl RefreshTokenRepo for MemoryRefreshTokenRepo {
+    async fn save_refresh_token(&self, token: &RefreshToken) -> Result<(), RefreshTokenRepoError> {
+        self.tokens.lock().unwrap().push(token.clone());
+        Ok(())
+    }
+
+    async fn get_refresh_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshToken>, RefreshTokenRepoError> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens
+            .iter()
+            .find(|token| token.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn rotate_refresh_token(
+        &self,
+        used_id: Uuid,
+        successor: &RefreshToken,
+    ) -> Result<bool, RefreshTokenRepoError> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let Some(used) = tokens.iter_mut().find(|token| {
+            token.id == used_id && token.used_at.is_none() && token.revoked_at.is_none()
+        }) else {
+            return Ok(false);
+        };
+        used.used_at = Some(chrono::Utc::now());
+        tokens.push(successor.clone());
+        Ok(true)
+    }
+
+    async fn revoke_refresh_token_family(
+        &self,
+        family_id: Uuid,
+    ) -> Result<(), RefreshTokenRepoError> {
+        let mut tokens = self.tokens.lock().unwrap();
+        for token in tokens
+            .iter_mut()
+            .filter(|token| token.family_id == family_id)
+        {
+            token.revoked_at.get_or_insert(chrono::Utc::now());
+        }
+        Ok(())
+    }
+
+    async fn revoke_refresh_tokens_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<(), RefreshTokenRepoError> {
+        let mut tokens = self.tokens.lock().unwrap();
+        for token in tokens.iter_mut().filter(|token| token.user_id == user_id) {
+            token.revoked_at.get_or_insert(chrono::Utc::now());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    application::port::{
+        refresh_token_repository::RefreshTokenRepo, revoked_token_repository::RevokedTokenCacheRepo,
+    },
+    domain::oauth::{refresh_token::RefreshToken, sau_jwt::SAUClaims},
+};
+
+#[derive(Clone)]
+pub struct TokenRevocationService<C: RevokedTokenCacheRepo, R: RefreshTokenRepo> {
+    revoked_token_repo: C,
+    refresh_token_repo: R,
+    access_token_ttl: u64,
+    // presented tokens are accepted this many seconds past their `exp`
+    leeway: u64,
+}
+
+impl<C: RevokedTokenCacheRepo, R: RefreshTokenRepo> TokenRevocationService<C, R> {
+    pub fn new(
+        revoked_token_repo: C,
+        refresh_token_repo: R,
+        access_token_ttl: u64,
+        leeway: u64,
+    ) -> Self {
+        Self {
+            revoked_token_repo,
+            refresh_token_repo,
+            access_token_ttl,
+            leeway,
+        }
+    }
+
+    // the `jti` is kept only for the remaining lifetime of the token with the leeway,
+    // an expired token is rejected by its `exp` anyway.
+    pub async fn revoke_access_token(
+        &self,
+        claims: &SAUClaims,
+    ) -> Result<(), TokenRevocationServiceError> {
+        let remaining = claims.exp + self.leeway as i64 - Utc::now().timestamp();
+        if remaining <= 0 {
+            return Ok(());
+        }
+        self.revoked_token_repo
+            .set_revoked_jti(claims.jti, remaining as u64)
+            .await
+            .map_err(|e| TokenRevocationServiceError::Cache(e.to_string()))
+    }
+
+    // revokes the whole family of the refresh token.
+    // returns false when the token is unknown, which is not an error for revocation.
+    pub async fn revoke_refresh_token(
+        &self,
+        token: &str,
+    ) -> Result<bool, TokenRevocationServiceError> {
+        let refresh_token = self
+            .refresh_token_repo
+            .get_refresh_token_by_hash(&RefreshToken::hash(token))
+            .await
+            .map_err(|e| TokenRevocationServiceError::Repository(e.to_string()))?;
+        let Some(refresh_token) = refresh_token else {
+            return Ok(false);
+        };
+        self.refresh_token_repo
+            .revoke_refresh_token_family(refresh_token.family_id)
+            .await
+            .map_err(|e| TokenRevocationServiceError::Repository(e.to_string()))?;
+        Ok(true)
+    }
+
+    // revokes every access token issued to the user so far and all of its refresh tokens,
+    // e.g. when the account is deactivated. kept in milliseconds, a token issued right after
+    // in the same second (e.g. on reactivation) stays valid.
+    pub async fn revoke_all_for_user(
+        &self,
+        user_id: Uuid,
+    ) -> Result<(), TokenRevocationServiceError> {
+        self.revoked_token_repo
+            .set_user_revoked_before(
+                user_id,
+                Utc::now().timestamp_millis(),
+                self.access_token_ttl + self.leeway,
+            )
+            .await
+            .map_err(|e| TokenRevocationServiceError::Cache(e.to_string()))?;
+        self.refresh_token_repo
+            .revoke_refresh_tokens_by_user_id(user_id)
+            .await
+            .map_err(|e| TokenRevocationServiceError::Repository(e.to_string()))
+    }
+
+    pub async fn is_revoked(
+        &self,
+        claims: &SAUClaims,
+    ) -> Result<bool, TokenRevocationServiceError> {
+        let revoked_before = self
+            .revoked_token_repo
+            .get_user_revoked_before(claims.sub)
+            .await
+            .map_err(|e| TokenRevocationServiceError::Cache(e.to_string()))?;
+        if revoked_before.is_some_and(|revoked_before| claims.issued_at_millis() <= revoked_before)
+        {
+            return Ok(true);
+        }
+        self.revoked_token_repo
+            .is_revoked_jti(claims.jti)
+            .await
+            .map_err(|e| TokenRevocationServiceError::Cache(e.to_string()))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TokenRevocationServiceError {
+    #[error("revoked token cache error : {0}")]
+    Cache(String),
+
+    #[error("refresh token repository error : {0}")]
+    Repository(String),
+}
+
+#[cfg(test)]
+mod tests {
+    include!("token_revocation_service_test.rs");
+}
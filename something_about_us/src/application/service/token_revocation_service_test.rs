@@ -0,0 +1,125 @@
+use super::TokenRevocationService;
+use crate::{
+    application::{
+        port::refresh_token_repository::RefreshTokenRepo,
+        service::token_repo_test::{MemoryRefreshTokenRepo, MemoryRevokedTokenRepo},
+    },
+    domain::oauth::{refresh_token::RefreshToken, sau_jwt::SAUClaims},
+};
+use uuid::Uuid;
+
+type TestService = TokenRevocationService<MemoryRevokedTokenRepo, MemoryRefreshTokenRepo>;
+
+fn service() -> (TestService, MemoryRevokedTokenRepo, MemoryRefreshTokenRepo) {
+    let revoked = MemoryRevokedTokenRepo::default();
+    let refresh = MemoryRefreshTokenRepo::default();
+    (
+        TokenRevocationService::new(revoked.clone(), refresh.clone(), 3600, 60),
+        revoked,
+        refresh,
+    )
+}
+
+fn claims(sub: Uuid, iat: i64, exp: i64) -> SAUClaims {
+    SAUClaims {
+        aud: "test-audience".to_string(),
+        iss: "test-issuer".to_string(),
+        sub,
+        exp,
+        jti: Uuid::now_v7(),
+        iat,
+        nbf: iat,
+        roles: vec![],
+        scope: None,
+        orgs: vec![],
+    }
+}
+
+async fn save_refresh_token(repo: &MemoryRefreshTokenRepo, user_id: Uuid) -> (String, Uuid) {
+    let (token, refresh_token) =
+        RefreshToken::new_family(user_id, chrono::Duration::hours(1)).unwrap();
+    repo.save_refresh_token(&refresh_token).await.unwrap();
+    (token, refresh_token.family_id)
+}
+
+#[tokio::test]
+async fn test_revoke_access_token_for_remaining_lifetime() {
+    let (service, revoked, _) = service();
+    let now = chrono::Utc::now().timestamp();
+    let token = claims(Uuid::now_v7(), now, now + 600);
+
+    assert!(!service.is_revoked(&token).await.unwrap());
+    service.revoke_access_token(&token).await.unwrap();
+    assert!(service.is_revoked(&token).await.unwrap());
+
+    // a token is accepted up to `leeway` past its `exp`
+    let ttl = revoked.jtis.lock().unwrap()[&token.jti];
+    assert!(ttl > 600 && ttl <= 660);
+}
+
+#[tokio::test]
+async fn test_revoke_expired_access_token_is_noop() {
+    let (service, revoked, _) = service();
+    let now = chrono::Utc::now().timestamp();
+    let token = claims(Uuid::now_v7(), now - 600, now - 61);
+
+    service.revoke_access_token(&token).await.unwrap();
+    assert!(revoked.jtis.lock().unwrap().is_empty());
+
+    // still accepted within the leeway, so it is still revoked
+    let token = claims(Uuid::now_v7(), now - 600, now - 1);
+    service.revoke_access_token(&token).await.unwrap();
+    assert!(service.is_revoked(&token).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_revoke_refresh_token_revokes_family() {
+    let (service, _, refresh) = service();
+    let user_id = Uuid::now_v7();
+    let (token, family_id) = save_refresh_token(&refresh, user_id).await;
+    let (_, other_family_id) = save_refresh_token(&refresh, user_id).await;
+
+    assert!(service.revoke_refresh_token(&token).await.unwrap());
+
+    let tokens = refresh.tokens.lock().unwrap();
+    for token in tokens.iter() {
+        assert_eq!(token.revoked_at.is_some(), token.family_id == family_id);
+    }
+    assert!(tokens
+        .iter()
+        .any(|token| token.family_id == other_family_id));
+}
+
+#[tokio::test]
+async fn test_revoke_unknown_refresh_token() {
+    let (service, _, _) = service();
+    assert!(!service.revoke_refresh_token("not-issued").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_revoke_all_for_user() {
+    let (service, revoked, refresh) = service();
+    let user_id = Uuid::now_v7();
+    let other_user_id = Uuid::now_v7();
+    let now = chrono::Utc::now().timestamp();
+    save_refresh_token(&refresh, user_id).await;
+    save_refresh_token(&refresh, other_user_id).await;
+    let issued_before = claims(user_id, now, now + 600);
+    let other_user = claims(other_user_id, now, now + 600);
+    tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+
+    service.revoke_all_for_user(user_id).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    // e.g. a login right after a reactivation, within the same second
+    let issued_after = claims(user_id, now, now + 600);
+
+    assert!(service.is_revoked(&issued_before).await.unwrap());
+    assert!(!service.is_revoked(&issued_after).await.unwrap());
+    assert!(!service.is_revoked(&other_user).await.unwrap());
+    assert_eq!(revoked.users.lock().unwrap()[&user_id].1, 3660);
+
+    let tokens = refresh.tokens.lock().unwrap();
+    for token in tokens.iter() {
+        assert_eq!(token.revoked_at.is_some(), token.user_id == user_id);
+    }
+}
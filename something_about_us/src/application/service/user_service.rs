@@ -1,40 +1,394 @@
+use tracing::{info, warn};
+use uuid::Uuid;
+
 use crate::{
-    application::port::sau_user_repository::SAUUserRepo,
-    domain::{idp::supported_idp::SupportIdp, user::sau_user::SAUUser},
+    application::port::sau_user_repository::{SAUUserRepo, SAUUserRepoError},
+    domain::{
+        idp::supported_idp::SupportIdp,
+        oauth::oauth_provider::IdpIdentity,
+        user::{
+            sau_user::{Email, SAUUser, Username, MAX_USERNAME_LEN},
+            user_filter::{UserFilter, UserPage},
+            user_identity::UserIdentity,
+            user_profile::{self, UserProfile},
+        },
+    },
 };
 
+pub const DEFAULT_USER_PAGE_SIZE: u64 = 50;
+pub const MAX_USER_PAGE_SIZE: u64 = 100;
+// `<username>-2` up to this suffix is tried when the idp username is taken
+const MAX_USERNAME_SUFFIX: u32 = 9;
+
 #[derive(Clone)]
 pub struct UserService<U: SAUUserRepo> {
     user_repo: U,
+    refresh_profile_on_login: bool,
 }
 
 // #[async_trait::async_trait]
 impl<U: SAUUserRepo> UserService<U> {
-    pub fn new(user_repo: U) -> Self {
-        Self { user_repo }
+    pub fn new(user_repo: U, refresh_profile_on_login: bool) -> Self {
+        Self {
+            user_repo,
+            refresh_profile_on_login,
+        }
+    }
+
+    pub async fn get_user_by_id(&self, user_id: Uuid) -> Result<Option<SAUUser>, UserServiceError> {
+        self.user_repo
+            .get_user_by_id(user_id)
+            .await
+            .map_err(|e| UserServiceError::UserFetch(e.to_string()))
+    }
+
+    // the user a token is about to be issued to, refused once deactivated
+    pub async fn get_active_user(&self, user_id: Uuid) -> Result<SAUUser, UserServiceError> {
+        let user = self
+            .get_user_by_id(user_id)
+            .await?
+            .ok_or(UserServiceError::UserNotFound)?;
+        if !user.is_active {
+            return Err(UserServiceError::UserInactive);
+        }
+        Ok(user)
     }
 
+    // signs the user of an idp identity in, creating it with the idp profile on first login.
+    // a deactivated user is refused with `UserInactive`.
     pub async fn get_or_create_user_from_callback(
         &self,
         idp: SupportIdp,
-        idp_id: String,
+        identity: IdpIdentity,
     ) -> Result<SAUUser, UserServiceError> {
         let user = self
             .user_repo
-            .get_user_by_idp_and_idp_id(&idp, &idp_id)
+            .get_user_by_idp_and_idp_id(&idp, &identity.idp_uid)
+            .await
+            .map_err(|e| UserServiceError::UserFetch(e.to_string()))?;
+        let Some(user) = user else {
+            return self.create_user(idp, identity).await;
+        };
+        if !user.is_active {
+            return Err(UserServiceError::UserInactive);
+        }
+
+        // invitations to an idp login are matched against it, so it follows renames at the idp
+        self.user_repo
+            .update_identity_username(&idp, &identity.idp_uid, identity.username.as_deref())
+            .await
+            .map_err(|e| UserServiceError::UserUpdate(e.to_string()))?;
+        if self.refresh_profile_on_login {
+            return self.refresh_profile(user, identity).await;
+        }
+        Ok(user)
+    }
+
+    // changes the username and email chosen by the user, fields left `None` are kept.
+    // a changed email has to be verified again.
+    pub async fn update_profile(
+        &self,
+        user_id: Uuid,
+        username: Option<String>,
+        email: Option<String>,
+    ) -> Result<SAUUser, UserServiceError> {
+        let username = username
+            .map(Username::new)
+            .transpose()
+            .map_err(|e| UserServiceError::InvalidProfile(e.to_string()))?;
+        let email = email
+            .map(Email::new)
+            .transpose()
+            .map_err(|e| UserServiceError::InvalidProfile(e.to_string()))?;
+
+        let user = self
+            .get_user_by_id(user_id)
+            .await?
+            .ok_or(UserServiceError::UserNotFound)?;
+        let username = username.filter(|username| user.username.as_ref() != Some(username));
+        let email = email.filter(|email| user.email.as_ref() != Some(email));
+        if username.is_none() && email.is_none() {
+            return Ok(user);
+        }
+
+        self.user_repo
+            .update_user_profile(user_id, username.as_ref(), email.as_ref())
+            .await
+            .map_err(|e| match e {
+                SAUUserRepoError::UsernameTaken => UserServiceError::UsernameTaken,
+                SAUUserRepoError::EmailTaken => UserServiceError::EmailTaken,
+                e => UserServiceError::UserUpdate(e.to_string()),
+            })?
+            .ok_or(UserServiceError::UserNotFound)
+    }
+
+    pub async fn get_identities(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<UserIdentity>, UserServiceError> {
+        self.user_repo
+            .get_identities_by_user_id(user_id)
+            .await
+            .map_err(|e| UserServiceError::UserFetch(e.to_string()))
+    }
+
+    // links an idp identity to an already authenticated user,
+    // after which signing in with it resolves to the same user.
+    // a deactivated user is refused with `UserInactive`.
+    pub async fn link_identity(
+        &self,
+        user_id: Uuid,
+        idp: SupportIdp,
+        identity: IdpIdentity,
+    ) -> Result<UserIdentity, UserServiceError> {
+        self.get_active_user(user_id).await?;
+        self.user_repo
+            .link_identity(
+                user_id,
+                &idp,
+                &identity.idp_uid,
+                identity.username.as_deref(),
+            )
+            .await
+            .map_err(|e| match e {
+                SAUUserRepoError::IdentityAlreadyLinked => UserServiceError::IdentityAlreadyLinked,
+                e => UserServiceError::IdentityLink(e.to_string()),
+            })
+    }
+
+    pub async fn unlink_identity(
+        &self,
+        user_id: Uuid,
+        identity_id: Uuid,
+    ) -> Result<(), UserServiceError> {
+        self.user_repo
+            .unlink_identity(user_id, identity_id)
+            .await
+            .map_err(|e| match e {
+                SAUUserRepoError::IdentityNotFound => UserServiceError::IdentityNotFound,
+                SAUUserRepoError::LastIdentity => UserServiceError::LastIdentity,
+                e => UserServiceError::IdentityLink(e.to_string()),
+            })
+    }
+
+    // one page of the users matching `filter` after the `cursor` of the previous page,
+    // `limit` defaults to `DEFAULT_USER_PAGE_SIZE` and is capped at `MAX_USER_PAGE_SIZE`
+    pub async fn list_users(
+        &self,
+        filter: &UserFilter,
+        cursor: Option<Uuid>,
+        limit: Option<u64>,
+    ) -> Result<UserPage, UserServiceError> {
+        let limit = limit
+            .unwrap_or(DEFAULT_USER_PAGE_SIZE)
+            .clamp(1, MAX_USER_PAGE_SIZE);
+        // one more than asked tells whether another page follows
+        let mut users = self
+            .user_repo
+            .list_users(filter, cursor, limit + 1)
+            .await
+            .map_err(|e| UserServiceError::UserFetch(e.to_string()))?;
+        let next_cursor = if users.len() as u64 > limit {
+            users.truncate(limit as usize);
+            users.last().map(|user| user.id)
+        } else {
+            None
+        };
+        Ok(UserPage { users, next_cursor })
+    }
+
+    // an inactive user keeps its data but can no longer sign in
+    pub async fn set_active(
+        &self,
+        user_id: Uuid,
+        is_active: bool,
+    ) -> Result<SAUUser, UserServiceError> {
+        self.user_repo
+            .set_user_active(user_id, is_active)
+            .await
+            .map_err(|e| UserServiceError::UserUpdate(e.to_string()))?
+            .ok_or(UserServiceError::UserNotFound)
+    }
+
+    pub async fn delete_user(&self, user_id: Uuid) -> Result<(), UserServiceError> {
+        let deleted = self
+            .user_repo
+            .delete_user(user_id)
+            .await
+            .map_err(|e| match e {
+                SAUUserRepoError::LastOrganizationOwner => UserServiceError::LastOrganizationOwner,
+                e => UserServiceError::UserDelete(e.to_string()),
+            })?;
+        if !deleted {
+            return Err(UserServiceError::UserNotFound);
+        }
+        Ok(())
+    }
+
+    async fn create_user(
+        &self,
+        idp: SupportIdp,
+        identity: IdpIdentity,
+    ) -> Result<SAUUser, UserServiceError> {
+        let mut profile = UserProfile {
+            username: self.claimable_username(identity.username.clone()).await?,
+            email: self.claimable_email(identity.email).await?,
+            email_verified: identity.email_verified,
+            display_name: user_profile::display_name(identity.display_name),
+            avatar_url: user_profile::avatar_url(identity.avatar_url),
+        };
+
+        // a username or email taken since it was checked is left out instead of failing the login
+        loop {
+            match self
+                .user_repo
+                .create_user_by_idp_and_idp_id(
+                    &idp,
+                    &identity.idp_uid,
+                    identity.username.as_deref(),
+                    &profile,
+                )
+                .await
+            {
+                Err(SAUUserRepoError::UsernameTaken) if profile.username.is_some() => {
+                    profile.username = None;
+                }
+                Err(SAUUserRepoError::EmailTaken) if profile.email.is_some() => {
+                    profile.email = None;
+                }
+                result => return result.map_err(|e| UserServiceError::UserCreate(e.to_string())),
+            }
+        }
+    }
+
+    // takes over what changed at the idp. a username or email held by another user is not
+    // suffixed or claimed like on creation, the current one is kept instead.
+    async fn refresh_profile(
+        &self,
+        user: SAUUser,
+        identity: IdpIdentity,
+    ) -> Result<SAUUser, UserServiceError> {
+        let username = match identity.username.map(Username::new) {
+            Some(Ok(username)) if user.username.as_ref() != Some(&username) => {
+                self.free_username(username).await?
+            }
+            _ => None,
+        };
+        let email = match identity.email {
+            Some(email) if user.email.as_ref().map(Email::as_str) != Some(email.as_str()) => {
+                self.claimable_email(Some(email)).await?
+            }
+            // `email_verified` is written with the email, the same one is sent back for it
+            Some(_) if user.email_verified != identity.email_verified => user.email.clone(),
+            _ => None,
+        };
+        let profile = UserProfile {
+            username,
+            email,
+            email_verified: identity.email_verified,
+            display_name: user_profile::display_name(identity.display_name)
+                .filter(|name| user.display_name.as_ref() != Some(name)),
+            avatar_url: user_profile::avatar_url(identity.avatar_url)
+                .filter(|url| user.avatar_url.as_ref() != Some(url)),
+        };
+        if profile.username.is_none()
+            && profile.email.is_none()
+            && profile.display_name.is_none()
+            && profile.avatar_url.is_none()
+        {
+            return Ok(user);
+        }
+
+        match self
+            .user_repo
+            .update_user_idp_profile(user.id, &profile)
+            .await
+        {
+            Ok(Some(updated)) => Ok(updated),
+            Ok(None) => Err(UserServiceError::UserNotFound),
+            // lost a race for the username or email, the login goes on with the old profile
+            Err(SAUUserRepoError::UsernameTaken | SAUUserRepoError::EmailTaken) => {
+                info!("idp profile conflicts with another user, not refreshed");
+                Ok(user)
+            }
+            Err(e) => Err(UserServiceError::UserUpdate(e.to_string())),
+        }
+    }
+
+    // the idp username when free, otherwise the first free of `<username>-2` and up
+    async fn claimable_username(
+        &self,
+        username: Option<String>,
+    ) -> Result<Option<Username>, UserServiceError> {
+        let Some(username) = username else {
+            return Ok(None);
+        };
+        let username = match Username::new(username) {
+            Ok(username) => username,
+            Err(e) => {
+                warn!("ignore idp username : {}", e);
+                return Ok(None);
+            }
+        };
+        if let Some(username) = self.free_username(username.clone()).await? {
+            return Ok(Some(username));
+        }
+
+        for suffix in 2..=MAX_USERNAME_SUFFIX {
+            let suffix = format!("-{}", suffix);
+            let mut base = username.as_str().to_string();
+            while base.len() + suffix.len() > MAX_USERNAME_LEN {
+                base.pop();
+            }
+            let Ok(candidate) = Username::new(format!("{}{}", base, suffix)) else {
+                continue;
+            };
+            if let Some(candidate) = self.free_username(candidate).await? {
+                return Ok(Some(candidate));
+            }
+        }
+        info!("idp username is already used by other users");
+        Ok(None)
+    }
+
+    async fn free_username(
+        &self,
+        username: Username,
+    ) -> Result<Option<Username>, UserServiceError> {
+        let exists = self
+            .user_repo
+            .exists_user_by_username(&username)
             .await
             .map_err(|e| UserServiceError::UserFetch(e.to_string()))?;
-        match user {
-            Some(value) => Ok(value),
-            None => {
-                let new_user = self
-                    .user_repo
-                    .create_user_by_idp_and_idp_id(&idp, &idp_id)
-                    .await
-                    .map_err(|e| UserServiceError::UserCreate(e.to_string()))?;
-                Ok(new_user)
+        Ok((!exists).then_some(username))
+    }
+
+    // the email column is unique, so an address already owned by another user is not recorded.
+    async fn claimable_email(
+        &self,
+        email: Option<String>,
+    ) -> Result<Option<Email>, UserServiceError> {
+        let Some(email) = email else {
+            return Ok(None);
+        };
+        let email = match Email::new(email) {
+            Ok(email) => email,
+            Err(e) => {
+                warn!("ignore idp email : {}", e);
+                return Ok(None);
             }
+        };
+
+        let exists = self
+            .user_repo
+            .exists_user_by_email(&email)
+            .await
+            .map_err(|e| UserServiceError::UserFetch(e.to_string()))?;
+        if exists {
+            info!("idp email is already used by another user");
+            return Ok(None);
         }
+        Ok(Some(email))
     }
 }
 
@@ -45,4 +399,45 @@ pub enum UserServiceError {
 
     #[error("user service create error : {0}")]
     UserCreate(String),
+
+    #[error("user service update error : {0}")]
+    UserUpdate(String),
+
+    #[error("user service delete error : {0}")]
+    UserDelete(String),
+
+    #[error("user not found")]
+    UserNotFound,
+
+    #[error("user is not active")]
+    UserInactive,
+
+    #[error("{0}")]
+    InvalidProfile(String),
+
+    #[error("username is already taken")]
+    UsernameTaken,
+
+    #[error("email is already used by another user")]
+    EmailTaken,
+
+    #[error("user service identity link error : {0}")]
+    IdentityLink(String),
+
+    #[error("identity is already linked to another user")]
+    IdentityAlreadyLinked,
+
+    #[error("identity not found")]
+    IdentityNotFound,
+
+    #[error("last identity of a user can not be unlinked")]
+    LastIdentity,
+
+    #[error("user is the last owner of an organization")]
+    LastOrganizationOwner,
+}
+
+#[cfg(test)]
+mod tests {
+    include!("user_service_test.rs");
 }
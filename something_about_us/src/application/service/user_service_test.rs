@@ -0,0 +1,899 @@
+use super::{UserService, UserServiceError};
+use crate::{
+    application::port::sau_user_repository::{SAUUserRepo, SAUUserRepoError},
+    domain::{
+        idp::supported_idp::SupportIdp,
+        oauth::oauth_provider::IdpIdentity,
+        user::{
+            sau_user::{Email, SAUUser, Username},
+            user_filter::{UserFilter, UserPage},
+            user_identity::UserIdentity,
+            user_profile::UserProfile,
+        },
+    },
+};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+// in-memory repository following the same linking rules as the postgres one.
+#[derive(Clone, Default)]
+struct MemoryUserRepo {
+    users: Arc<Mutex<Vec<SAUUser>>>,
+    identities: Arc<Mutex<Vec<UserIdentity>>>,
+}
+
+#[async_trait::async_trait]
+impl SAUUserRepo for MemoryUserRepo {
+    async fn get_user_by_id(&self, user_id: Uuid) -> Result<Option<SAUUser>, SAUUserRepoError> {
+        let users = self.users.lock().unwrap();
+        Ok(users.iter().find(|user| user.id == user_id).cloned())
+    }
+
+    async fn get_user_by_idp_and_idp_id(
+        &self,
+        idp: &SupportIdp,
+        idp_id: &str,
+    ) -> Result<Option<SAUUser>, SAUUserRepoError> {
+        let identities = self.identities.lock().unwrap();
+        let Some(identity) = identities
+            .iter()
+            .find(|identity| &identity.idp == idp && identity.idp_uid == idp_id)
+        else {
+            return Ok(None);
+        };
+        let users = self.users.lock().unwrap();
+        Ok(users
+            .iter()
+            .find(|user| user.id == identity.user_id)
+            .cloned())
+    }
+
+    async fn create_user_by_idp_and_idp_id(
+        &self,
+        idp: &SupportIdp,
+        idp_id: &str,
+        idp_username: Option<&str>,
+        profile: &UserProfile,
+    ) -> Result<SAUUser, SAUUserRepoError> {
+        let now = chrono::Utc::now();
+        let user = SAUUser {
+            id: Uuid::now_v7(),
+            username: profile.username.clone(),
+            email: profile.email.clone(),
+            email_verified: profile.email.is_some() && profile.email_verified,
+            display_name: profile.display_name.clone(),
+            avatar_url: profile.avatar_url.clone(),
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        };
+        {
+            let mut users = self.users.lock().unwrap();
+            if user.username.is_some() && users.iter().any(|other| other.username == user.username)
+            {
+                return Err(SAUUserRepoError::UsernameTaken);
+            }
+            if user.email.is_some() && users.iter().any(|other| other.email == user.email) {
+                return Err(SAUUserRepoError::EmailTaken);
+            }
+            users.push(user.clone());
+        }
+        self.link_identity(user.id, idp, idp_id, idp_username)
+            .await?;
+        Ok(user)
+    }
+
+    async fn update_identity_username(
+        &self,
+        idp: &SupportIdp,
+        idp_id: &str,
+        idp_username: Option<&str>,
+    ) -> Result<(), SAUUserRepoError> {
+        let mut identities = self.identities.lock().unwrap();
+        if let Some(identity) = identities
+            .iter_mut()
+            .find(|identity| &identity.idp == idp && identity.idp_uid == idp_id)
+        {
+            identity.idp_username = idp_username.map(str::to_string);
+        }
+        Ok(())
+    }
+
+    async fn update_user_profile(
+        &self,
+        user_id: Uuid,
+        username: Option<&Username>,
+        email: Option<&Email>,
+    ) -> Result<Option<SAUUser>, SAUUserRepoError> {
+        let mut users = self.users.lock().unwrap();
+        let taken = |check: &dyn Fn(&SAUUser) -> bool| {
+            users.iter().any(|user| user.id != user_id && check(user))
+        };
+        if username.is_some_and(|username| taken(&|user| user.username.as_ref() == Some(username)))
+        {
+            return Err(SAUUserRepoError::UsernameTaken);
+        }
+        if email.is_some_and(|email| taken(&|user| user.email.as_ref() == Some(email))) {
+            return Err(SAUUserRepoError::EmailTaken);
+        }
+
+        let Some(user) = users.iter_mut().find(|user| user.id == user_id) else {
+            return Ok(None);
+        };
+        if let Some(username) = username {
+            user.username = Some(username.clone());
+        }
+        if let Some(email) = email {
+            user.email = Some(email.clone());
+            user.email_verified = false;
+        }
+        user.updated_at = chrono::Utc::now();
+        Ok(Some(user.clone()))
+    }
+
+    async fn update_user_idp_profile(
+        &self,
+        user_id: Uuid,
+        profile: &UserProfile,
+    ) -> Result<Option<SAUUser>, SAUUserRepoError> {
+        let user = self
+            .update_user_profile(user_id, profile.username.as_ref(), profile.email.as_ref())
+            .await?;
+        let mut users = self.users.lock().unwrap();
+        let Some(user) = user.and_then(|user| users.iter_mut().find(|other| other.id == user.id))
+        else {
+            return Ok(None);
+        };
+        if profile.email.is_some() {
+            user.email_verified = profile.email_verified;
+        }
+        if let Some(display_name) = &profile.display_name {
+            user.display_name = Some(display_name.clone());
+        }
+        if let Some(avatar_url) = &profile.avatar_url {
+            user.avatar_url = Some(avatar_url.clone());
+        }
+        Ok(Some(user.clone()))
+    }
+
+    async fn list_users(
+        &self,
+        filter: &UserFilter,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<Vec<SAUUser>, SAUUserRepoError> {
+        let users = self.users.lock().unwrap();
+        let identities = self.identities.lock().unwrap();
+        let prefixed = |value: Option<&str>, prefix: &str| {
+            value.is_some_and(|value| value.to_lowercase().starts_with(&prefix.to_lowercase()))
+        };
+        let mut found: Vec<SAUUser> = users
+            .iter()
+            .filter(|user| after.is_none_or(|after| user.id > after))
+            .filter(|user| {
+                filter.idp.as_ref().is_none_or(|idp| {
+                    identities
+                        .iter()
+                        .any(|identity| identity.user_id == user.id && &identity.idp == idp)
+                })
+            })
+            .filter(|user| {
+                filter
+                    .is_active
+                    .is_none_or(|is_active| user.is_active == is_active)
+            })
+            .filter(|user| {
+                filter
+                    .created_from
+                    .is_none_or(|from| user.created_at >= from)
+            })
+            .filter(|user| filter.created_to.is_none_or(|to| user.created_at < to))
+            .filter(|user| {
+                filter.search.as_deref().is_none_or(|search| {
+                    prefixed(user.username.as_ref().map(Username::as_str), search)
+                        || prefixed(user.email.as_ref().map(Email::as_str), search)
+                })
+            })
+            .cloned()
+            .collect();
+        found.sort_by_key(|user| user.id);
+        found.truncate(limit as usize);
+        Ok(found)
+    }
+
+    async fn set_user_active(
+        &self,
+        user_id: Uuid,
+        is_active: bool,
+    ) -> Result<Option<SAUUser>, SAUUserRepoError> {
+        let mut users = self.users.lock().unwrap();
+        Ok(users
+            .iter_mut()
+            .find(|user| user.id == user_id)
+            .map(|user| {
+                user.is_active = is_active;
+                user.clone()
+            }))
+    }
+
+    async fn delete_user(&self, user_id: Uuid) -> Result<bool, SAUUserRepoError> {
+        let mut users = self.users.lock().unwrap();
+        let before = users.len();
+        users.retain(|user| user.id != user_id);
+        self.identities
+            .lock()
+            .unwrap()
+            .retain(|identity| identity.user_id != user_id);
+        Ok(users.len() < before)
+    }
+
+    async fn exists_user_by_email(&self, email: &Email) -> Result<bool, SAUUserRepoError> {
+        let users = self.users.lock().unwrap();
+        Ok(users.iter().any(|user| user.email.as_ref() == Some(email)))
+    }
+
+    async fn exists_user_by_username(
+        &self,
+        username: &Username,
+    ) -> Result<bool, SAUUserRepoError> {
+        let users = self.users.lock().unwrap();
+        Ok(users.iter().any(|user| user.username.as_ref() == Some(username)))
+    }
+
+    async fn get_identities_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<UserIdentity>, SAUUserRepoError> {
+        let identities = self.identities.lock().unwrap();
+        Ok(identities
+            .iter()
+            .filter(|identity| identity.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn link_identity(
+        &self,
+        user_id: Uuid,
+        idp: &SupportIdp,
+        idp_id: &str,
+        idp_username: Option<&str>,
+    ) -> Result<UserIdentity, SAUUserRepoError> {
+        let mut identities = self.identities.lock().unwrap();
+        if let Some(identity) = identities
+            .iter()
+            .find(|identity| &identity.idp == idp && identity.idp_uid == idp_id)
+        {
+            if identity.user_id != user_id {
+                return Err(SAUUserRepoError::IdentityAlreadyLinked);
+            }
+            return Ok(identity.clone());
+        }
+
+        let identity = UserIdentity {
+            id: Uuid::now_v7(),
+            user_id,
+            idp: idp.clone(),
+            idp_uid: idp_id.to_string(),
+            idp_username: idp_username.map(str::to_string),
+            created_at: chrono::Utc::now(),
+        };
+        identities.push(identity.clone());
+        Ok(identity)
+    }
+
+    async fn unlink_identity(
+        &self,
+        user_id: Uuid,
+        identity_id: Uuid,
+    ) -> Result<(), SAUUserRepoError> {
+        let mut identities = self.identities.lock().unwrap();
+        let owned = identities
+            .iter()
+            .filter(|identity| identity.user_id == user_id)
+            .count();
+        let position = identities
+            .iter()
+            .position(|identity| identity.id == identity_id && identity.user_id == user_id)
+            .ok_or(SAUUserRepoError::IdentityNotFound)?;
+        if owned <= 1 {
+            return Err(SAUUserRepoError::LastIdentity);
+        }
+        identities.remove(position);
+        Ok(())
+    }
+}
+
+fn idp(name: &str) -> SupportIdp {
+    SupportIdp::try_from(name).unwrap()
+}
+
+fn identity(idp_uid: &str, email: Option<&str>) -> IdpIdentity {
+    IdpIdentity {
+        email: email.map(str::to_string),
+        email_verified: email.is_some(),
+        ..IdpIdentity::new(idp_uid.to_string())
+    }
+}
+
+fn profile_identity(idp_uid: &str, username: &str, display_name: &str) -> IdpIdentity {
+    IdpIdentity {
+        username: Some(username.to_string()),
+        display_name: Some(display_name.to_string()),
+        avatar_url: Some(format!("https://avatars.example.com/{}.png", idp_uid)),
+        ..IdpIdentity::new(idp_uid.to_string())
+    }
+}
+
+#[tokio::test]
+async fn test_linked_identity_resolves_to_same_user() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await
+        .unwrap();
+
+    service
+        .link_identity(user.id, idp("google"), identity("google-1", None))
+        .await
+        .unwrap();
+
+    let resolved = service
+        .get_or_create_user_from_callback(idp("google"), identity("google-1", None))
+        .await
+        .unwrap();
+    assert_eq!(resolved.id, user.id);
+    assert_eq!(service.get_identities(user.id).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_link_is_idempotent() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await
+        .unwrap();
+
+    let first = service
+        .link_identity(user.id, idp("gitlab"), identity("gl-1", None))
+        .await
+        .unwrap();
+    let second = service
+        .link_identity(user.id, idp("gitlab"), identity("gl-1", None))
+        .await
+        .unwrap();
+    assert_eq!(first.id, second.id);
+}
+
+#[tokio::test]
+async fn test_link_identity_of_other_user() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await
+        .unwrap();
+    service
+        .get_or_create_user_from_callback(idp("google"), identity("google-1", None))
+        .await
+        .unwrap();
+
+    let result = service
+        .link_identity(user.id, idp("google"), identity("google-1", None))
+        .await;
+    assert!(matches!(
+        result,
+        Err(UserServiceError::IdentityAlreadyLinked)
+    ));
+}
+
+#[tokio::test]
+async fn test_link_identity_for_inactive_user() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await
+        .unwrap();
+    service.set_active(user.id, false).await.unwrap();
+
+    let result = service
+        .link_identity(user.id, idp("google"), identity("google-1", None))
+        .await;
+    assert!(matches!(result, Err(UserServiceError::UserInactive)));
+    assert_eq!(service.get_identities(user.id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_unlink_identity() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await
+        .unwrap();
+    let linked = service
+        .link_identity(user.id, idp("google"), identity("google-1", None))
+        .await
+        .unwrap();
+
+    service.unlink_identity(user.id, linked.id).await.unwrap();
+
+    let identities = service.get_identities(user.id).await.unwrap();
+    assert_eq!(identities.len(), 1);
+    assert_eq!(identities[0].idp, idp("github"));
+}
+
+#[tokio::test]
+async fn test_unlink_last_identity() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await
+        .unwrap();
+    let identities = service.get_identities(user.id).await.unwrap();
+
+    let result = service.unlink_identity(user.id, identities[0].id).await;
+    assert!(matches!(result, Err(UserServiceError::LastIdentity)));
+}
+
+#[tokio::test]
+async fn test_unlink_identity_of_other_user() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await
+        .unwrap();
+    let other = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-2", None))
+        .await
+        .unwrap();
+    let other_identity = service.get_identities(other.id).await.unwrap().remove(0);
+
+    let result = service.unlink_identity(user.id, other_identity.id).await;
+    assert!(matches!(result, Err(UserServiceError::IdentityNotFound)));
+}
+
+#[tokio::test]
+async fn test_email_already_used_is_not_claimed() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let first = service
+        .get_or_create_user_from_callback(
+            idp("google"),
+            identity("google-1", Some("user@example.com")),
+        )
+        .await
+        .unwrap();
+    let second = service
+        .get_or_create_user_from_callback(idp("gitlab"), identity("gl-1", Some("user@example.com")))
+        .await
+        .unwrap();
+
+    assert_eq!(first.email.unwrap().as_str(), "user@example.com");
+    assert!(first.email_verified);
+    assert_eq!(second.email, None);
+    assert!(!second.email_verified);
+}
+
+#[tokio::test]
+async fn test_get_user_by_id() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await
+        .unwrap();
+
+    let found = service.get_user_by_id(user.id).await.unwrap().unwrap();
+    assert_eq!(found.id, user.id);
+    assert!(service
+        .get_user_by_id(Uuid::now_v7())
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_update_profile() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(
+            idp("google"),
+            identity("google-1", Some("user@example.com")),
+        )
+        .await
+        .unwrap();
+
+    // the same email keeps its verification
+    let updated = service
+        .update_profile(
+            user.id,
+            Some("octocat".to_string()),
+            Some("user@example.com".to_string()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.username.unwrap().as_str(), "octocat");
+    assert!(updated.email_verified);
+
+    let updated = service
+        .update_profile(user.id, None, Some("new@example.com".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(updated.username.unwrap().as_str(), "octocat");
+    assert_eq!(updated.email.unwrap().as_str(), "new@example.com");
+    assert!(!updated.email_verified);
+}
+
+#[tokio::test]
+async fn test_update_profile_conflicts() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let first = service
+        .get_or_create_user_from_callback(
+            idp("google"),
+            identity("google-1", Some("user@example.com")),
+        )
+        .await
+        .unwrap();
+    service
+        .update_profile(first.id, Some("octocat".to_string()), None)
+        .await
+        .unwrap();
+    let second = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await
+        .unwrap();
+
+    let result = service
+        .update_profile(second.id, Some("octocat".to_string()), None)
+        .await;
+    assert!(matches!(result, Err(UserServiceError::UsernameTaken)));
+    let result = service
+        .update_profile(second.id, None, Some("user@example.com".to_string()))
+        .await;
+    assert!(matches!(result, Err(UserServiceError::EmailTaken)));
+}
+
+#[tokio::test]
+async fn test_update_profile_validation() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await
+        .unwrap();
+
+    let result = service
+        .update_profile(user.id, Some(String::new()), None)
+        .await;
+    assert!(matches!(result, Err(UserServiceError::InvalidProfile(_))));
+    let result = service
+        .update_profile(user.id, None, Some("not-an-email".to_string()))
+        .await;
+    assert!(matches!(result, Err(UserServiceError::InvalidProfile(_))));
+    let result = service
+        .update_profile(Uuid::now_v7(), Some("octocat".to_string()), None)
+        .await;
+    assert!(matches!(result, Err(UserServiceError::UserNotFound)));
+}
+
+#[tokio::test]
+async fn test_list_users_pages() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let mut created = vec![];
+    for idp_uid in ["gh-1", "gh-2", "gh-3"] {
+        let user = service
+            .get_or_create_user_from_callback(idp("github"), identity(idp_uid, None))
+            .await
+            .unwrap();
+        created.push(user.id);
+    }
+
+    let page = service
+        .list_users(&UserFilter::default(), None, Some(2))
+        .await
+        .unwrap();
+    assert_eq!(
+        page.users.iter().map(|user| user.id).collect::<Vec<_>>(),
+        created[..2]
+    );
+    assert_eq!(page.next_cursor, Some(created[1]));
+
+    let page = service
+        .list_users(&UserFilter::default(), page.next_cursor, Some(2))
+        .await
+        .unwrap();
+    assert_eq!(page.users.len(), 1);
+    assert_eq!(page.users[0].id, created[2]);
+    assert!(page.next_cursor.is_none());
+}
+
+#[tokio::test]
+async fn test_list_users_filters() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let octocat = service
+        .get_or_create_user_from_callback(
+            idp("github"),
+            identity("gh-1", Some("Octocat@example.com")),
+        )
+        .await
+        .unwrap();
+    let other = service
+        .get_or_create_user_from_callback(idp("google"), identity("g-1", Some("other@example.com")))
+        .await
+        .unwrap();
+    service.set_active(other.id, false).await.unwrap();
+
+    let ids = |page: UserPage| page.users.iter().map(|user| user.id).collect::<Vec<_>>();
+    let filter = UserFilter {
+        search: Some("octo".to_string()),
+        ..Default::default()
+    };
+    let page = service.list_users(&filter, None, None).await.unwrap();
+    assert_eq!(ids(page), vec![octocat.id]);
+    let filter = UserFilter {
+        idp: Some(idp("google")),
+        ..Default::default()
+    };
+    let page = service.list_users(&filter, None, None).await.unwrap();
+    assert_eq!(ids(page), vec![other.id]);
+    let filter = UserFilter {
+        is_active: Some(true),
+        ..Default::default()
+    };
+    let page = service.list_users(&filter, None, None).await.unwrap();
+    assert_eq!(ids(page), vec![octocat.id]);
+}
+
+#[tokio::test]
+async fn test_deactivate_and_delete_user() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await
+        .unwrap();
+
+    let deactivated = service.set_active(user.id, false).await.unwrap();
+    assert!(!deactivated.is_active);
+    let reactivated = service.set_active(user.id, true).await.unwrap();
+    assert!(reactivated.is_active);
+
+    service.delete_user(user.id).await.unwrap();
+    assert!(service.get_user_by_id(user.id).await.unwrap().is_none());
+    let result = service.delete_user(user.id).await;
+    assert!(matches!(result, Err(UserServiceError::UserNotFound)));
+    let result = service.set_active(user.id, false).await;
+    assert!(matches!(result, Err(UserServiceError::UserNotFound)));
+}
+
+#[tokio::test]
+async fn test_inactive_user_is_refused() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await
+        .unwrap();
+    assert_eq!(service.get_active_user(user.id).await.unwrap().id, user.id);
+
+    service.set_active(user.id, false).await.unwrap();
+    let result = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await;
+    assert!(matches!(result, Err(UserServiceError::UserInactive)));
+    let result = service.get_active_user(user.id).await;
+    assert!(matches!(result, Err(UserServiceError::UserInactive)));
+
+    service.set_active(user.id, true).await.unwrap();
+    let result = service
+        .get_or_create_user_from_callback(idp("github"), identity("gh-1", None))
+        .await;
+    assert_eq!(result.unwrap().id, user.id);
+    let result = service.get_active_user(Uuid::now_v7()).await;
+    assert!(matches!(result, Err(UserServiceError::UserNotFound)));
+}
+
+#[tokio::test]
+async fn test_new_user_is_seeded_with_idp_profile() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(
+            idp("github"),
+            IdpIdentity {
+                email: Some("octocat@example.com".to_string()),
+                ..profile_identity("gh-1", "octocat", " The Octocat ")
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(user.username.as_ref().map(Username::as_str), Some("octocat"));
+    assert_eq!(
+        user.email.as_ref().map(Email::as_str),
+        Some("octocat@example.com")
+    );
+    assert!(!user.email_verified);
+    assert_eq!(user.display_name.as_deref(), Some("The Octocat"));
+    assert_eq!(
+        user.avatar_url.as_deref(),
+        Some("https://avatars.example.com/gh-1.png")
+    );
+}
+
+#[tokio::test]
+async fn test_taken_idp_username_is_suffixed() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let first = service
+        .get_or_create_user_from_callback(idp("github"), profile_identity("gh-1", "octocat", "A"))
+        .await
+        .unwrap();
+    let second = service
+        .get_or_create_user_from_callback(idp("gitlab"), profile_identity("gl-1", "octocat", "B"))
+        .await
+        .unwrap();
+    let third = service
+        .get_or_create_user_from_callback(idp("google"), profile_identity("g-1", "octocat", "C"))
+        .await
+        .unwrap();
+
+    assert_eq!(first.username.as_ref().map(Username::as_str), Some("octocat"));
+    assert_eq!(
+        second.username.as_ref().map(Username::as_str),
+        Some("octocat-2")
+    );
+    assert_eq!(
+        third.username.as_ref().map(Username::as_str),
+        Some("octocat-3")
+    );
+
+    // the suffix still fits the username length limit
+    let long = "a".repeat(50);
+    service
+        .get_or_create_user_from_callback(idp("github"), profile_identity("gh-2", &long, "D"))
+        .await
+        .unwrap();
+    let user = service
+        .get_or_create_user_from_callback(idp("gitlab"), profile_identity("gl-2", &long, "E"))
+        .await
+        .unwrap();
+    assert_eq!(
+        user.username.as_ref().map(Username::as_str),
+        Some(format!("{}-2", "a".repeat(48)).as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_profile_is_kept_on_later_login_by_default() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), profile_identity("gh-1", "octocat", "Old"))
+        .await
+        .unwrap();
+
+    let again = service
+        .get_or_create_user_from_callback(idp("github"), profile_identity("gh-1", "monalisa", "New"))
+        .await
+        .unwrap();
+    assert_eq!(again, user);
+}
+
+#[tokio::test]
+async fn test_profile_is_refreshed_on_later_login() {
+    let service = UserService::new(MemoryUserRepo::default(), true);
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), profile_identity("gh-1", "octocat", "Old"))
+        .await
+        .unwrap();
+    service
+        .get_or_create_user_from_callback(idp("gitlab"), profile_identity("gl-1", "monalisa", "B"))
+        .await
+        .unwrap();
+
+    let refreshed = service
+        .get_or_create_user_from_callback(
+            idp("github"),
+            IdpIdentity {
+                email: Some("octocat@example.com".to_string()),
+                email_verified: true,
+                avatar_url: None,
+                ..profile_identity("gh-1", "octocat-renamed", "New")
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(refreshed.id, user.id);
+    assert_eq!(
+        refreshed.username.as_ref().map(Username::as_str),
+        Some("octocat-renamed")
+    );
+    assert_eq!(
+        refreshed.email.as_ref().map(Email::as_str),
+        Some("octocat@example.com")
+    );
+    assert!(refreshed.email_verified);
+    assert_eq!(refreshed.display_name.as_deref(), Some("New"));
+    // a value the idp no longer releases is kept
+    assert_eq!(refreshed.avatar_url, user.avatar_url);
+
+    // a username held by another user is not taken over
+    let refreshed = service
+        .get_or_create_user_from_callback(idp("github"), profile_identity("gh-1", "monalisa", "New"))
+        .await
+        .unwrap();
+    assert_eq!(
+        refreshed.username.as_ref().map(Username::as_str),
+        Some("octocat-renamed")
+    );
+}
+
+#[tokio::test]
+async fn test_email_verified_is_refreshed_with_same_email() {
+    let service = UserService::new(MemoryUserRepo::default(), true);
+    let identity = |email_verified| IdpIdentity {
+        email: Some("octocat@example.com".to_string()),
+        email_verified,
+        ..profile_identity("gh-1", "octocat", "Octo")
+    };
+    let user = service
+        .get_or_create_user_from_callback(idp("github"), identity(false))
+        .await
+        .unwrap();
+    assert!(!user.email_verified);
+
+    // verified at the idp since, the address itself is unchanged
+    let refreshed = service
+        .get_or_create_user_from_callback(idp("github"), identity(true))
+        .await
+        .unwrap();
+    assert_eq!(refreshed.email, user.email);
+    assert!(refreshed.email_verified);
+
+    let refreshed = service
+        .get_or_create_user_from_callback(idp("github"), identity(false))
+        .await
+        .unwrap();
+    assert!(!refreshed.email_verified);
+
+    // an idp no longer releasing the email leaves the flag alone
+    let refreshed = service
+        .get_or_create_user_from_callback(
+            idp("github"),
+            IdpIdentity {
+                email: None,
+                email_verified: true,
+                ..profile_identity("gh-1", "octocat", "Octo")
+            },
+        )
+        .await
+        .unwrap();
+    assert!(!refreshed.email_verified);
+}
+
+#[tokio::test]
+async fn test_identity_keeps_idp_login() {
+    let service = UserService::new(MemoryUserRepo::default(), false);
+    service
+        .get_or_create_user_from_callback(idp("github"), profile_identity("gh-1", "octocat", "A"))
+        .await
+        .unwrap();
+    // the SAU username is suffixed, the identity keeps the login at the idp
+    let user = service
+        .get_or_create_user_from_callback(idp("gitlab"), profile_identity("gl-1", "octocat", "B"))
+        .await
+        .unwrap();
+    let identities = service.get_identities(user.id).await.unwrap();
+    assert_eq!(identities[0].idp_username.as_deref(), Some("octocat"));
+
+    // a rename at the idp reaches the identity with the next login
+    service
+        .get_or_create_user_from_callback(idp("gitlab"), profile_identity("gl-1", "hubot", "B"))
+        .await
+        .unwrap();
+    let identities = service.get_identities(user.id).await.unwrap();
+    assert_eq!(identities[0].idp_username.as_deref(), Some("hubot"));
+    assert_eq!(
+        service
+            .get_user_by_id(user.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .username
+            .as_ref()
+            .map(Username::as_str),
+        Some("octocat-2")
+    );
+}
@@ -1,4 +1,5 @@
 pub mod error;
 pub mod idp;
 pub mod oauth;
+pub mod organization;
 pub mod user;
@@ -1,17 +1,24 @@
 use crate::domain::idp::error::SupportIdpError;
 use sonic_rs::{Deserialize, Serialize};
 
+// name of an identity provider declared as `[[providers]]` in config, e.g. `github`.
+// the set of providers is only known at runtime, so any valid name can be parsed here
+// and resolving it to a configured provider is up to the oauth service.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum SupportIdp {
-    Github,
-}
+#[serde(try_from = "String", into = "String")]
+pub struct SupportIdp(String);
 
 impl SupportIdp {
     pub fn as_str(&self) -> &str {
-        match self {
-            SupportIdp::Github => "github",
-        }
+        self.0.as_str()
+    }
+
+    fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name.len() <= 50
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
     }
 }
 
@@ -19,10 +26,29 @@ impl TryFrom<&str> for SupportIdp {
     type Error = SupportIdpError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let result = match value.to_lowercase().as_str() {
-            "github" => SupportIdp::Github,
-            _ => return Err(SupportIdpError::CastingError(value.to_string())),
-        };
-        Ok(result)
+        let lowercase = value.to_lowercase();
+        if !Self::is_valid_name(&lowercase) {
+            return Err(SupportIdpError::CastingError(value.to_string()));
+        }
+        Ok(SupportIdp(lowercase))
     }
 }
+
+impl TryFrom<String> for SupportIdp {
+    type Error = SupportIdpError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        SupportIdp::try_from(value.as_str())
+    }
+}
+
+impl From<SupportIdp> for String {
+    fn from(value: SupportIdp) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("supported_idp_test.rs");
+}
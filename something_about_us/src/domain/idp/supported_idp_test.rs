@@ -0,0 +1,44 @@
+use super::SupportIdp;
+use crate::domain::idp::error::SupportIdpError;
+
+#[test]
+fn test_from_str() {
+    let idp = SupportIdp::try_from("github").unwrap();
+    assert_eq!(idp.as_str(), "github");
+}
+
+#[test]
+fn test_from_str_lowercases_name() {
+    assert_eq!(
+        SupportIdp::try_from("GitHub-Enterprise").unwrap(),
+        SupportIdp::try_from("github-enterprise").unwrap()
+    );
+}
+
+#[test]
+fn test_invalid_name() {
+    for name in ["", "key cloak", "dex/../github", &"x".repeat(51)] {
+        match SupportIdp::try_from(name) {
+            Err(SupportIdpError::CastingError(value)) => assert_eq!(value, name),
+            other => panic!("Expected CastingError, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_serde_round_trip() {
+    let idp = SupportIdp::try_from("authentik").unwrap();
+    let serialized = sonic_rs::to_string(&idp).unwrap();
+    assert_eq!(serialized, "\"authentik\"");
+
+    let deserialized: SupportIdp = sonic_rs::from_str("\"github\"").unwrap();
+    assert_eq!(deserialized.as_str(), "github");
+
+    assert!(sonic_rs::from_str::<SupportIdp>("\"not valid\"").is_err());
+}
+
+#[test]
+fn test_into_string() {
+    let idp = SupportIdp::try_from("gitlab").unwrap();
+    assert_eq!(String::from(idp), "gitlab");
+}
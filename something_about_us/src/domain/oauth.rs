@@ -1,5 +1,15 @@
 pub mod auth_session;
+pub mod authorization_code;
+pub mod client_assertion;
+pub mod constant_time;
+pub mod device_authorization;
 pub mod error;
+pub mod id_token;
+pub mod link_ticket;
+pub mod login_session;
 pub mod oauth_provider;
+pub mod oidc_client;
+pub mod refresh_token;
 pub mod sau_jwt;
 pub mod sau_jwt_issuer;
+pub mod signing_key;
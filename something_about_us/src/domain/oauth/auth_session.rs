@@ -1,6 +1,11 @@
 use sonic_rs::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::{
+    idp::supported_idp::SupportIdp,
+    oauth::{authorization_code::AuthorizationRequest, constant_time::constant_time_eq},
+};
+
 pub const AUTH_SESSION_COOKIE_NAME: &str = "something_about_us_auth_session";
 pub const AUTH_HTTP_AGENT_NAME: &str = "SomethingAboutUs";
 
@@ -9,6 +14,34 @@ pub struct AuthSession {
     pub id: Uuid,
     pub pkce_verifier: String,
     pub csrf_token: String,
+    #[serde(default)]
+    pub nonce: Option<String>,
+    // set when the login was started by a relying party at `/oidc/authorize`
+    #[serde(default)]
+    pub authorization: Option<AuthorizationRequest>,
+    // set when the login approves a device at `/oidc/device`
+    #[serde(default)]
+    pub device_user_code: Option<String>,
+    // set when the login links the identity to this user at `/oauth/{idp}/link`
+    #[serde(default)]
+    pub link_user_id: Option<Uuid>,
+    // allowed frontend url a plain login is sent back to with the tokens
+    #[serde(default)]
+    pub return_to: Option<String>,
+    // provider the login was started for, only its callback may consume the session
+    #[serde(default)]
+    pub idp: Option<SupportIdp>,
+}
+
+impl AuthSession {
+    // compares the `state` echoed by the IdP in constant time
+    pub fn verify_csrf(&self, state: &str) -> bool {
+        constant_time_eq(state.as_bytes(), self.csrf_token.as_bytes())
+    }
+
+    pub fn is_for_idp(&self, idp: &SupportIdp) -> bool {
+        self.idp.as_ref() == Some(idp)
+    }
 }
 
 #[cfg(test)]
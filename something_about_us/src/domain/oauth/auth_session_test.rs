@@ -6,6 +6,12 @@ fn create_test_auth_session() -> AuthSession {
         id: Uuid::new_v4(),
         pkce_verifier: "test-pkce-verifier-123456789".to_string(),
         csrf_token: "test-csrf-token-987654321".to_string(),
+        nonce: None,
+        authorization: None,
+        device_user_code: None,
+        link_user_id: None,
+        return_to: None,
+        idp: None,
     }
 }
 
@@ -47,6 +53,12 @@ fn test_auth_session_round_trip() {
         id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
         pkce_verifier: "verifier123".to_string(),
         csrf_token: "token456".to_string(),
+        nonce: None,
+        authorization: None,
+        device_user_code: None,
+        link_user_id: None,
+        return_to: None,
+        idp: None,
     };
     
     let json = sonic_rs::to_string(&session1).unwrap();
@@ -77,6 +89,12 @@ fn test_auth_session_with_empty_strings() {
         id: Uuid::new_v4(),
         pkce_verifier: String::new(),
         csrf_token: String::new(),
+        nonce: None,
+        authorization: None,
+        device_user_code: None,
+        link_user_id: None,
+        return_to: None,
+        idp: None,
     };
     
     // Should still serialize/deserialize correctly
@@ -96,6 +114,12 @@ fn test_auth_session_with_special_characters() {
         id: Uuid::new_v4(),
         pkce_verifier: "verifier-with-special-chars!@#$%^&*()".to_string(),
         csrf_token: "token_with_underscores_and_numbers_123".to_string(),
+        nonce: None,
+        authorization: None,
+        device_user_code: None,
+        link_user_id: None,
+        return_to: None,
+        idp: None,
     };
     
     let serialized = sonic_rs::to_string(&session).expect("Failed to serialize");
@@ -114,6 +138,12 @@ fn test_auth_session_with_long_strings() {
         id: Uuid::new_v4(),
         pkce_verifier: long_verifier.clone(),
         csrf_token: long_token.clone(),
+        nonce: None,
+        authorization: None,
+        device_user_code: None,
+        link_user_id: None,
+        return_to: None,
+        idp: None,
     };
     
     let serialized = sonic_rs::to_string(&session).expect("Failed to serialize");
@@ -131,14 +161,74 @@ fn test_unique_session_ids() {
         id: Uuid::new_v4(),
         pkce_verifier: "verifier1".to_string(),
         csrf_token: "token1".to_string(),
+        nonce: None,
+        authorization: None,
+        device_user_code: None,
+        link_user_id: None,
+        return_to: None,
+        idp: None,
     };
     
     let session2 = AuthSession {
         id: Uuid::new_v4(),
         pkce_verifier: "verifier2".to_string(),
         csrf_token: "token2".to_string(),
+        nonce: None,
+        authorization: None,
+        device_user_code: None,
+        link_user_id: None,
+        return_to: None,
+        idp: None,
     };
     
     // UUIDs should be different
     assert_ne!(session1.id, session2.id);
-}
\ No newline at end of file
+}
+#[test]
+fn test_auth_session_without_nonce_deserialization() {
+    let json = r#"{"id":"550e8400-e29b-41d4-a716-446655440000","pkce_verifier":"verifier","csrf_token":"token"}"#;
+    let session: AuthSession = sonic_rs::from_str(json).expect("Failed to deserialize");
+
+    assert!(session.nonce.is_none());
+}
+
+#[test]
+fn test_auth_session_verify_csrf() {
+    let session = create_test_auth_session();
+
+    assert!(session.verify_csrf("test-csrf-token-987654321"));
+    assert!(!session.verify_csrf("test-csrf-token-98765432"));
+    assert!(!session.verify_csrf("test-csrf-token-987654320"));
+    assert!(!session.verify_csrf(""));
+}
+
+#[test]
+fn test_auth_session_bound_to_idp() {
+    let github = crate::domain::idp::supported_idp::SupportIdp::try_from("github").unwrap();
+    let gitlab = crate::domain::idp::supported_idp::SupportIdp::try_from("gitlab").unwrap();
+    let mut session = create_test_auth_session();
+
+    // a session stored without its idp is never accepted
+    assert!(!session.is_for_idp(&github));
+
+    session.idp = Some(github.clone());
+    assert!(session.is_for_idp(&github));
+    assert!(!session.is_for_idp(&gitlab));
+
+    let serialized = sonic_rs::to_string(&session).expect("Failed to serialize");
+    let deserialized: AuthSession = sonic_rs::from_str(&serialized).expect("Failed to deserialize");
+    assert_eq!(deserialized.idp, Some(github));
+}
+
+#[test]
+fn test_auth_session_keeps_link_user_id() {
+    let user_id = Uuid::now_v7();
+    let session = AuthSession {
+        link_user_id: Some(user_id),
+        ..create_test_auth_session()
+    };
+    let serialized = sonic_rs::to_string(&session).expect("Failed to serialize");
+    let deserialized: AuthSession = sonic_rs::from_str(&serialized).expect("Failed to deserialize");
+
+    assert_eq!(deserialized.link_user_id, Some(user_id));
+}
@@ -0,0 +1,78 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use ring::{
+    digest::{digest, SHA256},
+    rand::{SecureRandom, SystemRandom},
+};
+use sonic_rs::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::oauth::{constant_time::constant_time_eq, error::SAUOAuthDomainError};
+
+// opaque value handed to the relying party, only its hash is stored.
+pub type OpaqueAuthorizationCode = String;
+
+pub const OPENID_SCOPE: &str = "openid";
+
+const AUTHORIZATION_CODE_BYTES: usize = 32;
+
+// `/authorize` request of a relying party, carried through the upstream login in `AuthSession`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizationRequest {
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub scope: String,
+    pub state: Option<String>,
+    pub nonce: Option<String>,
+    // S256 is the only accepted PKCE method
+    pub code_challenge: String,
+}
+
+impl AuthorizationRequest {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scope
+            .split_whitespace()
+            .any(|granted| granted == scope)
+    }
+}
+
+// single use and short lived, exchanged at the token endpoint together with the PKCE verifier.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizationCode {
+    pub user_id: Uuid,
+    pub request: AuthorizationRequest,
+    // unix seconds of the upstream login
+    pub auth_time: i64,
+}
+
+impl AuthorizationCode {
+    pub fn generate() -> Result<OpaqueAuthorizationCode, SAUOAuthDomainError> {
+        let mut bytes = [0u8; AUTHORIZATION_CODE_BYTES];
+        SystemRandom::new().fill(&mut bytes).map_err(|_| {
+            SAUOAuthDomainError::AuthorizationCodeIssueFailed("rng failed".to_string())
+        })?;
+        Ok(BASE64_URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    pub fn hash(code: &str) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(digest(&SHA256, code.as_bytes()))
+    }
+
+    // RFC 7636 S256, the verifier is 43 to 128 characters of [A-Za-z0-9-._~]
+    pub fn verify_pkce(&self, code_verifier: &str) -> bool {
+        let well_formed = (43..=128).contains(&code_verifier.len())
+            && code_verifier
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"-._~".contains(&b));
+        if !well_formed {
+            return false;
+        }
+
+        let challenge = BASE64_URL_SAFE_NO_PAD.encode(digest(&SHA256, code_verifier.as_bytes()));
+        constant_time_eq(challenge.as_bytes(), self.request.code_challenge.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("authorization_code_test.rs");
+}
@@ -0,0 +1,67 @@
+use super::{AuthorizationCode, AuthorizationRequest};
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use ring::digest::{digest, SHA256};
+use uuid::Uuid;
+
+const VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+fn code(code_challenge: &str) -> AuthorizationCode {
+    AuthorizationCode {
+        user_id: Uuid::now_v7(),
+        request: AuthorizationRequest {
+            client_id: "web-app".to_string(),
+            redirect_uri: "https://app.example.com/callback".to_string(),
+            scope: "openid email".to_string(),
+            state: None,
+            nonce: None,
+            code_challenge: code_challenge.to_string(),
+        },
+        auth_time: 0,
+    }
+}
+
+#[test]
+fn test_verify_pkce_rfc7636_example() {
+    // Appendix B of RFC 7636
+    let code = code("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM");
+    assert!(code.verify_pkce(VERIFIER));
+}
+
+#[test]
+fn test_verify_pkce_rejects_other_verifier() {
+    let challenge = BASE64_URL_SAFE_NO_PAD.encode(digest(&SHA256, VERIFIER.as_bytes()));
+    let code = code(&challenge);
+    assert!(!code.verify_pkce(&"a".repeat(43)));
+}
+
+#[test]
+fn test_verify_pkce_rejects_malformed_verifier() {
+    let short = "a".repeat(42);
+    let code = code(&BASE64_URL_SAFE_NO_PAD.encode(digest(&SHA256, short.as_bytes())));
+    assert!(!code.verify_pkce(&short));
+
+    let invalid = format!("{}!", "a".repeat(42));
+    let code = self::code(&BASE64_URL_SAFE_NO_PAD.encode(digest(&SHA256, invalid.as_bytes())));
+    assert!(!code.verify_pkce(&invalid));
+}
+
+#[test]
+fn test_generate_is_random_and_hashed() {
+    let first = AuthorizationCode::generate().unwrap();
+    let second = AuthorizationCode::generate().unwrap();
+    assert_ne!(first, second);
+    assert_eq!(
+        AuthorizationCode::hash(&first),
+        AuthorizationCode::hash(&first)
+    );
+    assert_ne!(AuthorizationCode::hash(&first), first);
+}
+
+#[test]
+fn test_has_scope() {
+    let code = code("");
+    assert!(code.request.has_scope("openid"));
+    assert!(code.request.has_scope("email"));
+    assert!(!code.request.has_scope("profile"));
+    assert!(!code.request.has_scope("open"));
+}
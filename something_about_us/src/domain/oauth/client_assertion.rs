@@ -0,0 +1,119 @@
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use sonic_rs::Deserialize;
+
+use crate::domain::oauth::{
+    error::SAUOAuthDomainError, oidc_client::OidcClient, sau_jwt_issuer::DEFAULT_JWT_LEEWAY,
+};
+
+// `client_assertion_type` of RFC 7523 `private_key_jwt` client authentication
+pub const CLIENT_ASSERTION_TYPE: &str = "urn:ietf:params:oauth:client-assertion-type:jwt-bearer";
+
+// seconds, an assertion is a one-off proof and its `jti` is remembered until it expires
+pub const CLIENT_ASSERTION_MAX_LIFETIME: i64 = 300;
+
+// asymmetric only, an HMAC assertion would just be another shared secret
+pub const ASSERTION_ALGORITHMS: [Algorithm; 9] = [
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+// JWT a client signs with one of its registered keys to authenticate at the token endpoint.
+#[derive(Deserialize, Debug)]
+pub struct ClientAssertion {
+    pub iss: String,
+    pub sub: String,
+    pub exp: i64,
+    pub jti: String,
+}
+
+impl ClientAssertion {
+    // `iss` read without any check, only to find the client whose keys verify the assertion
+    pub fn peek_client_id(assertion: &str) -> Option<String> {
+        let mut validation = Validation::default();
+        validation.insecure_disable_signature_validation();
+        validation.validate_exp = false;
+        validation.validate_aud = false;
+        validation.required_spec_claims.clear();
+        jsonwebtoken::decode::<ClientAssertion>(
+            assertion,
+            &DecodingKey::from_secret(&[]),
+            &validation,
+        )
+        .ok()
+        .map(|data| data.claims.iss)
+    }
+
+    // `iss` and `sub` must be the client id and `aud` one of `audiences`, the token endpoint
+    // or the issuer. the `kid` of the header picks the key, it may be left out for a single key.
+    pub fn verify(
+        assertion: &str,
+        client: &OidcClient,
+        audiences: &[&str],
+    ) -> Result<Self, SAUOAuthDomainError> {
+        let jwks = client
+            .jwks
+            .as_ref()
+            .ok_or(SAUOAuthDomainError::InvalidClient(
+                "no jwks registered for private_key_jwt".to_string(),
+            ))?;
+        let header = jsonwebtoken::decode_header(assertion)
+            .map_err(|e| SAUOAuthDomainError::InvalidClient(e.to_string()))?;
+        if !ASSERTION_ALGORITHMS.contains(&header.alg) {
+            return Err(SAUOAuthDomainError::InvalidClient(format!(
+                "unsupported assertion algorithm {:?}",
+                header.alg
+            )));
+        }
+        let jwk = match header.kid.as_deref() {
+            Some(kid) => jwks.find(kid),
+            None if jwks.keys.len() == 1 => jwks.keys.first(),
+            None => None,
+        }
+        .ok_or(SAUOAuthDomainError::InvalidClient(
+            "assertion key is not registered".to_string(),
+        ))?;
+        // the key family has to match `alg`, jsonwebtoken rejects anything else
+        let key = DecodingKey::from_jwk(jwk)
+            .map_err(|e| SAUOAuthDomainError::InvalidClient(e.to_string()))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&client.client_id]);
+        validation.set_audience(audiences);
+        validation.sub = Some(client.client_id.clone());
+        validation.set_required_spec_claims(&["exp", "iss", "sub", "aud"]);
+        validation.leeway = DEFAULT_JWT_LEEWAY;
+
+        let claims = jsonwebtoken::decode::<ClientAssertion>(assertion, &key, &validation)
+            .map_err(|e| SAUOAuthDomainError::InvalidClient(e.to_string()))?
+            .claims;
+        if claims.exp > Utc::now().timestamp() + CLIENT_ASSERTION_MAX_LIFETIME {
+            return Err(SAUOAuthDomainError::InvalidClient(
+                "assertion lives too long".to_string(),
+            ));
+        }
+        if claims.jti.is_empty() {
+            return Err(SAUOAuthDomainError::InvalidClient(
+                "assertion has no jti".to_string(),
+            ));
+        }
+        Ok(claims)
+    }
+
+    // seconds the `jti` has to be kept to refuse a replay
+    pub fn replay_window(&self) -> u64 {
+        (self.exp - Utc::now().timestamp() + DEFAULT_JWT_LEEWAY as i64).max(1) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("client_assertion_test.rs");
+}
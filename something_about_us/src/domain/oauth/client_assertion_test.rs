@@ -0,0 +1,127 @@
+use super::{ClientAssertion, CLIENT_ASSERTION_MAX_LIFETIME};
+use crate::domain::oauth::oidc_client::{ClientRegistration, GrantType, OidcClient};
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{
+    jwk::{
+        AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, OctetKeyPairParameters,
+        OctetKeyPairType,
+    },
+    Algorithm, EncodingKey, Header,
+};
+use ring::{
+    rand::SystemRandom,
+    signature::{Ed25519KeyPair, KeyPair},
+};
+
+const TOKEN_ENDPOINT: &str = "https://auth.example.com/api/v1/oidc/token";
+
+struct ClientKey {
+    kid: String,
+    encoding_key: EncodingKey,
+    jwk: Jwk,
+}
+
+fn client_key(kid: &str) -> ClientKey {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    ClientKey {
+        kid: kid.to_string(),
+        encoding_key: EncodingKey::from_ed_der(pkcs8.as_ref()),
+        jwk: Jwk {
+            common: CommonParameters {
+                key_id: Some(kid.to_string()),
+                ..Default::default()
+            },
+            algorithm: AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                key_type: OctetKeyPairType::OctetKeyPair,
+                curve: EllipticCurve::Ed25519,
+                x: BASE64_URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+            }),
+        },
+    }
+}
+
+fn service_client(keys: &[&ClientKey]) -> OidcClient {
+    let (client, _) = OidcClient::register(ClientRegistration {
+        client_id: "billing-worker".to_string(),
+        confidential: false,
+        redirect_uris: Vec::new(),
+        allowed_scopes: vec!["orders:read".to_string()],
+        allowed_grant_types: vec![GrantType::ClientCredentials],
+        access_token_ttl: None,
+        refresh_token_ttl: None,
+        audience: None,
+        jwks: Some(JwkSet {
+            keys: keys.iter().map(|key| key.jwk.clone()).collect(),
+        }),
+    })
+    .unwrap();
+    client
+}
+
+fn assertion(key: &ClientKey, iss: &str, aud: &str, lifetime: i64) -> String {
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some(key.kid.clone());
+    let claims = serde_json::json!({
+        "iss": iss,
+        "sub": iss,
+        "aud": aud,
+        "exp": chrono::Utc::now().timestamp() + lifetime,
+        "jti": uuid::Uuid::now_v7().to_string(),
+    });
+    jsonwebtoken::encode(&header, &claims, &key.encoding_key).unwrap()
+}
+
+#[test]
+fn test_verify_assertion() {
+    let key = client_key("key-1");
+    let client = service_client(&[&key]);
+    let assertion = assertion(&key, "billing-worker", TOKEN_ENDPOINT, 60);
+
+    assert_eq!(
+        ClientAssertion::peek_client_id(&assertion).as_deref(),
+        Some("billing-worker")
+    );
+    let claims = ClientAssertion::verify(&assertion, &client, &[TOKEN_ENDPOINT]).unwrap();
+    assert_eq!(claims.sub, "billing-worker");
+    assert!(claims.replay_window() >= 60);
+}
+
+#[test]
+fn test_assertion_of_unregistered_key() {
+    let registered = client_key("key-1");
+    let other = client_key("key-1");
+    let client = service_client(&[&registered]);
+
+    let forged = assertion(&other, "billing-worker", TOKEN_ENDPOINT, 60);
+    assert!(ClientAssertion::verify(&forged, &client, &[TOKEN_ENDPOINT]).is_err());
+}
+
+#[test]
+fn test_assertion_claims_are_checked() {
+    let key = client_key("key-1");
+    let client = service_client(&[&key]);
+
+    let other_client = assertion(&key, "other-worker", TOKEN_ENDPOINT, 60);
+    let other_audience = assertion(&key, "billing-worker", "https://api.example.com", 60);
+    let expired = assertion(&key, "billing-worker", TOKEN_ENDPOINT, -3600);
+    let long_lived = assertion(
+        &key,
+        "billing-worker",
+        TOKEN_ENDPOINT,
+        CLIENT_ASSERTION_MAX_LIFETIME + 3600,
+    );
+    for invalid in [other_client, other_audience, expired, long_lived] {
+        assert!(ClientAssertion::verify(&invalid, &client, &[TOKEN_ENDPOINT]).is_err());
+    }
+}
+
+#[test]
+fn test_assertion_key_is_picked_by_kid() {
+    let first = client_key("key-1");
+    let second = client_key("key-2");
+    let client = service_client(&[&first, &second]);
+
+    let assertion = assertion(&second, "billing-worker", TOKEN_ENDPOINT, 60);
+    assert!(ClientAssertion::verify(&assertion, &client, &[TOKEN_ENDPOINT]).is_ok());
+}
@@ -0,0 +1,10 @@
+// compares secrets without an early return, the position of the first mismatch must not
+// leak through the response time. only the length may differ in timing.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    include!("constant_time_test.rs");
+}
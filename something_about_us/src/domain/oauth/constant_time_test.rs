@@ -0,0 +1,11 @@
+use super::constant_time_eq;
+
+#[test]
+fn test_constant_time_eq() {
+    assert!(constant_time_eq(b"csrf-token", b"csrf-token"));
+    assert!(constant_time_eq(b"", b""));
+    assert!(!constant_time_eq(b"csrf-token", b"csrf-tokem"));
+    assert!(!constant_time_eq(b"csrf-token", b"csrf-toke"));
+    assert!(!constant_time_eq(b"csrf-toke", b"csrf-token"));
+    assert!(!constant_time_eq(b"", b"csrf-token"));
+}
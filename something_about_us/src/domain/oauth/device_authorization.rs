@@ -0,0 +1,139 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use ring::{
+    digest::{digest, SHA256},
+    rand::{SecureRandom, SystemRandom},
+};
+use sonic_rs::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::oauth::error::SAUOAuthDomainError;
+
+// opaque value polled by the device, only its hash is stored.
+pub type OpaqueDeviceCode = String;
+
+// `grant_type` of RFC 8628 device access token requests
+pub const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+// binds the confirmation form of `/oidc/device` to the browser it was shown in
+pub const DEVICE_CONFIRMATION_COOKIE_NAME: &str = "something_about_us_device_confirmation";
+
+const DEVICE_CODE_BYTES: usize = 32;
+
+// RFC 8628 6.1, no vowels so no words are spelled and no look-alike characters
+const USER_CODE_ALPHABET: &[u8] = b"BCDFGHJKLMNPQRSTVWXZ";
+const USER_CODE_LENGTH: usize = 8;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum DeviceAuthorizationStatus {
+    // the user has not logged in at the verification uri yet
+    Pending,
+    Approved {
+        user_id: Uuid,
+        // unix seconds of the upstream login
+        auth_time: i64,
+    },
+}
+
+// device authorization request of a client, waiting for a user to approve it in a browser.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DeviceAuthorization {
+    pub client_id: String,
+    pub scope: String,
+    // normalized `XXXX-XXXX`, typed in by the user
+    pub user_code: String,
+    // unix seconds
+    pub expires_at: i64,
+    // minimum seconds between two polls of the token endpoint
+    pub interval: u64,
+    pub status: DeviceAuthorizationStatus,
+}
+
+impl DeviceAuthorization {
+    pub fn new(
+        client_id: String,
+        scope: String,
+        ttl: u64,
+        interval: u64,
+    ) -> Result<Self, SAUOAuthDomainError> {
+        Ok(Self {
+            client_id,
+            scope,
+            user_code: Self::generate_user_code()?,
+            expires_at: Utc::now().timestamp() + ttl as i64,
+            interval,
+            status: DeviceAuthorizationStatus::Pending,
+        })
+    }
+
+    pub fn generate_device_code() -> Result<OpaqueDeviceCode, SAUOAuthDomainError> {
+        Self::random_token()
+    }
+
+    // echoed by the confirmation form and its cookie, a cross-site page can not read it
+    pub fn generate_confirmation_token() -> Result<String, SAUOAuthDomainError> {
+        Self::random_token()
+    }
+
+    fn random_token() -> Result<String, SAUOAuthDomainError> {
+        let mut bytes = [0u8; DEVICE_CODE_BYTES];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| SAUOAuthDomainError::DeviceCodeIssueFailed("rng failed".to_string()))?;
+        Ok(BASE64_URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    pub fn hash(device_code: &str) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(digest(&SHA256, device_code.as_bytes()))
+    }
+
+    // bytes are drawn until enough fall in a multiple of the alphabet size, so every letter is as likely
+    fn generate_user_code() -> Result<String, SAUOAuthDomainError> {
+        let rng = SystemRandom::new();
+        let limit = u8::MAX - u8::MAX % USER_CODE_ALPHABET.len() as u8;
+        let mut code = String::with_capacity(USER_CODE_LENGTH);
+        let mut bytes = [0u8; USER_CODE_LENGTH * 2];
+        while code.len() < USER_CODE_LENGTH {
+            rng.fill(&mut bytes).map_err(|_| {
+                SAUOAuthDomainError::DeviceCodeIssueFailed("rng failed".to_string())
+            })?;
+            code.extend(
+                bytes
+                    .iter()
+                    .filter(|&&b| b < limit)
+                    .map(|&b| USER_CODE_ALPHABET[b as usize % USER_CODE_ALPHABET.len()] as char)
+                    .take(USER_CODE_LENGTH - code.len()),
+            );
+        }
+        Ok(format!("{}-{}", &code[..4], &code[4..]))
+    }
+
+    // users type codes in any case and with or without the dash, `None` when it cannot be a user code
+    pub fn normalize_user_code(input: &str) -> Option<String> {
+        let code: String = input
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        if code.len() != USER_CODE_LENGTH || !code.bytes().all(|b| USER_CODE_ALPHABET.contains(&b))
+        {
+            return None;
+        }
+        Some(format!("{}-{}", &code[..4], &code[4..]))
+    }
+
+    // seconds left, `None` once expired
+    pub fn remaining_ttl(&self) -> Option<u64> {
+        let remaining = self.expires_at - Utc::now().timestamp();
+        (remaining > 0).then_some(remaining as u64)
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.status == DeviceAuthorizationStatus::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("device_authorization_test.rs");
+}
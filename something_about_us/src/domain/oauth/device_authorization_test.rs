@@ -0,0 +1,61 @@
+use super::{DeviceAuthorization, DeviceAuthorizationStatus};
+
+#[test]
+fn test_new_device_authorization() {
+    let authorization =
+        DeviceAuthorization::new("cli".to_string(), "openid".to_string(), 600, 5).unwrap();
+
+    assert_eq!(authorization.status, DeviceAuthorizationStatus::Pending);
+    assert!(authorization.is_pending());
+    assert_eq!(authorization.user_code.len(), 9);
+    assert_eq!(
+        DeviceAuthorization::normalize_user_code(&authorization.user_code).as_deref(),
+        Some(authorization.user_code.as_str())
+    );
+    assert!(authorization.remaining_ttl().is_some_and(|ttl| ttl <= 600));
+}
+
+#[test]
+fn test_normalize_user_code() {
+    for input in ["BCDF-GHJK", "bcdfghjk", " bcdf ghjk ", "BcDf-gHjK"] {
+        assert_eq!(
+            DeviceAuthorization::normalize_user_code(input).as_deref(),
+            Some("BCDF-GHJK")
+        );
+    }
+    // vowels and digits are never issued
+    for input in ["", "BCDF-GHJ", "BCDF-GHJKL", "ABCD-EFGH", "1234-5678"] {
+        assert!(DeviceAuthorization::normalize_user_code(input).is_none());
+    }
+}
+
+#[test]
+fn test_expired_device_authorization() {
+    let mut authorization =
+        DeviceAuthorization::new("cli".to_string(), String::new(), 600, 5).unwrap();
+    authorization.expires_at = chrono::Utc::now().timestamp() - 1;
+
+    assert!(authorization.remaining_ttl().is_none());
+}
+
+#[test]
+fn test_device_code_hash_is_stable() {
+    let code = DeviceAuthorization::generate_device_code().unwrap();
+
+    assert_ne!(code, DeviceAuthorization::generate_device_code().unwrap());
+    assert_eq!(
+        DeviceAuthorization::hash(&code),
+        DeviceAuthorization::hash(&code)
+    );
+    assert_ne!(DeviceAuthorization::hash(&code), code);
+}
+
+#[test]
+fn test_confirmation_token() {
+    let token = DeviceAuthorization::generate_confirmation_token().unwrap();
+    assert_eq!(token.len(), 43);
+    assert_ne!(
+        token,
+        DeviceAuthorization::generate_confirmation_token().unwrap()
+    );
+}
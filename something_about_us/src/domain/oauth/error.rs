@@ -22,6 +22,33 @@ pub enum SAUOAuthDomainError {
 
     #[error("jwt issue failed: {0}")]
     JwtIssueFailed(String),
+
+    #[error("provider discovery failed: {0}")]
+    DiscoveryFailed(String),
+
+    #[error("invalid id token: {0}")]
+    InvalidIdToken(String),
+
+    #[error("refresh token issue failed: {0}")]
+    RefreshTokenIssueFailed(String),
+
+    #[error("invalid jwt: {0}")]
+    InvalidJwt(String),
+
+    #[error("authorization code issue failed: {0}")]
+    AuthorizationCodeIssueFailed(String),
+
+    #[error("device code issue failed: {0}")]
+    DeviceCodeIssueFailed(String),
+
+    #[error("login session issue failed: {0}")]
+    LoginSessionIssueFailed(String),
+
+    #[error("link ticket issue failed: {0}")]
+    LinkTicketIssueFailed(String),
+
+    #[error("invalid client: {0}")]
+    InvalidClient(String),
 }
 
 #[cfg(test)]
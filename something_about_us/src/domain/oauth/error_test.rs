@@ -42,6 +42,30 @@ fn test_jwt_issue_failed_error_display() {
     assert_eq!(error.to_string(), "jwt issue failed: key not found");
 }
 
+#[test]
+fn test_discovery_failed_error_display() {
+    let error = SAUOAuthDomainError::DiscoveryFailed("connection refused".to_string());
+    assert_eq!(error.to_string(), "provider discovery failed: connection refused");
+}
+
+#[test]
+fn test_invalid_id_token_error_display() {
+    let error = SAUOAuthDomainError::InvalidIdToken("nonce mismatch".to_string());
+    assert_eq!(error.to_string(), "invalid id token: nonce mismatch");
+}
+
+#[test]
+fn test_refresh_token_issue_failed_error_display() {
+    let error = SAUOAuthDomainError::RefreshTokenIssueFailed("rng failed".to_string());
+    assert_eq!(error.to_string(), "refresh token issue failed: rng failed");
+}
+
+#[test]
+fn test_invalid_jwt_error_display() {
+    let error = SAUOAuthDomainError::InvalidJwt("ExpiredSignature".to_string());
+    assert_eq!(error.to_string(), "invalid jwt: ExpiredSignature");
+}
+
 #[test]
 fn test_error_debug_format() {
     let error = SAUOAuthDomainError::InvalidIssuer("test".to_string());
@@ -54,4 +78,24 @@ fn test_error_debug_format() {
 fn test_error_is_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<SAUOAuthDomainError>();
-}
\ No newline at end of file
+}
+#[test]
+fn test_authorization_code_issue_failed_error_display() {
+    let error = SAUOAuthDomainError::AuthorizationCodeIssueFailed("rng failed".to_string());
+    assert_eq!(
+        error.to_string(),
+        "authorization code issue failed: rng failed"
+    );
+}
+
+#[test]
+fn test_invalid_client_error_display() {
+    let error = SAUOAuthDomainError::InvalidClient("invalid client id ``".to_string());
+    assert_eq!(error.to_string(), "invalid client: invalid client id ``");
+}
+
+#[test]
+fn test_link_ticket_issue_failed_error_display() {
+    let error = SAUOAuthDomainError::LinkTicketIssueFailed("rng failed".to_string());
+    assert_eq!(error.to_string(), "link ticket issue failed: rng failed");
+}
@@ -0,0 +1,94 @@
+use sonic_rs::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{oauth::authorization_code::AuthorizationCode, user::sau_user::SAUUser};
+
+pub const EMAIL_SCOPE: &str = "email";
+pub const PROFILE_SCOPE: &str = "profile";
+
+// login dependent part of an ID token, `iss`/`iat`/`exp` are set by the issuer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdToken {
+    pub sub: Uuid,
+    pub aud: String,
+    pub auth_time: i64,
+    pub nonce: Option<String>,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+    pub preferred_username: Option<String>,
+    pub name: Option<String>,
+    pub picture: Option<String>,
+}
+
+impl IdToken {
+    // profile claims are only included for the scopes the client asked for
+    pub fn new(user: &SAUUser, code: &AuthorizationCode) -> Self {
+        let request = &code.request;
+        let email = user
+            .email
+            .as_ref()
+            .filter(|_| request.has_scope(EMAIL_SCOPE));
+        let profile = request.has_scope(PROFILE_SCOPE);
+        Self {
+            sub: user.id,
+            aud: request.client_id.clone(),
+            auth_time: code.auth_time,
+            nonce: request.nonce.clone(),
+            email: email.map(|email| email.as_str().to_string()),
+            email_verified: email.map(|_| user.email_verified),
+            preferred_username: user
+                .username
+                .as_ref()
+                .filter(|_| profile)
+                .map(|username| username.as_str().to_string()),
+            name: user.display_name.clone().filter(|_| profile),
+            picture: user.avatar_url.clone().filter(|_| profile),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: Uuid,
+    pub aud: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub auth_time: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_verified: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub picture: Option<String>,
+}
+
+impl IdTokenClaims {
+    pub fn new(id_token: &IdToken, iss: String, iat: i64, exp: i64) -> Self {
+        Self {
+            iss,
+            sub: id_token.sub,
+            aud: id_token.aud.clone(),
+            exp,
+            iat,
+            auth_time: id_token.auth_time,
+            nonce: id_token.nonce.clone(),
+            email: id_token.email.clone(),
+            email_verified: id_token.email_verified,
+            preferred_username: id_token.preferred_username.clone(),
+            name: id_token.name.clone(),
+            picture: id_token.picture.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("id_token_test.rs");
+}
@@ -0,0 +1,79 @@
+use super::{IdToken, IdTokenClaims};
+use crate::domain::{
+    oauth::authorization_code::{AuthorizationCode, AuthorizationRequest},
+    user::sau_user::{Email, SAUUser, Username},
+};
+use uuid::Uuid;
+
+fn user() -> SAUUser {
+    let now = chrono::Utc::now();
+    SAUUser {
+        id: Uuid::now_v7(),
+        username: Some(Username::new("octocat".to_string()).unwrap()),
+        email: Some(Email::new("octocat@example.com".to_string()).unwrap()),
+        email_verified: true,
+        display_name: Some("The Octocat".to_string()),
+        avatar_url: Some("https://avatars.example.com/octocat.png".to_string()),
+        is_active: true,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn code(scope: &str) -> AuthorizationCode {
+    AuthorizationCode {
+        user_id: Uuid::now_v7(),
+        request: AuthorizationRequest {
+            client_id: "web-app".to_string(),
+            redirect_uri: "https://app.example.com/callback".to_string(),
+            scope: scope.to_string(),
+            state: Some("state".to_string()),
+            nonce: Some("n-0S6_WzA2Mj".to_string()),
+            code_challenge: "challenge".to_string(),
+        },
+        auth_time: 1_700_000_000,
+    }
+}
+
+#[test]
+fn test_id_token_for_openid_scope_only() {
+    let user = user();
+    let id_token = IdToken::new(&user, &code("openid"));
+
+    assert_eq!(id_token.sub, user.id);
+    assert_eq!(id_token.aud, "web-app");
+    assert_eq!(id_token.auth_time, 1_700_000_000);
+    assert_eq!(id_token.nonce.as_deref(), Some("n-0S6_WzA2Mj"));
+    assert_eq!(id_token.email, None);
+    assert_eq!(id_token.email_verified, None);
+    assert_eq!(id_token.preferred_username, None);
+    assert_eq!(id_token.name, None);
+    assert_eq!(id_token.picture, None);
+}
+
+#[test]
+fn test_id_token_with_email_and_profile_scopes() {
+    let id_token = IdToken::new(&user(), &code("openid email profile"));
+
+    assert_eq!(id_token.email.as_deref(), Some("octocat@example.com"));
+    assert_eq!(id_token.email_verified, Some(true));
+    assert_eq!(id_token.preferred_username.as_deref(), Some("octocat"));
+    assert_eq!(id_token.name.as_deref(), Some("The Octocat"));
+    assert_eq!(
+        id_token.picture.as_deref(),
+        Some("https://avatars.example.com/octocat.png")
+    );
+}
+
+#[test]
+fn test_id_token_claims_skip_missing_values() {
+    let id_token = IdToken::new(&user(), &code("openid"));
+    let claims = IdTokenClaims::new(&id_token, "https://auth.example.com".to_string(), 1, 2);
+    let json = sonic_rs::to_string(&claims).unwrap();
+
+    assert!(json.contains("\"nonce\""));
+    assert!(json.contains("\"auth_time\""));
+    assert!(!json.contains("email"));
+    assert!(!json.contains("preferred_username"));
+    assert!(!json.contains("picture"));
+}
@@ -0,0 +1,45 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use ring::rand::{SecureRandom, SystemRandom};
+use sonic_rs::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{idp::supported_idp::SupportIdp, oauth::error::SAUOAuthDomainError};
+
+const TICKET_BYTES: usize = 32;
+
+// lifetime in seconds, only long enough for the frontend to navigate to the link url
+pub const LINK_TICKET_TTL: u64 = 60;
+
+// one-time grant for a browser navigation to start linking an identity to `user_id`,
+// a Bearer-authenticated request can not be a top-level redirect to the IdP.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LinkTicket {
+    pub ticket: String,
+    pub user_id: Uuid,
+    pub idp: SupportIdp,
+    pub return_to: Option<String>,
+}
+
+impl LinkTicket {
+    pub fn new(
+        user_id: Uuid,
+        idp: SupportIdp,
+        return_to: Option<String>,
+    ) -> Result<Self, SAUOAuthDomainError> {
+        let mut bytes = [0u8; TICKET_BYTES];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| SAUOAuthDomainError::LinkTicketIssueFailed("rng failed".to_string()))?;
+        Ok(Self {
+            ticket: BASE64_URL_SAFE_NO_PAD.encode(bytes),
+            user_id,
+            idp,
+            return_to,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("link_ticket_test.rs");
+}
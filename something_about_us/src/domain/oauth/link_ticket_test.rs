@@ -0,0 +1,18 @@
+use super::LinkTicket;
+use crate::domain::idp::supported_idp::SupportIdp;
+use uuid::Uuid;
+
+#[test]
+fn test_new_link_ticket() {
+    let user_id = Uuid::now_v7();
+    let github = SupportIdp::try_from("github").unwrap();
+    let ticket = LinkTicket::new(user_id, github.clone(), None).unwrap();
+
+    assert_eq!(ticket.user_id, user_id);
+    assert_eq!(ticket.idp, github);
+    assert_eq!(ticket.ticket.len(), 43);
+    assert_ne!(
+        ticket.ticket,
+        LinkTicket::new(user_id, github, None).unwrap().ticket
+    );
+}
@@ -0,0 +1,54 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use ring::rand::{SecureRandom, SystemRandom};
+use sonic_rs::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::oauth::{constant_time::constant_time_eq, error::SAUOAuthDomainError};
+
+pub const LOGIN_SESSION_COOKIE_NAME: &str = "something_about_us_session";
+
+const CSRF_TOKEN_BYTES: usize = 32;
+
+// server-side session of the backend-for-frontend mode, the browser only holds its id in
+// an HttpOnly cookie and trades it for short-lived access tokens.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LoginSession {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    // echoed by the frontend on every state-changing request made with the cookie
+    pub csrf_token: String,
+    // unix seconds
+    pub expires_at: i64,
+}
+
+impl LoginSession {
+    pub fn new(user_id: Uuid, ttl: u64) -> Result<Self, SAUOAuthDomainError> {
+        let mut bytes = [0u8; CSRF_TOKEN_BYTES];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| SAUOAuthDomainError::LoginSessionIssueFailed("rng failed".to_string()))?;
+        Ok(Self {
+            id: Uuid::now_v7(),
+            user_id,
+            csrf_token: BASE64_URL_SAFE_NO_PAD.encode(bytes),
+            expires_at: Utc::now().timestamp() + ttl as i64,
+        })
+    }
+
+    // compares the `X-CSRF-Token` header in constant time
+    pub fn verify_csrf(&self, csrf_token: &str) -> bool {
+        constant_time_eq(csrf_token.as_bytes(), self.csrf_token.as_bytes())
+    }
+
+    // seconds left, `None` once expired
+    pub fn remaining_ttl(&self) -> Option<u64> {
+        let remaining = self.expires_at - Utc::now().timestamp();
+        (remaining > 0).then_some(remaining as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("login_session_test.rs");
+}
@@ -0,0 +1,35 @@
+use super::LoginSession;
+use uuid::Uuid;
+
+#[test]
+fn test_new_login_session() {
+    let user_id = Uuid::now_v7();
+    let session = LoginSession::new(user_id, 3600).unwrap();
+
+    assert_eq!(session.user_id, user_id);
+    assert_eq!(session.csrf_token.len(), 43);
+    assert!(session.remaining_ttl().is_some_and(|ttl| ttl <= 3600));
+    assert_ne!(
+        session.csrf_token,
+        LoginSession::new(user_id, 3600).unwrap().csrf_token
+    );
+}
+
+#[test]
+fn test_verify_csrf() {
+    let session = LoginSession::new(Uuid::now_v7(), 3600).unwrap();
+
+    assert!(session.verify_csrf(&session.csrf_token.clone()));
+    assert!(!session.verify_csrf(""));
+    assert!(!session.verify_csrf(&session.csrf_token[1..]));
+    assert!(!session.verify_csrf(&format!("{}x", session.csrf_token)));
+    assert!(!session.verify_csrf(&"A".repeat(session.csrf_token.len())));
+}
+
+#[test]
+fn test_expired_login_session() {
+    let mut session = LoginSession::new(Uuid::now_v7(), 3600).unwrap();
+    session.expires_at = chrono::Utc::now().timestamp();
+
+    assert!(session.remaining_ttl().is_none());
+}
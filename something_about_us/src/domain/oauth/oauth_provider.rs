@@ -4,6 +4,33 @@ use crate::domain::oauth::{
     auth_session::AuthSession, error::SAUOAuthDomainError, sau_jwt::OAuthAccessToken,
 };
 
+// identity and profile of the authenticated user as asserted by the idp,
+// every profile field is `None` when the idp does not release it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdpIdentity {
+    pub idp_uid: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    // the login handle at the idp, e.g. the github `login`
+    pub username: Option<String>,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+impl IdpIdentity {
+    // an identity the idp released nothing but its user id for
+    pub fn new(idp_uid: String) -> Self {
+        Self {
+            idp_uid,
+            email: None,
+            email_verified: false,
+            username: None,
+            display_name: None,
+            avatar_url: None,
+        }
+    }
+}
+
 #[async_trait::async_trait]
 pub trait OAuthRequest: Send + Sync {
     async fn login(&self) -> Result<(Url, AuthSession), SAUOAuthDomainError>;
@@ -12,10 +39,24 @@ pub trait OAuthRequest: Send + Sync {
         code: String,
         pkce_verifier: String,
     ) -> Result<OAuthAccessToken, SAUOAuthDomainError>;
-    async fn get_user_id(
+    // the user id and profile of the access token owner from the idp user api
+    async fn get_user_info(
         &self,
         access_token: OAuthAccessToken,
-    ) -> Result<String, SAUOAuthDomainError>;
+    ) -> Result<IdpIdentity, SAUOAuthDomainError>;
+
+    // exchange the authorization code and resolve the identity of the session owner.
+    // providers that identify users by an `id_token` override this to check the session nonce.
+    async fn authenticate(
+        &self,
+        code: String,
+        auth_session: &AuthSession,
+    ) -> Result<IdpIdentity, SAUOAuthDomainError> {
+        let access_token = self
+            .callback(code, auth_session.pkce_verifier.clone())
+            .await?;
+        self.get_user_info(access_token).await
+    }
 }
 
 #[cfg(test)]
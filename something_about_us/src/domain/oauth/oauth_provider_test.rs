@@ -1,4 +1,4 @@
-use super::OAuthRequest;
+use super::{IdpIdentity, OAuthRequest};
 use crate::domain::oauth::{
     auth_session::AuthSession, error::SAUOAuthDomainError, sau_jwt::OAuthAccessToken,
 };
@@ -9,7 +9,7 @@ use uuid::Uuid;
 struct MockOAuthProvider {
     should_fail_login: bool,
     should_fail_callback: bool,
-    should_fail_user_id: bool,
+    should_fail_user_info: bool,
 }
 
 impl MockOAuthProvider {
@@ -17,7 +17,7 @@ impl MockOAuthProvider {
         Self {
             should_fail_login: false,
             should_fail_callback: false,
-            should_fail_user_id: false,
+            should_fail_user_info: false,
         }
     }
 
@@ -25,7 +25,7 @@ impl MockOAuthProvider {
         Self {
             should_fail_login: true,
             should_fail_callback: false,
-            should_fail_user_id: false,
+            should_fail_user_info: false,
         }
     }
 
@@ -33,15 +33,15 @@ impl MockOAuthProvider {
         Self {
             should_fail_login: false,
             should_fail_callback: true,
-            should_fail_user_id: false,
+            should_fail_user_info: false,
         }
     }
 
-    fn with_user_id_failure() -> Self {
+    fn with_user_info_failure() -> Self {
         Self {
             should_fail_login: false,
             should_fail_callback: false,
-            should_fail_user_id: true,
+            should_fail_user_info: true,
         }
     }
 }
@@ -63,6 +63,12 @@ impl OAuthRequest for MockOAuthProvider {
             id: Uuid::new_v4(),
             pkce_verifier: "mock-pkce-verifier".to_string(),
             csrf_token: "mock-csrf-token".to_string(),
+            nonce: None,
+            authorization: None,
+            device_user_code: None,
+            link_user_id: None,
+            return_to: None,
+            idp: None,
         };
 
         Ok((url, session))
@@ -94,11 +100,11 @@ impl OAuthRequest for MockOAuthProvider {
         Ok("mock-access-token".to_string())
     }
 
-    async fn get_user_id(
+    async fn get_user_info(
         &self,
         access_token: OAuthAccessToken,
-    ) -> Result<String, SAUOAuthDomainError> {
-        if self.should_fail_user_id {
+    ) -> Result<IdpIdentity, SAUOAuthDomainError> {
+        if self.should_fail_user_info {
             return Err(SAUOAuthDomainError::UserInfoFetchFailed(
                 "Mock user info failure".to_string(),
             ));
@@ -110,7 +116,11 @@ impl OAuthRequest for MockOAuthProvider {
             ));
         }
 
-        Ok("mock-user-id".to_string())
+        Ok(IdpIdentity {
+            username: Some("mock-user".to_string()),
+            display_name: Some("Mock User".to_string()),
+            ..IdpIdentity::new("mock-user-id".to_string())
+        })
     }
 }
 
@@ -210,19 +220,20 @@ async fn test_oauth_callback_empty_verifier() {
 }
 
 #[tokio::test]
-async fn test_get_user_id_success() {
+async fn test_get_user_info_success() {
     let provider = MockOAuthProvider::new();
-    let result = provider.get_user_id("test-access-token".to_string()).await;
+    let result = provider.get_user_info("test-access-token".to_string()).await;
 
     assert!(result.is_ok());
-    let user_id = result.unwrap();
-    assert_eq!(user_id, "mock-user-id");
+    let identity = result.unwrap();
+    assert_eq!(identity.idp_uid, "mock-user-id");
+    assert_eq!(identity.username.as_deref(), Some("mock-user"));
 }
 
 #[tokio::test]
-async fn test_get_user_id_failure() {
-    let provider = MockOAuthProvider::with_user_id_failure();
-    let result = provider.get_user_id("test-access-token".to_string()).await;
+async fn test_get_user_info_failure() {
+    let provider = MockOAuthProvider::with_user_info_failure();
+    let result = provider.get_user_info("test-access-token".to_string()).await;
 
     assert!(result.is_err());
     match result.unwrap_err() {
@@ -234,9 +245,9 @@ async fn test_get_user_id_failure() {
 }
 
 #[tokio::test]
-async fn test_get_user_id_empty_token() {
+async fn test_get_user_info_empty_token() {
     let provider = MockOAuthProvider::new();
-    let result = provider.get_user_id(String::new()).await;
+    let result = provider.get_user_info(String::new()).await;
 
     assert!(result.is_err());
     match result.unwrap_err() {
@@ -261,12 +272,12 @@ async fn test_oauth_flow_integration() {
         .await
         .expect("Callback should succeed");
 
-    // Step 3: Get user ID
-    let user_id = provider
-        .get_user_id(access_token)
+    // Step 3: Get user info
+    let identity = provider
+        .get_user_info(access_token)
         .await
-        .expect("Get user ID should succeed");
-    assert_eq!(user_id, "mock-user-id");
+        .expect("Get user info should succeed");
+    assert_eq!(identity.idp_uid, "mock-user-id");
 }
 
 #[test]
@@ -275,3 +286,30 @@ fn test_trait_object_compatibility() {
     // This test ensures the trait can be used as a trait object
     assert!(!std::ptr::addr_of!(*provider).is_null());
 }
+
+#[tokio::test]
+async fn test_authenticate_default_flow() {
+    let provider = MockOAuthProvider::new();
+    let (_, session) = provider.login().await.expect("Login should succeed");
+
+    let identity = provider
+        .authenticate("auth-code".to_string(), &session)
+        .await
+        .expect("Authenticate should succeed");
+    assert_eq!(identity.idp_uid, "mock-user-id");
+    assert_eq!(identity.email, None);
+    assert!(!identity.email_verified);
+    assert_eq!(identity.display_name.as_deref(), Some("Mock User"));
+}
+
+#[tokio::test]
+async fn test_authenticate_propagates_callback_failure() {
+    let provider = MockOAuthProvider::with_callback_failure();
+    let (_, session) = MockOAuthProvider::new().login().await.unwrap();
+
+    let result = provider.authenticate("auth-code".to_string(), &session).await;
+    assert!(matches!(
+        result,
+        Err(SAUOAuthDomainError::CallBackFailed(_))
+    ));
+}
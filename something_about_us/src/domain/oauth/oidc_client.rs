@@ -0,0 +1,265 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use jsonwebtoken::jwk::{AlgorithmParameters, JwkSet};
+use ring::{
+    digest::{digest, SHA256},
+    rand::{SecureRandom, SystemRandom},
+};
+
+use crate::domain::oauth::{
+    constant_time::constant_time_eq, device_authorization::DEVICE_CODE_GRANT_TYPE,
+    error::SAUOAuthDomainError, sau_jwt_issuer::SAUJwtIssuer,
+};
+
+// generated secret handed to the client once, only its hash is persisted.
+pub type OpaqueClientSecret = String;
+
+const CLIENT_SECRET_BYTES: usize = 32;
+const CLIENT_ID_MAX_LEN: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrantType {
+    AuthorizationCode,
+    RefreshToken,
+    ClientCredentials,
+    DeviceCode,
+}
+
+impl GrantType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GrantType::AuthorizationCode => "authorization_code",
+            GrantType::RefreshToken => "refresh_token",
+            GrantType::ClientCredentials => "client_credentials",
+            GrantType::DeviceCode => DEVICE_CODE_GRANT_TYPE,
+        }
+    }
+}
+
+impl TryFrom<&str> for GrantType {
+    type Error = SAUOAuthDomainError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "authorization_code" => Ok(GrantType::AuthorizationCode),
+            "refresh_token" => Ok(GrantType::RefreshToken),
+            "client_credentials" => Ok(GrantType::ClientCredentials),
+            DEVICE_CODE_GRANT_TYPE => Ok(GrantType::DeviceCode),
+            _ => Err(SAUOAuthDomainError::InvalidClient(format!(
+                "unsupported grant type `{}`",
+                value
+            ))),
+        }
+    }
+}
+
+// what an operator asks for when registering a client
+#[derive(Debug, Clone)]
+pub struct ClientRegistration {
+    pub client_id: String,
+    // public clients (SPA, native apps) get no secret, PKCE is their only proof
+    pub confidential: bool,
+    pub redirect_uris: Vec<String>,
+    pub allowed_scopes: Vec<String>,
+    pub allowed_grant_types: Vec<GrantType>,
+    pub access_token_ttl: Option<u64>,
+    pub refresh_token_ttl: Option<u64>,
+    // `aud` of the access tokens issued to the client, the client id by default
+    pub audience: Option<String>,
+    // public keys the client signs its `private_key_jwt` assertions with
+    pub jwks: Option<JwkSet>,
+}
+
+// application registered to use SAU as its OpenID provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidcClient {
+    pub client_id: String,
+    pub secret_hash: Option<String>,
+    pub redirect_uris: Vec<String>,
+    pub allowed_scopes: Vec<String>,
+    pub allowed_grant_types: Vec<GrantType>,
+    pub access_token_ttl: Option<u64>,
+    pub refresh_token_ttl: Option<u64>,
+    pub audience: String,
+    pub jwks: Option<JwkSet>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OidcClient {
+    pub fn register(
+        registration: ClientRegistration,
+    ) -> Result<(Self, Option<OpaqueClientSecret>), SAUOAuthDomainError> {
+        Self::validate_client_id(&registration.client_id)?;
+        Self::validate_redirect_uris(&registration.redirect_uris)?;
+        if registration.allowed_grant_types.is_empty() {
+            return Err(SAUOAuthDomainError::InvalidClient(
+                "at least one grant type is required".to_string(),
+            ));
+        }
+        if registration
+            .allowed_grant_types
+            .contains(&GrantType::AuthorizationCode)
+            && registration.redirect_uris.is_empty()
+        {
+            return Err(SAUOAuthDomainError::InvalidClient(
+                "at least one redirect uri is required".to_string(),
+            ));
+        }
+        if let Some(jwks) = &registration.jwks {
+            Self::validate_jwks(jwks)?;
+        }
+        // machine clients have no user and no PKCE, they must prove who they are
+        if registration
+            .allowed_grant_types
+            .contains(&GrantType::ClientCredentials)
+            && !registration.confidential
+            && registration.jwks.is_none()
+        {
+            return Err(SAUOAuthDomainError::InvalidClient(
+                "the client_credentials grant requires a secret or jwks".to_string(),
+            ));
+        }
+        let audience = registration
+            .audience
+            .unwrap_or_else(|| registration.client_id.clone());
+        SAUJwtIssuer::validate_audience(&audience)?;
+
+        let secret = registration
+            .confidential
+            .then(Self::generate_secret)
+            .transpose()?;
+        let now = Utc::now();
+        let client = Self {
+            client_id: registration.client_id,
+            secret_hash: secret.as_deref().map(Self::hash_secret),
+            redirect_uris: registration.redirect_uris,
+            allowed_scopes: registration.allowed_scopes,
+            allowed_grant_types: registration.allowed_grant_types,
+            access_token_ttl: registration.access_token_ttl,
+            refresh_token_ttl: registration.refresh_token_ttl,
+            audience,
+            jwks: registration.jwks,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        };
+        Ok((client, secret))
+    }
+
+    // the previous secret stops working right away
+    pub fn rotate_secret(&mut self) -> Result<OpaqueClientSecret, SAUOAuthDomainError> {
+        if self.secret_hash.is_none() {
+            return Err(SAUOAuthDomainError::InvalidClient(
+                "the client has no secret".to_string(),
+            ));
+        }
+        let secret = Self::generate_secret()?;
+        self.secret_hash = Some(Self::hash_secret(&secret));
+        self.updated_at = Utc::now();
+        Ok(secret)
+    }
+
+    pub fn set_active(&mut self, is_active: bool) {
+        self.is_active = is_active;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn is_confidential(&self) -> bool {
+        self.secret_hash.is_some() || self.jwks.is_some()
+    }
+
+    pub fn allows_grant_type(&self, grant_type: GrantType) -> bool {
+        self.allowed_grant_types.contains(&grant_type)
+    }
+
+    pub fn allows_scope(&self, scope: &str) -> bool {
+        self.allowed_scopes.iter().any(|allowed| allowed == scope)
+    }
+
+    // redirect uris are compared as registered, without any normalization
+    pub fn allows_redirect_uri(&self, redirect_uri: &str) -> bool {
+        self.redirect_uris.iter().any(|uri| uri == redirect_uri)
+    }
+
+    // a disabled client can not authenticate,
+    // a client with only `jwks` has to use `private_key_jwt`
+    pub fn authenticate(&self, client_secret: Option<&str>) -> bool {
+        if !self.is_active {
+            return false;
+        }
+        match (&self.secret_hash, client_secret) {
+            (None, None) => self.jwks.is_none(),
+            (Some(expected), Some(secret)) => {
+                constant_time_eq(expected.as_bytes(), Self::hash_secret(secret).as_bytes())
+            }
+            _ => false,
+        }
+    }
+
+    pub fn hash_secret(secret: &str) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(digest(&SHA256, secret.as_bytes()))
+    }
+
+    fn generate_secret() -> Result<OpaqueClientSecret, SAUOAuthDomainError> {
+        let mut bytes = [0u8; CLIENT_SECRET_BYTES];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| SAUOAuthDomainError::InvalidClient("rng failed".to_string()))?;
+        Ok(BASE64_URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    fn validate_client_id(client_id: &str) -> Result<(), SAUOAuthDomainError> {
+        let valid = !client_id.is_empty()
+            && client_id.len() <= CLIENT_ID_MAX_LEN
+            && client_id
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'));
+        if !valid {
+            return Err(SAUOAuthDomainError::InvalidClient(format!(
+                "invalid client id `{}`",
+                client_id
+            )));
+        }
+        Ok(())
+    }
+
+    // only public keys, a shared `oct` key would be just another secret
+    fn validate_jwks(jwks: &JwkSet) -> Result<(), SAUOAuthDomainError> {
+        if jwks.keys.is_empty() {
+            return Err(SAUOAuthDomainError::InvalidClient(
+                "jwks has no keys".to_string(),
+            ));
+        }
+        if jwks
+            .keys
+            .iter()
+            .any(|jwk| matches!(jwk.algorithm, AlgorithmParameters::OctetKey(_)))
+        {
+            return Err(SAUOAuthDomainError::InvalidClient(
+                "jwks must only contain public keys".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_redirect_uris(redirect_uris: &[String]) -> Result<(), SAUOAuthDomainError> {
+        for redirect_uri in redirect_uris {
+            // the code is appended to the query, a fragment would swallow it
+            let valid = url::Url::parse(redirect_uri).is_ok_and(|uri| uri.fragment().is_none());
+            if !valid {
+                return Err(SAUOAuthDomainError::InvalidClient(format!(
+                    "invalid redirect uri `{}`",
+                    redirect_uri
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("oidc_client_test.rs");
+}
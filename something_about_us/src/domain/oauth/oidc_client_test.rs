@@ -0,0 +1,185 @@
+use super::{ClientRegistration, GrantType, OidcClient};
+
+fn registration(confidential: bool) -> ClientRegistration {
+    ClientRegistration {
+        client_id: "web-app".to_string(),
+        confidential,
+        redirect_uris: vec!["https://app.example.com/callback".to_string()],
+        allowed_scopes: vec!["openid".to_string(), "email".to_string()],
+        allowed_grant_types: vec![GrantType::AuthorizationCode, GrantType::RefreshToken],
+        access_token_ttl: None,
+        refresh_token_ttl: None,
+        audience: None,
+        jwks: None,
+    }
+}
+
+fn client(confidential: bool) -> (OidcClient, Option<String>) {
+    OidcClient::register(registration(confidential)).unwrap()
+}
+
+#[test]
+fn test_confidential_client_authentication() {
+    let (client, secret) = client(true);
+    let secret = secret.unwrap();
+    assert!(client.is_confidential());
+    assert!(client.authenticate(Some(&secret)));
+    assert!(!client.authenticate(Some("wrong")));
+    assert!(!client.authenticate(None));
+    // only the hash is kept
+    assert_ne!(client.secret_hash.as_deref(), Some(secret.as_str()));
+}
+
+#[test]
+fn test_public_client_authentication() {
+    let (client, secret) = client(false);
+    assert!(secret.is_none());
+    assert!(!client.is_confidential());
+    assert!(client.authenticate(None));
+    // a public client has no secret that could be checked
+    assert!(!client.authenticate(Some("s3cret")));
+}
+
+#[test]
+fn test_redirect_uri_exact_match() {
+    let (client, _) = client(false);
+    assert!(client.allows_redirect_uri("https://app.example.com/callback"));
+    assert!(!client.allows_redirect_uri("https://app.example.com/callback/"));
+    assert!(!client.allows_redirect_uri("https://app.example.com/callback?next=/"));
+    assert!(!client.allows_redirect_uri("https://evil.example.com/callback"));
+}
+
+#[test]
+fn test_rotate_secret() {
+    let (mut client, secret) = client(true);
+    let secret = secret.unwrap();
+
+    let rotated = client.rotate_secret().unwrap();
+    assert_ne!(rotated, secret);
+    assert!(client.authenticate(Some(&rotated)));
+    assert!(!client.authenticate(Some(&secret)));
+
+    let (mut public, _) = OidcClient::register(registration(false)).unwrap();
+    assert!(public.rotate_secret().is_err());
+}
+
+#[test]
+fn test_disabled_client_can_not_authenticate() {
+    let (mut client, secret) = client(true);
+    client.set_active(false);
+    assert!(!client.authenticate(secret.as_deref()));
+
+    client.set_active(true);
+    assert!(client.authenticate(secret.as_deref()));
+}
+
+#[test]
+fn test_audience_defaults_to_client_id() {
+    let (client, _) = client(false);
+    assert_eq!(client.audience, "web-app");
+
+    let mut with_audience = registration(false);
+    with_audience.audience = Some("orders-api".to_string());
+    let (client, _) = OidcClient::register(with_audience).unwrap();
+    assert_eq!(client.audience, "orders-api");
+
+    let mut empty_audience = registration(false);
+    empty_audience.audience = Some(String::new());
+    assert!(OidcClient::register(empty_audience).is_err());
+}
+
+#[test]
+fn test_invalid_client_id() {
+    for client_id in ["", "web app", "web/app", &"x".repeat(65)] {
+        let mut invalid = registration(false);
+        invalid.client_id = client_id.to_string();
+        assert!(OidcClient::register(invalid).is_err(), "{client_id}");
+    }
+}
+
+#[test]
+fn test_invalid_redirect_uri() {
+    let mut relative = registration(false);
+    relative.redirect_uris = vec!["/callback".to_string()];
+    assert!(OidcClient::register(relative).is_err());
+
+    let mut fragment = registration(false);
+    fragment.redirect_uris = vec!["https://app.example.com/#/callback".to_string()];
+    assert!(OidcClient::register(fragment).is_err());
+
+    let mut empty = registration(false);
+    empty.redirect_uris = Vec::new();
+    assert!(OidcClient::register(empty).is_err());
+}
+
+#[test]
+fn test_grant_types() {
+    let (client, _) = client(false);
+    assert!(client.allows_grant_type(GrantType::AuthorizationCode));
+    assert!(client.allows_scope("email"));
+    assert!(!client.allows_scope("profile"));
+
+    let mut no_grant = registration(false);
+    no_grant.allowed_grant_types = Vec::new();
+    assert!(OidcClient::register(no_grant).is_err());
+
+    assert_eq!(
+        GrantType::try_from("refresh_token").unwrap(),
+        GrantType::RefreshToken
+    );
+    assert!(GrantType::try_from("password").is_err());
+}
+
+#[test]
+fn test_client_credentials_requires_a_credential() {
+    let mut public = registration(false);
+    public.allowed_grant_types = vec![GrantType::ClientCredentials];
+    assert!(OidcClient::register(public.clone()).is_err());
+
+    public.confidential = true;
+    assert!(OidcClient::register(public).is_ok());
+    assert_eq!(
+        GrantType::try_from("client_credentials").unwrap(),
+        GrantType::ClientCredentials
+    );
+}
+
+#[test]
+fn test_device_code_grant_for_public_client() {
+    // a CLI has neither a secret nor a redirect uri
+    let mut cli = registration(false);
+    cli.redirect_uris = Vec::new();
+    cli.allowed_grant_types = vec![GrantType::DeviceCode, GrantType::RefreshToken];
+    let (client, secret) = OidcClient::register(cli).unwrap();
+
+    assert!(secret.is_none());
+    assert!(client.allows_grant_type(GrantType::DeviceCode));
+    assert_eq!(
+        GrantType::try_from(GrantType::DeviceCode.as_str()).unwrap(),
+        GrantType::DeviceCode
+    );
+}
+
+#[test]
+fn test_jwks_client_needs_an_assertion() {
+    let mut with_jwks = registration(false);
+    with_jwks.jwks = Some(
+        serde_json::from_value(serde_json::json!({
+            "keys": [{"kty": "OKP", "crv": "Ed25519", "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}]
+        }))
+        .unwrap(),
+    );
+    let (client, secret) = OidcClient::register(with_jwks).unwrap();
+    assert!(secret.is_none());
+    assert!(client.is_confidential());
+    assert!(!client.authenticate(None));
+
+    let mut shared_key = registration(false);
+    shared_key.jwks = Some(
+        serde_json::from_value(serde_json::json!({
+            "keys": [{"kty": "oct", "k": "c2VjcmV0"}]
+        }))
+        .unwrap(),
+    );
+    assert!(OidcClient::register(shared_key).is_err());
+}
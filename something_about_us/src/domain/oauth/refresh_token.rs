@@ -0,0 +1,106 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use ring::{
+    digest::{digest, SHA256},
+    rand::{SecureRandom, SystemRandom},
+};
+use uuid::Uuid;
+
+use crate::domain::oauth::error::SAUOAuthDomainError;
+
+// opaque value handed to the client, only its hash is persisted.
+pub type OpaqueRefreshToken = String;
+
+const REFRESH_TOKEN_BYTES: usize = 32;
+
+// every rotation issues a successor in the same family, so replaying a used token
+// can revoke all of its descendants at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub family_id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    // set when the family was issued to a registered client
+    pub client_id: Option<String>,
+    // scope granted to that client, answered again on every refresh
+    pub scope: Option<String>,
+}
+
+impl RefreshToken {
+    pub fn new_family(
+        user_id: Uuid,
+        ttl: Duration,
+    ) -> Result<(OpaqueRefreshToken, Self), SAUOAuthDomainError> {
+        Self::issue(Uuid::now_v7(), user_id, None, None, ttl)
+    }
+
+    pub fn new_client_family(
+        user_id: Uuid,
+        client_id: String,
+        scope: String,
+        ttl: Duration,
+    ) -> Result<(OpaqueRefreshToken, Self), SAUOAuthDomainError> {
+        Self::issue(Uuid::now_v7(), user_id, Some(client_id), Some(scope), ttl)
+    }
+
+    pub fn rotate(&self, ttl: Duration) -> Result<(OpaqueRefreshToken, Self), SAUOAuthDomainError> {
+        Self::issue(
+            self.family_id,
+            self.user_id,
+            self.client_id.clone(),
+            self.scope.clone(),
+            ttl,
+        )
+    }
+
+    pub fn lifetime(&self) -> Duration {
+        self.expires_at - self.created_at
+    }
+
+    pub fn hash(token: &str) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(digest(&SHA256, token.as_bytes()))
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+
+    fn issue(
+        family_id: Uuid,
+        user_id: Uuid,
+        client_id: Option<String>,
+        scope: Option<String>,
+        ttl: Duration,
+    ) -> Result<(OpaqueRefreshToken, Self), SAUOAuthDomainError> {
+        let mut bytes = [0u8; REFRESH_TOKEN_BYTES];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| SAUOAuthDomainError::RefreshTokenIssueFailed("rng failed".to_string()))?;
+        let token = BASE64_URL_SAFE_NO_PAD.encode(bytes);
+
+        let now = Utc::now();
+        let refresh_token = Self {
+            id: Uuid::now_v7(),
+            family_id,
+            user_id,
+            token_hash: Self::hash(&token),
+            expires_at: now + ttl,
+            used_at: None,
+            revoked_at: None,
+            created_at: now,
+            client_id,
+            scope,
+        };
+        Ok((token, refresh_token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("refresh_token_test.rs");
+}
@@ -0,0 +1,69 @@
+use super::RefreshToken;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+#[test]
+fn test_new_family_hashes_token() {
+    let user_id = Uuid::now_v7();
+    let (token, refresh_token) = RefreshToken::new_family(user_id, Duration::days(30)).unwrap();
+
+    assert_eq!(refresh_token.user_id, user_id);
+    assert_eq!(refresh_token.token_hash, RefreshToken::hash(&token));
+    assert_ne!(refresh_token.token_hash, token);
+    assert!(refresh_token.used_at.is_none());
+    assert!(refresh_token.revoked_at.is_none());
+}
+
+#[test]
+fn test_tokens_are_unique() {
+    let user_id = Uuid::now_v7();
+    let (first, _) = RefreshToken::new_family(user_id, Duration::days(30)).unwrap();
+    let (second, _) = RefreshToken::new_family(user_id, Duration::days(30)).unwrap();
+    assert_ne!(first, second);
+    // 32 random bytes, base64url without padding
+    assert_eq!(first.len(), 43);
+}
+
+#[test]
+fn test_rotate_keeps_family() {
+    let (_, refresh_token) = RefreshToken::new_family(Uuid::now_v7(), Duration::days(30)).unwrap();
+    let (token, successor) = refresh_token.rotate(Duration::days(30)).unwrap();
+
+    assert_eq!(successor.family_id, refresh_token.family_id);
+    assert_eq!(successor.user_id, refresh_token.user_id);
+    assert_ne!(successor.id, refresh_token.id);
+    assert_eq!(successor.token_hash, RefreshToken::hash(&token));
+}
+
+#[test]
+fn test_is_expired() {
+    let (_, refresh_token) = RefreshToken::new_family(Uuid::now_v7(), Duration::hours(1)).unwrap();
+    let now = Utc::now();
+    assert!(!refresh_token.is_expired(now));
+    assert!(refresh_token.is_expired(now + Duration::hours(2)));
+}
+
+#[test]
+fn test_hash_is_deterministic() {
+    assert_eq!(RefreshToken::hash("token"), RefreshToken::hash("token"));
+    assert_ne!(RefreshToken::hash("token"), RefreshToken::hash("other"));
+}
+
+#[test]
+fn test_client_family_keeps_client_scope_and_lifetime() {
+    let (_, refresh_token) = RefreshToken::new_client_family(
+        Uuid::now_v7(),
+        "web-app".to_string(),
+        "openid profile".to_string(),
+        Duration::days(7),
+    )
+    .unwrap();
+    assert_eq!(refresh_token.client_id.as_deref(), Some("web-app"));
+    assert_eq!(refresh_token.scope.as_deref(), Some("openid profile"));
+    assert_eq!(refresh_token.lifetime(), Duration::days(7));
+
+    let (_, successor) = refresh_token.rotate(refresh_token.lifetime()).unwrap();
+    assert_eq!(successor.client_id.as_deref(), Some("web-app"));
+    assert_eq!(successor.scope.as_deref(), Some("openid profile"));
+    assert_eq!(successor.lifetime(), Duration::days(7));
+}
@@ -1,9 +1,16 @@
 use sonic_rs::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::organization::sau_organization::MemberRole;
+
 pub type OAuthAccessToken = String;
 pub type SAUJwt = String;
 
+// `typ` header of user access tokens and of the service tokens of the `client_credentials` grant,
+// a service token can never pass as a user token and the other way around.
+pub const ACCESS_TOKEN_TYPE: &str = "JWT";
+pub const SERVICE_TOKEN_TYPE: &str = "service+jwt";
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SAUClaims {
     pub aud: String,
@@ -13,6 +20,54 @@ pub struct SAUClaims {
     pub jti: Uuid,
     pub iat: i64,
     pub nbf: i64,
+    // names of the roles assigned to the user when the token was issued
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
+    // space separated permissions of those roles
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    // organizations of the user, only with `jwt.orgs_claim`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub orgs: Vec<OrgClaim>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OrgClaim {
+    pub id: Uuid,
+    pub slug: String,
+    pub role: MemberRole,
+}
+
+impl SAUClaims {
+    // unix milliseconds, `iat` only has seconds but the `jti` of an issued token is a UUIDv7
+    pub fn issued_at_millis(&self) -> i64 {
+        self.jti
+            .get_timestamp()
+            .map(|timestamp| {
+                let (seconds, nanos) = timestamp.to_unix();
+                seconds as i64 * 1000 + (nanos / 1_000_000) as i64
+            })
+            .unwrap_or(self.iat * 1000)
+    }
+
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.scope
+            .as_deref()
+            .is_some_and(|scope| scope.split(' ').any(|granted| granted == permission))
+    }
+}
+
+// service token of the `client_credentials` grant, `sub` is the client id
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ServiceClaims {
+    pub aud: String,
+    pub iss: String,
+    pub sub: String,
+    pub scope: String,
+    pub exp: i64,
+    pub jti: Uuid,
+    pub iat: i64,
+    pub nbf: i64,
 }
 
 #[cfg(test)]
@@ -1,21 +1,70 @@
-use crate::domain::oauth::{error::SAUOAuthDomainError, sau_jwt::SAUJwt};
-use jsonwebtoken::{jwk::JwkSet, DecodingKey, EncodingKey};
+use crate::domain::{
+    oauth::{
+        error::SAUOAuthDomainError,
+        id_token::IdToken,
+        sau_jwt::{SAUClaims, SAUJwt, ServiceClaims},
+    },
+    user::role::UserGrants,
+};
+use jsonwebtoken::{jwk::JwkSet, Algorithm, DecodingKey, EncodingKey};
+use sonic_rs::Deserialize;
 use std::{collections::HashMap, sync::Arc, time::Duration};
 use uuid::Uuid;
 
+// seconds, the same default jsonwebtoken applies
+pub const DEFAULT_JWT_LEEWAY: u64 = 60;
+
 #[derive(Clone)]
 pub struct SAUJwtIssuer {
     pub header: jsonwebtoken::Header,
     pub iss: String,
     pub aud: String,
     pub access_token_ttl: Duration,
+    pub leeway: u64,
     pub key_pair: Arc<HashMap<Uuid, KeyPair>>,
 }
 
+// signing algorithm of one key, EdDSA for keys configured without one
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SigningAlgorithm {
+    #[default]
+    EdDSA,
+    ES256,
+    RS256,
+}
+
+impl From<SigningAlgorithm> for Algorithm {
+    fn from(value: SigningAlgorithm) -> Self {
+        match value {
+            SigningAlgorithm::EdDSA => Algorithm::EdDSA,
+            SigningAlgorithm::ES256 => Algorithm::ES256,
+            SigningAlgorithm::RS256 => Algorithm::RS256,
+        }
+    }
+}
+
+// base64url encoded public key parameters published in the JWKS
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublicKeyParameters {
+    Ed25519 { x: String },
+    P256 { x: String, y: String },
+    Rsa { n: String, e: String },
+}
+
 pub struct KeyPair {
     pub private_key: EncodingKey,
     pub public_key: DecodingKey,
-    pub x: String,
+    pub parameters: PublicKeyParameters,
+}
+
+impl KeyPair {
+    pub fn algorithm(&self) -> SigningAlgorithm {
+        match self.parameters {
+            PublicKeyParameters::Ed25519 { .. } => SigningAlgorithm::EdDSA,
+            PublicKeyParameters::P256 { .. } => SigningAlgorithm::ES256,
+            PublicKeyParameters::Rsa { .. } => SigningAlgorithm::RS256,
+        }
+    }
 }
 
 impl SAUJwtIssuer {
@@ -25,7 +74,7 @@ impl SAUJwtIssuer {
         access_token_ttl: u64,
         key_pair: HashMap<Uuid, KeyPair>,
     ) -> Self {
-        let header = jsonwebtoken::Header::new(jsonwebtoken::Algorithm::EdDSA);
+        let header = jsonwebtoken::Header::new(Algorithm::EdDSA);
         Self::validate_issuer(&iss).expect("validation error");
         Self::validate_audience(&aud).expect("validation error");
         Self {
@@ -33,10 +82,16 @@ impl SAUJwtIssuer {
             iss,
             aud,
             access_token_ttl: Duration::from_secs(access_token_ttl),
+            leeway: DEFAULT_JWT_LEEWAY,
             key_pair: Arc::new(key_pair),
         }
     }
 
+    pub fn with_leeway(mut self, leeway: u64) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
     pub fn validate(&self) -> Result<(), SAUOAuthDomainError> {
         Self::validate_issuer(&self.iss)?;
         Self::validate_audience(&self.aud)?;
@@ -58,9 +113,44 @@ impl SAUJwtIssuer {
     }
 }
 
+// the header `alg` is set from the signing key, so keys of different algorithms
+// can be published side by side.
 pub trait JwtIssue {
-    fn issue_with_id(&self, kid: &Uuid, uid: &Uuid) -> Result<SAUJwt, SAUOAuthDomainError>;
+    // `grants` become the `roles` and `scope` claims.
+    fn issue_with_id(
+        &self,
+        kid: &Uuid,
+        uid: &Uuid,
+        grants: &UserGrants,
+    ) -> Result<SAUJwt, SAUOAuthDomainError>;
+    // access token of a registered client, with its own `aud` and lifetime.
+    fn issue_for_audience(
+        &self,
+        kid: &Uuid,
+        uid: &Uuid,
+        aud: &str,
+        ttl: Duration,
+        grants: &UserGrants,
+    ) -> Result<SAUJwt, SAUOAuthDomainError>;
+    // `client_credentials` token of a client, `sub` is the client id and `scope` the granted scopes.
+    fn issue_service_token(
+        &self,
+        kid: &Uuid,
+        client_id: &str,
+        aud: &str,
+        scope: &str,
+        ttl: Duration,
+    ) -> Result<SAUJwt, SAUOAuthDomainError>;
+    // OpenID Connect ID token, valid as long as an access token.
+    fn issue_id_token(&self, kid: &Uuid, id_token: &IdToken)
+        -> Result<SAUJwt, SAUOAuthDomainError>;
     fn create_jwks(&self) -> JwkSet;
+    // checks the signature with the key named by `kid` and `exp`/`nbf`/`iss`/`aud`.
+    fn verify(&self, jwt: &str) -> Result<SAUClaims, SAUOAuthDomainError>;
+    // same checks as `verify` but any `aud`, for endpoints serving every client.
+    fn verify_any_audience(&self, jwt: &str) -> Result<SAUClaims, SAUOAuthDomainError>;
+    // checks of `verify_any_audience` for service tokens.
+    fn verify_service_token(&self, jwt: &str) -> Result<ServiceClaims, SAUOAuthDomainError>;
 }
 
 // JwtIssue implementation is provided in infrastructure/auth/jwt_issuer_helper.rs
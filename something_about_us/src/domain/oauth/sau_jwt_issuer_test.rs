@@ -1,5 +1,5 @@
-use super::{KeyPair, SAUJwtIssuer};
-use crate::domain::oauth::error::SAUOAuthDomainError;
+use super::{KeyPair, PublicKeyParameters, SAUJwtIssuer};
+use crate::domain::{oauth::error::SAUOAuthDomainError, user::role::UserGrants};
 use jsonwebtoken::{DecodingKey, EncodingKey};
 use std::{collections::HashMap, time::Duration};
 use uuid::Uuid;
@@ -17,7 +17,9 @@ MCowBQYDK2VwAyEAhSDwCYkwp1R0i33ctD73Wg2/Og+SorMWtOvI/PJJtEo=
     KeyPair {
         private_key: EncodingKey::from_ed_pem(private_key_pem.as_bytes()).unwrap(),
         public_key: DecodingKey::from_ed_pem(public_key_pem.as_bytes()).unwrap(),
-        x: "hSDwCYkwp1R0i33ctD73Wg2/Og+SorMWtOvI/PJJtEo".to_string(),
+        parameters: PublicKeyParameters::Ed25519 {
+            x: "hSDwCYkwp1R0i33ctD73Wg2/Og+SorMWtOvI/PJJtEo".to_string(),
+        },
     }
 }
 
@@ -151,9 +153,13 @@ fn test_clone() {
 #[test]
 fn test_key_pair_structure() {
     let key_pair = create_test_key_pair();
-    assert!(!key_pair.x.is_empty());
+    assert_eq!(key_pair.algorithm(), super::SigningAlgorithm::EdDSA);
+    let PublicKeyParameters::Ed25519 { x } = &key_pair.parameters else {
+        panic!("expected an Ed25519 key");
+    };
+    assert!(!x.is_empty());
     // 키가 올바르게 생성되었는지 확인하기 위해 간단한 검증
-    assert!(key_pair.x.len() > 10);
+    assert!(x.len() > 10);
 }
 
 #[test]
@@ -191,4 +197,137 @@ fn test_access_token_ttl_conversion() {
     );
 
     assert_eq!(issuer.access_token_ttl, Duration::from_secs(7200));
-}
\ No newline at end of file
+}
+// the fixed test key pair above is not a matching pair, so signatures are checked
+// with a freshly generated one, the same way `JwtIssuerHelper` creates keys.
+fn create_signing_issuer() -> SAUJwtIssuer {
+    use ring::signature::KeyPair as _;
+    let pkcs8 =
+        ring::signature::Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+    let key_pair = ring::signature::Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+
+    let mut key_pairs = HashMap::new();
+    key_pairs.insert(
+        Uuid::new_v4(),
+        KeyPair {
+            private_key: EncodingKey::from_ed_der(pkcs8.as_ref()),
+            public_key: DecodingKey::from_ed_der(key_pair.public_key().as_ref()),
+            parameters: PublicKeyParameters::Ed25519 { x: String::new() },
+        },
+    );
+    SAUJwtIssuer::new(
+        "test-issuer".to_string(),
+        "test-audience".to_string(),
+        3600,
+        key_pairs,
+    )
+}
+
+fn issue_test_jwt(issuer: &SAUJwtIssuer, uid: &Uuid) -> String {
+    use super::JwtIssue;
+    let kid = *issuer.key_pair.keys().next().unwrap();
+    issuer
+        .issue_with_id(&kid, uid, &UserGrants::default())
+        .unwrap()
+}
+
+#[test]
+fn test_verify_issued_jwt() {
+    use super::JwtIssue;
+    let issuer = create_signing_issuer();
+    let uid = Uuid::now_v7();
+    let jwt = issue_test_jwt(&issuer, &uid);
+
+    let claims = issuer.verify(&jwt).unwrap();
+    assert_eq!(claims.sub, uid);
+    assert_eq!(claims.iss, "test-issuer");
+    assert_eq!(claims.aud, "test-audience");
+}
+
+#[test]
+fn test_verify_rejects_other_audience() {
+    use super::JwtIssue;
+    let issuer = create_signing_issuer();
+    let jwt = issue_test_jwt(&issuer, &Uuid::now_v7());
+
+    let mut other = issuer.clone();
+    other.aud = "other-audience".to_string();
+    assert!(matches!(
+        other.verify(&jwt),
+        Err(SAUOAuthDomainError::InvalidJwt(_))
+    ));
+}
+
+#[test]
+fn test_verify_rejects_unknown_kid() {
+    use super::JwtIssue;
+    let issuer = create_signing_issuer();
+    let jwt = issue_test_jwt(&issuer, &Uuid::now_v7());
+
+    let other = create_signing_issuer();
+    assert!(matches!(
+        other.verify(&jwt),
+        Err(SAUOAuthDomainError::InvalidJwt(_))
+    ));
+}
+
+#[test]
+fn test_verify_rejects_tampered_payload() {
+    use super::JwtIssue;
+    let issuer = create_signing_issuer();
+    let jwt = issue_test_jwt(&issuer, &Uuid::now_v7());
+    let forged = issue_test_jwt(&issuer, &Uuid::now_v7());
+
+    let mut parts: Vec<&str> = jwt.split('.').collect();
+    parts[1] = forged.split('.').nth(1).unwrap();
+    assert!(issuer.verify(&parts.join(".")).is_err());
+}
+
+fn encode_expired_jwt(issuer: &SAUJwtIssuer, expired_for: i64) -> String {
+    let (kid, key_pair) = issuer.key_pair.iter().next().unwrap();
+    let mut header = issuer.header.clone();
+    header.kid = Some(kid.to_string());
+
+    let now = chrono::Utc::now().timestamp();
+    let claims = crate::domain::oauth::sau_jwt::SAUClaims {
+        aud: issuer.aud.clone(),
+        iss: issuer.iss.clone(),
+        sub: Uuid::now_v7(),
+        exp: now - expired_for,
+        jti: Uuid::now_v7(),
+        iat: now - 3600,
+        nbf: now - 3600,
+        roles: vec![],
+        scope: None,
+        orgs: vec![],
+    };
+    jsonwebtoken::encode(&header, &claims, &key_pair.private_key).unwrap()
+}
+
+#[test]
+fn test_new_uses_default_leeway() {
+    let issuer = create_test_issuer();
+    assert_eq!(issuer.leeway, super::DEFAULT_JWT_LEEWAY);
+    assert_eq!(issuer.with_leeway(5).leeway, 5);
+}
+
+#[test]
+fn test_verify_accepts_expired_jwt_within_leeway() {
+    use super::JwtIssue;
+    let issuer = create_signing_issuer().with_leeway(60);
+    let jwt = encode_expired_jwt(&issuer, 30);
+
+    assert!(issuer.verify(&jwt).is_ok());
+}
+
+#[test]
+fn test_verify_rejects_expired_jwt_beyond_leeway() {
+    use super::JwtIssue;
+    let issuer = create_signing_issuer().with_leeway(10);
+    let jwt = encode_expired_jwt(&issuer, 30);
+
+    assert!(matches!(
+        issuer.verify(&jwt),
+        Err(SAUOAuthDomainError::InvalidJwt(_))
+    ));
+}
@@ -12,6 +12,9 @@ fn create_test_claims() -> SAUClaims {
         jti: Uuid::new_v4(),
         iat: now,
         nbf: now,
+        roles: vec![],
+        scope: None,
+        orgs: vec![],
     }
 }
 
@@ -77,6 +80,9 @@ fn test_token_expiration_logic() {
         jti: Uuid::new_v4(),
         iat: now,
         nbf: now,
+        roles: vec![],
+        scope: None,
+        orgs: vec![],
     };
     
     // Token should not be expired yet
@@ -99,6 +105,9 @@ fn test_expired_token() {
         jti: Uuid::new_v4(),
         iat: past_time - 3600,
         nbf: past_time - 3600,
+        roles: vec![],
+        scope: None,
+        orgs: vec![],
     };
     
     // Token should be expired
@@ -116,6 +125,9 @@ fn test_future_token() {
         jti: Uuid::new_v4(),
         iat: future_time,
         nbf: future_time,
+        roles: vec![],
+        scope: None,
+        orgs: vec![],
     };
     
     // Token should not be valid yet (nbf > now)
@@ -144,10 +156,45 @@ fn test_claims_with_different_timezones() {
         jti: Uuid::new_v4(),
         iat: timestamp,
         nbf: timestamp,
+        roles: vec![],
+        scope: None,
+        orgs: vec![],
     };
     
     // Verify timestamps are consistent
     assert_eq!(claims.iat, timestamp);
     assert_eq!(claims.nbf, timestamp);
     assert_eq!(claims.exp, timestamp + 3600);
-}
\ No newline at end of file
+}
+#[test]
+fn test_roles_and_scope_claims() {
+    let mut claims = create_test_claims();
+    let serialized = sonic_rs::to_string(&claims).unwrap();
+    assert!(!serialized.contains("roles"));
+    assert!(!serialized.contains("scope"));
+    assert!(!claims.has_permission("users:read"));
+
+    claims.roles = vec!["support".to_string()];
+    claims.scope = Some("users:read users:write".to_string());
+    let deserialized: SAUClaims =
+        sonic_rs::from_str(&sonic_rs::to_string(&claims).unwrap()).unwrap();
+    assert_eq!(deserialized.roles, vec!["support"]);
+    assert!(deserialized.has_permission("users:read"));
+    assert!(!deserialized.has_permission("users"));
+    assert!(!deserialized.has_permission("admin"));
+}
+
+#[test]
+fn test_issued_at_millis() {
+    let claims = SAUClaims {
+        jti: Uuid::now_v7(),
+        ..create_test_claims()
+    };
+    let millis = claims.issued_at_millis();
+    assert!(millis >= claims.iat * 1000);
+    assert!(millis <= Utc::now().timestamp_millis());
+
+    // a `jti` without a timestamp falls back to `iat`
+    let claims = create_test_claims();
+    assert_eq!(claims.issued_at_millis(), claims.iat * 1000);
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+// pending  : published in the JWKS ahead of time, not signing yet
+// active   : the signing key
+// retiring : replaced by a newer key, still published until every token it signed has expired
+// retired  : no longer published
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningKeyState {
+    Pending,
+    Active,
+    Retiring,
+    Retired,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningKeySchedule {
+    pub kid: Uuid,
+    pub activates_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyRotationPolicy {
+    // how long a key signs before its successor takes over
+    pub rotation_interval: Duration,
+    // how long a new key is published before it starts signing
+    pub publish_ahead: Duration,
+}
+
+// a key signs from `activates_at` until the next key activates, so the lifecycle
+// of every key follows from the activation times alone.
+#[derive(Debug, Clone, Default)]
+pub struct SigningKeyRing {
+    keys: Vec<SigningKeySchedule>,
+}
+
+impl SigningKeyRing {
+    pub fn new(mut keys: Vec<SigningKeySchedule>) -> Self {
+        keys.sort_by_key(|key| key.activates_at);
+        Self { keys }
+    }
+
+    pub fn keys(&self) -> &[SigningKeySchedule] {
+        &self.keys
+    }
+
+    pub fn insert(&mut self, key: SigningKeySchedule) {
+        let position = self
+            .keys
+            .partition_point(|other| other.activates_at <= key.activates_at);
+        self.keys.insert(position, key);
+    }
+
+    pub fn contains(&self, kid: &Uuid) -> bool {
+        self.keys.iter().any(|key| &key.kid == kid)
+    }
+
+    // `token_lifetime` is the longest time a signed token stays valid, ttl plus leeway.
+    pub fn state(
+        &self,
+        kid: &Uuid,
+        now: DateTime<Utc>,
+        token_lifetime: Duration,
+    ) -> Option<SigningKeyState> {
+        let index = self.keys.iter().position(|key| &key.kid == kid)?;
+        let key = &self.keys[index];
+        if now < key.activates_at {
+            return Some(SigningKeyState::Pending);
+        }
+
+        let replaced_at = self.keys[index + 1..]
+            .iter()
+            .map(|next| next.activates_at)
+            .find(|activates_at| *activates_at > key.activates_at && *activates_at <= now);
+        let state = match replaced_at {
+            None => SigningKeyState::Active,
+            Some(replaced_at) if now < replaced_at + token_lifetime => SigningKeyState::Retiring,
+            Some(_) => SigningKeyState::Retired,
+        };
+        Some(state)
+    }
+
+    // the most recently activated key
+    pub fn signing_key(&self, now: DateTime<Utc>) -> Option<&SigningKeySchedule> {
+        self.keys.iter().rev().find(|key| key.activates_at <= now)
+    }
+
+    // every key that belongs in the JWKS
+    pub fn published(
+        &self,
+        now: DateTime<Utc>,
+        token_lifetime: Duration,
+    ) -> Vec<&SigningKeySchedule> {
+        self.keys
+            .iter()
+            .filter(|key| {
+                self.state(&key.kid, now, token_lifetime) != Some(SigningKeyState::Retired)
+            })
+            .collect()
+    }
+
+    pub fn next_activation(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.keys
+            .iter()
+            .map(|key| key.activates_at)
+            .find(|activates_at| *activates_at > now)
+    }
+
+    // activation time of the successor that has to be created now, if any.
+    // a successor is always published at least `publish_ahead` before it signs,
+    // only an empty ring gets a key that signs right away.
+    pub fn successor_activation(
+        &self,
+        now: DateTime<Utc>,
+        policy: &KeyRotationPolicy,
+    ) -> Option<DateTime<Utc>> {
+        if self.next_activation(now).is_some() {
+            return None;
+        }
+        let Some(active) = self.signing_key(now) else {
+            return Some(now);
+        };
+        let earliest = now + policy.publish_ahead;
+
+        let due = active.activates_at + policy.rotation_interval;
+        (earliest >= due).then_some(due.max(earliest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("signing_key_test.rs");
+}
@@ -0,0 +1,115 @@
+use super::{KeyRotationPolicy, SigningKeyRing, SigningKeySchedule, SigningKeyState};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use uuid::Uuid;
+
+fn at(hours: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap() + Duration::hours(hours)
+}
+
+fn key(hours: i64) -> SigningKeySchedule {
+    SigningKeySchedule {
+        kid: Uuid::now_v7(),
+        activates_at: at(hours),
+    }
+}
+
+fn policy() -> KeyRotationPolicy {
+    KeyRotationPolicy {
+        rotation_interval: Duration::hours(24),
+        publish_ahead: Duration::hours(2),
+    }
+}
+
+const TOKEN_LIFETIME: Duration = Duration::hours(1);
+
+#[test]
+fn test_lifecycle_of_a_replaced_key() {
+    let old = key(0);
+    let new = key(24);
+    let ring = SigningKeyRing::new(vec![new.clone(), old.clone()]);
+
+    let state = |kid: &Uuid, hours: i64| ring.state(kid, at(hours), TOKEN_LIFETIME).unwrap();
+    assert_eq!(state(&old.kid, 23), SigningKeyState::Active);
+    assert_eq!(state(&new.kid, 23), SigningKeyState::Pending);
+
+    assert_eq!(state(&old.kid, 24), SigningKeyState::Retiring);
+    assert_eq!(state(&new.kid, 24), SigningKeyState::Active);
+
+    assert_eq!(state(&old.kid, 25), SigningKeyState::Retired);
+    assert_eq!(state(&new.kid, 25), SigningKeyState::Active);
+}
+
+#[test]
+fn test_unknown_key_has_no_state() {
+    let ring = SigningKeyRing::new(vec![key(0)]);
+    assert_eq!(ring.state(&Uuid::now_v7(), at(0), TOKEN_LIFETIME), None);
+}
+
+#[test]
+fn test_signing_key_switches_at_activation() {
+    let old = key(0);
+    let new = key(24);
+    let ring = SigningKeyRing::new(vec![old.clone(), new.clone()]);
+
+    assert_eq!(ring.signing_key(at(23)).unwrap().kid, old.kid);
+    assert_eq!(ring.signing_key(at(24)).unwrap().kid, new.kid);
+    assert!(ring.signing_key(at(-1)).is_none());
+}
+
+#[test]
+fn test_published_keys() {
+    let old = key(0);
+    let current = key(24);
+    let pending = key(48);
+    let ring = SigningKeyRing::new(vec![old.clone(), current.clone(), pending.clone()]);
+
+    let kids = |hours: i64| {
+        ring.published(at(hours), TOKEN_LIFETIME)
+            .into_iter()
+            .map(|key| key.kid)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(kids(24), vec![old.kid, current.kid, pending.kid]);
+    assert_eq!(kids(30), vec![current.kid, pending.kid]);
+}
+
+#[test]
+fn test_insert_keeps_activation_order() {
+    let mut ring = SigningKeyRing::new(vec![key(0), key(48)]);
+    let middle = key(24);
+    ring.insert(middle.clone());
+
+    assert_eq!(ring.keys()[1], middle);
+    assert!(ring.contains(&middle.kid));
+}
+
+#[test]
+fn test_no_successor_before_publish_ahead() {
+    let ring = SigningKeyRing::new(vec![key(0)]);
+    assert_eq!(ring.successor_activation(at(21), &policy()), None);
+}
+
+#[test]
+fn test_successor_is_published_ahead() {
+    let ring = SigningKeyRing::new(vec![key(0)]);
+    assert_eq!(ring.successor_activation(at(22), &policy()), Some(at(24)));
+}
+
+#[test]
+fn test_overdue_successor_is_still_published_ahead() {
+    let ring = SigningKeyRing::new(vec![key(0)]);
+    assert_eq!(ring.successor_activation(at(30), &policy()), Some(at(32)));
+}
+
+#[test]
+fn test_no_second_successor_while_one_is_pending() {
+    let ring = SigningKeyRing::new(vec![key(0), key(24)]);
+    assert_eq!(ring.successor_activation(at(23), &policy()), None);
+    assert_eq!(ring.next_activation(at(23)), Some(at(24)));
+}
+
+#[test]
+fn test_empty_ring_needs_a_key() {
+    let ring = SigningKeyRing::default();
+    assert_eq!(ring.successor_activation(at(0), &policy()), Some(at(0)));
+}
@@ -0,0 +1,3 @@
+pub mod error;
+pub mod invitation;
+pub mod sau_organization;
@@ -0,0 +1,17 @@
+#[derive(thiserror::Error, Debug)]
+pub enum SAUOrganizationDomainError {
+    #[error("Invalid slug : {0}")]
+    InvalidSlug(String),
+
+    #[error("Invalid name : {0}")]
+    InvalidName(String),
+
+    #[error("Invalid member role : {0}")]
+    InvalidMemberRole(String),
+
+    #[error("Invalid invitation : {0}")]
+    InvalidInvitation(String),
+
+    #[error("invitation issue failed : {0}")]
+    InvitationIssueFailed(String),
+}
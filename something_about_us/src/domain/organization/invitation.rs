@@ -0,0 +1,117 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use ring::{
+    digest::{digest, SHA256},
+    rand::{SecureRandom, SystemRandom},
+};
+use uuid::Uuid;
+
+use crate::domain::{
+    idp::supported_idp::SupportIdp,
+    organization::{error::SAUOrganizationDomainError, sau_organization::MemberRole},
+    user::{
+        sau_user::{Email, SAUUser},
+        user_identity::UserIdentity,
+    },
+};
+
+// opaque value handed to the inviter once, only its hash is persisted.
+pub type OpaqueInvitationToken = String;
+
+const INVITATION_TOKEN_BYTES: usize = 32;
+
+// who may accept an invitation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvitationTarget {
+    // the verified email of the invitee
+    Email(Email),
+    // the login of the invitee at `idp`, as the idp asserted it when the identity signed in
+    IdpUsername { idp: SupportIdp, username: String },
+}
+
+// single use, accepting it makes the invitee a member with `role`.
+// the token alone is not enough, the accepting user also has to match the target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invitation {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub token_hash: String,
+    pub target: InvitationTarget,
+    pub role: MemberRole,
+    pub invited_by: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Invitation {
+    pub fn new(
+        organization_id: Uuid,
+        target: InvitationTarget,
+        role: MemberRole,
+        invited_by: Uuid,
+        ttl: Duration,
+    ) -> Result<(OpaqueInvitationToken, Self), SAUOrganizationDomainError> {
+        if let InvitationTarget::IdpUsername { username, .. } = &target {
+            if username.is_empty() || username.len() > 50 {
+                return Err(SAUOrganizationDomainError::InvalidInvitation(format!(
+                    "invalid username : {}",
+                    username
+                )));
+            }
+        }
+
+        let mut bytes = [0u8; INVITATION_TOKEN_BYTES];
+        SystemRandom::new().fill(&mut bytes).map_err(|_| {
+            SAUOrganizationDomainError::InvitationIssueFailed("rng failed".to_string())
+        })?;
+        let token = BASE64_URL_SAFE_NO_PAD.encode(bytes);
+
+        let now = Utc::now();
+        let invitation = Self {
+            id: Uuid::now_v7(),
+            organization_id,
+            token_hash: Self::hash(&token),
+            target,
+            role,
+            invited_by,
+            expires_at: now + ttl,
+            created_at: now,
+        };
+        Ok((token, invitation))
+    }
+
+    pub fn hash(token: &str) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(digest(&SHA256, token.as_bytes()))
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+
+    // an unverified email never matches, anyone can put any address on an account.
+    // an idp login is only taken from the identity, the SAU username is chosen by the user.
+    pub fn is_for(&self, user: &SAUUser, identities: &[UserIdentity]) -> bool {
+        match &self.target {
+            InvitationTarget::Email(email) => {
+                user.email_verified
+                    && user
+                        .email
+                        .as_ref()
+                        .is_some_and(|own| own.as_str().eq_ignore_ascii_case(email.as_str()))
+            }
+            InvitationTarget::IdpUsername { idp, username } => identities.iter().any(|identity| {
+                identity.user_id == user.id
+                    && &identity.idp == idp
+                    && identity
+                        .idp_username
+                        .as_deref()
+                        .is_some_and(|own| own.eq_ignore_ascii_case(username))
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("invitation_test.rs");
+}
@@ -0,0 +1,90 @@
+use super::{Invitation, InvitationTarget};
+use crate::domain::{
+    idp::supported_idp::SupportIdp,
+    organization::sau_organization::MemberRole,
+    user::{
+        sau_user::{Email, SAUUser, Username},
+        user_identity::UserIdentity,
+    },
+};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+fn user(username: Option<&str>, email: Option<&str>, email_verified: bool) -> SAUUser {
+    SAUUser {
+        id: Uuid::now_v7(),
+        username: username.map(|username| Username::new(username.to_string()).unwrap()),
+        email: email.map(|email| Email::new(email.to_string()).unwrap()),
+        email_verified,
+        display_name: None,
+        avatar_url: None,
+        is_active: true,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn identity(user: &SAUUser, idp: &str, idp_username: Option<&str>) -> UserIdentity {
+    UserIdentity {
+        id: Uuid::now_v7(),
+        user_id: user.id,
+        idp: SupportIdp::try_from(idp).unwrap(),
+        idp_uid: "1".to_string(),
+        idp_username: idp_username.map(str::to_string),
+        created_at: Utc::now(),
+    }
+}
+
+fn invitation(target: InvitationTarget) -> (String, Invitation) {
+    Invitation::new(
+        Uuid::now_v7(),
+        target,
+        MemberRole::Member,
+        Uuid::now_v7(),
+        Duration::days(7),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_new_invitation() {
+    let (token, invitation) = invitation(InvitationTarget::Email(
+        Email::new("octocat@example.com".to_string()).unwrap(),
+    ));
+
+    assert_eq!(invitation.token_hash, Invitation::hash(&token));
+    assert_ne!(invitation.token_hash, token);
+    assert!(!invitation.is_expired(Utc::now()));
+    assert!(invitation.is_expired(Utc::now() + Duration::days(8)));
+}
+
+#[test]
+fn test_email_invitation_needs_verified_email() {
+    let (_, invitation) = invitation(InvitationTarget::Email(
+        Email::new("Octocat@Example.com".to_string()).unwrap(),
+    ));
+
+    assert!(invitation.is_for(&user(None, Some("octocat@example.com"), true), &[]));
+    assert!(!invitation.is_for(&user(None, Some("octocat@example.com"), false), &[]));
+    assert!(!invitation.is_for(&user(None, Some("other@example.com"), true), &[]));
+    assert!(!invitation.is_for(&user(None, None, false), &[]));
+}
+
+#[test]
+fn test_idp_username_invitation_needs_identity() {
+    let (_, invitation) = invitation(InvitationTarget::IdpUsername {
+        idp: SupportIdp::try_from("github").unwrap(),
+        username: "octocat".to_string(),
+    });
+
+    // the idp login matches, whatever SAU username the invitee picked
+    let invitee = user(Some("octocat-2"), None, false);
+    assert!(invitation.is_for(&invitee, &[identity(&invitee, "github", Some("OctoCat"))]));
+    assert!(!invitation.is_for(&invitee, &[identity(&invitee, "gitlab", Some("octocat"))]));
+    assert!(!invitation.is_for(&invitee, &[identity(&invitee, "github", None)]));
+
+    // renaming the SAU account to the invited login is not enough
+    let other = user(Some("octocat"), None, false);
+    assert!(!invitation.is_for(&other, &[identity(&other, "github", Some("hubot"))]));
+    assert!(!invitation.is_for(&other, &[identity(&invitee, "github", Some("octocat"))]));
+}
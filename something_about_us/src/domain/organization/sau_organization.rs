@@ -0,0 +1,167 @@
+use chrono::Utc;
+use sonic_rs::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::organization::error::SAUOrganizationDomainError;
+
+// a group of users, e.g. a company, identified in urls and tokens by its slug
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Organization {
+    pub id: Uuid,
+    // never changes, services may key their data by it
+    pub slug: String,
+    pub name: String,
+    pub created_at: chrono::DateTime<Utc>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+impl Organization {
+    pub fn new(slug: String, name: String) -> Result<Self, SAUOrganizationDomainError> {
+        validate_slug(&slug)?;
+        let name = validate_name(name)?;
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::now_v7(),
+            slug,
+            name,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+// ordered by what the role may do, an owner can do everything an admin can
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub enum MemberRole {
+    Member,
+    Admin,
+    Owner,
+}
+
+impl MemberRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MemberRole::Member => "member",
+            MemberRole::Admin => "admin",
+            MemberRole::Owner => "owner",
+        }
+    }
+
+    // admins and owners manage members, teams and invitations
+    pub fn can_manage(&self) -> bool {
+        *self >= MemberRole::Admin
+    }
+}
+
+impl TryFrom<&str> for MemberRole {
+    type Error = SAUOrganizationDomainError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "member" => Ok(MemberRole::Member),
+            "admin" => Ok(MemberRole::Admin),
+            "owner" => Ok(MemberRole::Owner),
+            _ => Err(SAUOrganizationDomainError::InvalidMemberRole(
+                value.to_string(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for MemberRole {
+    type Error = SAUOrganizationDomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        MemberRole::try_from(value.as_str())
+    }
+}
+
+impl From<MemberRole> for String {
+    fn from(value: MemberRole) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OrganizationMember {
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub role: MemberRole,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+impl OrganizationMember {
+    pub fn new(organization_id: Uuid, user_id: Uuid, role: MemberRole) -> Self {
+        Self {
+            organization_id,
+            user_id,
+            role,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+// a group of members within one organization, its slug is unique in the organization
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Team {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+impl Team {
+    pub fn new(
+        organization_id: Uuid,
+        slug: String,
+        name: String,
+    ) -> Result<Self, SAUOrganizationDomainError> {
+        validate_slug(&slug)?;
+        let name = validate_name(name)?;
+        Ok(Self {
+            id: Uuid::now_v7(),
+            organization_id,
+            slug,
+            name,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TeamMember {
+    pub team_id: Uuid,
+    pub user_id: Uuid,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+// lowercase ascii letters, digits and `-`, not starting or ending with `-`
+pub fn validate_slug(slug: &str) -> Result<(), SAUOrganizationDomainError> {
+    let valid = !slug.is_empty()
+        && slug.len() <= 50
+        && !slug.starts_with('-')
+        && !slug.ends_with('-')
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid {
+        return Err(SAUOrganizationDomainError::InvalidSlug(slug.to_string()));
+    }
+    Ok(())
+}
+
+// 1 to 100 bytes once trimmed
+pub fn validate_name(name: String) -> Result<String, SAUOrganizationDomainError> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed.len() > 100 {
+        return Err(SAUOrganizationDomainError::InvalidName(name));
+    }
+    Ok(trimmed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    include!("sau_organization_test.rs");
+}
@@ -0,0 +1,51 @@
+use super::{MemberRole, Organization, Team};
+use uuid::Uuid;
+
+#[test]
+fn test_new_organization() {
+    let organization =
+        Organization::new("acme-corp".to_string(), " Acme Corp ".to_string()).unwrap();
+
+    assert_eq!(organization.slug, "acme-corp");
+    assert_eq!(organization.name, "Acme Corp");
+    assert_eq!(organization.created_at, organization.updated_at);
+}
+
+#[test]
+fn test_invalid_slug_and_name() {
+    for slug in [
+        "",
+        "Acme",
+        "acme corp",
+        "acme_corp",
+        "-acme",
+        "acme-",
+        &"a".repeat(51),
+    ] {
+        assert!(
+            Organization::new(slug.to_string(), "Acme".to_string()).is_err(),
+            "{}",
+            slug
+        );
+    }
+    assert!(Organization::new("acme".to_string(), "  ".to_string()).is_err());
+    assert!(Team::new(Uuid::now_v7(), "backend".to_string(), "a".repeat(101)).is_err());
+}
+
+#[test]
+fn test_member_role() {
+    assert!(MemberRole::Owner > MemberRole::Admin);
+    assert!(MemberRole::Admin > MemberRole::Member);
+    assert!(MemberRole::Owner.can_manage());
+    assert!(MemberRole::Admin.can_manage());
+    assert!(!MemberRole::Member.can_manage());
+
+    for role in [MemberRole::Member, MemberRole::Admin, MemberRole::Owner] {
+        assert_eq!(MemberRole::try_from(role.as_str()).unwrap(), role);
+    }
+    assert!(MemberRole::try_from("Owner").is_err());
+    assert_eq!(
+        sonic_rs::to_string(&MemberRole::Admin).unwrap(),
+        "\"admin\""
+    );
+}
@@ -1,2 +1,6 @@
 pub mod error;
+pub mod role;
 pub mod sau_user;
+pub mod user_filter;
+pub mod user_identity;
+pub mod user_profile;
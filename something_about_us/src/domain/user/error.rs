@@ -5,4 +5,10 @@ pub enum SAUUserDomainError {
 
     #[error("Invalid username : {0}")]
     InvalidUsername(String),
+
+    #[error("Invalid role name : {0}")]
+    InvalidRole(String),
+
+    #[error("Invalid permission : {0}")]
+    InvalidPermission(String),
 }
@@ -0,0 +1,107 @@
+use chrono::Utc;
+use sonic_rs::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{oauth::sau_jwt::OrgClaim, user::error::SAUUserDomainError};
+
+// permission every admin endpoint accepts besides `security.admin.user_ids`
+pub const ADMIN_PERMISSION: &str = "admin";
+
+// named set of permissions assigned to users, e.g. `support` with `users:read`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Role {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    // sorted and without duplicates
+    pub permissions: Vec<String>,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+impl Role {
+    pub fn new(
+        name: String,
+        description: Option<String>,
+        permissions: Vec<String>,
+    ) -> Result<Self, SAUUserDomainError> {
+        Self::validate_name(&name)?;
+        Ok(Self {
+            id: Uuid::now_v7(),
+            name,
+            description: description.filter(|description| !description.trim().is_empty()),
+            permissions: Self::normalize_permissions(permissions)?,
+            created_at: Utc::now(),
+        })
+    }
+
+    // lowercase ascii letters, digits, `-` and `_`, like provider names
+    pub fn validate_name(name: &str) -> Result<(), SAUUserDomainError> {
+        let valid = !name.is_empty()
+            && name.len() <= 50
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if !valid {
+            return Err(SAUUserDomainError::InvalidRole(name.to_string()));
+        }
+        Ok(())
+    }
+
+    // permissions end up space separated in the `scope` claim, e.g. `clients:write`
+    pub fn normalize_permissions(
+        permissions: Vec<String>,
+    ) -> Result<Vec<String>, SAUUserDomainError> {
+        for permission in &permissions {
+            let valid = !permission.is_empty()
+                && permission.len() <= 100
+                && permission.chars().all(|c| {
+                    c.is_ascii_lowercase()
+                        || c.is_ascii_digit()
+                        || matches!(c, ':' | '.' | '-' | '_')
+                });
+            if !valid {
+                return Err(SAUUserDomainError::InvalidPermission(permission.clone()));
+            }
+        }
+        let mut permissions = permissions;
+        permissions.sort();
+        permissions.dedup();
+        Ok(permissions)
+    }
+}
+
+// what the roles and organizations of a user grant, carried in its access tokens
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserGrants {
+    pub roles: Vec<String>,
+    pub permissions: Vec<String>,
+    pub orgs: Vec<OrgClaim>,
+}
+
+impl UserGrants {
+    pub fn from_roles(roles: &[Role]) -> Self {
+        let mut names: Vec<String> = roles.iter().map(|role| role.name.clone()).collect();
+        names.sort();
+        let mut permissions: Vec<String> = roles
+            .iter()
+            .flat_map(|role| role.permissions.iter().cloned())
+            .collect();
+        permissions.sort();
+        permissions.dedup();
+        Self {
+            roles: names,
+            permissions,
+            orgs: vec![],
+        }
+    }
+
+    // `None` without any permission, the claim is left out then
+    pub fn scope(&self) -> Option<String> {
+        (!self.permissions.is_empty()).then(|| self.permissions.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("role_test.rs");
+}
@@ -0,0 +1,61 @@
+use super::{Role, UserGrants};
+
+#[test]
+fn test_new_role() {
+    let role = Role::new(
+        "support".to_string(),
+        Some(" ".to_string()),
+        vec![
+            "users:read".to_string(),
+            "clients:read".to_string(),
+            "users:read".to_string(),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(role.name, "support");
+    assert_eq!(role.description, None);
+    assert_eq!(role.permissions, vec!["clients:read", "users:read"]);
+}
+
+#[test]
+fn test_invalid_role() {
+    for name in ["", "Support", "support team", &"a".repeat(51)] {
+        assert!(
+            Role::new(name.to_string(), None, vec![]).is_err(),
+            "{}",
+            name
+        );
+    }
+    for permission in ["", "users read", "Users:read", "users/read"] {
+        assert!(
+            Role::new("support".to_string(), None, vec![permission.to_string()]).is_err(),
+            "{}",
+            permission
+        );
+    }
+}
+
+#[test]
+fn test_user_grants() {
+    let support = Role::new(
+        "support".to_string(),
+        None,
+        vec!["users:read".to_string(), "clients:read".to_string()],
+    )
+    .unwrap();
+    let admin = Role::new(
+        "admin".to_string(),
+        None,
+        vec!["admin".to_string(), "users:read".to_string()],
+    )
+    .unwrap();
+
+    let grants = UserGrants::from_roles(&[support, admin]);
+    assert_eq!(grants.roles, vec!["admin", "support"]);
+    assert_eq!(
+        grants.scope().as_deref(),
+        Some("admin clients:read users:read")
+    );
+    assert_eq!(UserGrants::default().scope(), None);
+}
@@ -3,15 +3,20 @@ use sonic_rs::{Deserialize, Serialize};
 use std::fmt::Display;
 use uuid::Uuid;
 
-use crate::domain::{idp::supported_idp::SupportIdp, user::error::SAUUserDomainError};
+use crate::domain::user::error::SAUUserDomainError;
+
+// in bytes
+pub const MAX_USERNAME_LEN: usize = 50;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SAUUser {
     pub id: Uuid,
     pub username: Option<Username>,
     pub email: Option<Email>,
-    pub idp: SupportIdp,
-    pub idp_uid: String,
+    pub email_verified: bool,
+    // imported from the idp, not editable by the user
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
     pub is_active: bool,
     pub created_at: chrono::DateTime<Utc>,
     pub updated_at: chrono::DateTime<Utc>,
@@ -22,7 +27,7 @@ pub struct Username(String);
 
 impl Username {
     pub fn new(username: String) -> Result<Self, SAUUserDomainError> {
-        if username.is_empty() || username.len() > 50 {
+        if username.is_empty() || username.len() > MAX_USERNAME_LEN {
             Err(SAUUserDomainError::InvalidUsername(username))
         } else {
             Ok(Self(username))
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{idp::supported_idp::SupportIdp, user::sau_user::SAUUser};
+
+// criteria of the admin user listing, a field left `None` matches every user
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserFilter {
+    // users with an identity of this idp
+    pub idp: Option<SupportIdp>,
+    pub is_active: Option<bool>,
+    // inclusive
+    pub created_from: Option<DateTime<Utc>>,
+    // exclusive
+    pub created_to: Option<DateTime<Utc>>,
+    // case-insensitive prefix of the username or the email
+    pub search: Option<String>,
+}
+
+// users ordered by id, which being a UUIDv7 is also the creation order.
+// `next_cursor` is the id to continue after, `None` on the last page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserPage {
+    pub users: Vec<SAUUser>,
+    pub next_cursor: Option<Uuid>,
+}
@@ -0,0 +1,19 @@
+use chrono::Utc;
+use sonic_rs::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::idp::supported_idp::SupportIdp;
+
+// an idp account linked to a user, a user has one or more of them.
+// `(idp, idp_uid)` belongs to at most one user.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UserIdentity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub idp: SupportIdp,
+    pub idp_uid: String,
+    // the login at the idp as it asserted it at the last sign-in, `None` when not released.
+    // unlike the SAU username the user can not change it here
+    pub idp_username: Option<String>,
+    pub created_at: chrono::DateTime<Utc>,
+}
@@ -0,0 +1,40 @@
+use url::Url;
+
+use crate::domain::user::sau_user::{Email, Username};
+
+pub const MAX_DISPLAY_NAME_LEN: usize = 100;
+pub const MAX_AVATAR_URL_LEN: usize = 2048;
+
+// profile a user is created with from its idp, and later refreshed from it.
+// on a refresh a field left `None` keeps the stored value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserProfile {
+    pub username: Option<Username>,
+    pub email: Option<Email>,
+    pub email_verified: bool,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+// the idp name trimmed, dropped when blank or longer than `MAX_DISPLAY_NAME_LEN` chars
+pub fn display_name(name: Option<String>) -> Option<String> {
+    let name = name?.trim().to_string();
+    (!name.is_empty() && name.chars().count() <= MAX_DISPLAY_NAME_LEN).then_some(name)
+}
+
+// only http(s) urls are kept, anything else could not be shown as an image anyway
+pub fn avatar_url(url: Option<String>) -> Option<String> {
+    let url = url?;
+    if url.len() > MAX_AVATAR_URL_LEN {
+        return None;
+    }
+    Url::parse(&url)
+        .ok()
+        .filter(|parsed| matches!(parsed.scheme(), "http" | "https"))
+        .map(|_| url)
+}
+
+#[cfg(test)]
+mod tests {
+    include!("user_profile_test.rs");
+}
@@ -0,0 +1,24 @@
+use super::{avatar_url, display_name, MAX_DISPLAY_NAME_LEN};
+
+#[test]
+fn test_display_name() {
+    assert_eq!(
+        display_name(Some("  The Octocat ".to_string())).as_deref(),
+        Some("The Octocat")
+    );
+    assert_eq!(display_name(Some(" ".to_string())), None);
+    assert_eq!(display_name(None), None);
+
+    let longest = "가".repeat(MAX_DISPLAY_NAME_LEN);
+    assert_eq!(display_name(Some(longest.clone())), Some(longest.clone()));
+    assert_eq!(display_name(Some(format!("{}a", longest))), None);
+}
+
+#[test]
+fn test_avatar_url() {
+    let url = "https://avatars.githubusercontent.com/u/583231?v=4".to_string();
+    assert_eq!(avatar_url(Some(url.clone())), Some(url));
+    assert_eq!(avatar_url(Some("javascript:alert(1)".to_string())), None);
+    assert_eq!(avatar_url(Some("not a url".to_string())), None);
+    assert_eq!(avatar_url(None), None);
+}
@@ -1 +1,2 @@
 pub mod jwt_issuer_helper;
+pub mod key_rotation;
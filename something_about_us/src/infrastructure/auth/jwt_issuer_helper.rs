@@ -1,90 +1,185 @@
-use std::{collections::HashMap, path::PathBuf, str::FromStr};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
-use jsonwebtoken::jwk::{
-    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, OctetKeyPairParameters,
-    PublicKeyUse,
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{
+    jwk::{
+        AlgorithmParameters, CommonParameters, EllipticCurve, EllipticCurveKeyParameters,
+        EllipticCurveKeyType, Jwk, JwkSet, KeyAlgorithm, OctetKeyPairParameters, OctetKeyPairType,
+        PublicKeyUse, RSAKeyParameters, RSAKeyType,
+    },
+    DecodingKey, EncodingKey, Validation,
+};
+use ring::{
+    rand::SystemRandom,
+    signature::{EcdsaKeyPair, Ed25519KeyPair, KeyPair as _, ECDSA_P256_SHA256_FIXED_SIGNING},
 };
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use rsa::{
+    pkcs1::EncodeRsaPrivateKey,
+    pkcs8::{DecodePrivateKey, EncodePrivateKey},
+    rand_core::OsRng,
+    traits::PublicKeyParts,
+    RsaPrivateKey,
+};
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
 use uuid::Uuid;
 
 use crate::{
-    domain::oauth::{
-        error::SAUOAuthDomainError,
-        sau_jwt::{SAUClaims, SAUJwt},
-        sau_jwt_issuer::{JwtIssue, KeyPair, SAUJwtIssuer},
+    domain::{
+        oauth::{
+            error::SAUOAuthDomainError,
+            id_token::{IdToken, IdTokenClaims},
+            sau_jwt::{SAUClaims, SAUJwt, ServiceClaims, ACCESS_TOKEN_TYPE, SERVICE_TOKEN_TYPE},
+            sau_jwt_issuer::{
+                JwtIssue, KeyPair, PublicKeyParameters, SAUJwtIssuer, SigningAlgorithm,
+            },
+            signing_key::{SigningKeyRing, SigningKeySchedule},
+        },
+        user::role::UserGrants,
+    },
+    infrastructure::{
+        auth::key_rotation::{read_key_ring, write_key_schedule},
+        config::types::JwtConfig,
     },
-    infrastructure::config::types::JwtConfig,
 };
 
+const RSA_KEY_BITS: usize = 2048;
+
 pub struct JwtIssuerHelper;
 
 impl JwtIssuerHelper {
-    pub async fn make_jwtissuer(config: &JwtConfig) -> SAUJwtIssuer {
+    // builds the issuer from every published key in `keys_path` and returns it with the signing kid.
+    pub async fn make_jwtissuer(config: &JwtConfig) -> (SAUJwtIssuer, Uuid) {
         let helper = JwtIssuerHelper {};
-        let key_pair = helper.read_or_create_key(&config).await;
+        let now = Utc::now();
+        let path = PathBuf::from_str(&config.keys_path).expect("fail to parse jwt path");
 
-        SAUJwtIssuer::new(
-            config.iss.clone(),
-            config.aud.clone(),
-            config.access_token_ttl,
-            key_pair,
-        )
+        let mut key_ring = read_key_ring(&path)
+            .await
+            .expect("fail to read jwks key schedules");
+        helper
+            .adopt_configured_keys(config, &path, &mut key_ring, now)
+            .await
+            .expect("fail to read or generate jwks key pair");
+        helper
+            .build_issuer(config, &path, &key_ring, now)
+            .await
+            .expect("fail to build jwt issuer")
     }
 
+    // configured keys without a schedule are adopted, the first one signs from now on
+    // and the others are treated as replaced keys that only stay published for one token lifetime.
     // if `key path + kid` exists, read the key pair from the file.
     // if not exists, generate a new key pair and write it to the file.
-    // it only support Ed25519 key pair and pkcs8 format.
-    async fn read_or_create_key(&self, config: &JwtConfig) -> HashMap<Uuid, KeyPair> {
-        let path = PathBuf::from_str(&config.keys_path).expect("fail to parse jwt path");
-        let kids = config.keys.iter().map(|k| k.kid).collect::<Vec<Uuid>>();
+    async fn adopt_configured_keys(
+        &self,
+        config: &JwtConfig,
+        path: &Path,
+        key_ring: &mut SigningKeyRing,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        for (index, key) in config.keys.iter().enumerate() {
+            match self.read_key(path.to_path_buf(), key.kid).await? {
+                Some(pair) if pair.algorithm() != key.algorithm => {
+                    return Err(format!(
+                        "jwks key {} is {:?} but configured as {:?}",
+                        key.kid,
+                        pair.algorithm(),
+                        key.algorithm
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    self.gen_key_pair(path.to_path_buf(), key.kid, key.algorithm)
+                        .await?;
+                }
+            }
+            if key_ring.contains(&key.kid) {
+                continue;
+            }
 
-        let mut key_pair = HashMap::new();
-        for kid in kids {
-            let key = match self.read_key(path.clone(), kid).await {
-                Some(key) => key,
-                None => self
-                    .gen_key_pair(path.clone(), kid)
-                    .await
-                    .expect("fail to generate jwks key pair"),
+            let schedule = SigningKeySchedule {
+                kid: key.kid,
+                activates_at: if index == 0 {
+                    now
+                } else {
+                    DateTime::UNIX_EPOCH
+                },
             };
-            key_pair.insert(kid, key);
+            write_key_schedule(path, &schedule).await?;
+            key_ring.insert(schedule);
         }
+        Ok(())
+    }
+
+    // loads the key pair of every published key, retired ones are left on disk.
+    pub(crate) async fn build_issuer(
+        &self,
+        config: &JwtConfig,
+        path: &Path,
+        key_ring: &SigningKeyRing,
+        now: DateTime<Utc>,
+    ) -> Result<(SAUJwtIssuer, Uuid), String> {
+        let token_lifetime = Duration::seconds((config.access_token_ttl + config.leeway) as i64);
+        let signing_kid = key_ring
+            .signing_key(now)
+            .ok_or("no jwks key is active".to_string())?
+            .kid;
 
-        key_pair
+        let mut key_pair = HashMap::new();
+        for key in key_ring.published(now, token_lifetime) {
+            match self.read_key(path.to_path_buf(), key.kid).await {
+                Ok(Some(pair)) => {
+                    key_pair.insert(key.kid, pair);
+                }
+                Ok(None) => warn!("jwks key {} is published but its pem is missing", key.kid),
+                Err(e) => warn!("jwks key {} is published but skipped : {}", key.kid, e),
+            }
+        }
+        if !key_pair.contains_key(&signing_kid) {
+            return Err(format!("pem of the signing key {} is missing", signing_kid));
+        }
+
+        let issuer = SAUJwtIssuer::new(
+            config.iss.clone(),
+            config.aud.clone(),
+            config.access_token_ttl,
+            key_pair,
+        )
+        .with_leeway(config.leeway);
+        Ok((issuer, signing_kid))
     }
 
-    async fn read_key(&self, path: PathBuf, kid: Uuid) -> Option<KeyPair> {
+    // key files are pkcs8 DER, the algorithm is taken from the key itself.
+    // `None` when there is no file for `kid`, an unreadable or broken one is an error.
+    pub(crate) async fn read_key(
+        &self,
+        path: PathBuf,
+        kid: Uuid,
+    ) -> Result<Option<KeyPair>, String> {
         let key_path_string = format!("{}/{}.pem", path.display(), kid);
 
-        if let Ok(mut fs) = tokio::fs::File::options()
-            .read(true)
-            .open(key_path_string)
-            .await
-        {
-            let mut buf = Vec::with_capacity(100);
-            fs.read_to_end(&mut buf)
-                .await
-                .expect("fail to read jwks file");
-            let key_pair = ring::signature::Ed25519KeyPair::from_pkcs8(&buf)
-                .expect("fail to read pkcs8 jwk key");
-
-            let private_key = jsonwebtoken::EncodingKey::from_ed_der(&buf);
-            let public_key = jsonwebtoken::DecodingKey::from_ed_der(
-                ring::signature::KeyPair::public_key(&key_pair).as_ref(),
-            );
-
-            return Some(KeyPair {
-                private_key,
-                public_key,
-                x: BASE64_URL_SAFE_NO_PAD
-                    .encode(ring::signature::KeyPair::public_key(&key_pair).as_ref()),
-            });
-        }
-        None
+        let buf = match tokio::fs::read(&key_path_string).await {
+            Ok(buf) => buf,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("fail to read {} : {}", key_path_string, e)),
+        };
+        key_pair_from_pkcs8(&buf)
+            .map(Some)
+            .map_err(|e| format!("fail to parse {} : {}", key_path_string, e))
     }
 
-    async fn gen_key_pair(&self, path: PathBuf, kid: Uuid) -> Result<KeyPair, String> {
+    pub(crate) async fn gen_key_pair(
+        &self,
+        path: PathBuf,
+        kid: Uuid,
+        algorithm: SigningAlgorithm,
+    ) -> Result<KeyPair, String> {
         let key_path_string = format!("{}/{}.pem", path.display(), kid);
 
         if let Ok(mut fs) = tokio::fs::File::options()
@@ -93,23 +188,12 @@ impl JwtIssuerHelper {
             .open(key_path_string.clone())
             .await
         {
-            let rng = ring::rand::SystemRandom::new();
-            let gen_key =
-                ring::signature::Ed25519KeyPair::generate_pkcs8(&rng).map_err(|e| e.to_string())?;
-            fs.write_all(gen_key.as_ref())
+            // RSA key generation takes a while, keep it off the runtime threads
+            let pkcs8 = tokio::task::spawn_blocking(move || generate_pkcs8(algorithm))
                 .await
-                .map_err(|e| e.to_string())?;
-            let key_pair = ring::signature::Ed25519KeyPair::from_pkcs8(gen_key.as_ref())
-                .map_err(|e| e.to_string())?;
-
-            return Ok(KeyPair {
-                private_key: jsonwebtoken::EncodingKey::from_ed_der(gen_key.as_ref()),
-                public_key: jsonwebtoken::DecodingKey::from_ed_der(
-                    ring::signature::KeyPair::public_key(&key_pair).as_ref(),
-                ),
-                x: BASE64_URL_SAFE_NO_PAD
-                    .encode(ring::signature::KeyPair::public_key(&key_pair).as_ref()),
-            });
+                .map_err(|e| e.to_string())??;
+            fs.write_all(&pkcs8).await.map_err(|e| e.to_string())?;
+            return key_pair_from_pkcs8(&pkcs8);
         }
 
         Err(format!(
@@ -119,57 +203,274 @@ impl JwtIssuerHelper {
     }
 }
 
-impl JwtIssue for SAUJwtIssuer {
-    fn issue_with_id(&self, kid: &Uuid, uid: &Uuid) -> Result<SAUJwt, SAUOAuthDomainError> {
-        let mut header = self.header.clone();
-        header.kid = Some(kid.to_string());
+// RSA keys are not generated by ring, so they come from the `rsa` crate.
+fn generate_pkcs8(algorithm: SigningAlgorithm) -> Result<Vec<u8>, String> {
+    let rng = SystemRandom::new();
+    match algorithm {
+        SigningAlgorithm::EdDSA => Ed25519KeyPair::generate_pkcs8(&rng)
+            .map(|pkcs8| pkcs8.as_ref().to_vec())
+            .map_err(|e| e.to_string()),
+        SigningAlgorithm::ES256 => {
+            EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+                .map(|pkcs8| pkcs8.as_ref().to_vec())
+                .map_err(|e| e.to_string())
+        }
+        SigningAlgorithm::RS256 => RsaPrivateKey::new(&mut OsRng, RSA_KEY_BITS)
+            .map_err(|e| e.to_string())?
+            .to_pkcs8_der()
+            .map(|pkcs8| pkcs8.as_bytes().to_vec())
+            .map_err(|e| e.to_string()),
+    }
+}
 
-        let private_key = self
+fn key_pair_from_pkcs8(pkcs8: &[u8]) -> Result<KeyPair, String> {
+    if let Ok(key_pair) = Ed25519KeyPair::from_pkcs8(pkcs8) {
+        let public_key = key_pair.public_key().as_ref();
+        return Ok(KeyPair {
+            private_key: EncodingKey::from_ed_der(pkcs8),
+            public_key: DecodingKey::from_ed_der(public_key),
+            parameters: PublicKeyParameters::Ed25519 {
+                x: BASE64_URL_SAFE_NO_PAD.encode(public_key),
+            },
+        });
+    }
+
+    if let Ok(key_pair) = EcdsaKeyPair::from_pkcs8(
+        &ECDSA_P256_SHA256_FIXED_SIGNING,
+        pkcs8,
+        &SystemRandom::new(),
+    ) {
+        // uncompressed point, 0x04 || x || y
+        let public_key = key_pair.public_key().as_ref();
+        let (x, y) = public_key[1..].split_at(32);
+        return Ok(KeyPair {
+            private_key: EncodingKey::from_ec_der(pkcs8),
+            public_key: DecodingKey::from_ec_der(public_key),
+            parameters: PublicKeyParameters::P256 {
+                x: BASE64_URL_SAFE_NO_PAD.encode(x),
+                y: BASE64_URL_SAFE_NO_PAD.encode(y),
+            },
+        });
+    }
+
+    let key = RsaPrivateKey::from_pkcs8_der(pkcs8)
+        .map_err(|_| "key is neither Ed25519, P-256 nor RSA pkcs8".to_string())?;
+    // jsonwebtoken signs with the pkcs1 form of RSA keys
+    let pkcs1 = key.to_pkcs1_der().map_err(|e| e.to_string())?;
+    let n = key.n().to_bytes_be();
+    let e = key.e().to_bytes_be();
+    Ok(KeyPair {
+        private_key: EncodingKey::from_rsa_der(pkcs1.as_bytes()),
+        public_key: DecodingKey::from_rsa_raw_components(&n, &e),
+        parameters: PublicKeyParameters::Rsa {
+            n: BASE64_URL_SAFE_NO_PAD.encode(n),
+            e: BASE64_URL_SAFE_NO_PAD.encode(e),
+        },
+    })
+}
+
+impl SAUJwtIssuer {
+    fn sign<T: sonic_rs::Serialize>(
+        &self,
+        kid: &Uuid,
+        typ: &str,
+        claims: &T,
+    ) -> Result<SAUJwt, SAUOAuthDomainError> {
+        let key_pair = self
             .key_pair
-            .get(&kid)
+            .get(kid)
             .or_else(|| self.key_pair.values().next())
             .ok_or(SAUOAuthDomainError::JwtIssueFailed(format!(
                 "kid : {} not found",
-                kid.to_string()
-            )))?
-            .private_key
-            .clone();
+                kid
+            )))?;
+
+        let mut header = self.header.clone();
+        header.alg = key_pair.algorithm().into();
+        header.kid = Some(kid.to_string());
+        header.typ = Some(typ.to_string());
+
+        jsonwebtoken::encode(&header, claims, &key_pair.private_key)
+            .map_err(|e| SAUOAuthDomainError::JwtIssueFailed(e.to_string()))
+    }
+}
+
+impl JwtIssue for SAUJwtIssuer {
+    fn issue_with_id(
+        &self,
+        kid: &Uuid,
+        uid: &Uuid,
+        grants: &UserGrants,
+    ) -> Result<SAUJwt, SAUOAuthDomainError> {
+        self.issue_for_audience(kid, uid, &self.aud, self.access_token_ttl, grants)
+    }
 
+    fn issue_for_audience(
+        &self,
+        kid: &Uuid,
+        uid: &Uuid,
+        aud: &str,
+        ttl: std::time::Duration,
+        grants: &UserGrants,
+    ) -> Result<SAUJwt, SAUOAuthDomainError> {
         let now = chrono::Utc::now();
         let claim = SAUClaims {
-            aud: self.aud.clone(),
+            aud: aud.to_string(),
             iss: self.iss.clone(),
-            sub: uid.clone(),
-            exp: (now + self.access_token_ttl.clone()).timestamp(),
+            sub: *uid,
+            exp: (now + ttl).timestamp(),
             jti: Uuid::now_v7(),
             iat: now.timestamp(),
             nbf: now.timestamp(),
+            roles: grants.roles.clone(),
+            scope: grants.scope(),
+            orgs: grants.orgs.clone(),
         };
+        self.sign(kid, ACCESS_TOKEN_TYPE, &claim)
+    }
 
-        let jwt = jsonwebtoken::encode(&header, &claim, &private_key)
-            .map_err(|e| SAUOAuthDomainError::JwtIssueFailed(e.to_string()))?;
+    fn issue_service_token(
+        &self,
+        kid: &Uuid,
+        client_id: &str,
+        aud: &str,
+        scope: &str,
+        ttl: std::time::Duration,
+    ) -> Result<SAUJwt, SAUOAuthDomainError> {
+        let now = chrono::Utc::now();
+        let claims = ServiceClaims {
+            aud: aud.to_string(),
+            iss: self.iss.clone(),
+            sub: client_id.to_string(),
+            scope: scope.to_string(),
+            exp: (now + ttl).timestamp(),
+            jti: Uuid::now_v7(),
+            iat: now.timestamp(),
+            nbf: now.timestamp(),
+        };
+        self.sign(kid, SERVICE_TOKEN_TYPE, &claims)
+    }
 
-        Ok(jwt)
+    fn issue_id_token(
+        &self,
+        kid: &Uuid,
+        id_token: &IdToken,
+    ) -> Result<SAUJwt, SAUOAuthDomainError> {
+        let now = chrono::Utc::now();
+        let claims = IdTokenClaims::new(
+            id_token,
+            self.iss.clone(),
+            now.timestamp(),
+            (now + self.access_token_ttl).timestamp(),
+        );
+        self.sign(kid, ACCESS_TOKEN_TYPE, &claims)
     }
 
     fn create_jwks(&self) -> JwkSet {
         let mut keys = Vec::new();
         for (kid, key_pair) in self.key_pair.iter() {
+            let (key_algorithm, algorithm) = match &key_pair.parameters {
+                PublicKeyParameters::Ed25519 { x } => (
+                    KeyAlgorithm::EdDSA,
+                    AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                        key_type: OctetKeyPairType::OctetKeyPair,
+                        curve: EllipticCurve::Ed25519,
+                        x: x.clone(),
+                    }),
+                ),
+                PublicKeyParameters::P256 { x, y } => (
+                    KeyAlgorithm::ES256,
+                    AlgorithmParameters::EllipticCurve(EllipticCurveKeyParameters {
+                        key_type: EllipticCurveKeyType::EC,
+                        curve: EllipticCurve::P256,
+                        x: x.clone(),
+                        y: y.clone(),
+                    }),
+                ),
+                PublicKeyParameters::Rsa { n, e } => (
+                    KeyAlgorithm::RS256,
+                    AlgorithmParameters::RSA(RSAKeyParameters {
+                        key_type: RSAKeyType::RSA,
+                        n: n.clone(),
+                        e: e.clone(),
+                    }),
+                ),
+            };
             let jwk = Jwk {
                 common: CommonParameters {
                     key_id: Some(kid.to_string()),
-                    key_algorithm: Some(jsonwebtoken::jwk::KeyAlgorithm::EdDSA),
+                    key_algorithm: Some(key_algorithm),
                     public_key_use: Some(PublicKeyUse::Signature),
                     ..Default::default()
                 },
-                algorithm: AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
-                    key_type: jsonwebtoken::jwk::OctetKeyPairType::OctetKeyPair,
-                    curve: EllipticCurve::Ed25519,
-                    x: key_pair.x.clone(),
-                }),
+                algorithm,
             };
             keys.push(jwk);
         }
         JwkSet { keys }
     }
+
+    fn verify(&self, jwt: &str) -> Result<SAUClaims, SAUOAuthDomainError> {
+        self.decode(jwt, ACCESS_TOKEN_TYPE, Some(&self.aud))
+    }
+
+    fn verify_any_audience(&self, jwt: &str) -> Result<SAUClaims, SAUOAuthDomainError> {
+        self.decode(jwt, ACCESS_TOKEN_TYPE, None)
+    }
+
+    fn verify_service_token(&self, jwt: &str) -> Result<ServiceClaims, SAUOAuthDomainError> {
+        self.decode(jwt, SERVICE_TOKEN_TYPE, None)
+    }
+}
+
+impl SAUJwtIssuer {
+    fn decode<T: serde::de::DeserializeOwned>(
+        &self,
+        jwt: &str,
+        typ: &str,
+        aud: Option<&str>,
+    ) -> Result<T, SAUOAuthDomainError> {
+        let header = jsonwebtoken::decode_header(jwt)
+            .map_err(|e| SAUOAuthDomainError::InvalidJwt(e.to_string()))?;
+        if header.typ.as_deref() != Some(typ) {
+            return Err(SAUOAuthDomainError::InvalidJwt(format!(
+                "token type is not {}",
+                typ
+            )));
+        }
+        let kid = header
+            .kid
+            .as_deref()
+            .and_then(|kid| Uuid::parse_str(kid).ok())
+            .ok_or(SAUOAuthDomainError::InvalidJwt(
+                "kid is missing or malformed".to_string(),
+            ))?;
+        let key_pair = self
+            .key_pair
+            .get(&kid)
+            .ok_or(SAUOAuthDomainError::InvalidJwt(format!(
+                "kid : {} not found",
+                kid
+            )))?;
+
+        // only the algorithm of the key is accepted, whatever the header claims
+        let mut validation = Validation::new(key_pair.algorithm().into());
+        validation.set_issuer(&[&self.iss]);
+        match aud {
+            Some(aud) => validation.set_audience(&[aud]),
+            None => validation.validate_aud = false,
+        }
+        validation.set_required_spec_claims(&["exp", "nbf", "iss", "aud", "sub"]);
+        validation.validate_nbf = true;
+        validation.leeway = self.leeway;
+
+        jsonwebtoken::decode::<T>(jwt, &key_pair.public_key, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| SAUOAuthDomainError::InvalidJwt(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("jwt_issuer_helper_test.rs");
 }
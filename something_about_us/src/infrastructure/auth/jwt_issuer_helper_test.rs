@@ -0,0 +1,215 @@
+use super::{JwtIssuerHelper, RSA_KEY_BITS};
+use crate::{
+    domain::{
+        oauth::{
+            sau_jwt_issuer::{JwtIssue, PublicKeyParameters, SAUJwtIssuer, SigningAlgorithm},
+            signing_key::SigningKeyRing,
+        },
+        user::role::UserGrants,
+    },
+    infrastructure::{
+        auth::key_rotation::read_key_ring,
+        config::types::{JwtConfig, KeyConfig, KeyRotationConfig},
+    },
+};
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::jwk::AlgorithmParameters;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn config(keys_path: &std::path::Path, keys: &[(Uuid, SigningAlgorithm)]) -> JwtConfig {
+    JwtConfig {
+        iss: "test-issuer".to_string(),
+        aud: "test-audience".to_string(),
+        keys_path: keys_path.display().to_string(),
+        keys: keys
+            .iter()
+            .map(|(kid, algorithm)| KeyConfig {
+                kid: *kid,
+                algorithm: *algorithm,
+            })
+            .collect(),
+        access_token_ttl: 3600,
+        refresh_token_ttl: 86400,
+        client_credentials_ttl: 600,
+        leeway: 0,
+        rotation: KeyRotationConfig::default(),
+        orgs_claim: false,
+    }
+}
+
+async fn issuer_with(algorithm: SigningAlgorithm) -> (SAUJwtIssuer, Uuid) {
+    let dir = tempfile::tempdir().unwrap();
+    let kid = Uuid::now_v7();
+    let key_pair = JwtIssuerHelper {}
+        .gen_key_pair(dir.path().to_path_buf(), kid, algorithm)
+        .await
+        .unwrap();
+    let issuer = SAUJwtIssuer::new(
+        "test-issuer".to_string(),
+        "test-audience".to_string(),
+        3600,
+        HashMap::from([(kid, key_pair)]),
+    );
+    (issuer, kid)
+}
+
+#[tokio::test]
+async fn test_es256_issue_and_verify() {
+    let (issuer, kid) = issuer_with(SigningAlgorithm::ES256).await;
+    let uid = Uuid::now_v7();
+    let jwt = issuer
+        .issue_with_id(&kid, &uid, &UserGrants::default())
+        .unwrap();
+
+    let header = jsonwebtoken::decode_header(&jwt).unwrap();
+    assert_eq!(header.alg, jsonwebtoken::Algorithm::ES256);
+    assert_eq!(issuer.verify(&jwt).unwrap().sub, uid);
+
+    let jwks = issuer.create_jwks();
+    let AlgorithmParameters::EllipticCurve(params) = &jwks.keys[0].algorithm else {
+        panic!("expected an EC key");
+    };
+    assert_eq!(BASE64_URL_SAFE_NO_PAD.decode(&params.x).unwrap().len(), 32);
+    assert_eq!(BASE64_URL_SAFE_NO_PAD.decode(&params.y).unwrap().len(), 32);
+    assert_eq!(
+        jwks.keys[0].common.key_algorithm,
+        Some(jsonwebtoken::jwk::KeyAlgorithm::ES256)
+    );
+}
+
+#[tokio::test]
+async fn test_rs256_issue_and_verify() {
+    let (issuer, kid) = issuer_with(SigningAlgorithm::RS256).await;
+    let uid = Uuid::now_v7();
+    let jwt = issuer
+        .issue_with_id(&kid, &uid, &UserGrants::default())
+        .unwrap();
+
+    let header = jsonwebtoken::decode_header(&jwt).unwrap();
+    assert_eq!(header.alg, jsonwebtoken::Algorithm::RS256);
+    assert_eq!(issuer.verify(&jwt).unwrap().sub, uid);
+
+    let jwks = issuer.create_jwks();
+    let AlgorithmParameters::RSA(params) = &jwks.keys[0].algorithm else {
+        panic!("expected an RSA key");
+    };
+    assert_eq!(
+        BASE64_URL_SAFE_NO_PAD.decode(&params.n).unwrap().len() * 8,
+        RSA_KEY_BITS
+    );
+    assert_eq!(params.e, "AQAB");
+}
+
+#[tokio::test]
+async fn test_read_key_detects_algorithm() {
+    let dir = tempfile::tempdir().unwrap();
+    let helper = JwtIssuerHelper {};
+    for algorithm in [SigningAlgorithm::EdDSA, SigningAlgorithm::ES256] {
+        let kid = Uuid::now_v7();
+        let generated = helper
+            .gen_key_pair(dir.path().to_path_buf(), kid, algorithm)
+            .await
+            .unwrap();
+        let read = helper
+            .read_key(dir.path().to_path_buf(), kid)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(read.algorithm(), algorithm);
+        assert_eq!(read.parameters, generated.parameters);
+    }
+}
+
+#[tokio::test]
+async fn test_verify_rejects_algorithm_of_other_key() {
+    let (issuer, kid) = issuer_with(SigningAlgorithm::EdDSA).await;
+    let (other, other_kid) = issuer_with(SigningAlgorithm::ES256).await;
+    let jwt = other
+        .issue_with_id(&other_kid, &Uuid::now_v7(), &UserGrants::default())
+        .unwrap();
+
+    // an ES256 token claiming the kid of the EdDSA key
+    let mut header = jsonwebtoken::decode_header(&jwt).unwrap();
+    header.kid = Some(kid.to_string());
+    let claims = other.verify(&jwt).unwrap();
+    let key_pair = other.key_pair.get(&other_kid).unwrap();
+    let forged = jsonwebtoken::encode(&header, &claims, &key_pair.private_key).unwrap();
+
+    assert!(issuer.verify(&forged).is_err());
+}
+
+#[tokio::test]
+async fn test_configured_algorithm_mismatch() {
+    let dir = tempfile::tempdir().unwrap();
+    let kid = Uuid::now_v7();
+    let helper = JwtIssuerHelper {};
+    helper
+        .gen_key_pair(dir.path().to_path_buf(), kid, SigningAlgorithm::EdDSA)
+        .await
+        .unwrap();
+
+    let config = config(dir.path(), &[(kid, SigningAlgorithm::ES256)]);
+    let mut key_ring = SigningKeyRing::new(Vec::new());
+    let result = helper
+        .adopt_configured_keys(&config, dir.path(), &mut key_ring, chrono::Utc::now())
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_configured_es256_key_is_generated() {
+    let dir = tempfile::tempdir().unwrap();
+    let kid = Uuid::now_v7();
+    let config = config(dir.path(), &[(kid, SigningAlgorithm::ES256)]);
+
+    let (issuer, signing_kid) = JwtIssuerHelper::make_jwtissuer(&config).await;
+    assert_eq!(signing_kid, kid);
+    assert!(matches!(
+        issuer.key_pair.get(&kid).unwrap().parameters,
+        PublicKeyParameters::P256 { .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_read_key_missing_or_broken() {
+    let dir = tempfile::tempdir().unwrap();
+    let helper = JwtIssuerHelper {};
+    let kid = Uuid::now_v7();
+    assert!(helper
+        .read_key(dir.path().to_path_buf(), kid)
+        .await
+        .unwrap()
+        .is_none());
+
+    tokio::fs::write(dir.path().join(format!("{}.pem", kid)), b"not a key")
+        .await
+        .unwrap();
+    assert!(helper
+        .read_key(dir.path().to_path_buf(), kid)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_build_issuer_skips_broken_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = Uuid::now_v7();
+    let second = Uuid::now_v7();
+    let config = config(
+        dir.path(),
+        &[(first, SigningAlgorithm::EdDSA), (second, SigningAlgorithm::EdDSA)],
+    );
+    JwtIssuerHelper::make_jwtissuer(&config).await;
+    tokio::fs::write(dir.path().join(format!("{}.pem", second)), b"not a key")
+        .await
+        .unwrap();
+
+    let key_ring = read_key_ring(dir.path()).await.unwrap();
+    let (issuer, signing_kid) = JwtIssuerHelper {}
+        .build_issuer(&config, dir.path(), &key_ring, chrono::Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(signing_kid, first);
+    assert_eq!(issuer.key_pair.len(), 1);
+}
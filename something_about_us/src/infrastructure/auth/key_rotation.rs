@@ -0,0 +1,153 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+
+use chrono::{DateTime, Duration, Utc};
+use sonic_rs::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    application::service::jwt_service::JwtService,
+    domain::oauth::{
+        sau_jwt_issuer::SAUJwtIssuer,
+        signing_key::{KeyRotationPolicy, SigningKeyRing, SigningKeySchedule},
+    },
+    infrastructure::{auth::jwt_issuer_helper::JwtIssuerHelper, config::types::JwtConfig},
+};
+
+// lifecycle schedule stored next to `{kid}.pem` as `{kid}.json`
+#[derive(Serialize, Deserialize)]
+struct KeyScheduleFile {
+    kid: Uuid,
+    // unix seconds
+    activates_at: i64,
+}
+
+pub(crate) async fn read_key_ring(path: &Path) -> Result<SigningKeyRing, String> {
+    let mut dir = tokio::fs::read_dir(path)
+        .await
+        .map_err(|e| format!("fail to read {} : {}", path.display(), e))?;
+
+    let mut keys = Vec::new();
+    while let Some(entry) = dir.next_entry().await.map_err(|e| e.to_string())? {
+        let file_path = entry.path();
+        let is_schedule = file_path.extension().is_some_and(|ext| ext == "json")
+            && file_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| Uuid::parse_str(stem).is_ok());
+        if !is_schedule {
+            continue;
+        }
+
+        let data = tokio::fs::read(&file_path)
+            .await
+            .map_err(|e| format!("fail to read {} : {}", file_path.display(), e))?;
+        let file: KeyScheduleFile = sonic_rs::from_slice(&data)
+            .map_err(|e| format!("fail to parse {} : {}", file_path.display(), e))?;
+        let activates_at = DateTime::from_timestamp(file.activates_at, 0)
+            .ok_or(format!("invalid activates_at in {}", file_path.display()))?;
+        keys.push(SigningKeySchedule {
+            kid: file.kid,
+            activates_at,
+        });
+    }
+    Ok(SigningKeyRing::new(keys))
+}
+
+pub(crate) async fn write_key_schedule(
+    path: &Path,
+    schedule: &SigningKeySchedule,
+) -> Result<(), String> {
+    let file = KeyScheduleFile {
+        kid: schedule.kid,
+        activates_at: schedule.activates_at.timestamp(),
+    };
+    let body = sonic_rs::to_string(&file).map_err(|e| e.to_string())?;
+    let file_path = format!("{}/{}.json", path.display(), schedule.kid);
+    tokio::fs::write(&file_path, body)
+        .await
+        .map_err(|e| format!("fail to write {} : {}", file_path, e))
+}
+
+// one rotation step, returns when the next step is due.
+// the key ring is read from `keys_path` every time, so keys created by other instances
+// sharing the directory are picked up as well.
+pub async fn rotate_keys(
+    config: &JwtConfig,
+    jwt_service: &JwtService<SAUJwtIssuer>,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, String> {
+    let helper = JwtIssuerHelper {};
+    let path = PathBuf::from_str(&config.keys_path).map_err(|e| e.to_string())?;
+    let policy = KeyRotationPolicy {
+        rotation_interval: Duration::seconds(config.rotation.rotation_interval as i64),
+        publish_ahead: Duration::seconds(config.rotation.publish_ahead as i64),
+    };
+
+    // successors use the algorithm of the first configured key
+    let algorithm = config
+        .keys
+        .first()
+        .map(|key| key.algorithm)
+        .unwrap_or_default();
+
+    let mut key_ring = read_key_ring(&path).await?;
+    if let Some(activates_at) = key_ring.successor_activation(now, &policy) {
+        let schedule = SigningKeySchedule {
+            kid: Uuid::now_v7(),
+            activates_at,
+        };
+        helper
+            .gen_key_pair(path.clone(), schedule.kid, algorithm)
+            .await?;
+        write_key_schedule(&path, &schedule).await?;
+        info!(
+            "jwks key {} is published, signs from {}",
+            schedule.kid, schedule.activates_at
+        );
+        key_ring.insert(schedule);
+    }
+
+    let (issuer, kid) = helper.build_issuer(config, &path, &key_ring, now).await?;
+    if kid != jwt_service.current_kid() {
+        info!("jwks signing key switched to {}", kid);
+    }
+    jwt_service.replace_keys(Arc::new(issuer), kid);
+
+    let next_check = now + Duration::seconds(config.rotation.check_interval as i64);
+    Ok(key_ring
+        .next_activation(now)
+        .map_or(next_check, |activates_at| activates_at.min(next_check)))
+}
+
+// runs rotation steps in the background, waking up at every key activation
+// so the signing key switches on time.
+pub fn spawn_key_rotation(
+    config: JwtConfig,
+    jwt_service: JwtService<SAUJwtIssuer>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let now = Utc::now();
+            let next = match rotate_keys(&config, &jwt_service, now).await {
+                Ok(next) => next,
+                Err(e) => {
+                    warn!("jwks key rotation failed : {}", e);
+                    now + Duration::seconds(config.rotation.check_interval as i64)
+                }
+            };
+            let wait = (next - Utc::now()).to_std().unwrap_or_default();
+            tokio::time::sleep(wait).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    include!("key_rotation_test.rs");
+}
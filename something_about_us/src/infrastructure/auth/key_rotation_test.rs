@@ -0,0 +1,145 @@
+use super::{read_key_ring, rotate_keys};
+use crate::{
+    application::service::jwt_service::JwtService,
+    domain::{oauth::sau_jwt_issuer::SigningAlgorithm, user::role::UserGrants},
+    infrastructure::{
+        auth::jwt_issuer_helper::JwtIssuerHelper,
+        config::types::{JwtConfig, KeyConfig, KeyRotationConfig},
+    },
+};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const HOUR: u64 = 60 * 60;
+
+fn config(keys_path: &std::path::Path, kids: &[Uuid]) -> JwtConfig {
+    JwtConfig {
+        iss: "test-issuer".to_string(),
+        aud: "test-audience".to_string(),
+        keys_path: keys_path.display().to_string(),
+        keys: kids
+            .iter()
+            .map(|kid| KeyConfig {
+                kid: *kid,
+                algorithm: SigningAlgorithm::EdDSA,
+            })
+            .collect(),
+        access_token_ttl: HOUR,
+        refresh_token_ttl: 24 * HOUR,
+        client_credentials_ttl: HOUR,
+        leeway: 0,
+        rotation: KeyRotationConfig {
+            enabled: true,
+            rotation_interval: 24 * HOUR,
+            publish_ahead: 2 * HOUR,
+            check_interval: HOUR,
+        },
+        orgs_claim: false,
+    }
+}
+
+fn published_kids(
+    jwt_service: &JwtService<crate::domain::oauth::sau_jwt_issuer::SAUJwtIssuer>,
+) -> Vec<String> {
+    let mut kids: Vec<String> = jwt_service
+        .get_jwks()
+        .keys
+        .into_iter()
+        .filter_map(|jwk| jwk.common.key_id)
+        .collect();
+    kids.sort();
+    kids
+}
+
+#[tokio::test]
+async fn test_configured_keys_are_adopted() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = Uuid::now_v7();
+    let second = Uuid::now_v7();
+    let config = config(dir.path(), &[first, second]);
+
+    let (issuer, kid) = JwtIssuerHelper::make_jwtissuer(&config).await;
+    assert_eq!(kid, first);
+    // the second key is a replaced key, still published for one token lifetime
+    assert_eq!(issuer.key_pair.len(), 2);
+
+    // the schedules are persisted, a restart keeps the same signing key
+    let (_, kid) = JwtIssuerHelper::make_jwtissuer(&config).await;
+    assert_eq!(kid, first);
+    assert_eq!(read_key_ring(dir.path()).await.unwrap().keys().len(), 2);
+}
+
+#[tokio::test]
+async fn test_rotation_publishes_ahead_then_switches() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = Uuid::now_v7();
+    let config = config(dir.path(), &[first]);
+    let (issuer, kid) = JwtIssuerHelper::make_jwtissuer(&config).await;
+    let jwt_service = JwtService::new(Arc::new(issuer), kid);
+    let start = Utc::now();
+
+    // nothing to do until the successor has to be published
+    let next = rotate_keys(&config, &jwt_service, start).await.unwrap();
+    assert_eq!(next, start + Duration::hours(1));
+    assert_eq!(published_kids(&jwt_service).len(), 1);
+
+    // published ahead, the current key keeps signing
+    let next = rotate_keys(&config, &jwt_service, start + Duration::hours(23))
+        .await
+        .unwrap();
+    assert_eq!(jwt_service.current_kid(), first);
+    assert_eq!(published_kids(&jwt_service).len(), 2);
+    let successor_activation = read_key_ring(dir.path()).await.unwrap().keys()[1].activates_at;
+    assert!(successor_activation >= start + Duration::hours(23));
+    assert_eq!(next, start + Duration::hours(24));
+
+    // the successor signs from its activation, the old key is retiring
+    rotate_keys(&config, &jwt_service, successor_activation)
+        .await
+        .unwrap();
+    let successor = jwt_service.current_kid();
+    assert_ne!(successor, first);
+    assert_eq!(published_kids(&jwt_service).len(), 2);
+
+    // a token signed by the retiring key still verifies
+    let old_token = {
+        let (issuer, _) = JwtIssuerHelper::make_jwtissuer(&config).await;
+        JwtService::new(Arc::new(issuer), first)
+            .issue_with_id(&Uuid::now_v7(), &UserGrants::default())
+            .unwrap()
+    };
+    assert!(jwt_service.verify(&old_token).is_ok());
+
+    // retired once every token of the old key has expired
+    rotate_keys(
+        &config,
+        &jwt_service,
+        successor_activation + Duration::hours(1) + Duration::seconds(1),
+    )
+    .await
+    .unwrap();
+    assert_eq!(published_kids(&jwt_service), vec![successor.to_string()]);
+    assert!(dir.path().join(format!("{}.pem", first)).exists());
+}
+
+#[tokio::test]
+async fn test_successor_uses_configured_algorithm() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = config(dir.path(), &[Uuid::now_v7()]);
+    config.keys[0].algorithm = SigningAlgorithm::ES256;
+    let (issuer, kid) = JwtIssuerHelper::make_jwtissuer(&config).await;
+    let jwt_service = JwtService::new(Arc::new(issuer), kid);
+    let start = Utc::now();
+
+    rotate_keys(&config, &jwt_service, start + Duration::hours(23))
+        .await
+        .unwrap();
+    let successor = read_key_ring(dir.path()).await.unwrap().keys()[1].kid;
+    let key_pair = JwtIssuerHelper {}
+        .read_key(dir.path().to_path_buf(), successor)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(key_pair.algorithm(), SigningAlgorithm::ES256);
+}
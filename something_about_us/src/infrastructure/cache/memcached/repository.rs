@@ -4,6 +4,12 @@ use deadpool_memcached::Manager;
 use crate::application::port::auth_session_repository::AuthSessionCacheRepoError;
 
 pub mod auth_session_repo;
+pub mod authorization_code_repo;
+pub mod client_assertion_repo;
+pub mod device_authorization_repo;
+pub mod link_ticket_repo;
+pub mod login_session_repo;
+pub mod revoked_token_repo;
 
 #[derive(Clone)]
 pub struct CacheRepoMchd {
@@ -11,6 +11,7 @@ impl AuthSessionCacheRepo for CacheRepoMchd {
     async fn set_auth_session(
         &self,
         auth_session: &AuthSession,
+        ttl: u64,
     ) -> Result<(), AuthSessionCacheRepoError> {
         let mut client = self.get().await?;
         let body = sonic_rs::json!(auth_session);
@@ -18,7 +19,7 @@ impl AuthSessionCacheRepo for CacheRepoMchd {
             .set(
                 auth_session.id.to_string(),
                 body.to_string(),
-                Some(500),
+                Some(ttl as i64),
                 None,
             )
             .await
@@ -45,4 +46,19 @@ impl AuthSessionCacheRepo for CacheRepoMchd {
             )),
         }
     }
+
+    async fn take_auth_session(
+        &self,
+        session_id: Uuid,
+    ) -> Result<AuthSession, AuthSessionCacheRepoError> {
+        let auth_session = self.get_auth_session(session_id).await?;
+        let mut client = self.get().await?;
+        // only the caller whose delete succeeds owns the session, a replayed callback gets nothing
+        if client.delete(session_id.to_string()).await.is_err() {
+            return Err(AuthSessionCacheRepoError::SessionNotFound(
+                session_id.to_string(),
+            ));
+        }
+        Ok(auth_session)
+    }
 }
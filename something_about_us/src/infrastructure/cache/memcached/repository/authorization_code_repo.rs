@@ -0,0 +1,73 @@
+use deadpool::managed::Object;
+use deadpool_memcached::Manager;
+
+use crate::{
+    application::port::authorization_code_repository::{
+        AuthorizationCodeCacheRepo, AuthorizationCodeCacheRepoError,
+    },
+    domain::oauth::authorization_code::AuthorizationCode,
+    infrastructure::cache::memcached::repository::CacheRepoMchd,
+};
+
+fn authorization_code_key(code_hash: &str) -> String {
+    format!("authorization_code:{}", code_hash)
+}
+
+impl CacheRepoMchd {
+    async fn authorization_code_client(
+        &self,
+    ) -> Result<Object<Manager>, AuthorizationCodeCacheRepoError> {
+        self.conn
+            .get()
+            .await
+            .map_err(|e| AuthorizationCodeCacheRepoError::CacheConnectionError(e.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthorizationCodeCacheRepo for CacheRepoMchd {
+    async fn set_authorization_code(
+        &self,
+        code_hash: &str,
+        code: &AuthorizationCode,
+        ttl: u64,
+    ) -> Result<(), AuthorizationCodeCacheRepoError> {
+        let mut client = self.authorization_code_client().await?;
+        let body = sonic_rs::to_string(code).map_err(|e| {
+            AuthorizationCodeCacheRepoError::SetAuthorizationCodeError(e.to_string())
+        })?;
+        client
+            .set(
+                authorization_code_key(code_hash),
+                body,
+                Some(ttl as i64),
+                None,
+            )
+            .await
+            .map_err(|e| AuthorizationCodeCacheRepoError::SetAuthorizationCodeError(e.to_string()))
+    }
+
+    async fn take_authorization_code(
+        &self,
+        code_hash: &str,
+    ) -> Result<Option<AuthorizationCode>, AuthorizationCodeCacheRepoError> {
+        let mut client = self.authorization_code_client().await?;
+        let key = authorization_code_key(code_hash);
+        let Some(value) = client
+            .get(&key)
+            .await
+            .map_err(|e| AuthorizationCodeCacheRepoError::CacheConnectionError(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+
+        // only the caller whose delete succeeds owns the code, a concurrent exchange gets nothing
+        if client.delete(&key).await.is_err() {
+            return Ok(None);
+        }
+
+        sonic_rs::from_slice(&value.data)
+            .map(Some)
+            .map_err(|e| AuthorizationCodeCacheRepoError::InvalidEntry(e.to_string()))
+    }
+}
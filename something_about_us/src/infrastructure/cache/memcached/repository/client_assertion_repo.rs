@@ -0,0 +1,43 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use ring::digest::{digest, SHA256};
+
+use crate::{
+    application::port::client_assertion_repository::{
+        ClientAssertionCacheRepo, ClientAssertionCacheRepoError,
+    },
+    infrastructure::cache::memcached::repository::CacheRepoMchd,
+};
+
+// `jti` is chosen by the client, hashed to fit the memcached key rules
+fn client_assertion_key(client_id: &str, jti: &str) -> String {
+    let jti_hash = BASE64_URL_SAFE_NO_PAD.encode(digest(&SHA256, jti.as_bytes()));
+    format!("client_assertion:{}:{}", client_id, jti_hash)
+}
+
+#[async_trait::async_trait]
+impl ClientAssertionCacheRepo for CacheRepoMchd {
+    async fn claim_assertion_jti(
+        &self,
+        client_id: &str,
+        jti: &str,
+        ttl: u64,
+    ) -> Result<bool, ClientAssertionCacheRepoError> {
+        let mut client = self
+            .conn
+            .get()
+            .await
+            .map_err(|e| ClientAssertionCacheRepoError::CacheConnectionError(e.to_string()))?;
+
+        // `add` only stores a missing key, a replayed assertion fails here
+        let claimed = client
+            .add(
+                client_assertion_key(client_id, jti),
+                "1",
+                Some(ttl as i64),
+                None,
+            )
+            .await
+            .is_ok();
+        Ok(claimed)
+    }
+}
@@ -0,0 +1,221 @@
+use chrono::Utc;
+use deadpool::managed::Object;
+use deadpool_memcached::Manager;
+
+use crate::{
+    application::port::device_authorization_repository::{
+        DeviceAuthorizationCacheRepo, DeviceAuthorizationCacheRepoError,
+    },
+    domain::oauth::device_authorization::DeviceAuthorization,
+    infrastructure::cache::memcached::repository::CacheRepoMchd,
+};
+
+fn device_authorization_key(device_code_hash: &str) -> String {
+    format!("device_authorization:{}", device_code_hash)
+}
+
+fn user_code_key(user_code: &str) -> String {
+    format!("device_user_code:{}", user_code)
+}
+
+fn user_code_failure_key(slot: u64) -> String {
+    format!("device_user_code_failure:{}", slot)
+}
+
+fn device_poll_key(device_code_hash: &str) -> String {
+    format!("device_poll:{}", device_code_hash)
+}
+
+impl CacheRepoMchd {
+    async fn device_authorization_client(
+        &self,
+    ) -> Result<Object<Manager>, DeviceAuthorizationCacheRepoError> {
+        self.conn
+            .get()
+            .await
+            .map_err(|e| DeviceAuthorizationCacheRepoError::CacheConnectionError(e.to_string()))
+    }
+
+    // only the caller whose delete succeeds owns the value, a concurrent caller gets nothing
+    async fn take_device_value(
+        &self,
+        key: &str,
+    ) -> Result<Option<Vec<u8>>, DeviceAuthorizationCacheRepoError> {
+        let mut client = self.device_authorization_client().await?;
+        let Some(value) = client
+            .get(key)
+            .await
+            .map_err(|e| DeviceAuthorizationCacheRepoError::CacheConnectionError(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        if client.delete(key).await.is_err() {
+            return Ok(None);
+        }
+        Ok(Some(value.data))
+    }
+}
+
+#[async_trait::async_trait]
+impl DeviceAuthorizationCacheRepo for CacheRepoMchd {
+    async fn set_device_authorization(
+        &self,
+        device_code_hash: &str,
+        authorization: &DeviceAuthorization,
+        ttl: u64,
+    ) -> Result<(), DeviceAuthorizationCacheRepoError> {
+        let mut client = self.device_authorization_client().await?;
+        let body = sonic_rs::to_string(authorization).map_err(|e| {
+            DeviceAuthorizationCacheRepoError::SetDeviceAuthorizationError(e.to_string())
+        })?;
+        client
+            .set(
+                device_authorization_key(device_code_hash),
+                body,
+                Some(ttl as i64),
+                None,
+            )
+            .await
+            .map_err(|e| {
+                DeviceAuthorizationCacheRepoError::SetDeviceAuthorizationError(e.to_string())
+            })
+    }
+
+    async fn get_device_authorization(
+        &self,
+        device_code_hash: &str,
+    ) -> Result<Option<DeviceAuthorization>, DeviceAuthorizationCacheRepoError> {
+        let mut client = self.device_authorization_client().await?;
+        let Some(value) = client
+            .get(device_authorization_key(device_code_hash))
+            .await
+            .map_err(|e| DeviceAuthorizationCacheRepoError::CacheConnectionError(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        sonic_rs::from_slice(&value.data)
+            .map(Some)
+            .map_err(|e| DeviceAuthorizationCacheRepoError::InvalidEntry(e.to_string()))
+    }
+
+    async fn take_device_authorization(
+        &self,
+        device_code_hash: &str,
+    ) -> Result<Option<DeviceAuthorization>, DeviceAuthorizationCacheRepoError> {
+        let Some(data) = self
+            .take_device_value(&device_authorization_key(device_code_hash))
+            .await?
+        else {
+            return Ok(None);
+        };
+        sonic_rs::from_slice(&data)
+            .map(Some)
+            .map_err(|e| DeviceAuthorizationCacheRepoError::InvalidEntry(e.to_string()))
+    }
+
+    async fn set_user_code(
+        &self,
+        user_code: &str,
+        device_code_hash: &str,
+        ttl: u64,
+    ) -> Result<(), DeviceAuthorizationCacheRepoError> {
+        let mut client = self.device_authorization_client().await?;
+        client
+            .set(
+                user_code_key(user_code),
+                device_code_hash,
+                Some(ttl as i64),
+                None,
+            )
+            .await
+            .map_err(|e| {
+                DeviceAuthorizationCacheRepoError::SetDeviceAuthorizationError(e.to_string())
+            })
+    }
+
+    async fn get_user_code(
+        &self,
+        user_code: &str,
+    ) -> Result<Option<String>, DeviceAuthorizationCacheRepoError> {
+        let mut client = self.device_authorization_client().await?;
+        let Some(value) = client
+            .get(user_code_key(user_code))
+            .await
+            .map_err(|e| DeviceAuthorizationCacheRepoError::CacheConnectionError(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        String::from_utf8(value.data)
+            .map(Some)
+            .map_err(|e| DeviceAuthorizationCacheRepoError::InvalidEntry(e.to_string()))
+    }
+
+    async fn take_user_code(
+        &self,
+        user_code: &str,
+    ) -> Result<Option<String>, DeviceAuthorizationCacheRepoError> {
+        let Some(data) = self.take_device_value(&user_code_key(user_code)).await? else {
+            return Ok(None);
+        };
+        String::from_utf8(data)
+            .map(Some)
+            .map_err(|e| DeviceAuthorizationCacheRepoError::InvalidEntry(e.to_string()))
+    }
+
+    async fn user_code_failures(
+        &self,
+        limit: u64,
+    ) -> Result<u64, DeviceAuthorizationCacheRepoError> {
+        let mut client = self.device_authorization_client().await?;
+        let keys: Vec<String> = (0..limit).map(user_code_failure_key).collect();
+        let found = client
+            .get_many(&keys)
+            .await
+            .map_err(|e| DeviceAuthorizationCacheRepoError::CacheConnectionError(e.to_string()))?;
+        Ok(found.len() as u64)
+    }
+
+    async fn count_user_code_failure(
+        &self,
+        limit: u64,
+        window: u64,
+    ) -> Result<(), DeviceAuthorizationCacheRepoError> {
+        let mut client = self.device_authorization_client().await?;
+
+        // every failure takes a free slot for `window` seconds, `add` only stores a missing key
+        // so concurrent failures never share one. probing starts anywhere to keep it short.
+        let start = Utc::now().timestamp_subsec_nanos() as u64 % limit.max(1);
+        for offset in 0..limit {
+            let slot = (start + offset) % limit;
+            let claimed = client
+                .add(user_code_failure_key(slot), "1", Some(window as i64), None)
+                .await
+                .is_ok();
+            if claimed {
+                break;
+            }
+        }
+        // every slot taken means the limit is reached anyway
+        Ok(())
+    }
+
+    async fn claim_device_poll(
+        &self,
+        device_code_hash: &str,
+        interval: u64,
+    ) -> Result<bool, DeviceAuthorizationCacheRepoError> {
+        let mut client = self.device_authorization_client().await?;
+
+        // `add` only stores a missing key, a poll within the interval fails here
+        let claimed = client
+            .add(
+                device_poll_key(device_code_hash),
+                "1",
+                Some(interval as i64),
+                None,
+            )
+            .await
+            .is_ok();
+        Ok(claimed)
+    }
+}
@@ -0,0 +1,65 @@
+use deadpool::managed::Object;
+use deadpool_memcached::Manager;
+
+use crate::{
+    application::port::link_ticket_repository::{LinkTicketCacheRepo, LinkTicketCacheRepoError},
+    domain::oauth::link_ticket::LinkTicket,
+    infrastructure::cache::memcached::repository::CacheRepoMchd,
+};
+
+fn link_ticket_key(ticket: &str) -> String {
+    format!("link_ticket:{}", ticket)
+}
+
+impl CacheRepoMchd {
+    async fn link_ticket_client(&self) -> Result<Object<Manager>, LinkTicketCacheRepoError> {
+        self.conn
+            .get()
+            .await
+            .map_err(|e| LinkTicketCacheRepoError::CacheConnectionError(e.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl LinkTicketCacheRepo for CacheRepoMchd {
+    async fn set_link_ticket(
+        &self,
+        ticket: &LinkTicket,
+        ttl: u64,
+    ) -> Result<(), LinkTicketCacheRepoError> {
+        let mut client = self.link_ticket_client().await?;
+        let body = sonic_rs::to_string(ticket)
+            .map_err(|e| LinkTicketCacheRepoError::SetLinkTicketError(e.to_string()))?;
+        client
+            .set(
+                link_ticket_key(&ticket.ticket),
+                body,
+                Some(ttl as i64),
+                None,
+            )
+            .await
+            .map_err(|e| LinkTicketCacheRepoError::SetLinkTicketError(e.to_string()))
+    }
+
+    async fn take_link_ticket(
+        &self,
+        ticket: &str,
+    ) -> Result<Option<LinkTicket>, LinkTicketCacheRepoError> {
+        let mut client = self.link_ticket_client().await?;
+        let key = link_ticket_key(ticket);
+        let Some(value) = client
+            .get(&key)
+            .await
+            .map_err(|e| LinkTicketCacheRepoError::CacheConnectionError(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        // only the caller whose delete succeeds owns the ticket, a replayed link gets nothing
+        if client.delete(&key).await.is_err() {
+            return Ok(None);
+        }
+        sonic_rs::from_slice(&value.data)
+            .map(Some)
+            .map_err(|e| LinkTicketCacheRepoError::InvalidEntry(e.to_string()))
+    }
+}
@@ -0,0 +1,68 @@
+use deadpool::managed::Object;
+use deadpool_memcached::Manager;
+use uuid::Uuid;
+
+use crate::{
+    application::port::login_session_repository::{
+        LoginSessionCacheRepo, LoginSessionCacheRepoError,
+    },
+    domain::oauth::login_session::LoginSession,
+    infrastructure::cache::memcached::repository::CacheRepoMchd,
+};
+
+fn login_session_key(session_id: Uuid) -> String {
+    format!("login_session:{}", session_id)
+}
+
+impl CacheRepoMchd {
+    async fn login_session_client(&self) -> Result<Object<Manager>, LoginSessionCacheRepoError> {
+        self.conn
+            .get()
+            .await
+            .map_err(|e| LoginSessionCacheRepoError::CacheConnectionError(e.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl LoginSessionCacheRepo for CacheRepoMchd {
+    async fn set_login_session(
+        &self,
+        session: &LoginSession,
+        ttl: u64,
+    ) -> Result<(), LoginSessionCacheRepoError> {
+        let mut client = self.login_session_client().await?;
+        let body = sonic_rs::to_string(session)
+            .map_err(|e| LoginSessionCacheRepoError::SetLoginSessionError(e.to_string()))?;
+        client
+            .set(login_session_key(session.id), body, Some(ttl as i64), None)
+            .await
+            .map_err(|e| LoginSessionCacheRepoError::SetLoginSessionError(e.to_string()))
+    }
+
+    async fn get_login_session(
+        &self,
+        session_id: Uuid,
+    ) -> Result<Option<LoginSession>, LoginSessionCacheRepoError> {
+        let mut client = self.login_session_client().await?;
+        let Some(value) = client
+            .get(login_session_key(session_id))
+            .await
+            .map_err(|e| LoginSessionCacheRepoError::CacheConnectionError(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        sonic_rs::from_slice(&value.data)
+            .map(Some)
+            .map_err(|e| LoginSessionCacheRepoError::InvalidEntry(e.to_string()))
+    }
+
+    async fn delete_login_session(
+        &self,
+        session_id: Uuid,
+    ) -> Result<(), LoginSessionCacheRepoError> {
+        let mut client = self.login_session_client().await?;
+        // a missing key answers NOT_FOUND, the session is gone either way
+        let _ = client.delete(login_session_key(session_id)).await;
+        Ok(())
+    }
+}
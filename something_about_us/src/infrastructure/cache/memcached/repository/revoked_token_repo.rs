@@ -0,0 +1,97 @@
+use deadpool::managed::Object;
+use deadpool_memcached::Manager;
+use uuid::Uuid;
+
+use crate::{
+    application::port::revoked_token_repository::{
+        RevokedTokenCacheRepo, RevokedTokenCacheRepoError,
+    },
+    infrastructure::cache::memcached::repository::CacheRepoMchd,
+};
+
+// memcached reads an expiration above 30 days as an absolute unix time
+const MAX_RELATIVE_EXPIRATION: u64 = 60 * 60 * 24 * 30;
+
+fn expiration(ttl: u64) -> i64 {
+    if ttl > MAX_RELATIVE_EXPIRATION {
+        chrono::Utc::now().timestamp() + ttl as i64
+    } else {
+        ttl as i64
+    }
+}
+
+fn revoked_jti_key(jti: Uuid) -> String {
+    format!("revoked_jti:{}", jti)
+}
+
+fn revoked_user_key(user_id: Uuid) -> String {
+    format!("revoked_user:{}", user_id)
+}
+
+impl CacheRepoMchd {
+    async fn revoked_token_client(&self) -> Result<Object<Manager>, RevokedTokenCacheRepoError> {
+        self.conn
+            .get()
+            .await
+            .map_err(|e| RevokedTokenCacheRepoError::CacheConnectionError(e.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl RevokedTokenCacheRepo for CacheRepoMchd {
+    async fn set_revoked_jti(&self, jti: Uuid, ttl: u64) -> Result<(), RevokedTokenCacheRepoError> {
+        let mut client = self.revoked_token_client().await?;
+        client
+            .set(revoked_jti_key(jti), "1", Some(expiration(ttl)), None)
+            .await
+            .map_err(|e| RevokedTokenCacheRepoError::SetRevokedTokenError(e.to_string()))
+    }
+
+    async fn is_revoked_jti(&self, jti: Uuid) -> Result<bool, RevokedTokenCacheRepoError> {
+        let mut client = self.revoked_token_client().await?;
+        let result = client
+            .get(revoked_jti_key(jti))
+            .await
+            .map_err(|e| RevokedTokenCacheRepoError::CacheConnectionError(e.to_string()))?;
+        Ok(result.is_some())
+    }
+
+    async fn set_user_revoked_before(
+        &self,
+        user_id: Uuid,
+        revoked_before: i64,
+        ttl: u64,
+    ) -> Result<(), RevokedTokenCacheRepoError> {
+        let mut client = self.revoked_token_client().await?;
+        client
+            .set(
+                revoked_user_key(user_id),
+                revoked_before.to_string(),
+                Some(expiration(ttl)),
+                None,
+            )
+            .await
+            .map_err(|e| RevokedTokenCacheRepoError::SetRevokedTokenError(e.to_string()))
+    }
+
+    async fn get_user_revoked_before(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<i64>, RevokedTokenCacheRepoError> {
+        let mut client = self.revoked_token_client().await?;
+        let result = client
+            .get(revoked_user_key(user_id))
+            .await
+            .map_err(|e| RevokedTokenCacheRepoError::CacheConnectionError(e.to_string()))?;
+        result
+            .map(|value| {
+                std::str::from_utf8(&value.data)
+                    .ok()
+                    .and_then(|data| data.parse::<i64>().ok())
+                    .ok_or_else(|| {
+                        RevokedTokenCacheRepoError::InvalidEntry(revoked_user_key(user_id))
+                    })
+            })
+            .transpose()
+    }
+}
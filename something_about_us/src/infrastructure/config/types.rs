@@ -2,6 +2,8 @@ use sonic_rs::Deserialize;
 use url::Url;
 use uuid::Uuid;
 
+use crate::domain::oauth::sau_jwt_issuer::SigningAlgorithm;
+
 #[derive(Deserialize, Debug)]
 pub struct Config {
     pub server: Server,
@@ -9,8 +11,10 @@ pub struct Config {
     pub postgres: PostgresConfig,
     pub memcached: MemCachedConfig,
     pub jwt: JwtConfig,
-    pub oidc: OIDCProviderConfig,
+    pub providers: Vec<ProviderConfig>,
     pub security: SecurityConfig,
+    #[serde(default)]
+    pub users: UsersConfig,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -70,23 +74,122 @@ pub struct MemCachedRuntimeConfig {
     pub pool_size: u32,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct JwtConfig {
     pub iss: String,
     pub aud: String,
     pub keys_path: String,
     pub keys: Vec<KeyConfig>,
     pub access_token_ttl: u64,
+    #[serde(default = "JwtConfig::default_refresh_token_ttl")]
+    pub refresh_token_ttl: u64,
+    // lifetime of the service tokens of the `client_credentials` grant, at most `access_token_ttl`
+    #[serde(default = "JwtConfig::default_client_credentials_ttl")]
+    pub client_credentials_ttl: u64,
+    // allowed clock skew in seconds when checking `exp` / `nbf` of presented tokens
+    #[serde(default = "JwtConfig::default_leeway")]
+    pub leeway: u64,
+    #[serde(default)]
+    pub rotation: KeyRotationConfig,
+    // adds the organizations of the user with its role in each as `orgs` to access tokens
+    #[serde(default)]
+    pub orgs_claim: bool,
 }
 
-#[derive(Deserialize, Debug)]
+impl JwtConfig {
+    fn default_leeway() -> u64 {
+        crate::domain::oauth::sau_jwt_issuer::DEFAULT_JWT_LEEWAY
+    }
+
+    // 30 days in seconds
+    fn default_refresh_token_ttl() -> u64 {
+        60 * 60 * 24 * 30
+    }
+
+    // 15 minutes in seconds
+    fn default_client_credentials_ttl() -> u64 {
+        60 * 15
+    }
+}
+
+// scheduled signing key rotation, all durations are in seconds
+#[derive(Deserialize, Debug, Clone)]
+pub struct KeyRotationConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "KeyRotationConfig::default_rotation_interval")]
+    pub rotation_interval: u64,
+    #[serde(default = "KeyRotationConfig::default_publish_ahead")]
+    pub publish_ahead: u64,
+    #[serde(default = "KeyRotationConfig::default_check_interval")]
+    pub check_interval: u64,
+}
+
+impl KeyRotationConfig {
+    // 90 days
+    fn default_rotation_interval() -> u64 {
+        60 * 60 * 24 * 90
+    }
+
+    // 1 day
+    fn default_publish_ahead() -> u64 {
+        60 * 60 * 24
+    }
+
+    // 1 hour
+    fn default_check_interval() -> u64 {
+        60 * 60
+    }
+}
+
+impl Default for KeyRotationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rotation_interval: Self::default_rotation_interval(),
+            publish_ahead: Self::default_publish_ahead(),
+            check_interval: Self::default_check_interval(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
 pub struct KeyConfig {
     pub kid: Uuid,
+    // `EdDSA`, `ES256` or `RS256`, an existing key file has to be of the same algorithm
+    #[serde(default)]
+    pub algorithm: SigningAlgorithm,
 }
 
+// one `[[providers]]` entry, the `kind` selects the client and the remaining keys
 #[derive(Deserialize, Debug)]
-pub struct OIDCProviderConfig {
-    pub github: GithubConfig,
+pub struct ProviderConfig {
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: ProviderKindConfig,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ProviderKindConfig {
+    Github(GithubConfig),
+    Gitlab(GitlabConfig),
+    Google(GoogleConfig),
+    Microsoft(MicrosoftConfig),
+    // any OpenID Connect provider resolved by discovery
+    Oidc(GenericOidcConfig),
+}
+
+impl ProviderKindConfig {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProviderKindConfig::Github(_) => "github",
+            ProviderKindConfig::Gitlab(_) => "gitlab",
+            ProviderKindConfig::Google(_) => "google",
+            ProviderKindConfig::Microsoft(_) => "microsoft",
+            ProviderKindConfig::Oidc(_) => "oidc",
+        }
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -97,11 +200,116 @@ pub struct GithubConfig {
     pub auth_url: String,
     pub token_url: String,
     pub redirect_url: Url,
+    #[serde(default = "GithubConfig::default_scopes")]
+    pub scopes: Vec<String>,
+}
+
+impl GithubConfig {
+    fn default_scopes() -> Vec<String> {
+        vec!["read:user".to_string()]
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GoogleConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_url: Url,
+    #[serde(default = "GoogleConfig::default_issuer_url")]
+    pub issuer_url: String,
+    #[serde(default = "default_profile_scopes")]
+    pub scopes: Vec<String>,
+}
+
+impl GoogleConfig {
+    fn default_issuer_url() -> String {
+        crate::infrastructure::provider::google::GOOGLE_ISSUER_URL.to_string()
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitlabConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_url: Url,
+    // gitlab.com or the root url of a self-hosted instance
+    #[serde(default = "GitlabConfig::default_base_url")]
+    pub base_url: Url,
+    #[serde(default = "GitlabConfig::default_scopes")]
+    pub scopes: Vec<String>,
+}
+
+impl GitlabConfig {
+    fn default_scopes() -> Vec<String> {
+        vec!["read_user".to_string()]
+    }
+
+    fn default_base_url() -> Url {
+        Url::parse(crate::infrastructure::provider::gitlab::GITLAB_BASE_URL)
+            .expect("invalid default gitlab base url")
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MicrosoftConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_url: Url,
+    // tenant id, or `common` / `organizations` / `consumers` for multi-tenant sign-in
+    #[serde(default = "MicrosoftConfig::default_tenant")]
+    pub tenant: String,
+    // `tid` values accepted at sign-in, defaults to `tenant` itself
+    #[serde(default)]
+    pub allowed_tenants: Vec<String>,
+    #[serde(default = "MicrosoftConfig::default_authority_url")]
+    pub authority_url: String,
+    #[serde(default = "default_profile_scopes")]
+    pub scopes: Vec<String>,
+}
+
+impl MicrosoftConfig {
+    fn default_tenant() -> String {
+        crate::infrastructure::provider::microsoft::MICROSOFT_DEFAULT_TENANT.to_string()
+    }
+
+    fn default_authority_url() -> String {
+        crate::infrastructure::provider::microsoft::MICROSOFT_AUTHORITY_URL.to_string()
+    }
+}
+
+fn default_profile_scopes() -> Vec<String> {
+    vec!["email".to_string(), "profile".to_string()]
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GenericOidcConfig {
+    pub issuer_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_url: Url,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+// a new user is always seeded with the idp profile (username, email, display name, avatar)
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct UsersConfig {
+    // overwrites the profile with the idp one on every later login
+    #[serde(default)]
+    pub refresh_profile_on_login: bool,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct SecurityConfig {
     pub session: SessionSecurityConfig,
+    #[serde(default)]
+    pub introspection: IntrospectionSecurityConfig,
+    #[serde(default)]
+    pub admin: AdminSecurityConfig,
+    #[serde(default)]
+    pub login_redirect: LoginRedirectSecurityConfig,
+    #[serde(default)]
+    pub login_session: LoginSessionSecurityConfig,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -111,3 +319,78 @@ pub struct SessionSecurityConfig {
     pub same_site: String,
     pub http_only: bool,
 }
+
+// credentials of the resource servers allowed to call `/api/v1/token/introspect`
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct IntrospectionSecurityConfig {
+    #[serde(default)]
+    pub clients: Vec<IntrospectionClientConfig>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct IntrospectionClientConfig {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+// SAU users allowed to call the `/api/v1/admin` endpoints
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct AdminSecurityConfig {
+    #[serde(default)]
+    pub user_ids: Vec<Uuid>,
+}
+
+// frontends a browser login started at `/api/v1/oauth/{idp}/login` may return to with `return_to`
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct LoginRedirectSecurityConfig {
+    // origins like `https://app.example.com`, a `return_to` on any other origin is refused
+    #[serde(default)]
+    pub allowed_origins: Vec<Url>,
+    #[serde(default)]
+    pub token_delivery: TokenDelivery,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenDelivery {
+    // HttpOnly cookies, scripts of the frontend never see the tokens
+    #[default]
+    Cookie,
+    // `#access_token=...` appended to `return_to` for a SPA to read
+    Fragment,
+}
+
+// backend-for-frontend mode, a browser login returning to an allowed frontend gets a
+// server-side session cookie instead of tokens. durations are in seconds.
+#[derive(Deserialize, Debug, Clone)]
+pub struct LoginSessionSecurityConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "LoginSessionSecurityConfig::default_ttl")]
+    pub ttl: u64,
+    // lifetime of the access tokens handed out at `/api/v1/session/token`
+    #[serde(default = "LoginSessionSecurityConfig::default_token_ttl")]
+    pub token_ttl: u64,
+}
+
+impl LoginSessionSecurityConfig {
+    // 8 hours
+    fn default_ttl() -> u64 {
+        60 * 60 * 8
+    }
+
+    // 5 minutes
+    fn default_token_ttl() -> u64 {
+        60 * 5
+    }
+}
+
+impl Default for LoginSessionSecurityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl: Self::default_ttl(),
+            token_ttl: Self::default_token_ttl(),
+        }
+    }
+}
@@ -1,7 +1,140 @@
-use anyhow::{Ok, Result};
+use anyhow::{anyhow, Ok, Result};
+use std::collections::HashSet;
 
-use crate::infrastructure::config::types::Config;
+use crate::{
+    domain::idp::supported_idp::SupportIdp,
+    infrastructure::{
+        config::types::{
+            Config, JwtConfig, LoginRedirectSecurityConfig, LoginSessionSecurityConfig,
+            ProviderConfig, ProviderKindConfig,
+        },
+        provider::microsoft::MICROSOFT_MULTI_TENANT_ENDPOINTS,
+    },
+};
 
 pub fn check_config_validation(config: Config) -> Result<Config> {
+    check_providers(&config.providers)?;
+    check_jwt(&config.jwt)?;
+    check_login_redirect(&config.security.login_redirect)?;
+    check_login_session(
+        &config.security.login_session,
+        &config.security.login_redirect,
+        &config.jwt,
+    )?;
     Ok(config)
 }
+
+fn check_jwt(jwt: &JwtConfig) -> Result<()> {
+    if jwt.keys.is_empty() {
+        return Err(anyhow!("at least one `[[jwt.keys]]` entry is required"));
+    }
+    // a retiring key is only published for `access_token_ttl` after its last token
+    if jwt.client_credentials_ttl == 0 || jwt.client_credentials_ttl > jwt.access_token_ttl {
+        return Err(anyhow!(
+            "`jwt.client_credentials_ttl` must be positive and at most `access_token_ttl`"
+        ));
+    }
+
+    let rotation = &jwt.rotation;
+    if !rotation.enabled {
+        return Ok(());
+    }
+    if rotation.check_interval == 0 {
+        return Err(anyhow!("`jwt.rotation.check_interval` must be positive"));
+    }
+    // consumers have to see a key in the JWKS before it signs
+    if rotation.publish_ahead == 0 || rotation.publish_ahead >= rotation.rotation_interval {
+        return Err(anyhow!(
+            "`jwt.rotation.publish_ahead` must be positive and shorter than `rotation_interval`"
+        ));
+    }
+    Ok(())
+}
+
+// an allowed origin is compared to the origin of `return_to`, anything past it would be ignored
+fn check_login_redirect(login_redirect: &LoginRedirectSecurityConfig) -> Result<()> {
+    for origin in &login_redirect.allowed_origins {
+        let bare = matches!(origin.scheme(), "http" | "https")
+            && origin.has_host()
+            && origin.username().is_empty()
+            && origin.password().is_none()
+            && origin.path() == "/"
+            && origin.query().is_none()
+            && origin.fragment().is_none();
+        if !bare {
+            return Err(anyhow!(
+                "`security.login_redirect.allowed_origins` : `{}` is not a bare http(s) origin",
+                origin
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_login_session(
+    login_session: &LoginSessionSecurityConfig,
+    login_redirect: &LoginRedirectSecurityConfig,
+    jwt: &JwtConfig,
+) -> Result<()> {
+    if !login_session.enabled {
+        return Ok(());
+    }
+    // sessions are only created for logins returning to an allowed frontend
+    if login_redirect.allowed_origins.is_empty() {
+        return Err(anyhow!(
+            "`security.login_session` requires `security.login_redirect.allowed_origins`"
+        ));
+    }
+    if login_session.ttl == 0 {
+        return Err(anyhow!("`security.login_session.ttl` must be positive"));
+    }
+    if login_session.token_ttl == 0 || login_session.token_ttl > jwt.access_token_ttl {
+        return Err(anyhow!(
+            "`security.login_session.token_ttl` must be positive and at most `jwt.access_token_ttl`"
+        ));
+    }
+    Ok(())
+}
+
+fn check_providers(providers: &[ProviderConfig]) -> Result<()> {
+    if providers.is_empty() {
+        return Err(anyhow!("at least one `[[providers]]` entry is required"));
+    }
+
+    let mut names = HashSet::with_capacity(providers.len());
+    for provider in providers {
+        check_provider_name(&provider.name)?;
+        if !names.insert(provider.name.as_str()) {
+            return Err(anyhow!("duplicated provider name `{}`", provider.name));
+        }
+        if let ProviderKindConfig::Microsoft(microsoft) = &provider.kind {
+            let tenant = microsoft.tenant.to_lowercase();
+            if MICROSOFT_MULTI_TENANT_ENDPOINTS.contains(&tenant.as_str())
+                && microsoft.allowed_tenants.is_empty()
+            {
+                return Err(anyhow!(
+                    "provider `{}` : `allowed_tenants` is required for the `{}` tenant",
+                    provider.name,
+                    tenant
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// the name is the `{idp}` path segment, so it must already be in its canonical form
+fn check_provider_name(name: &str) -> Result<()> {
+    match SupportIdp::try_from(name) {
+        std::result::Result::Ok(idp) if idp.as_str() == name => Ok(()),
+        _ => Err(anyhow!(
+            "invalid provider name `{}` : use lowercase letters, digits, '-' or '_'",
+            name
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("validation_test.rs");
+}
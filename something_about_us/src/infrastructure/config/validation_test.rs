@@ -0,0 +1,249 @@
+use super::{check_jwt, check_login_redirect, check_login_session, check_providers};
+use crate::infrastructure::config::types::{
+    JwtConfig, LoginRedirectSecurityConfig, LoginSessionSecurityConfig, ProviderConfig,
+    ProviderKindConfig, TokenDelivery,
+};
+use sonic_rs::Deserialize;
+
+#[derive(Deserialize)]
+struct Providers {
+    providers: Vec<ProviderConfig>,
+}
+
+fn parse(toml_str: &str) -> Vec<ProviderConfig> {
+    toml::from_str::<Providers>(toml_str)
+        .expect("providers should parse")
+        .providers
+}
+
+const GITHUB_PROVIDERS: &str = r#"
+[[providers]]
+name = "github"
+kind = "github"
+client_id = "github-client"
+client_secret = "github-secret"
+resource_url = "https://api.github.com"
+auth_url = "https://github.com/login/oauth/authorize"
+token_url = "https://github.com/login/oauth/access_token"
+redirect_url = "http://127.0.0.1:3000/api/v1/oauth/github/callback"
+
+[[providers]]
+name = "github-enterprise"
+kind = "github"
+client_id = "ghe-client"
+client_secret = "ghe-secret"
+resource_url = "https://github.example.com/api/v3/"
+auth_url = "https://github.example.com/login/oauth/authorize"
+token_url = "https://github.example.com/login/oauth/access_token"
+redirect_url = "http://127.0.0.1:3000/api/v1/oauth/github-enterprise/callback"
+scopes = ["read:user", "user:email"]
+"#;
+
+#[test]
+fn test_same_kind_declared_twice() {
+    let providers = parse(GITHUB_PROVIDERS);
+    assert!(check_providers(&providers).is_ok());
+
+    assert_eq!(providers[1].name, "github-enterprise");
+    match (&providers[0].kind, &providers[1].kind) {
+        (ProviderKindConfig::Github(github), ProviderKindConfig::Github(enterprise)) => {
+            assert_eq!(github.scopes, vec!["read:user"]);
+            assert_eq!(enterprise.scopes, vec!["read:user", "user:email"]);
+            assert_eq!(
+                enterprise.auth_url,
+                "https://github.example.com/login/oauth/authorize"
+            );
+        }
+        other => panic!("Expected two github providers, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_kind_defaults() {
+    let providers = parse(
+        r#"
+        [[providers]]
+        name = "gitlab"
+        kind = "gitlab"
+        client_id = "gitlab-client"
+        client_secret = "gitlab-secret"
+        redirect_url = "http://127.0.0.1:3000/api/v1/oauth/gitlab/callback"
+
+        [[providers]]
+        name = "keycloak"
+        kind = "oidc"
+        issuer_url = "https://keycloak.example.com/realms/main"
+        client_id = "keycloak-client"
+        client_secret = "keycloak-secret"
+        redirect_url = "http://127.0.0.1:3000/api/v1/oauth/keycloak/callback"
+        "#,
+    );
+    assert!(check_providers(&providers).is_ok());
+
+    match &providers[0].kind {
+        ProviderKindConfig::Gitlab(gitlab) => {
+            assert_eq!(gitlab.base_url.as_str(), "https://gitlab.com/");
+            assert_eq!(gitlab.scopes, vec!["read_user"]);
+        }
+        other => panic!("Expected gitlab provider, got {:?}", other),
+    }
+    assert_eq!(providers[1].kind.as_str(), "oidc");
+}
+
+#[test]
+fn test_unknown_kind() {
+    let result = toml::from_str::<Providers>(
+        r#"
+        [[providers]]
+        name = "saml"
+        kind = "saml"
+        "#,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_empty_providers() {
+    assert!(check_providers(&[]).is_err());
+}
+
+#[test]
+fn test_duplicated_name() {
+    let duplicated = GITHUB_PROVIDERS.replace("\"github-enterprise\"", "\"github\"");
+    let providers = parse(&duplicated);
+    assert!(check_providers(&providers).is_err());
+}
+
+#[test]
+fn test_invalid_name() {
+    let invalid = GITHUB_PROVIDERS.replace("\"github-enterprise\"", "\"GitHub Enterprise\"");
+    let providers = parse(&invalid);
+    assert!(check_providers(&providers).is_err());
+}
+
+#[test]
+fn test_multi_tenant_microsoft_requires_allowed_tenants() {
+    let microsoft = r#"
+        [[providers]]
+        name = "microsoft"
+        kind = "microsoft"
+        client_id = "entra-client"
+        client_secret = "entra-secret"
+        redirect_url = "http://127.0.0.1:3000/api/v1/oauth/microsoft/callback"
+        "#;
+    assert!(check_providers(&parse(microsoft)).is_err());
+
+    let allowed = format!(
+        "{}allowed_tenants = [\"11111111-1111-1111-1111-111111111111\"]\n",
+        microsoft
+    );
+    assert!(check_providers(&parse(&allowed)).is_ok());
+}
+
+const JWT: &str = r#"
+iss = "https://auth.example.com"
+aud = "SomethingAboutUs-Project-Service"
+keys_path = "./jwks"
+access_token_ttl = 86400
+[[keys]]
+kid = "13f03b9f-f209-4dcd-86f0-69cc19e773eb"
+"#;
+
+fn parse_jwt(toml_str: &str) -> JwtConfig {
+    toml::from_str::<JwtConfig>(toml_str).expect("jwt should parse")
+}
+
+#[test]
+fn test_rotation_disabled_by_default() {
+    let jwt = parse_jwt(JWT);
+    assert!(!jwt.rotation.enabled);
+    assert!(check_jwt(&jwt).is_ok());
+}
+
+#[test]
+fn test_jwt_requires_a_key() {
+    let mut jwt = parse_jwt(JWT);
+    jwt.keys.clear();
+    assert!(check_jwt(&jwt).is_err());
+}
+
+#[test]
+fn test_rotation_publish_ahead_shorter_than_interval() {
+    let rotation = format!(
+        "{}[rotation]\nenabled = true\nrotation_interval = 86400\npublish_ahead = 3600\n",
+        JWT
+    );
+    assert!(check_jwt(&parse_jwt(&rotation)).is_ok());
+
+    let too_long = rotation.replace("publish_ahead = 3600", "publish_ahead = 86400");
+    assert!(check_jwt(&parse_jwt(&too_long)).is_err());
+
+    let zero = rotation.replace("publish_ahead = 3600", "publish_ahead = 0");
+    assert!(check_jwt(&parse_jwt(&zero)).is_err());
+}
+
+#[test]
+fn test_client_credentials_ttl_within_access_token_ttl() {
+    let jwt = parse_jwt(JWT);
+    assert_eq!(jwt.client_credentials_ttl, 900);
+    assert!(check_jwt(&jwt).is_ok());
+
+    let too_long = format!("client_credentials_ttl = 86401\n{}", JWT);
+    assert!(check_jwt(&parse_jwt(&too_long)).is_err());
+
+    let zero = format!("client_credentials_ttl = 0\n{}", JWT);
+    assert!(check_jwt(&parse_jwt(&zero)).is_err());
+}
+
+#[test]
+fn test_login_redirect_origins() {
+    let parse = |toml_str: &str| toml::from_str::<LoginRedirectSecurityConfig>(toml_str).unwrap();
+
+    let defaults = parse("");
+    assert!(defaults.allowed_origins.is_empty());
+    assert_eq!(defaults.token_delivery, TokenDelivery::Cookie);
+
+    let valid = parse(
+        r#"
+allowed_origins = ["https://app.example.com", "http://localhost:5173"]
+token_delivery = "fragment"
+"#,
+    );
+    assert_eq!(valid.token_delivery, TokenDelivery::Fragment);
+    assert!(check_login_redirect(&valid).is_ok());
+
+    for origin in [
+        "https://app.example.com/dashboard",
+        "https://app.example.com/?next=1",
+        "https://user@app.example.com",
+        "javascript:alert(1)",
+    ] {
+        let invalid = parse(&format!("allowed_origins = [\"{}\"]", origin));
+        assert!(check_login_redirect(&invalid).is_err(), "{}", origin);
+    }
+}
+
+#[test]
+fn test_login_session() {
+    let jwt = parse_jwt(JWT);
+    let login_redirect = toml::from_str::<LoginRedirectSecurityConfig>(
+        r#"allowed_origins = ["https://app.example.com"]"#,
+    )
+    .unwrap();
+    let parse = |toml_str: &str| toml::from_str::<LoginSessionSecurityConfig>(toml_str).unwrap();
+
+    let defaults = parse("");
+    assert!(!defaults.enabled);
+    assert_eq!(defaults.ttl, 28800);
+    assert_eq!(defaults.token_ttl, 300);
+    assert!(check_login_session(&defaults, &LoginRedirectSecurityConfig::default(), &jwt).is_ok());
+
+    let enabled = parse("enabled = true");
+    assert!(check_login_session(&enabled, &login_redirect, &jwt).is_ok());
+    assert!(check_login_session(&enabled, &LoginRedirectSecurityConfig::default(), &jwt).is_err());
+
+    for invalid in ["ttl = 0", "token_ttl = 0", "token_ttl = 86401"] {
+        let invalid = parse(&format!("enabled = true\n{}", invalid));
+        assert!(check_login_session(&invalid, &login_redirect, &jwt).is_err());
+    }
+}
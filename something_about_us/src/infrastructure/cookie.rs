@@ -4,3 +4,7 @@ use uuid::Uuid;
 pub trait AuthSessionCookieIssuer {
     fn issuer_auth_session_cookie(&self, session_id: Uuid) -> Cookie<'static>;
 }
+
+pub trait LoginSessionCookieIssuer {
+    fn issue_login_session_cookie(&self, session_id: Uuid) -> Cookie<'static>;
+}
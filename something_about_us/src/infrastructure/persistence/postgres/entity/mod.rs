@@ -2,4 +2,15 @@
 
 pub mod prelude;
 
+pub mod clients;
+pub mod identities;
+pub mod organization_invitations;
+pub mod organization_members;
+pub mod organizations;
+pub mod permissions;
+pub mod refresh_tokens;
+pub mod roles;
+pub mod team_members;
+pub mod teams;
+pub mod user_roles;
 pub mod users;
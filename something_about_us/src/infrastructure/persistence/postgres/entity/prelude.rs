@@ -1,3 +1,14 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.14
 
+pub use super::clients::Entity as Clients;
+pub use super::identities::Entity as Identities;
+pub use super::organization_invitations::Entity as OrganizationInvitations;
+pub use super::organization_members::Entity as OrganizationMembers;
+pub use super::organizations::Entity as Organizations;
+pub use super::permissions::Entity as Permissions;
+pub use super::refresh_tokens::Entity as RefreshTokens;
+pub use super::roles::Entity as Roles;
+pub use super::team_members::Entity as TeamMembers;
+pub use super::teams::Entity as Teams;
+pub use super::user_roles::Entity as UserRoles;
 pub use super::users::Entity as Users;
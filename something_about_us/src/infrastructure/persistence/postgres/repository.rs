@@ -1,5 +1,9 @@
 use sea_orm::DatabaseConnection;
 
+pub mod oidc_client_repo;
+pub mod organization_repo;
+pub mod refresh_token_repo;
+pub mod role_repo;
 pub mod user_repo;
 
 #[derive(Clone)]
@@ -0,0 +1,131 @@
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue::Set, DbErr, EntityTrait, JsonValue, QueryOrder,
+    TryInsertResult,
+};
+
+use crate::{
+    application::port::oidc_client_repository::{OidcClientRepo, OidcClientRepoError},
+    domain::oauth::oidc_client::{GrantType, OidcClient},
+    infrastructure::persistence::postgres::{entity::clients, repository::DatabaseRepoPg},
+};
+
+fn database_error(e: DbErr) -> OidcClientRepoError {
+    OidcClientRepoError::DatabaseError(e.to_string())
+}
+
+fn string_array(value: JsonValue) -> Result<Vec<String>, OidcClientRepoError> {
+    serde_json::from_value(value).map_err(|e| OidcClientRepoError::CastingError(e.to_string()))
+}
+
+impl From<&OidcClient> for clients::ActiveModel {
+    fn from(value: &OidcClient) -> Self {
+        let grant_types: Vec<&str> = value
+            .allowed_grant_types
+            .iter()
+            .map(GrantType::as_str)
+            .collect();
+        Self {
+            client_id: Set(value.client_id.clone()),
+            secret_hash: Set(value.secret_hash.clone()),
+            redirect_uris: Set(JsonValue::from(value.redirect_uris.clone())),
+            allowed_scopes: Set(JsonValue::from(value.allowed_scopes.clone())),
+            allowed_grant_types: Set(JsonValue::from(grant_types)),
+            access_token_ttl: Set(value.access_token_ttl.map(|ttl| ttl as i64)),
+            refresh_token_ttl: Set(value.refresh_token_ttl.map(|ttl| ttl as i64)),
+            audience: Set(value.audience.clone()),
+            is_active: Set(value.is_active),
+            created_at: Set(value.created_at.into()),
+            updated_at: Set(value.updated_at.into()),
+            jwks: Set(value.jwks.as_ref().map(|jwks| serde_json::json!(jwks))),
+        }
+    }
+}
+
+impl TryFrom<clients::Model> for OidcClient {
+    type Error = OidcClientRepoError;
+
+    fn try_from(value: clients::Model) -> Result<Self, Self::Error> {
+        let allowed_grant_types = string_array(value.allowed_grant_types)?
+            .iter()
+            .map(|grant_type| GrantType::try_from(grant_type.as_str()))
+            .collect::<Result<_, _>>()
+            .map_err(|e| OidcClientRepoError::CastingError(e.to_string()))?;
+
+        Ok(Self {
+            client_id: value.client_id,
+            secret_hash: value.secret_hash,
+            redirect_uris: string_array(value.redirect_uris)?,
+            allowed_scopes: string_array(value.allowed_scopes)?,
+            allowed_grant_types,
+            access_token_ttl: value.access_token_ttl.map(|ttl| ttl as u64),
+            refresh_token_ttl: value.refresh_token_ttl.map(|ttl| ttl as u64),
+            audience: value.audience,
+            jwks: value
+                .jwks
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| OidcClientRepoError::CastingError(e.to_string()))?,
+            is_active: value.is_active,
+            created_at: value.created_at.into(),
+            updated_at: value.updated_at.into(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl OidcClientRepo for DatabaseRepoPg {
+    async fn get_client_by_id(
+        &self,
+        client_id: &str,
+    ) -> Result<Option<OidcClient>, OidcClientRepoError> {
+        clients::Entity::find_by_id(client_id)
+            .one(&self.conn)
+            .await
+            .map_err(database_error)?
+            .map(OidcClient::try_from)
+            .transpose()
+    }
+
+    async fn list_clients(&self) -> Result<Vec<OidcClient>, OidcClientRepoError> {
+        clients::Entity::find()
+            .order_by_asc(clients::Column::CreatedAt)
+            .all(&self.conn)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .map(OidcClient::try_from)
+            .collect()
+    }
+
+    async fn create_client(&self, client: &OidcClient) -> Result<(), OidcClientRepoError> {
+        let result = clients::Entity::insert(clients::ActiveModel::from(client))
+            .on_conflict(
+                OnConflict::column(clients::Column::ClientId)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec_with_returning(&self.conn)
+            .await
+            .map_err(database_error)?;
+
+        match result {
+            TryInsertResult::Inserted(_) => Ok(()),
+            _ => Err(OidcClientRepoError::ClientAlreadyExists),
+        }
+    }
+
+    async fn update_client(&self, client: &OidcClient) -> Result<(), OidcClientRepoError> {
+        let mut model = clients::ActiveModel::from(client);
+        model.created_at = sea_orm::ActiveValue::NotSet;
+
+        clients::Entity::update(model)
+            .exec(&self.conn)
+            .await
+            .map_err(|e| match e {
+                DbErr::RecordNotUpdated => OidcClientRepoError::ClientNotFound,
+                e => database_error(e),
+            })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,564 @@
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ActiveValue::Set,
+    ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, SqlErr, TransactionTrait, TryInsertResult,
+};
+use uuid::Uuid;
+
+use crate::{
+    application::port::organization_repository::{OrganizationRepo, OrganizationRepoError},
+    domain::{
+        idp::supported_idp::SupportIdp,
+        organization::{
+            invitation::{Invitation, InvitationTarget},
+            sau_organization::{MemberRole, Organization, OrganizationMember, Team, TeamMember},
+        },
+        user::sau_user::Email,
+    },
+    infrastructure::persistence::postgres::{
+        entity::{
+            organization_invitations, organization_members, organizations, team_members, teams,
+        },
+        repository::DatabaseRepoPg,
+    },
+};
+
+fn database_error(e: DbErr) -> OrganizationRepoError {
+    OrganizationRepoError::DatabaseError(e.to_string())
+}
+
+// the user was deleted meanwhile
+fn member_insert_error(e: DbErr) -> OrganizationRepoError {
+    match e.sql_err() {
+        Some(SqlErr::ForeignKeyConstraintViolation(_)) => OrganizationRepoError::UserNotFound,
+        _ => database_error(e),
+    }
+}
+
+impl From<organizations::Model> for Organization {
+    fn from(value: organizations::Model) -> Self {
+        Self {
+            id: value.id,
+            slug: value.slug,
+            name: value.name,
+            created_at: value.created_at.into(),
+            updated_at: value.updated_at.into(),
+        }
+    }
+}
+
+impl From<teams::Model> for Team {
+    fn from(value: teams::Model) -> Self {
+        Self {
+            id: value.id,
+            organization_id: value.organization_id,
+            slug: value.slug,
+            name: value.name,
+            created_at: value.created_at.into(),
+        }
+    }
+}
+
+impl From<team_members::Model> for TeamMember {
+    fn from(value: team_members::Model) -> Self {
+        Self {
+            team_id: value.team_id,
+            user_id: value.user_id,
+            created_at: value.created_at.into(),
+        }
+    }
+}
+
+fn member_role(role: &str) -> Result<MemberRole, OrganizationRepoError> {
+    MemberRole::try_from(role).map_err(|e| OrganizationRepoError::CastingError(e.to_string()))
+}
+
+impl TryFrom<organization_members::Model> for OrganizationMember {
+    type Error = OrganizationRepoError;
+
+    fn try_from(value: organization_members::Model) -> Result<Self, Self::Error> {
+        Ok(Self {
+            organization_id: value.organization_id,
+            user_id: value.user_id,
+            role: member_role(&value.role)?,
+            created_at: value.created_at.into(),
+        })
+    }
+}
+
+impl TryFrom<organization_invitations::Model> for Invitation {
+    type Error = OrganizationRepoError;
+
+    fn try_from(value: organization_invitations::Model) -> Result<Self, Self::Error> {
+        let casting_error = |e: String| OrganizationRepoError::CastingError(e);
+        let target = match (value.email, value.idp, value.idp_username) {
+            (Some(email), _, _) => InvitationTarget::Email(
+                Email::new(email).map_err(|e| casting_error(e.to_string()))?,
+            ),
+            (None, Some(idp), Some(username)) => InvitationTarget::IdpUsername {
+                idp: SupportIdp::try_from(idp).map_err(|e| casting_error(e.to_string()))?,
+                username,
+            },
+            _ => {
+                return Err(casting_error(format!(
+                    "invitation {} has no target",
+                    value.id
+                )))
+            }
+        };
+        Ok(Self {
+            id: value.id,
+            organization_id: value.organization_id,
+            token_hash: value.token_hash,
+            target,
+            role: member_role(&value.role)?,
+            invited_by: value.invited_by,
+            expires_at: value.expires_at.into(),
+            created_at: value.created_at.into(),
+        })
+    }
+}
+
+impl DatabaseRepoPg {
+    // serializes the membership changes of an organization,
+    // so two owners can not demote each other at the same time
+    async fn lock_organization(
+        txn: &DatabaseTransaction,
+        organization_id: Uuid,
+    ) -> Result<(), OrganizationRepoError> {
+        organizations::Entity::find_by_id(organization_id)
+            .lock_exclusive()
+            .one(txn)
+            .await
+            .map_err(database_error)?;
+        Ok(())
+    }
+
+    async fn ensure_owner_left(
+        txn: &DatabaseTransaction,
+        organization_id: Uuid,
+    ) -> Result<(), OrganizationRepoError> {
+        let owners = organization_members::Entity::find()
+            .filter(organization_members::Column::OrganizationId.eq(organization_id))
+            .filter(organization_members::Column::Role.eq(MemberRole::Owner.as_str()))
+            .count(txn)
+            .await
+            .map_err(database_error)?;
+        if owners == 0 {
+            return Err(OrganizationRepoError::LastOwner);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl OrganizationRepo for DatabaseRepoPg {
+    async fn create_organization(
+        &self,
+        organization: &Organization,
+        owner: &OrganizationMember,
+    ) -> Result<(), OrganizationRepoError> {
+        let txn = self.conn.begin().await.map_err(database_error)?;
+        let result = organizations::Entity::insert(organizations::ActiveModel {
+            id: Set(organization.id),
+            slug: Set(organization.slug.clone()),
+            name: Set(organization.name.clone()),
+            created_at: Set(organization.created_at.into()),
+            updated_at: Set(organization.updated_at.into()),
+        })
+        .on_conflict(
+            OnConflict::column(organizations::Column::Slug)
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec_with_returning(&txn)
+        .await
+        .map_err(database_error)?;
+        if !matches!(result, TryInsertResult::Inserted(_)) {
+            return Err(OrganizationRepoError::SlugTaken);
+        }
+
+        organization_members::Entity::insert(organization_members::ActiveModel {
+            organization_id: Set(owner.organization_id),
+            user_id: Set(owner.user_id),
+            role: Set(owner.role.as_str().to_string()),
+            created_at: Set(owner.created_at.into()),
+        })
+        .exec_without_returning(&txn)
+        .await
+        .map_err(member_insert_error)?;
+        txn.commit().await.map_err(database_error)
+    }
+
+    async fn get_organization_by_id(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Option<Organization>, OrganizationRepoError> {
+        let model = organizations::Entity::find_by_id(organization_id)
+            .one(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(model.map(Organization::from))
+    }
+
+    async fn get_organization_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<Organization>, OrganizationRepoError> {
+        let model = organizations::Entity::find()
+            .filter(organizations::Column::Slug.eq(slug))
+            .one(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(model.map(Organization::from))
+    }
+
+    async fn list_organizations_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<(Organization, MemberRole)>, OrganizationRepoError> {
+        let models = organization_members::Entity::find()
+            .filter(organization_members::Column::UserId.eq(user_id))
+            .find_also_related(organizations::Entity)
+            .order_by_asc(organizations::Column::Slug)
+            .all(&self.conn)
+            .await
+            .map_err(database_error)?;
+
+        models
+            .into_iter()
+            .filter_map(|(member, organization)| Some((member, organization?)))
+            .map(|(member, organization)| {
+                Ok((Organization::from(organization), member_role(&member.role)?))
+            })
+            .collect()
+    }
+
+    async fn update_organization_name(
+        &self,
+        organization_id: Uuid,
+        name: &str,
+    ) -> Result<Option<Organization>, OrganizationRepoError> {
+        let result = organizations::Entity::update(organizations::ActiveModel {
+            id: Set(organization_id),
+            name: Set(name.to_string()),
+            updated_at: Set(chrono::Utc::now().into()),
+            ..Default::default()
+        })
+        .exec(&self.conn)
+        .await;
+
+        match result {
+            Ok(model) => Ok(Some(Organization::from(model))),
+            Err(DbErr::RecordNotUpdated) => Ok(None),
+            Err(e) => Err(database_error(e)),
+        }
+    }
+
+    async fn delete_organization(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<bool, OrganizationRepoError> {
+        let deleted = organizations::Entity::delete_by_id(organization_id)
+            .exec(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(deleted.rows_affected > 0)
+    }
+
+    async fn get_member(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<OrganizationMember>, OrganizationRepoError> {
+        organization_members::Entity::find_by_id((organization_id, user_id))
+            .one(&self.conn)
+            .await
+            .map_err(database_error)?
+            .map(OrganizationMember::try_from)
+            .transpose()
+    }
+
+    async fn list_members(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<OrganizationMember>, OrganizationRepoError> {
+        organization_members::Entity::find()
+            .filter(organization_members::Column::OrganizationId.eq(organization_id))
+            .order_by_asc(organization_members::Column::CreatedAt)
+            .all(&self.conn)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .map(OrganizationMember::try_from)
+            .collect()
+    }
+
+    async fn update_member_role(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+        role: MemberRole,
+    ) -> Result<bool, OrganizationRepoError> {
+        let txn = self.conn.begin().await.map_err(database_error)?;
+        Self::lock_organization(&txn, organization_id).await?;
+        let updated = organization_members::Entity::update_many()
+            .col_expr(
+                organization_members::Column::Role,
+                Expr::value(role.as_str()),
+            )
+            .filter(organization_members::Column::OrganizationId.eq(organization_id))
+            .filter(organization_members::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        if updated.rows_affected == 0 {
+            return Ok(false);
+        }
+
+        Self::ensure_owner_left(&txn, organization_id).await?;
+        txn.commit().await.map_err(database_error)?;
+        Ok(true)
+    }
+
+    async fn remove_member(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, OrganizationRepoError> {
+        let txn = self.conn.begin().await.map_err(database_error)?;
+        Self::lock_organization(&txn, organization_id).await?;
+        let deleted = organization_members::Entity::delete_by_id((organization_id, user_id))
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        if deleted.rows_affected == 0 {
+            return Ok(false);
+        }
+        Self::ensure_owner_left(&txn, organization_id).await?;
+
+        team_members::Entity::delete_many()
+            .filter(team_members::Column::UserId.eq(user_id))
+            .filter(
+                team_members::Column::TeamId.in_subquery(
+                    sea_orm::sea_query::Query::select()
+                        .column(teams::Column::Id)
+                        .from(teams::Entity)
+                        .and_where(teams::Column::OrganizationId.eq(organization_id))
+                        .to_owned(),
+                ),
+            )
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        txn.commit().await.map_err(database_error)?;
+        Ok(true)
+    }
+
+    async fn create_team(&self, team: &Team) -> Result<(), OrganizationRepoError> {
+        let result = teams::Entity::insert(teams::ActiveModel {
+            id: Set(team.id),
+            organization_id: Set(team.organization_id),
+            slug: Set(team.slug.clone()),
+            name: Set(team.name.clone()),
+            created_at: Set(team.created_at.into()),
+        })
+        .on_conflict(
+            OnConflict::columns([teams::Column::OrganizationId, teams::Column::Slug])
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec_with_returning(&self.conn)
+        .await
+        .map_err(database_error)?;
+        if !matches!(result, TryInsertResult::Inserted(_)) {
+            return Err(OrganizationRepoError::TeamSlugTaken);
+        }
+        Ok(())
+    }
+
+    async fn list_teams(&self, organization_id: Uuid) -> Result<Vec<Team>, OrganizationRepoError> {
+        let models = teams::Entity::find()
+            .filter(teams::Column::OrganizationId.eq(organization_id))
+            .order_by_asc(teams::Column::Slug)
+            .all(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(models.into_iter().map(Team::from).collect())
+    }
+
+    async fn get_team_by_slug(
+        &self,
+        organization_id: Uuid,
+        slug: &str,
+    ) -> Result<Option<Team>, OrganizationRepoError> {
+        let model = teams::Entity::find()
+            .filter(teams::Column::OrganizationId.eq(organization_id))
+            .filter(teams::Column::Slug.eq(slug))
+            .one(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(model.map(Team::from))
+    }
+
+    async fn delete_team(&self, team_id: Uuid) -> Result<bool, OrganizationRepoError> {
+        let deleted = teams::Entity::delete_by_id(team_id)
+            .exec(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(deleted.rows_affected > 0)
+    }
+
+    async fn list_team_members(
+        &self,
+        team_id: Uuid,
+    ) -> Result<Vec<TeamMember>, OrganizationRepoError> {
+        let models = team_members::Entity::find()
+            .filter(team_members::Column::TeamId.eq(team_id))
+            .order_by_asc(team_members::Column::CreatedAt)
+            .all(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(models.into_iter().map(TeamMember::from).collect())
+    }
+
+    async fn add_team_member(&self, member: &TeamMember) -> Result<(), OrganizationRepoError> {
+        team_members::Entity::insert(team_members::ActiveModel {
+            team_id: Set(member.team_id),
+            user_id: Set(member.user_id),
+            created_at: Set(member.created_at.into()),
+        })
+        .on_conflict(
+            OnConflict::columns([team_members::Column::TeamId, team_members::Column::UserId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec_without_returning(&self.conn)
+        .await
+        .map_err(member_insert_error)?;
+        Ok(())
+    }
+
+    async fn remove_team_member(
+        &self,
+        team_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, OrganizationRepoError> {
+        let deleted = team_members::Entity::delete_by_id((team_id, user_id))
+            .exec(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(deleted.rows_affected > 0)
+    }
+
+    async fn create_invitation(
+        &self,
+        invitation: &Invitation,
+    ) -> Result<(), OrganizationRepoError> {
+        let (email, idp, idp_username) = match &invitation.target {
+            InvitationTarget::Email(email) => (Some(email.to_string()), None, None),
+            InvitationTarget::IdpUsername { idp, username } => {
+                (None, Some(idp.as_str().to_string()), Some(username.clone()))
+            }
+        };
+        organization_invitations::Entity::insert(organization_invitations::ActiveModel {
+            id: Set(invitation.id),
+            organization_id: Set(invitation.organization_id),
+            token_hash: Set(invitation.token_hash.clone()),
+            email: Set(email),
+            idp: Set(idp),
+            idp_username: Set(idp_username),
+            role: Set(invitation.role.as_str().to_string()),
+            invited_by: Set(invitation.invited_by),
+            expires_at: Set(invitation.expires_at.into()),
+            created_at: Set(invitation.created_at.into()),
+        })
+        .exec_without_returning(&self.conn)
+        .await
+        .map_err(database_error)?;
+        Ok(())
+    }
+
+    async fn list_invitations(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<Invitation>, OrganizationRepoError> {
+        organization_invitations::Entity::find()
+            .filter(organization_invitations::Column::OrganizationId.eq(organization_id))
+            .order_by_asc(organization_invitations::Column::CreatedAt)
+            .all(&self.conn)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .map(Invitation::try_from)
+            .collect()
+    }
+
+    async fn get_invitation_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Invitation>, OrganizationRepoError> {
+        organization_invitations::Entity::find()
+            .filter(organization_invitations::Column::TokenHash.eq(token_hash))
+            .one(&self.conn)
+            .await
+            .map_err(database_error)?
+            .map(Invitation::try_from)
+            .transpose()
+    }
+
+    async fn accept_invitation(
+        &self,
+        invitation_id: Uuid,
+        member: &OrganizationMember,
+    ) -> Result<bool, OrganizationRepoError> {
+        let txn = self.conn.begin().await.map_err(database_error)?;
+        // only the caller whose delete succeeds joins, a replayed token finds nothing
+        let deleted = organization_invitations::Entity::delete_by_id(invitation_id)
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        if deleted.rows_affected == 0 {
+            return Ok(false);
+        }
+
+        organization_members::Entity::insert(organization_members::ActiveModel {
+            organization_id: Set(member.organization_id),
+            user_id: Set(member.user_id),
+            role: Set(member.role.as_str().to_string()),
+            created_at: Set(member.created_at.into()),
+        })
+        .on_conflict(
+            OnConflict::columns([
+                organization_members::Column::OrganizationId,
+                organization_members::Column::UserId,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .do_nothing()
+        .exec_without_returning(&txn)
+        .await
+        .map_err(member_insert_error)?;
+        txn.commit().await.map_err(database_error)?;
+        Ok(true)
+    }
+
+    async fn delete_invitation(
+        &self,
+        organization_id: Uuid,
+        invitation_id: Uuid,
+    ) -> Result<bool, OrganizationRepoError> {
+        let deleted = organization_invitations::Entity::delete_many()
+            .filter(organization_invitations::Column::Id.eq(invitation_id))
+            .filter(organization_invitations::Column::OrganizationId.eq(organization_id))
+            .exec(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(deleted.rows_affected > 0)
+    }
+}
@@ -0,0 +1,138 @@
+use sea_orm::{
+    sea_query::Expr, ActiveValue::Set, ColumnTrait, DbErr, EntityTrait, QueryFilter,
+    TransactionTrait,
+};
+use uuid::Uuid;
+
+use crate::{
+    application::port::refresh_token_repository::{RefreshTokenRepo, RefreshTokenRepoError},
+    domain::oauth::refresh_token::RefreshToken,
+    infrastructure::persistence::postgres::{entity::refresh_tokens, repository::DatabaseRepoPg},
+};
+
+fn database_error(e: DbErr) -> RefreshTokenRepoError {
+    RefreshTokenRepoError::DatabaseError(e.to_string())
+}
+
+impl From<&RefreshToken> for refresh_tokens::ActiveModel {
+    fn from(value: &RefreshToken) -> Self {
+        Self {
+            id: Set(value.id),
+            family_id: Set(value.family_id),
+            user_id: Set(value.user_id),
+            token_hash: Set(value.token_hash.clone()),
+            expires_at: Set(value.expires_at.into()),
+            used_at: Set(value.used_at.map(Into::into)),
+            revoked_at: Set(value.revoked_at.map(Into::into)),
+            created_at: Set(value.created_at.into()),
+            client_id: Set(value.client_id.clone()),
+            scope: Set(value.scope.clone()),
+        }
+    }
+}
+
+impl From<refresh_tokens::Model> for RefreshToken {
+    fn from(value: refresh_tokens::Model) -> Self {
+        Self {
+            id: value.id,
+            family_id: value.family_id,
+            user_id: value.user_id,
+            token_hash: value.token_hash,
+            expires_at: value.expires_at.into(),
+            used_at: value.used_at.map(Into::into),
+            revoked_at: value.revoked_at.map(Into::into),
+            created_at: value.created_at.into(),
+            client_id: value.client_id,
+            scope: value.scope,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl RefreshTokenRepo for DatabaseRepoPg {
+    async fn save_refresh_token(&self, token: &RefreshToken) -> Result<(), RefreshTokenRepoError> {
+        refresh_tokens::Entity::insert(refresh_tokens::ActiveModel::from(token))
+            .exec(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(())
+    }
+
+    async fn get_refresh_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshToken>, RefreshTokenRepoError> {
+        let model = refresh_tokens::Entity::find()
+            .filter(refresh_tokens::Column::TokenHash.eq(token_hash))
+            .one(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(model.map(RefreshToken::from))
+    }
+
+    async fn rotate_refresh_token(
+        &self,
+        used_id: Uuid,
+        successor: &RefreshToken,
+    ) -> Result<bool, RefreshTokenRepoError> {
+        let txn = self.conn.begin().await.map_err(database_error)?;
+
+        // only one caller can flip `used_at`, the loser sees zero affected rows
+        let result = refresh_tokens::Entity::update_many()
+            .col_expr(
+                refresh_tokens::Column::UsedAt,
+                Expr::current_timestamp().into(),
+            )
+            .filter(refresh_tokens::Column::Id.eq(used_id))
+            .filter(refresh_tokens::Column::UsedAt.is_null())
+            .filter(refresh_tokens::Column::RevokedAt.is_null())
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        if result.rows_affected != 1 {
+            txn.rollback().await.map_err(database_error)?;
+            return Ok(false);
+        }
+
+        refresh_tokens::Entity::insert(refresh_tokens::ActiveModel::from(successor))
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        txn.commit().await.map_err(database_error)?;
+        Ok(true)
+    }
+
+    async fn revoke_refresh_token_family(
+        &self,
+        family_id: Uuid,
+    ) -> Result<(), RefreshTokenRepoError> {
+        refresh_tokens::Entity::update_many()
+            .col_expr(
+                refresh_tokens::Column::RevokedAt,
+                Expr::current_timestamp().into(),
+            )
+            .filter(refresh_tokens::Column::FamilyId.eq(family_id))
+            .filter(refresh_tokens::Column::RevokedAt.is_null())
+            .exec(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(())
+    }
+
+    async fn revoke_refresh_tokens_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<(), RefreshTokenRepoError> {
+        refresh_tokens::Entity::update_many()
+            .col_expr(
+                refresh_tokens::Column::RevokedAt,
+                Expr::current_timestamp().into(),
+            )
+            .filter(refresh_tokens::Column::UserId.eq(user_id))
+            .filter(refresh_tokens::Column::RevokedAt.is_null())
+            .exec(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ActiveValue::Set,
+    ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, SqlErr, TransactionTrait, TryInsertResult,
+};
+use uuid::Uuid;
+
+use crate::{
+    application::port::role_repository::{RoleRepo, RoleRepoError},
+    domain::user::role::Role,
+    infrastructure::persistence::postgres::{
+        entity::{permissions, roles, user_roles},
+        repository::DatabaseRepoPg,
+    },
+};
+
+fn database_error(e: DbErr) -> RoleRepoError {
+    RoleRepoError::DatabaseError(e.to_string())
+}
+
+impl DatabaseRepoPg {
+    // loads the permissions of every role in one query
+    async fn with_permissions(
+        &self,
+        models: Vec<roles::Model>,
+    ) -> Result<Vec<Role>, RoleRepoError> {
+        let role_ids: Vec<Uuid> = models.iter().map(|model| model.id).collect();
+        let mut granted: HashMap<Uuid, Vec<String>> = HashMap::new();
+        for permission in permissions::Entity::find()
+            .filter(permissions::Column::RoleId.is_in(role_ids))
+            .order_by_asc(permissions::Column::Name)
+            .all(&self.conn)
+            .await
+            .map_err(database_error)?
+        {
+            granted
+                .entry(permission.role_id)
+                .or_default()
+                .push(permission.name);
+        }
+
+        Ok(models
+            .into_iter()
+            .map(|model| Role {
+                permissions: granted.remove(&model.id).unwrap_or_default(),
+                id: model.id,
+                name: model.name,
+                description: model.description,
+                created_at: model.created_at.into(),
+            })
+            .collect())
+    }
+
+    async fn insert_permissions(
+        txn: &DatabaseTransaction,
+        role: &Role,
+    ) -> Result<(), RoleRepoError> {
+        if role.permissions.is_empty() {
+            return Ok(());
+        }
+        permissions::Entity::insert_many(role.permissions.iter().map(|name| {
+            permissions::ActiveModel {
+                role_id: Set(role.id),
+                name: Set(name.clone()),
+            }
+        }))
+        .exec(txn)
+        .await
+        .map_err(database_error)?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl RoleRepo for DatabaseRepoPg {
+    async fn list_roles(&self) -> Result<Vec<Role>, RoleRepoError> {
+        let models = roles::Entity::find()
+            .order_by_asc(roles::Column::Name)
+            .all(&self.conn)
+            .await
+            .map_err(database_error)?;
+        self.with_permissions(models).await
+    }
+
+    async fn get_role_by_name(&self, name: &str) -> Result<Option<Role>, RoleRepoError> {
+        let Some(model) = roles::Entity::find()
+            .filter(roles::Column::Name.eq(name))
+            .one(&self.conn)
+            .await
+            .map_err(database_error)?
+        else {
+            return Ok(None);
+        };
+        Ok(self.with_permissions(vec![model]).await?.pop())
+    }
+
+    async fn create_role(&self, role: &Role) -> Result<(), RoleRepoError> {
+        let txn = self.conn.begin().await.map_err(database_error)?;
+        let result = roles::Entity::insert(roles::ActiveModel {
+            id: Set(role.id),
+            name: Set(role.name.clone()),
+            description: Set(role.description.clone()),
+            created_at: Set(role.created_at.into()),
+        })
+        .on_conflict(
+            OnConflict::column(roles::Column::Name)
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec_with_returning(&txn)
+        .await
+        .map_err(database_error)?;
+        if !matches!(result, TryInsertResult::Inserted(_)) {
+            return Err(RoleRepoError::RoleAlreadyExists);
+        }
+
+        Self::insert_permissions(&txn, role).await?;
+        txn.commit().await.map_err(database_error)
+    }
+
+    async fn update_role(&self, role: &Role) -> Result<(), RoleRepoError> {
+        let txn = self.conn.begin().await.map_err(database_error)?;
+        let updated = roles::Entity::update_many()
+            .col_expr(
+                roles::Column::Description,
+                Expr::value(role.description.clone()),
+            )
+            .filter(roles::Column::Id.eq(role.id))
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        if updated.rows_affected == 0 {
+            return Err(RoleRepoError::RoleNotFound);
+        }
+
+        permissions::Entity::delete_many()
+            .filter(permissions::Column::RoleId.eq(role.id))
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        Self::insert_permissions(&txn, role).await?;
+        txn.commit().await.map_err(database_error)
+    }
+
+    async fn delete_role(&self, role_id: Uuid) -> Result<bool, RoleRepoError> {
+        let deleted = roles::Entity::delete_by_id(role_id)
+            .exec(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(deleted.rows_affected > 0)
+    }
+
+    async fn assign_role(&self, user_id: Uuid, role_id: Uuid) -> Result<(), RoleRepoError> {
+        let result = user_roles::Entity::insert(user_roles::ActiveModel {
+            user_id: Set(user_id),
+            role_id: Set(role_id),
+            created_at: Set(chrono::Utc::now().into()),
+        })
+        .on_conflict(
+            OnConflict::columns([user_roles::Column::UserId, user_roles::Column::RoleId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec_without_returning(&self.conn)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            // the user or the role was deleted meanwhile
+            Err(e) => match e.sql_err() {
+                Some(SqlErr::ForeignKeyConstraintViolation(message))
+                    if message.contains("fk_user_roles_user_id") =>
+                {
+                    Err(RoleRepoError::UserNotFound)
+                }
+                Some(SqlErr::ForeignKeyConstraintViolation(_)) => Err(RoleRepoError::RoleNotFound),
+                _ => Err(database_error(e)),
+            },
+        }
+    }
+
+    async fn unassign_role(&self, user_id: Uuid, role_id: Uuid) -> Result<bool, RoleRepoError> {
+        let deleted = user_roles::Entity::delete_by_id((user_id, role_id))
+            .exec(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(deleted.rows_affected > 0)
+    }
+
+    async fn get_roles_by_user_id(&self, user_id: Uuid) -> Result<Vec<Role>, RoleRepoError> {
+        let models = roles::Entity::find()
+            .join(JoinType::InnerJoin, roles::Relation::UserRoles.def())
+            .filter(user_roles::Column::UserId.eq(user_id))
+            .order_by_asc(roles::Column::Name)
+            .all(&self.conn)
+            .await
+            .map_err(database_error)?;
+        self.with_permissions(models).await
+    }
+}
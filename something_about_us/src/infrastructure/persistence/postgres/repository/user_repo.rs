@@ -1,31 +1,109 @@
-use sea_orm::{ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter, TryInsertResult};
+use sea_orm::{
+    sea_query::{extension::postgres::PgExpr, Expr, LikeExpr, OnConflict, Query},
+    ActiveValue::{NotSet, Set},
+    ColumnTrait, Condition, DbErr, EntityTrait, JoinType, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, SqlErr, TransactionTrait, TryInsertResult,
+};
 use uuid::Uuid;
 
 use crate::{
     application::port::sau_user_repository::{SAUUserRepo, SAUUserRepoError},
     domain::{
         idp::supported_idp::SupportIdp,
-        user::sau_user::{Email, SAUUser, Username},
+        organization::sau_organization::MemberRole,
+        user::{
+            sau_user::{Email, SAUUser, Username},
+            user_filter::UserFilter,
+            user_identity::UserIdentity,
+            user_profile::UserProfile,
+        },
+    },
+    infrastructure::persistence::postgres::{
+        entity::{identities, organization_members, organizations, users},
+        repository::DatabaseRepoPg,
     },
-    infrastructure::persistence::postgres::{entity::users, repository::DatabaseRepoPg},
 };
 
+fn database_error(e: DbErr) -> SAUUserRepoError {
+    SAUUserRepoError::DatabaseError(e.to_string())
+}
+
+// postgres names the violated constraint, e.g. `users_username_key`
+fn profile_error(e: DbErr) -> SAUUserRepoError {
+    match e.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(message)) if message.contains("username") => {
+            SAUUserRepoError::UsernameTaken
+        }
+        Some(SqlErr::UniqueConstraintViolation(message)) if message.contains("email") => {
+            SAUUserRepoError::EmailTaken
+        }
+        _ => database_error(e),
+    }
+}
+
+fn identity_conflict() -> OnConflict {
+    OnConflict::columns([identities::Column::Idp, identities::Column::IdpUid])
+        .do_nothing()
+        .to_owned()
+}
+
+// `%`, `_` and `\` of the search are matched literally
+fn prefix_pattern(prefix: &str) -> LikeExpr {
+    let mut pattern = String::with_capacity(prefix.len() + 1);
+    for c in prefix.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    LikeExpr::new(pattern).escape('\\')
+}
+
+impl DatabaseRepoPg {
+    async fn get_identity_by_idp_and_idp_id(
+        &self,
+        idp: &SupportIdp,
+        idp_id: &str,
+    ) -> Result<Option<identities::Model>, SAUUserRepoError> {
+        identities::Entity::find()
+            .filter(
+                identities::Column::Idp
+                    .eq(idp.as_str())
+                    .and(identities::Column::IdpUid.eq(idp_id)),
+            )
+            .one(&self.conn)
+            .await
+            .map_err(database_error)
+    }
+}
+
 #[async_trait::async_trait]
 impl SAUUserRepo for DatabaseRepoPg {
+    async fn get_user_by_id(&self, user_id: Uuid) -> Result<Option<SAUUser>, SAUUserRepoError> {
+        users::Entity::find_by_id(user_id)
+            .one(&self.conn)
+            .await
+            .map_err(database_error)?
+            .map(SAUUser::try_from)
+            .transpose()
+    }
+
     async fn get_user_by_idp_and_idp_id(
         &self,
         idp: &SupportIdp,
-        idp_id: &String,
+        idp_id: &str,
     ) -> Result<Option<SAUUser>, SAUUserRepoError> {
         users::Entity::find()
+            .join(JoinType::InnerJoin, users::Relation::Identities.def())
             .filter(
-                users::Column::Idp
+                identities::Column::Idp
                     .eq(idp.as_str())
-                    .and(users::Column::IdpUid.eq(idp_id)),
+                    .and(identities::Column::IdpUid.eq(idp_id)),
             )
             .one(&self.conn)
             .await
-            .map_err(|e| SAUUserRepoError::DatabaseError(e.to_string()))?
+            .map_err(database_error)?
             .map(SAUUser::try_from)
             .transpose()
     }
@@ -33,31 +111,57 @@ impl SAUUserRepo for DatabaseRepoPg {
     async fn create_user_by_idp_and_idp_id(
         &self,
         idp: &SupportIdp,
-        idp_id: &String,
+        idp_id: &str,
+        idp_username: Option<&str>,
+        profile: &UserProfile,
     ) -> Result<SAUUser, SAUUserRepoError> {
         let now = chrono::Utc::now().into();
+        let user_id = Uuid::now_v7();
         let new_user = users::ActiveModel {
-            id: Set(Uuid::now_v7()),
-            username: Set(None),
-            email: Set(None),
+            id: Set(user_id),
+            username: Set(profile
+                .username
+                .as_ref()
+                .map(|username| username.as_str().to_string())),
+            email: Set(profile
+                .email
+                .as_ref()
+                .map(|email| email.as_str().to_string())),
+            email_verified: Set(profile.email.is_some() && profile.email_verified),
+            display_name: Set(profile.display_name.clone()),
+            avatar_url: Set(profile.avatar_url.clone()),
             is_active: Set(true),
-            idp: Set(idp.as_str().to_string()),
-            idp_uid: Set(idp_id.clone()),
             created_at: Set(now),
             updated_at: Set(now),
         };
+        let new_identity = identities::ActiveModel {
+            id: Set(Uuid::now_v7()),
+            user_id: Set(user_id),
+            idp: Set(idp.as_str().to_string()),
+            idp_uid: Set(idp_id.to_string()),
+            idp_username: Set(idp_username.map(str::to_string)),
+            created_at: Set(now),
+        };
 
-        let result = users::Entity::insert(new_user)
+        let txn = self.conn.begin().await.map_err(database_error)?;
+        let user = users::Entity::insert(new_user)
+            .exec_with_returning(&txn)
+            .await
+            .map_err(profile_error)?;
+        let identity = identities::Entity::insert(new_identity)
+            .on_conflict(identity_conflict())
             .do_nothing()
-            .exec_with_returning(&self.conn)
+            .exec_with_returning(&txn)
             .await
-            .map_err(|e| SAUUserRepoError::DatabaseError(e.to_string()))?;
+            .map_err(database_error)?;
 
-        if let TryInsertResult::Inserted(model) = result {
-            return SAUUser::try_from(model)
-                .map_err(|e| SAUUserRepoError::CastingError(e.to_string()));
+        if let TryInsertResult::Inserted(_) = identity {
+            txn.commit().await.map_err(database_error)?;
+            return SAUUser::try_from(user);
         }
 
+        // the same identity signed in concurrently, drop our user and use the winner
+        txn.rollback().await.map_err(database_error)?;
         let search = self.get_user_by_idp_and_idp_id(idp, idp_id).await?;
 
         match search {
@@ -67,6 +171,296 @@ impl SAUUserRepo for DatabaseRepoPg {
             )),
         }
     }
+
+    async fn update_identity_username(
+        &self,
+        idp: &SupportIdp,
+        idp_id: &str,
+        idp_username: Option<&str>,
+    ) -> Result<(), SAUUserRepoError> {
+        identities::Entity::update_many()
+            .col_expr(
+                identities::Column::IdpUsername,
+                Expr::value(idp_username.map(str::to_string)),
+            )
+            .filter(
+                identities::Column::Idp
+                    .eq(idp.as_str())
+                    .and(identities::Column::IdpUid.eq(idp_id)),
+            )
+            .exec(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(())
+    }
+
+    async fn update_user_profile(
+        &self,
+        user_id: Uuid,
+        username: Option<&Username>,
+        email: Option<&Email>,
+    ) -> Result<Option<SAUUser>, SAUUserRepoError> {
+        let model = users::ActiveModel {
+            id: Set(user_id),
+            username: username.map_or(NotSet, |username| Set(Some(username.as_str().to_string()))),
+            email: email.map_or(NotSet, |email| Set(Some(email.as_str().to_string()))),
+            email_verified: email.map_or(NotSet, |_| Set(false)),
+            updated_at: Set(chrono::Utc::now().into()),
+            ..Default::default()
+        };
+
+        match users::Entity::update(model).exec(&self.conn).await {
+            Ok(user) => SAUUser::try_from(user).map(Some),
+            Err(DbErr::RecordNotUpdated) => Ok(None),
+            Err(e) => Err(profile_error(e)),
+        }
+    }
+
+    async fn update_user_idp_profile(
+        &self,
+        user_id: Uuid,
+        profile: &UserProfile,
+    ) -> Result<Option<SAUUser>, SAUUserRepoError> {
+        let set = |value: &Option<String>| value.clone().map_or(NotSet, |value| Set(Some(value)));
+        let model = users::ActiveModel {
+            id: Set(user_id),
+            username: set(&profile
+                .username
+                .as_ref()
+                .map(|username| username.as_str().to_string())),
+            email: set(&profile
+                .email
+                .as_ref()
+                .map(|email| email.as_str().to_string())),
+            email_verified: profile
+                .email
+                .as_ref()
+                .map_or(NotSet, |_| Set(profile.email_verified)),
+            display_name: set(&profile.display_name),
+            avatar_url: set(&profile.avatar_url),
+            updated_at: Set(chrono::Utc::now().into()),
+            ..Default::default()
+        };
+
+        match users::Entity::update(model).exec(&self.conn).await {
+            Ok(user) => SAUUser::try_from(user).map(Some),
+            Err(DbErr::RecordNotUpdated) => Ok(None),
+            Err(e) => Err(profile_error(e)),
+        }
+    }
+
+    async fn list_users(
+        &self,
+        filter: &UserFilter,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<Vec<SAUUser>, SAUUserRepoError> {
+        let mut query = users::Entity::find()
+            .order_by_asc(users::Column::Id)
+            .limit(limit);
+        if let Some(after) = after {
+            query = query.filter(users::Column::Id.gt(after));
+        }
+        if let Some(idp) = &filter.idp {
+            query = query.filter(
+                users::Column::Id.in_subquery(
+                    Query::select()
+                        .column(identities::Column::UserId)
+                        .from(identities::Entity)
+                        .and_where(identities::Column::Idp.eq(idp.as_str()))
+                        .to_owned(),
+                ),
+            );
+        }
+        if let Some(is_active) = filter.is_active {
+            query = query.filter(users::Column::IsActive.eq(is_active));
+        }
+        if let Some(created_from) = filter.created_from {
+            query = query.filter(users::Column::CreatedAt.gte(created_from));
+        }
+        if let Some(created_to) = filter.created_to {
+            query = query.filter(users::Column::CreatedAt.lt(created_to));
+        }
+        if let Some(search) = &filter.search {
+            let pattern = prefix_pattern(search);
+            query = query.filter(
+                Condition::any()
+                    .add(Expr::col(users::Column::Username).ilike(pattern.clone()))
+                    .add(Expr::col(users::Column::Email).ilike(pattern)),
+            );
+        }
+
+        query
+            .all(&self.conn)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .map(SAUUser::try_from)
+            .collect()
+    }
+
+    async fn set_user_active(
+        &self,
+        user_id: Uuid,
+        is_active: bool,
+    ) -> Result<Option<SAUUser>, SAUUserRepoError> {
+        let model = users::ActiveModel {
+            id: Set(user_id),
+            is_active: Set(is_active),
+            updated_at: Set(chrono::Utc::now().into()),
+            ..Default::default()
+        };
+
+        match users::Entity::update(model).exec(&self.conn).await {
+            Ok(user) => SAUUser::try_from(user).map(Some),
+            Err(DbErr::RecordNotUpdated) => Ok(None),
+            Err(e) => Err(database_error(e)),
+        }
+    }
+
+    async fn delete_user(&self, user_id: Uuid) -> Result<bool, SAUUserRepoError> {
+        let txn = self.conn.begin().await.map_err(database_error)?;
+
+        let mut owned: Vec<Uuid> = organization_members::Entity::find()
+            .select_only()
+            .column(organization_members::Column::OrganizationId)
+            .filter(organization_members::Column::UserId.eq(user_id))
+            .filter(organization_members::Column::Role.eq(MemberRole::Owner.as_str()))
+            .into_tuple()
+            .all(&txn)
+            .await
+            .map_err(database_error)?;
+        // the organization rows hold off membership changes until the user is gone,
+        // taken in id order so concurrent deletes can not deadlock
+        owned.sort();
+        for organization_id in owned {
+            organizations::Entity::find_by_id(organization_id)
+                .lock_exclusive()
+                .one(&txn)
+                .await
+                .map_err(database_error)?;
+            let other_owners = organization_members::Entity::find()
+                .filter(organization_members::Column::OrganizationId.eq(organization_id))
+                .filter(organization_members::Column::Role.eq(MemberRole::Owner.as_str()))
+                .filter(organization_members::Column::UserId.ne(user_id))
+                .count(&txn)
+                .await
+                .map_err(database_error)?;
+            if other_owners == 0 {
+                return Err(SAUUserRepoError::LastOrganizationOwner);
+            }
+        }
+
+        let result = users::Entity::delete_by_id(user_id)
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        txn.commit().await.map_err(database_error)?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn exists_user_by_email(&self, email: &Email) -> Result<bool, SAUUserRepoError> {
+        let count = users::Entity::find()
+            .filter(users::Column::Email.eq(email.as_str()))
+            .count(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(count > 0)
+    }
+
+    async fn exists_user_by_username(&self, username: &Username) -> Result<bool, SAUUserRepoError> {
+        let count = users::Entity::find()
+            .filter(users::Column::Username.eq(username.as_str()))
+            .count(&self.conn)
+            .await
+            .map_err(database_error)?;
+        Ok(count > 0)
+    }
+
+    async fn get_identities_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<UserIdentity>, SAUUserRepoError> {
+        identities::Entity::find()
+            .filter(identities::Column::UserId.eq(user_id))
+            .order_by_asc(identities::Column::CreatedAt)
+            .all(&self.conn)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .map(UserIdentity::try_from)
+            .collect()
+    }
+
+    async fn link_identity(
+        &self,
+        user_id: Uuid,
+        idp: &SupportIdp,
+        idp_id: &str,
+        idp_username: Option<&str>,
+    ) -> Result<UserIdentity, SAUUserRepoError> {
+        let new_identity = identities::ActiveModel {
+            id: Set(Uuid::now_v7()),
+            user_id: Set(user_id),
+            idp: Set(idp.as_str().to_string()),
+            idp_uid: Set(idp_id.to_string()),
+            idp_username: Set(idp_username.map(str::to_string)),
+            created_at: Set(chrono::Utc::now().into()),
+        };
+
+        let result = identities::Entity::insert(new_identity)
+            .on_conflict(identity_conflict())
+            .do_nothing()
+            .exec_with_returning(&self.conn)
+            .await
+            .map_err(database_error)?;
+
+        if let TryInsertResult::Inserted(model) = result {
+            return UserIdentity::try_from(model);
+        }
+
+        match self.get_identity_by_idp_and_idp_id(idp, idp_id).await? {
+            Some(model) if model.user_id == user_id => UserIdentity::try_from(model),
+            Some(_) => Err(SAUUserRepoError::IdentityAlreadyLinked),
+            None => Err(SAUUserRepoError::DatabaseError(
+                "failed to link identity".to_string(),
+            )),
+        }
+    }
+
+    async fn unlink_identity(
+        &self,
+        user_id: Uuid,
+        identity_id: Uuid,
+    ) -> Result<(), SAUUserRepoError> {
+        let txn = self.conn.begin().await.map_err(database_error)?;
+
+        // lock the user row so concurrent unlinks can not remove every identity
+        users::Entity::find_by_id(user_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await
+            .map_err(database_error)?
+            .ok_or(SAUUserRepoError::IdentityNotFound)?;
+
+        let identities = identities::Entity::find()
+            .filter(identities::Column::UserId.eq(user_id))
+            .all(&txn)
+            .await
+            .map_err(database_error)?;
+        if !identities.iter().any(|identity| identity.id == identity_id) {
+            return Err(SAUUserRepoError::IdentityNotFound);
+        }
+        if identities.len() <= 1 {
+            return Err(SAUUserRepoError::LastIdentity);
+        }
+
+        identities::Entity::delete_by_id(identity_id)
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        txn.commit().await.map_err(database_error)
+    }
 }
 
 impl TryFrom<users::Model> for SAUUser {
@@ -85,18 +479,34 @@ impl TryFrom<users::Model> for SAUUser {
             .transpose()
             .map_err(|e| SAUUserRepoError::CastingError(e.to_string()))?;
 
+        Ok(Self {
+            id: value.id,
+            username,
+            email,
+            email_verified: value.email_verified,
+            display_name: value.display_name,
+            avatar_url: value.avatar_url,
+            is_active: value.is_active,
+            created_at: value.created_at.into(),
+            updated_at: value.updated_at.into(),
+        })
+    }
+}
+
+impl TryFrom<identities::Model> for UserIdentity {
+    type Error = SAUUserRepoError;
+
+    fn try_from(value: identities::Model) -> Result<Self, Self::Error> {
         let idp = SupportIdp::try_from(value.idp.as_str())
             .map_err(|e| SAUUserRepoError::CastingError(e.to_string()))?;
 
         Ok(Self {
             id: value.id,
-            username,
-            email,
+            user_id: value.user_id,
             idp,
             idp_uid: value.idp_uid,
-            is_active: value.is_active,
+            idp_username: value.idp_username,
             created_at: value.created_at.into(),
-            updated_at: value.updated_at.into(),
         })
     }
 }
@@ -1 +1,5 @@
 pub mod github;
+pub mod gitlab;
+pub mod google;
+pub mod microsoft;
+pub mod oidc;
@@ -13,7 +13,7 @@ use crate::{
     domain::oauth::{
         auth_session::{AuthSession, AUTH_HTTP_AGENT_NAME},
         error::SAUOAuthDomainError,
-        oauth_provider::OAuthRequest,
+        oauth_provider::{IdpIdentity, OAuthRequest},
         sau_jwt::OAuthAccessToken,
     },
     infrastructure::config::types::GithubConfig,
@@ -40,6 +40,7 @@ type GithubClient = Arc<
 #[derive(Clone)]
 pub struct GithubOAuthClient {
     auth_client: GithubClient,
+    scopes: Vec<Scope>,
     resource_endpoint: Url,
     auth_callback_http_client: reqwest::Client,
     resource_http_request_client: reqwest::Client,
@@ -71,6 +72,7 @@ impl From<&GithubConfig> for GithubOAuthClient {
 
         Self {
             auth_client: Arc::new(auth_client),
+            scopes: value.scopes.iter().cloned().map(Scope::new).collect(),
             resource_endpoint,
             resource_http_request_client,
             auth_callback_http_client,
@@ -134,6 +136,20 @@ struct GithubPlan {
     pub private_repos: i32,
 }
 
+// `email` is the public profile email, github does not say whether it is verified
+impl From<GithubApiUserResponse> for IdpIdentity {
+    fn from(value: GithubApiUserResponse) -> Self {
+        Self {
+            idp_uid: value.id.to_string(),
+            email: value.email,
+            email_verified: false,
+            username: Some(value.login),
+            display_name: value.name,
+            avatar_url: Some(value.avatar_url),
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl OAuthRequest for GithubOAuthClient {
     async fn login(&self) -> Result<(Url, AuthSession), SAUOAuthDomainError> {
@@ -142,7 +158,7 @@ impl OAuthRequest for GithubOAuthClient {
         let (auth_url, csrf_token) = self
             .auth_client
             .authorize_url(CsrfToken::new_random)
-            .add_scope(Scope::new("read:user".to_string()))
+            .add_scopes(self.scopes.clone())
             .set_pkce_challenge(pkce_challenge)
             .url();
 
@@ -150,6 +166,12 @@ impl OAuthRequest for GithubOAuthClient {
             id: Uuid::now_v7(),
             pkce_verifier: pkce_verifier.secret().to_string(),
             csrf_token: csrf_token.secret().to_string(),
+            nonce: None,
+            authorization: None,
+            device_user_code: None,
+            link_user_id: None,
+            return_to: None,
+            idp: None,
         };
 
         Ok((auth_url, auth_session))
@@ -170,13 +192,15 @@ impl OAuthRequest for GithubOAuthClient {
 
         Ok(resp.access_token().secret().to_string())
     }
-    async fn get_user_id(
+
+    async fn get_user_info(
         &self,
         access_token: OAuthAccessToken,
-    ) -> Result<String, SAUOAuthDomainError> {
-        let user_info_url = self.resource_endpoint.join("user").map_err(|e| {
-            SAUOAuthDomainError::InvalidUrl(format!("for user api : {}", e.to_string()))
-        })?;
+    ) -> Result<IdpIdentity, SAUOAuthDomainError> {
+        let user_info_url = self
+            .resource_endpoint
+            .join("user")
+            .map_err(|e| SAUOAuthDomainError::InvalidUrl(format!("for user api : {}", e)))?;
         let response = self
             .resource_http_request_client
             .get(user_info_url)
@@ -200,6 +224,6 @@ impl OAuthRequest for GithubOAuthClient {
                 SAUOAuthDomainError::UserInfoFetchFailed(e.to_string())
             })?;
 
-        Ok(body.id.to_string())
+        Ok(IdpIdentity::from(body))
     }
 }
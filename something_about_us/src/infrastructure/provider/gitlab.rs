@@ -0,0 +1,202 @@
+use oauth2::{
+    basic::BasicClient, AuthType, AuthUrl, Client, ClientId, ClientSecret, CsrfToken,
+    PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, Scope, TokenResponse, TokenUrl,
+};
+use reqwest::redirect::Policy;
+use sonic_rs::Deserialize;
+use std::sync::Arc;
+use tracing::warn;
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    domain::oauth::{
+        auth_session::{AuthSession, AUTH_HTTP_AGENT_NAME},
+        error::SAUOAuthDomainError,
+        oauth_provider::{IdpIdentity, OAuthRequest},
+        sau_jwt::OAuthAccessToken,
+    },
+    infrastructure::config::types::GitlabConfig,
+};
+
+pub const GITLAB_BASE_URL: &str = "https://gitlab.com";
+
+type GitlabClient = Arc<
+    Client<
+        oauth2::StandardErrorResponse<oauth2::basic::BasicErrorResponseType>,
+        oauth2::StandardTokenResponse<oauth2::EmptyExtraTokenFields, oauth2::basic::BasicTokenType>,
+        oauth2::StandardTokenIntrospectionResponse<
+            oauth2::EmptyExtraTokenFields,
+            oauth2::basic::BasicTokenType,
+        >,
+        oauth2::StandardRevocableToken,
+        oauth2::StandardErrorResponse<oauth2::RevocationErrorResponseType>,
+        oauth2::EndpointSet,
+        oauth2::EndpointNotSet,
+        oauth2::EndpointNotSet,
+        oauth2::EndpointNotSet,
+        oauth2::EndpointSet,
+    >,
+>;
+
+// gitlab.com or a self-hosted instance, every endpoint is resolved from `base_url`.
+#[derive(Clone)]
+pub struct GitlabOAuthClient {
+    auth_client: GitlabClient,
+    scopes: Vec<Scope>,
+    user_endpoint: Url,
+    auth_callback_http_client: reqwest::Client,
+    resource_http_request_client: reqwest::Client,
+}
+
+impl From<&GitlabConfig> for GitlabOAuthClient {
+    fn from(value: &GitlabConfig) -> Self {
+        // keep the path of instances served under a sub path, e.g. `https://example.com/gitlab`
+        let mut base_url = value.base_url.clone();
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+        let endpoint = |path: &str| {
+            base_url
+                .join(path)
+                .expect("invalid gitlab endpoint url")
+                .to_string()
+        };
+
+        let idp_secret = ClientSecret::new(value.client_secret.clone());
+        let idp_id = ClientId::new(value.client_id.clone());
+        let auth_url = AuthUrl::new(endpoint("oauth/authorize"))
+            .expect("invalid gitlab authorization endpoint url");
+        let token_url =
+            TokenUrl::new(endpoint("oauth/token")).expect("invalid gitlab token endpoint url");
+        let redirect_url = value.redirect_url.clone();
+
+        let auth_client = BasicClient::new(idp_id)
+            .set_client_secret(idp_secret)
+            .set_auth_type(AuthType::RequestBody)
+            .set_auth_uri(auth_url)
+            .set_token_uri(token_url)
+            .set_redirect_uri(RedirectUrl::from_url(redirect_url));
+
+        let resource_http_request_client = reqwest::Client::new();
+        let auth_callback_http_client = reqwest::Client::builder()
+            .redirect(Policy::none())
+            .build()
+            .expect("gitlab callback http client init failed");
+        let user_endpoint =
+            Url::parse(&endpoint("api/v4/user")).expect("invalid gitlab user endpoint url");
+
+        Self {
+            auth_client: Arc::new(auth_client),
+            scopes: value.scopes.iter().cloned().map(Scope::new).collect(),
+            user_endpoint,
+            resource_http_request_client,
+            auth_callback_http_client,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+struct GitlabApiUserResponse {
+    pub id: i64,
+    pub username: String,
+    pub name: Option<String>,
+    pub state: String,
+    pub email: Option<String>,
+    pub avatar_url: Option<String>,
+    pub web_url: String,
+}
+
+// `email` is the public profile email, its confirmation is not part of the response
+impl From<GitlabApiUserResponse> for IdpIdentity {
+    fn from(value: GitlabApiUserResponse) -> Self {
+        Self {
+            idp_uid: value.id.to_string(),
+            email: value.email,
+            email_verified: false,
+            username: Some(value.username),
+            display_name: value.name,
+            avatar_url: value.avatar_url,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl OAuthRequest for GitlabOAuthClient {
+    async fn login(&self) -> Result<(Url, AuthSession), SAUOAuthDomainError> {
+        let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+
+        let (auth_url, csrf_token) = self
+            .auth_client
+            .authorize_url(CsrfToken::new_random)
+            .add_scopes(self.scopes.clone())
+            .set_pkce_challenge(pkce_challenge)
+            .url();
+
+        let auth_session = AuthSession {
+            id: Uuid::now_v7(),
+            pkce_verifier: pkce_verifier.secret().to_string(),
+            csrf_token: csrf_token.secret().to_string(),
+            nonce: None,
+            authorization: None,
+            device_user_code: None,
+            link_user_id: None,
+            return_to: None,
+            idp: None,
+        };
+
+        Ok((auth_url, auth_session))
+    }
+
+    async fn callback(
+        &self,
+        code: String,
+        pkce_verifier: String,
+    ) -> Result<OAuthAccessToken, SAUOAuthDomainError> {
+        let resp = self
+            .auth_client
+            .exchange_code(oauth2::AuthorizationCode::new(code))
+            .set_pkce_verifier(PkceCodeVerifier::new(pkce_verifier))
+            .request_async(&self.auth_callback_http_client)
+            .await
+            .map_err(|e| SAUOAuthDomainError::CallBackFailed(e.to_string()))?;
+
+        Ok(resp.access_token().secret().to_string())
+    }
+
+    async fn get_user_info(
+        &self,
+        access_token: OAuthAccessToken,
+    ) -> Result<IdpIdentity, SAUOAuthDomainError> {
+        let response = self
+            .resource_http_request_client
+            .get(self.user_endpoint.clone())
+            .bearer_auth(access_token)
+            .header(reqwest::header::USER_AGENT, AUTH_HTTP_AGENT_NAME)
+            .send()
+            .await
+            .map_err(|e| SAUOAuthDomainError::UserInfoFetchFailed(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(SAUOAuthDomainError::UserInfoFetchFailed(
+                response.status().to_string(),
+            ));
+        }
+
+        let body = response
+            .json::<GitlabApiUserResponse>()
+            .await
+            .map_err(|e| {
+                warn!("{:?}", e);
+                SAUOAuthDomainError::UserInfoFetchFailed(e.to_string())
+            })?;
+
+        Ok(IdpIdentity::from(body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("gitlab_test.rs");
+}
@@ -0,0 +1,158 @@
+use super::GitlabOAuthClient;
+use crate::{
+    domain::oauth::{error::SAUOAuthDomainError, oauth_provider::OAuthRequest},
+    infrastructure::config::types::GitlabConfig,
+};
+use axum::{
+    http::{HeaderMap, StatusCode},
+    routing::{get, post},
+    Form, Json, Router,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+const CLIENT_ID: &str = "mock-application-id";
+const ACCESS_TOKEN: &str = "mock-access-token";
+
+fn gitlab_config(base_url: &str) -> GitlabConfig {
+    GitlabConfig {
+        client_id: CLIENT_ID.to_string(),
+        client_secret: "mock-secret".to_string(),
+        redirect_url: url::Url::parse("http://127.0.0.1:3000/api/v1/oauth/gitlab/callback")
+            .unwrap(),
+        base_url: url::Url::parse(base_url).unwrap(),
+        scopes: vec!["read_user".to_string()],
+    }
+}
+
+async fn token(Form(params): Form<HashMap<String, String>>) -> (StatusCode, Json<Value>) {
+    if params.get("code").map(String::as_str) != Some("mock-code")
+        || params.get("client_id").map(String::as_str) != Some(CLIENT_ID)
+        || !params.contains_key("code_verifier")
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "invalid_grant" })),
+        );
+    }
+    (
+        StatusCode::OK,
+        Json(json!({
+            "access_token": ACCESS_TOKEN,
+            "token_type": "Bearer",
+            "expires_in": 7200,
+        })),
+    )
+}
+
+async fn user(headers: HeaderMap) -> (StatusCode, Json<Value>) {
+    let authorization = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+    if authorization != Some(&format!("Bearer {}", ACCESS_TOKEN)) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({ "message": "401 Unauthorized" })),
+        );
+    }
+    (
+        StatusCode::OK,
+        Json(json!({
+            "id": 4242,
+            "username": "mock",
+            "name": "Mock User",
+            "state": "active",
+            "email": "mock@example.com",
+            "avatar_url": "https://gitlab.example.com/uploads/mock.png",
+            "web_url": "https://gitlab.example.com/mock",
+        })),
+    )
+}
+
+// self-hosted gitlab served under `/gitlab`
+async fn start_mock_gitlab() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}/gitlab", listener.local_addr().unwrap());
+
+    let router = Router::new()
+        .route("/gitlab/oauth/token", post(token))
+        .route("/gitlab/api/v4/user", get(user));
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    base_url
+}
+
+#[tokio::test]
+async fn test_login_url_defaults_to_gitlab_com() {
+    let client = GitlabOAuthClient::from(&gitlab_config("https://gitlab.com"));
+
+    let (url, session) = client.login().await.expect("login should succeed");
+    let query = url.query_pairs().collect::<HashMap<_, _>>();
+
+    assert!(url
+        .as_str()
+        .starts_with("https://gitlab.com/oauth/authorize?"));
+    assert_eq!(query["client_id"], CLIENT_ID);
+    assert_eq!(query["state"], session.csrf_token);
+    assert_eq!(query["scope"], "read_user");
+    assert_eq!(query["code_challenge_method"], "S256");
+    assert!(session.nonce.is_none());
+}
+
+#[tokio::test]
+async fn test_login_url_keeps_self_hosted_path() {
+    let client = GitlabOAuthClient::from(&gitlab_config("https://example.com/gitlab"));
+
+    let (url, _) = client.login().await.expect("login should succeed");
+    assert!(url
+        .as_str()
+        .starts_with("https://example.com/gitlab/oauth/authorize?"));
+}
+
+#[tokio::test]
+async fn test_authenticate_returns_gitlab_user_id() {
+    let base_url = start_mock_gitlab().await;
+    let client = GitlabOAuthClient::from(&gitlab_config(&base_url));
+    let (_, session) = client.login().await.unwrap();
+
+    let identity = client
+        .authenticate("mock-code".to_string(), &session)
+        .await
+        .expect("authenticate should succeed");
+    assert_eq!(identity.idp_uid, "4242");
+    assert_eq!(identity.username.as_deref(), Some("mock"));
+    assert_eq!(identity.display_name.as_deref(), Some("Mock User"));
+    assert_eq!(identity.email.as_deref(), Some("mock@example.com"));
+    assert!(!identity.email_verified);
+    assert_eq!(
+        identity.avatar_url.as_deref(),
+        Some("https://gitlab.example.com/uploads/mock.png")
+    );
+}
+
+#[tokio::test]
+async fn test_callback_rejects_invalid_code() {
+    let base_url = start_mock_gitlab().await;
+    let client = GitlabOAuthClient::from(&gitlab_config(&base_url));
+    let (_, session) = client.login().await.unwrap();
+
+    let result = client
+        .callback("other-code".to_string(), session.pkce_verifier)
+        .await;
+    assert!(matches!(
+        result,
+        Err(SAUOAuthDomainError::CallBackFailed(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_get_user_info_rejects_invalid_token() {
+    let base_url = start_mock_gitlab().await;
+    let client = GitlabOAuthClient::from(&gitlab_config(&base_url));
+
+    let result = client.get_user_info("expired-token".to_string()).await;
+    assert!(matches!(
+        result,
+        Err(SAUOAuthDomainError::UserInfoFetchFailed(_))
+    ));
+}
@@ -0,0 +1,69 @@
+use url::Url;
+
+use crate::{
+    domain::oauth::{
+        auth_session::AuthSession,
+        error::SAUOAuthDomainError,
+        oauth_provider::{IdpIdentity, OAuthRequest},
+        sau_jwt::OAuthAccessToken,
+    },
+    infrastructure::{
+        config::types::{GenericOidcConfig, GoogleConfig},
+        provider::oidc::OidcOAuthClient,
+    },
+};
+
+pub const GOOGLE_ISSUER_URL: &str = "https://accounts.google.com";
+
+// google sign-in with PKCE authorization-code flow.
+// the subject, `email`/`email_verified` and the `name`/`picture` profile come from
+// the verified google `id_token`.
+#[derive(Clone)]
+pub struct GoogleOAuthClient {
+    oidc_client: OidcOAuthClient,
+}
+
+impl GoogleOAuthClient {
+    pub async fn discover(name: &str, config: &GoogleConfig) -> Result<Self, SAUOAuthDomainError> {
+        let oidc_config = GenericOidcConfig {
+            issuer_url: config.issuer_url.clone(),
+            client_id: config.client_id.clone(),
+            client_secret: config.client_secret.clone(),
+            redirect_url: config.redirect_url.clone(),
+            scopes: config.scopes.clone(),
+        };
+        let oidc_client = OidcOAuthClient::discover(name, &oidc_config).await?;
+
+        Ok(Self { oidc_client })
+    }
+}
+
+#[async_trait::async_trait]
+impl OAuthRequest for GoogleOAuthClient {
+    async fn login(&self) -> Result<(Url, AuthSession), SAUOAuthDomainError> {
+        self.oidc_client.login().await
+    }
+
+    async fn callback(
+        &self,
+        code: String,
+        pkce_verifier: String,
+    ) -> Result<OAuthAccessToken, SAUOAuthDomainError> {
+        self.oidc_client.callback(code, pkce_verifier).await
+    }
+
+    async fn get_user_info(
+        &self,
+        access_token: OAuthAccessToken,
+    ) -> Result<IdpIdentity, SAUOAuthDomainError> {
+        self.oidc_client.get_user_info(access_token).await
+    }
+
+    async fn authenticate(
+        &self,
+        code: String,
+        auth_session: &AuthSession,
+    ) -> Result<IdpIdentity, SAUOAuthDomainError> {
+        self.oidc_client.authenticate(code, auth_session).await
+    }
+}
@@ -0,0 +1,176 @@
+use openidconnect::{
+    core::{CoreGenderClaim, CoreProviderMetadata},
+    AdditionalClaims, IdTokenClaims, JsonWebKeySet,
+};
+use sonic_rs::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    domain::oauth::{
+        auth_session::AuthSession,
+        error::SAUOAuthDomainError,
+        oauth_provider::{IdpIdentity, OAuthRequest},
+        sau_jwt::OAuthAccessToken,
+    },
+    infrastructure::{
+        config::types::{GenericOidcConfig, MicrosoftConfig},
+        provider::oidc::OidcOAuthClient,
+    },
+};
+
+pub const MICROSOFT_AUTHORITY_URL: &str = "https://login.microsoftonline.com";
+pub const MICROSOFT_DEFAULT_TENANT: &str = "common";
+// endpoints accepting users of any tenant, the `tid` allowlist must be configured for them
+pub const MICROSOFT_MULTI_TENANT_ENDPOINTS: [&str; 3] = ["common", "organizations", "consumers"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct MicrosoftClaims {
+    tid: String,
+    oid: String,
+}
+
+impl AdditionalClaims for MicrosoftClaims {}
+
+// microsoft entra id (azure ad) sign-in against a single tenant or a multi-tenant endpoint.
+// the `iss` of multi-tenant metadata is a `{tenantid}` template, so the issuer is checked
+// against the `tid` claim here instead of the metadata, and `tid` must be an allowed tenant.
+// the idp user id is the immutable object id `oid`, not the pairwise `sub`.
+#[derive(Clone)]
+pub struct MicrosoftOAuthClient {
+    oidc_client: OidcOAuthClient,
+    authority_url: String,
+    allowed_tenants: Vec<String>,
+}
+
+impl MicrosoftOAuthClient {
+    pub async fn discover(
+        name: &str,
+        config: &MicrosoftConfig,
+    ) -> Result<Self, SAUOAuthDomainError> {
+        let authority_url = config.authority_url.trim_end_matches('/').to_string();
+        let tenant = config.tenant.to_lowercase();
+        let metadata_url = Url::parse(&format!(
+            "{}/{}/v2.0/.well-known/openid-configuration",
+            authority_url, tenant
+        ))
+        .map_err(|e| SAUOAuthDomainError::InvalidUrl(format!("authority of {} : {}", name, e)))?;
+
+        let http_client = OidcOAuthClient::http_client()?;
+        let discovery_failed = |e: &dyn std::fmt::Display| {
+            SAUOAuthDomainError::DiscoveryFailed(format!("{} : {}", name, e))
+        };
+        let provider_metadata = http_client
+            .get(metadata_url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| discovery_failed(&e))?
+            .json::<CoreProviderMetadata>()
+            .await
+            .map_err(|e| discovery_failed(&e))?;
+        let jwks = JsonWebKeySet::fetch_async(provider_metadata.jwks_uri(), &http_client)
+            .await
+            .map_err(|e| discovery_failed(&e))?;
+
+        let oidc_config = GenericOidcConfig {
+            issuer_url: provider_metadata.issuer().to_string(),
+            client_id: config.client_id.clone(),
+            client_secret: config.client_secret.clone(),
+            redirect_url: config.redirect_url.clone(),
+            scopes: config.scopes.clone(),
+        };
+        let oidc_client = OidcOAuthClient::from_provider_metadata(
+            name,
+            &oidc_config,
+            provider_metadata.set_jwks(jwks),
+            http_client,
+        );
+
+        // a single tenant only trusts itself unless told otherwise
+        let allowed_tenants = if config.allowed_tenants.is_empty() {
+            vec![tenant]
+        } else {
+            config
+                .allowed_tenants
+                .iter()
+                .map(|tenant| tenant.to_lowercase())
+                .collect()
+        };
+
+        Ok(Self {
+            oidc_client,
+            authority_url,
+            allowed_tenants,
+        })
+    }
+
+    fn identity_from_claims(
+        &self,
+        claims: &IdTokenClaims<MicrosoftClaims, CoreGenderClaim>,
+    ) -> Result<IdpIdentity, SAUOAuthDomainError> {
+        let microsoft_claims = claims.additional_claims();
+        let tid = microsoft_claims.tid.to_lowercase();
+        if !self.allowed_tenants.contains(&tid) {
+            return Err(SAUOAuthDomainError::InvalidIdToken(format!(
+                "tenant {} is not allowed",
+                tid
+            )));
+        }
+
+        let expected_issuer = format!("{}/{}/v2.0", self.authority_url, tid);
+        if claims.issuer().as_str() != expected_issuer {
+            return Err(SAUOAuthDomainError::InvalidIdToken(format!(
+                "unexpected issuer {} for tenant {}",
+                claims.issuer().as_str(),
+                tid
+            )));
+        }
+
+        Ok(IdpIdentity {
+            idp_uid: microsoft_claims.oid.clone(),
+            ..OidcOAuthClient::identity_from_claims(claims)
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl OAuthRequest for MicrosoftOAuthClient {
+    async fn login(&self) -> Result<(Url, AuthSession), SAUOAuthDomainError> {
+        self.oidc_client.login().await
+    }
+
+    async fn callback(
+        &self,
+        code: String,
+        pkce_verifier: String,
+    ) -> Result<OAuthAccessToken, SAUOAuthDomainError> {
+        self.oidc_client.callback(code, pkce_verifier).await
+    }
+
+    // the userinfo endpoint only knows the pairwise `sub`, `oid` comes from the `id_token`.
+    async fn get_user_info(
+        &self,
+        _access_token: OAuthAccessToken,
+    ) -> Result<IdpIdentity, SAUOAuthDomainError> {
+        Err(SAUOAuthDomainError::UserInfoFetchFailed(
+            "microsoft user id is only available from the id_token".to_string(),
+        ))
+    }
+
+    async fn authenticate(
+        &self,
+        code: String,
+        auth_session: &AuthSession,
+    ) -> Result<IdpIdentity, SAUOAuthDomainError> {
+        let claims = self
+            .oidc_client
+            .exchange_id_token::<MicrosoftClaims>(code, auth_session, false)
+            .await?;
+        self.identity_from_claims(&claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("microsoft_test.rs");
+}
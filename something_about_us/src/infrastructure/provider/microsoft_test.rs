@@ -0,0 +1,213 @@
+use super::MicrosoftOAuthClient;
+use crate::{
+    domain::oauth::{error::SAUOAuthDomainError, oauth_provider::OAuthRequest},
+    infrastructure::config::types::MicrosoftConfig,
+};
+use axum::{extract::State, routing::get, routing::post, Json, Router};
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use ring::signature::{EcdsaKeyPair, KeyPair as _, ECDSA_P256_SHA256_FIXED_SIGNING};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+const CLIENT_ID: &str = "mock-client";
+const ALLOWED_TENANT: &str = "11111111-1111-1111-1111-111111111111";
+const OTHER_TENANT: &str = "22222222-2222-2222-2222-222222222222";
+const OBJECT_ID: &str = "33333333-3333-3333-3333-333333333333";
+
+// minimal entra id serving tenant metadata, keys and token endpoints.
+struct MockEntra {
+    authority_url: String,
+    encoding_key: jsonwebtoken::EncodingKey,
+    jwk: Value,
+    id_token_claims: Mutex<Value>,
+}
+
+async fn metadata(State(entra): State<Arc<MockEntra>>) -> Json<Value> {
+    let tenant_url = format!("{}/common", entra.authority_url);
+    Json(json!({
+        "issuer": format!("{}/{{tenantid}}/v2.0", entra.authority_url),
+        "authorization_endpoint": format!("{}/oauth2/v2.0/authorize", tenant_url),
+        "token_endpoint": format!("{}/oauth2/v2.0/token", tenant_url),
+        "jwks_uri": format!("{}/discovery/v2.0/keys", tenant_url),
+        "response_types_supported": ["code"],
+        "subject_types_supported": ["pairwise"],
+        "id_token_signing_alg_values_supported": ["ES256"],
+    }))
+}
+
+async fn keys(State(entra): State<Arc<MockEntra>>) -> Json<Value> {
+    Json(json!({ "keys": [entra.jwk] }))
+}
+
+async fn token(State(entra): State<Arc<MockEntra>>) -> Json<Value> {
+    let mut header = jsonwebtoken::Header::new(jsonwebtoken::Algorithm::ES256);
+    header.kid = Some("key-1".to_string());
+    let claims = entra.id_token_claims.lock().unwrap().clone();
+    let id_token = jsonwebtoken::encode(&header, &claims, &entra.encoding_key).unwrap();
+
+    Json(json!({
+        "access_token": "mock-access-token",
+        "token_type": "Bearer",
+        "expires_in": 3600,
+        "id_token": id_token,
+    }))
+}
+
+async fn start_mock_entra() -> Arc<MockEntra> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let authority_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let rng = ring::rand::SystemRandom::new();
+    let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+    let key_pair =
+        EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng).unwrap();
+    let public_key = key_pair.public_key().as_ref();
+
+    let entra = Arc::new(MockEntra {
+        authority_url,
+        encoding_key: jsonwebtoken::EncodingKey::from_ec_der(pkcs8.as_ref()),
+        jwk: json!({
+            "kty": "EC",
+            "crv": "P-256",
+            "use": "sig",
+            "alg": "ES256",
+            "kid": "key-1",
+            "x": BASE64_URL_SAFE_NO_PAD.encode(&public_key[1..33]),
+            "y": BASE64_URL_SAFE_NO_PAD.encode(&public_key[33..65]),
+        }),
+        id_token_claims: Mutex::new(Value::Null),
+    });
+
+    // every tenant path is served by the same metadata, like the real multi-tenant endpoints
+    let router = Router::new()
+        .route(
+            "/{tenant}/v2.0/.well-known/openid-configuration",
+            get(metadata),
+        )
+        .route("/common/discovery/v2.0/keys", get(keys))
+        .route("/common/oauth2/v2.0/token", post(token))
+        .with_state(entra.clone());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    entra
+}
+
+async fn discover_client(
+    entra: &MockEntra,
+    tenant: &str,
+    allowed_tenants: &[&str],
+) -> MicrosoftOAuthClient {
+    let config = MicrosoftConfig {
+        client_id: CLIENT_ID.to_string(),
+        client_secret: "mock-secret".to_string(),
+        redirect_url: url::Url::parse("http://127.0.0.1:3000/api/v1/oauth/microsoft/callback")
+            .unwrap(),
+        tenant: tenant.to_string(),
+        allowed_tenants: allowed_tenants.iter().map(|t| t.to_string()).collect(),
+        authority_url: entra.authority_url.clone(),
+        scopes: vec!["email".to_string(), "profile".to_string()],
+    };
+    MicrosoftOAuthClient::discover("microsoft", &config)
+        .await
+        .expect("discovery should succeed")
+}
+
+fn id_token_claims(entra: &MockEntra, tid: &str, nonce: &str) -> Value {
+    let now = chrono::Utc::now().timestamp();
+    json!({
+        "iss": format!("{}/{}/v2.0", entra.authority_url, tid),
+        "aud": CLIENT_ID,
+        "sub": "pairwise-subject",
+        "oid": OBJECT_ID,
+        "tid": tid,
+        "nonce": nonce,
+        "iat": now,
+        "exp": now + 300,
+    })
+}
+
+#[tokio::test]
+async fn test_login_url_contains_nonce() {
+    let entra = start_mock_entra().await;
+    let client = discover_client(&entra, "common", &[ALLOWED_TENANT]).await;
+
+    let (url, session) = client.login().await.expect("login should succeed");
+    let query = url
+        .query_pairs()
+        .collect::<std::collections::HashMap<_, _>>();
+
+    assert!(url.as_str().starts_with(&format!(
+        "{}/common/oauth2/v2.0/authorize",
+        entra.authority_url
+    )));
+    assert_eq!(query["nonce"].as_ref(), session.nonce.as_deref().unwrap());
+    assert!(query["scope"].split(' ').any(|scope| scope == "openid"));
+}
+
+#[tokio::test]
+async fn test_authenticate_returns_object_id() {
+    let entra = start_mock_entra().await;
+    let client = discover_client(&entra, "common", &[ALLOWED_TENANT]).await;
+    let (_, session) = client.login().await.unwrap();
+    *entra.id_token_claims.lock().unwrap() =
+        id_token_claims(&entra, ALLOWED_TENANT, session.nonce.as_deref().unwrap());
+
+    let identity = client
+        .authenticate("mock-code".to_string(), &session)
+        .await
+        .expect("authenticate should succeed");
+    assert_eq!(identity.idp_uid, OBJECT_ID);
+}
+
+#[tokio::test]
+async fn test_authenticate_rejects_not_allowed_tenant() {
+    let entra = start_mock_entra().await;
+    let client = discover_client(&entra, "organizations", &[ALLOWED_TENANT]).await;
+    let (_, session) = client.login().await.unwrap();
+    *entra.id_token_claims.lock().unwrap() =
+        id_token_claims(&entra, OTHER_TENANT, session.nonce.as_deref().unwrap());
+
+    let result = client.authenticate("mock-code".to_string(), &session).await;
+    assert!(matches!(
+        result,
+        Err(SAUOAuthDomainError::InvalidIdToken(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_authenticate_rejects_issuer_of_other_tenant() {
+    let entra = start_mock_entra().await;
+    let client = discover_client(&entra, "common", &[ALLOWED_TENANT]).await;
+    let (_, session) = client.login().await.unwrap();
+    let mut claims = id_token_claims(&entra, ALLOWED_TENANT, session.nonce.as_deref().unwrap());
+    claims["iss"] = json!(format!("{}/{}/v2.0", entra.authority_url, OTHER_TENANT));
+    *entra.id_token_claims.lock().unwrap() = claims;
+
+    let result = client.authenticate("mock-code".to_string(), &session).await;
+    assert!(matches!(
+        result,
+        Err(SAUOAuthDomainError::InvalidIdToken(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_single_tenant_allows_only_itself() {
+    let entra = start_mock_entra().await;
+    let client = discover_client(&entra, ALLOWED_TENANT, &[]).await;
+    let (_, session) = client.login().await.unwrap();
+    let nonce = session.nonce.clone().unwrap();
+
+    *entra.id_token_claims.lock().unwrap() = id_token_claims(&entra, ALLOWED_TENANT, &nonce);
+    let identity = client
+        .authenticate("mock-code".to_string(), &session)
+        .await
+        .expect("own tenant should be allowed");
+    assert_eq!(identity.idp_uid, OBJECT_ID);
+
+    *entra.id_token_claims.lock().unwrap() = id_token_claims(&entra, OTHER_TENANT, &nonce);
+    let result = client.authenticate("mock-code".to_string(), &session).await;
+    assert!(matches!(
+        result,
+        Err(SAUOAuthDomainError::InvalidIdToken(_))
+    ));
+}
@@ -0,0 +1,303 @@
+use openidconnect::{
+    core::{
+        CoreAuthenticationFlow, CoreClient, CoreGenderClaim, CoreJweContentEncryptionAlgorithm,
+        CoreJwsSigningAlgorithm, CoreProviderMetadata,
+    },
+    AdditionalClaims, AuthorizationCode, ClaimsVerificationError, ClientId, ClientSecret,
+    CsrfToken, EmptyAdditionalClaims, EndpointMaybeSet, EndpointNotSet, EndpointSet, IdToken,
+    IdTokenClaims, IssuerUrl, JsonWebKeySet, Nonce, OAuth2TokenResponse, PkceCodeChallenge,
+    PkceCodeVerifier, RedirectUrl, Scope, SignatureVerificationError, TokenResponse,
+};
+use reqwest::redirect::Policy;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    domain::oauth::{
+        auth_session::AuthSession,
+        error::SAUOAuthDomainError,
+        oauth_provider::{IdpIdentity, OAuthRequest},
+        sau_jwt::OAuthAccessToken,
+    },
+    infrastructure::config::types::GenericOidcConfig,
+};
+
+type OidcClient = CoreClient<
+    EndpointSet,
+    EndpointNotSet,
+    EndpointNotSet,
+    EndpointNotSet,
+    EndpointMaybeSet,
+    EndpointMaybeSet,
+>;
+
+// OpenID Connect relying party built from the idp `.well-known/openid-configuration`.
+// the idp user id is the `sub` claim of the verified `id_token`.
+// `email`, `email_verified`, `preferred_username`, `name` and `picture` are taken from
+// the same claims when the idp releases them.
+#[derive(Clone)]
+pub struct OidcOAuthClient {
+    name: String,
+    client_id: ClientId,
+    client_secret: ClientSecret,
+    redirect_url: RedirectUrl,
+    scopes: Vec<Scope>,
+    provider_metadata: Arc<RwLock<CoreProviderMetadata>>,
+    http_client: reqwest::Client,
+}
+
+type OidcIdToken<AC> =
+    IdToken<AC, CoreGenderClaim, CoreJweContentEncryptionAlgorithm, CoreJwsSigningAlgorithm>;
+
+impl OidcOAuthClient {
+    pub async fn discover(
+        name: &str,
+        config: &GenericOidcConfig,
+    ) -> Result<Self, SAUOAuthDomainError> {
+        let issuer_url = IssuerUrl::new(config.issuer_url.clone())
+            .map_err(|e| SAUOAuthDomainError::InvalidUrl(format!("issuer of {} : {}", name, e)))?;
+
+        let http_client = Self::http_client()?;
+        let provider_metadata = CoreProviderMetadata::discover_async(issuer_url, &http_client)
+            .await
+            .map_err(|e| SAUOAuthDomainError::DiscoveryFailed(format!("{} : {}", name, e)))?;
+
+        Ok(Self::from_provider_metadata(
+            name,
+            config,
+            provider_metadata,
+            http_client,
+        ))
+    }
+
+    // for providers whose metadata can not pass the strict issuer check of `discover`.
+    pub(crate) fn from_provider_metadata(
+        name: &str,
+        config: &GenericOidcConfig,
+        provider_metadata: CoreProviderMetadata,
+        http_client: reqwest::Client,
+    ) -> Self {
+        info!(
+            "oidc provider {} discovered : {}",
+            name,
+            provider_metadata.issuer().as_str()
+        );
+
+        Self {
+            name: name.to_string(),
+            client_id: ClientId::new(config.client_id.clone()),
+            client_secret: ClientSecret::new(config.client_secret.clone()),
+            redirect_url: RedirectUrl::from_url(config.redirect_url.clone()),
+            scopes: config.scopes.iter().cloned().map(Scope::new).collect(),
+            provider_metadata: Arc::new(RwLock::new(provider_metadata)),
+            http_client,
+        }
+    }
+
+    pub(crate) fn http_client() -> Result<reqwest::Client, SAUOAuthDomainError> {
+        // following redirects opens the client up to SSRF vulnerabilities.
+        reqwest::Client::builder()
+            .redirect(Policy::none())
+            .build()
+            .map_err(|e| SAUOAuthDomainError::DiscoveryFailed(e.to_string()))
+    }
+
+    async fn client(&self) -> OidcClient {
+        let provider_metadata = self.provider_metadata.read().await.clone();
+        CoreClient::from_provider_metadata(
+            provider_metadata,
+            self.client_id.clone(),
+            Some(self.client_secret.clone()),
+        )
+        .set_redirect_uri(self.redirect_url.clone())
+    }
+
+    pub(crate) fn identity_from_claims<AC: AdditionalClaims>(
+        claims: &IdTokenClaims<AC, CoreGenderClaim>,
+    ) -> IdpIdentity {
+        IdpIdentity {
+            idp_uid: claims.subject().to_string(),
+            email: claims.email().map(|email| email.to_string()),
+            email_verified: claims.email_verified().unwrap_or(false),
+            username: claims
+                .preferred_username()
+                .map(|username| username.to_string()),
+            display_name: claims
+                .name()
+                .and_then(|name| name.get(None))
+                .map(|name| name.to_string()),
+            avatar_url: claims
+                .picture()
+                .and_then(|picture| picture.get(None))
+                .map(|picture| picture.to_string()),
+        }
+    }
+
+    // the idp may rotate its signing keys at any time, so an unknown `kid` triggers a refetch.
+    async fn refresh_jwks(&self) -> Result<OidcClient, SAUOAuthDomainError> {
+        {
+            let mut provider_metadata = self.provider_metadata.write().await;
+            let jwks = JsonWebKeySet::fetch_async(provider_metadata.jwks_uri(), &self.http_client)
+                .await
+                .map_err(|e| {
+                    SAUOAuthDomainError::DiscoveryFailed(format!("{} : {}", self.name, e))
+                })?;
+            *provider_metadata = provider_metadata.clone().set_jwks(jwks);
+        }
+        Ok(self.client().await)
+    }
+
+    // exchanges the code and returns the verified `id_token` claims.
+    // `require_issuer_match` may only be turned off when the caller checks `iss` itself.
+    pub(crate) async fn exchange_id_token<AC: AdditionalClaims + Clone>(
+        &self,
+        code: String,
+        auth_session: &AuthSession,
+        require_issuer_match: bool,
+    ) -> Result<IdTokenClaims<AC, CoreGenderClaim>, SAUOAuthDomainError> {
+        let nonce = auth_session.nonce.clone().map(Nonce::new).ok_or(
+            SAUOAuthDomainError::CallBackFailed("nonce is missing in auth session".to_string()),
+        )?;
+
+        let client = self.client().await;
+        let token_response = client
+            .exchange_code(AuthorizationCode::new(code))
+            .map_err(|e| SAUOAuthDomainError::CallBackFailed(e.to_string()))?
+            .set_pkce_verifier(PkceCodeVerifier::new(auth_session.pkce_verifier.clone()))
+            .request_async(&self.http_client)
+            .await
+            .map_err(|e| SAUOAuthDomainError::CallBackFailed(e.to_string()))?;
+
+        let id_token = token_response
+            .id_token()
+            .ok_or(SAUOAuthDomainError::InvalidIdToken(
+                "id_token is missing in token response".to_string(),
+            ))?
+            .to_string()
+            .parse::<OidcIdToken<AC>>()
+            .map_err(|e| SAUOAuthDomainError::InvalidIdToken(e.to_string()))?;
+
+        let verifier = client
+            .id_token_verifier()
+            .require_issuer_match(require_issuer_match);
+        let claims = match id_token.claims(&verifier, &nonce) {
+            Err(ClaimsVerificationError::SignatureVerification(
+                SignatureVerificationError::NoMatchingKey,
+            )) => {
+                warn!(
+                    "{} : id_token signed by unknown key, refetch jwks",
+                    self.name
+                );
+                let client = self.refresh_jwks().await?;
+                let verifier = client
+                    .id_token_verifier()
+                    .require_issuer_match(require_issuer_match);
+                let claims = id_token.claims(&verifier, &nonce).cloned();
+                claims
+            }
+            verified => verified.cloned(),
+        };
+
+        claims.map_err(|e| SAUOAuthDomainError::InvalidIdToken(e.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl OAuthRequest for OidcOAuthClient {
+    async fn login(&self) -> Result<(Url, AuthSession), SAUOAuthDomainError> {
+        let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+
+        let client = self.client().await;
+        let (auth_url, csrf_token, nonce) = client
+            .authorize_url(
+                CoreAuthenticationFlow::AuthorizationCode,
+                CsrfToken::new_random,
+                Nonce::new_random,
+            )
+            .add_scopes(self.scopes.clone())
+            .set_pkce_challenge(pkce_challenge)
+            .url();
+
+        let auth_session = AuthSession {
+            id: Uuid::now_v7(),
+            pkce_verifier: pkce_verifier.secret().to_string(),
+            csrf_token: csrf_token.secret().to_string(),
+            nonce: Some(nonce.secret().to_string()),
+            authorization: None,
+            device_user_code: None,
+            link_user_id: None,
+            return_to: None,
+            idp: None,
+        };
+
+        Ok((auth_url, auth_session))
+    }
+
+    async fn callback(
+        &self,
+        code: String,
+        pkce_verifier: String,
+    ) -> Result<OAuthAccessToken, SAUOAuthDomainError> {
+        let resp = self
+            .client()
+            .await
+            .exchange_code(AuthorizationCode::new(code))
+            .map_err(|e| SAUOAuthDomainError::CallBackFailed(e.to_string()))?
+            .set_pkce_verifier(PkceCodeVerifier::new(pkce_verifier))
+            .request_async(&self.http_client)
+            .await
+            .map_err(|e| SAUOAuthDomainError::CallBackFailed(e.to_string()))?;
+
+        Ok(resp.access_token().secret().to_string())
+    }
+
+    async fn get_user_info(
+        &self,
+        access_token: OAuthAccessToken,
+    ) -> Result<IdpIdentity, SAUOAuthDomainError> {
+        let user_info: openidconnect::core::CoreUserInfoClaims = self
+            .client()
+            .await
+            .user_info(openidconnect::AccessToken::new(access_token), None)
+            .map_err(|e| SAUOAuthDomainError::UserInfoFetchFailed(e.to_string()))?
+            .request_async(&self.http_client)
+            .await
+            .map_err(|e| SAUOAuthDomainError::UserInfoFetchFailed(e.to_string()))?;
+
+        Ok(IdpIdentity {
+            idp_uid: user_info.subject().to_string(),
+            email: user_info.email().map(|email| email.to_string()),
+            email_verified: user_info.email_verified().unwrap_or(false),
+            username: user_info
+                .preferred_username()
+                .map(|username| username.to_string()),
+            display_name: user_info
+                .name()
+                .and_then(|name| name.get(None))
+                .map(|name| name.to_string()),
+            avatar_url: user_info
+                .picture()
+                .and_then(|picture| picture.get(None))
+                .map(|picture| picture.to_string()),
+        })
+    }
+
+    async fn authenticate(
+        &self,
+        code: String,
+        auth_session: &AuthSession,
+    ) -> Result<IdpIdentity, SAUOAuthDomainError> {
+        let claims = self
+            .exchange_id_token::<EmptyAdditionalClaims>(code, auth_session, true)
+            .await?;
+        Ok(Self::identity_from_claims(&claims))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("oidc_test.rs");
+}
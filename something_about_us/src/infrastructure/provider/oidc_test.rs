@@ -0,0 +1,286 @@
+use super::OidcOAuthClient;
+use crate::{
+    domain::oauth::{error::SAUOAuthDomainError, oauth_provider::OAuthRequest},
+    infrastructure::config::types::GenericOidcConfig,
+};
+use axum::{extract::State, routing::get, routing::post, Json, Router};
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use ring::signature::{EcdsaKeyPair, KeyPair as _, ECDSA_P256_SHA256_FIXED_SIGNING};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+const CLIENT_ID: &str = "mock-client";
+const SUBJECT: &str = "mock-subject";
+
+struct SigningKey {
+    kid: String,
+    encoding_key: jsonwebtoken::EncodingKey,
+    jwk: Value,
+}
+
+impl SigningKey {
+    fn generate(kid: &str) -> Self {
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+        let key_pair =
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+                .unwrap();
+        // uncompressed point : 0x04 || x || y
+        let public_key = key_pair.public_key().as_ref();
+        Self {
+            kid: kid.to_string(),
+            encoding_key: jsonwebtoken::EncodingKey::from_ec_der(pkcs8.as_ref()),
+            jwk: json!({
+                "kty": "EC",
+                "crv": "P-256",
+                "use": "sig",
+                "alg": "ES256",
+                "kid": kid,
+                "x": BASE64_URL_SAFE_NO_PAD.encode(&public_key[1..33]),
+                "y": BASE64_URL_SAFE_NO_PAD.encode(&public_key[33..65]),
+            }),
+        }
+    }
+}
+
+// minimal identity provider serving discovery, jwks and token endpoints.
+struct MockIdp {
+    issuer: String,
+    published_keys: Mutex<Vec<Value>>,
+    signing_key: Mutex<Arc<SigningKey>>,
+    id_token_claims: Mutex<Value>,
+}
+
+async fn discovery(State(idp): State<Arc<MockIdp>>) -> Json<Value> {
+    Json(json!({
+        "issuer": idp.issuer,
+        "authorization_endpoint": format!("{}/authorize", idp.issuer),
+        "token_endpoint": format!("{}/token", idp.issuer),
+        "userinfo_endpoint": format!("{}/userinfo", idp.issuer),
+        "jwks_uri": format!("{}/jwks", idp.issuer),
+        "response_types_supported": ["code"],
+        "subject_types_supported": ["public"],
+        "id_token_signing_alg_values_supported": ["ES256"],
+    }))
+}
+
+async fn jwks(State(idp): State<Arc<MockIdp>>) -> Json<Value> {
+    Json(json!({ "keys": *idp.published_keys.lock().unwrap() }))
+}
+
+async fn token(State(idp): State<Arc<MockIdp>>) -> Json<Value> {
+    let signing_key = idp.signing_key.lock().unwrap().clone();
+    let mut header = jsonwebtoken::Header::new(jsonwebtoken::Algorithm::ES256);
+    header.kid = Some(signing_key.kid.clone());
+    let claims = idp.id_token_claims.lock().unwrap().clone();
+    let id_token = jsonwebtoken::encode(&header, &claims, &signing_key.encoding_key).unwrap();
+
+    Json(json!({
+        "access_token": "mock-access-token",
+        "token_type": "Bearer",
+        "expires_in": 3600,
+        "id_token": id_token,
+    }))
+}
+
+async fn start_mock_idp() -> Arc<MockIdp> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let issuer = format!("http://{}", listener.local_addr().unwrap());
+    let signing_key = Arc::new(SigningKey::generate("key-1"));
+
+    let idp = Arc::new(MockIdp {
+        issuer,
+        published_keys: Mutex::new(vec![signing_key.jwk.clone()]),
+        signing_key: Mutex::new(signing_key),
+        id_token_claims: Mutex::new(Value::Null),
+    });
+
+    let router = Router::new()
+        .route("/.well-known/openid-configuration", get(discovery))
+        .route("/jwks", get(jwks))
+        .route("/token", post(token))
+        .with_state(idp.clone());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    idp
+}
+
+async fn discover_client(idp: &MockIdp) -> OidcOAuthClient {
+    let config = GenericOidcConfig {
+        issuer_url: idp.issuer.clone(),
+        client_id: CLIENT_ID.to_string(),
+        client_secret: "mock-secret".to_string(),
+        redirect_url: url::Url::parse("http://127.0.0.1:3000/api/v1/oauth/mock/callback").unwrap(),
+        scopes: vec!["email".to_string()],
+    };
+    OidcOAuthClient::discover("mock", &config)
+        .await
+        .expect("discovery should succeed")
+}
+
+fn id_token_claims(idp: &MockIdp, nonce: &str) -> Value {
+    let now = chrono::Utc::now().timestamp();
+    json!({
+        "iss": idp.issuer,
+        "aud": CLIENT_ID,
+        "sub": SUBJECT,
+        "nonce": nonce,
+        "iat": now,
+        "exp": now + 300,
+    })
+}
+
+#[tokio::test]
+async fn test_login_url_contains_oidc_parameters() {
+    let idp = start_mock_idp().await;
+    let client = discover_client(&idp).await;
+
+    let (url, session) = client.login().await.expect("login should succeed");
+    let query = url
+        .query_pairs()
+        .collect::<std::collections::HashMap<_, _>>();
+
+    assert!(url
+        .as_str()
+        .starts_with(&format!("{}/authorize", idp.issuer)));
+    assert_eq!(query["client_id"], CLIENT_ID);
+    assert_eq!(query["state"], session.csrf_token);
+    assert_eq!(query["nonce"].as_ref(), session.nonce.as_deref().unwrap());
+    assert_eq!(query["code_challenge_method"], "S256");
+    assert!(query["scope"].split(' ').any(|scope| scope == "openid"));
+    assert!(query["scope"].split(' ').any(|scope| scope == "email"));
+}
+
+#[tokio::test]
+async fn test_authenticate_returns_subject() {
+    let idp = start_mock_idp().await;
+    let client = discover_client(&idp).await;
+    let (_, session) = client.login().await.unwrap();
+    *idp.id_token_claims.lock().unwrap() = id_token_claims(&idp, session.nonce.as_deref().unwrap());
+
+    let identity = client
+        .authenticate("mock-code".to_string(), &session)
+        .await
+        .expect("authenticate should succeed");
+    assert_eq!(identity.idp_uid, SUBJECT);
+    assert_eq!(identity.email, None);
+    assert!(!identity.email_verified);
+}
+
+#[tokio::test]
+async fn test_authenticate_rejects_invalid_claims() {
+    let idp = start_mock_idp().await;
+    let client = discover_client(&idp).await;
+    let (_, session) = client.login().await.unwrap();
+    let valid = id_token_claims(&idp, session.nonce.as_deref().unwrap());
+
+    let now = chrono::Utc::now().timestamp();
+    let tampered = [
+        ("nonce", json!("other-nonce")),
+        ("aud", json!("other-client")),
+        ("iss", json!("https://evil.example.com")),
+        ("exp", json!(now - 600)),
+    ];
+    for (claim, value) in tampered {
+        let mut claims = valid.clone();
+        claims[claim] = value;
+        *idp.id_token_claims.lock().unwrap() = claims;
+
+        let result = client.authenticate("mock-code".to_string(), &session).await;
+        assert!(
+            matches!(result, Err(SAUOAuthDomainError::InvalidIdToken(_))),
+            "invalid `{}` must be rejected",
+            claim
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_authenticate_returns_email_claims() {
+    let idp = start_mock_idp().await;
+    let client = discover_client(&idp).await;
+    let (_, session) = client.login().await.unwrap();
+    let mut claims = id_token_claims(&idp, session.nonce.as_deref().unwrap());
+    claims["email"] = json!("user@example.com");
+    claims["email_verified"] = json!(true);
+    *idp.id_token_claims.lock().unwrap() = claims;
+
+    let identity = client
+        .authenticate("mock-code".to_string(), &session)
+        .await
+        .expect("authenticate should succeed");
+    assert_eq!(identity.email.as_deref(), Some("user@example.com"));
+    assert!(identity.email_verified);
+}
+
+#[tokio::test]
+async fn test_authenticate_returns_profile_claims() {
+    let idp = start_mock_idp().await;
+    let client = discover_client(&idp).await;
+    let (_, session) = client.login().await.unwrap();
+    let mut claims = id_token_claims(&idp, session.nonce.as_deref().unwrap());
+    claims["preferred_username"] = json!("mock");
+    claims["name"] = json!("Mock User");
+    claims["picture"] = json!("https://idp.example.com/mock.png");
+    *idp.id_token_claims.lock().unwrap() = claims;
+
+    let identity = client
+        .authenticate("mock-code".to_string(), &session)
+        .await
+        .expect("authenticate should succeed");
+    assert_eq!(identity.username.as_deref(), Some("mock"));
+    assert_eq!(identity.display_name.as_deref(), Some("Mock User"));
+    assert_eq!(
+        identity.avatar_url.as_deref(),
+        Some("https://idp.example.com/mock.png")
+    );
+}
+
+#[tokio::test]
+async fn test_authenticate_refetches_rotated_jwks() {
+    let idp = start_mock_idp().await;
+    let client = discover_client(&idp).await;
+    let (_, session) = client.login().await.unwrap();
+    *idp.id_token_claims.lock().unwrap() = id_token_claims(&idp, session.nonce.as_deref().unwrap());
+
+    let rotated = Arc::new(SigningKey::generate("key-2"));
+    idp.published_keys.lock().unwrap().push(rotated.jwk.clone());
+    *idp.signing_key.lock().unwrap() = rotated;
+
+    let identity = client
+        .authenticate("mock-code".to_string(), &session)
+        .await
+        .expect("authenticate should succeed after jwks refetch");
+    assert_eq!(identity.idp_uid, SUBJECT);
+}
+
+#[tokio::test]
+async fn test_authenticate_requires_session_nonce() {
+    let idp = start_mock_idp().await;
+    let client = discover_client(&idp).await;
+    let (_, mut session) = client.login().await.unwrap();
+    session.nonce = None;
+
+    let result = client.authenticate("mock-code".to_string(), &session).await;
+    assert!(matches!(
+        result,
+        Err(SAUOAuthDomainError::CallBackFailed(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_discover_unreachable_issuer() {
+    let config = GenericOidcConfig {
+        issuer_url: "http://127.0.0.1:1".to_string(),
+        client_id: CLIENT_ID.to_string(),
+        client_secret: "mock-secret".to_string(),
+        redirect_url: url::Url::parse("http://127.0.0.1:3000/callback").unwrap(),
+        scopes: Vec::new(),
+    };
+
+    let result = OidcOAuthClient::discover("mock", &config).await;
+    assert!(matches!(
+        result,
+        Err(SAUOAuthDomainError::DiscoveryFailed(_))
+    ));
+}
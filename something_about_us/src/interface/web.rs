@@ -1,6 +1,8 @@
+pub mod auth;
 pub mod dto;
 pub mod error;
 pub mod openapi;
 pub mod server;
 pub mod state;
 pub mod v1;
+pub mod well_known;
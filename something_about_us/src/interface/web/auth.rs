@@ -0,0 +1,325 @@
+use axum::{
+    extract::{FromRef, FromRequestParts},
+    http::{header::AUTHORIZATION, request::Parts, HeaderMap},
+    middleware::{from_extractor_with_state, FromExtractorLayer},
+};
+use axum_extra::extract::CookieJar;
+use base64::{prelude::BASE64_STANDARD, Engine};
+use std::marker::PhantomData;
+use uuid::Uuid;
+
+use crate::{
+    application::service::{
+        jwt_service::JwtService,
+        login_session_service::LoginSessionService,
+        organization_service::OrganizationService,
+        role_service::RoleService,
+        token_revocation_service::TokenRevocationService,
+        user_service::{UserService, UserServiceError},
+    },
+    domain::{
+        oauth::{
+            login_session::{LoginSession, LOGIN_SESSION_COOKIE_NAME},
+            sau_jwt::SAUClaims,
+            sau_jwt_issuer::SAUJwtIssuer,
+        },
+        user::{
+            role::{UserGrants, ADMIN_PERMISSION},
+            sau_user::SAUUser,
+        },
+    },
+    infrastructure::{
+        cache::memcached::repository::CacheRepoMchd,
+        persistence::postgres::repository::DatabaseRepoPg,
+    },
+    interface::web::{
+        error::WebError,
+        state::{admin_access::AdminAccess, AppState},
+    },
+};
+
+// caller of a protected route, resolved from a verified and not revoked
+// `Authorization: Bearer <jwt>` header.
+#[derive(Debug)]
+pub struct AuthenticatedUser {
+    pub claims: SAUClaims,
+}
+
+impl AuthenticatedUser {
+    pub fn user_id(&self) -> Uuid {
+        self.claims.sub
+    }
+}
+
+impl<S> FromRequestParts<S> for AuthenticatedUser
+where
+    S: Send + Sync,
+    JwtService<SAUJwtIssuer>: FromRef<S>,
+    TokenRevocationService<CacheRepoMchd, DatabaseRepoPg>: FromRef<S>,
+{
+    type Rejection = WebError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let claims = authenticate(parts, state, false).await?;
+        Ok(Self { claims })
+    }
+}
+
+// like `AuthenticatedUser` but also accepts the access tokens issued to registered
+// clients with their own `aud`, for endpoints every client may call (e.g. userinfo).
+#[derive(Debug)]
+pub struct AnyAudienceUser(pub AuthenticatedUser);
+
+impl<S> FromRequestParts<S> for AnyAudienceUser
+where
+    S: Send + Sync,
+    JwtService<SAUJwtIssuer>: FromRef<S>,
+    TokenRevocationService<CacheRepoMchd, DatabaseRepoPg>: FromRef<S>,
+{
+    type Rejection = WebError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let claims = authenticate(parts, state, true).await?;
+        Ok(Self(AuthenticatedUser { claims }))
+    }
+}
+
+// an `AuthenticatedUser` listed in `security.admin.user_ids` or granted the `admin` permission
+// by a role, anyone else gets `403`
+#[derive(Debug)]
+pub struct AdminUser(pub AuthenticatedUser);
+
+impl<S> FromRequestParts<S> for AdminUser
+where
+    S: Send + Sync,
+    JwtService<SAUJwtIssuer>: FromRef<S>,
+    TokenRevocationService<CacheRepoMchd, DatabaseRepoPg>: FromRef<S>,
+    AdminAccess: FromRef<S>,
+{
+    type Rejection = WebError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthenticatedUser::from_request_parts(parts, state).await?;
+        let admin = AdminAccess::from_ref(state).is_admin(user.user_id())
+            || user.claims.has_permission(ADMIN_PERMISSION);
+        if !admin {
+            return Err(WebError::Forbidden("admin only".to_string()));
+        }
+        Ok(Self(user))
+    }
+}
+
+// a permission a route can require, e.g.
+// `struct ReadUsers; impl Permission for ReadUsers { const NAME: &'static str = "users:read"; }`
+pub trait Permission: Send + Sync + 'static {
+    const NAME: &'static str;
+}
+
+// permissions of the admin API a role can grant besides `admin`
+pub struct ReadRoles;
+
+impl Permission for ReadRoles {
+    const NAME: &'static str = "roles:read";
+}
+
+pub struct AssignRoles;
+
+impl Permission for AssignRoles {
+    const NAME: &'static str = "roles:assign";
+}
+
+// an `AuthenticatedUser` listed in `security.admin.user_ids` or whose access token grants `P`
+// or `admin` in its `scope`, anyone else gets `403`. the token carries the permissions of when
+// it was issued, so a change of the roles applies once the user gets a new access token.
+#[derive(Debug)]
+pub struct PermittedUser<P: Permission> {
+    pub user: AuthenticatedUser,
+    admin: bool,
+    permission: PhantomData<P>,
+}
+
+impl<P: Permission> PermittedUser<P> {
+    fn permit(user: AuthenticatedUser, admin_access: &AdminAccess) -> Result<Self, WebError> {
+        let admin =
+            admin_access.is_admin(user.user_id()) || user.claims.has_permission(ADMIN_PERMISSION);
+        if !admin && !is_permitted(&user.claims, P::NAME) {
+            return Err(WebError::Forbidden(format!(
+                "permission {} is required",
+                P::NAME
+            )));
+        }
+        Ok(Self {
+            user,
+            admin,
+            permission: PhantomData,
+        })
+    }
+
+    // whether the caller could also pass `AdminUser`
+    pub fn is_admin(&self) -> bool {
+        self.admin
+    }
+}
+
+impl<S, P> FromRequestParts<S> for PermittedUser<P>
+where
+    S: Send + Sync,
+    P: Permission,
+    JwtService<SAUJwtIssuer>: FromRef<S>,
+    TokenRevocationService<CacheRepoMchd, DatabaseRepoPg>: FromRef<S>,
+    AdminAccess: FromRef<S>,
+{
+    type Rejection = WebError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthenticatedUser::from_request_parts(parts, state).await?;
+        Self::permit(user, &AdminAccess::from_ref(state))
+    }
+}
+
+fn is_permitted(claims: &SAUClaims, permission: &str) -> bool {
+    claims.has_permission(permission) || claims.has_permission(ADMIN_PERMISSION)
+}
+
+pub const CSRF_TOKEN_HEADER: &str = "x-csrf-token";
+
+// caller holding a backend-for-frontend session cookie. the cookie is sent by the browser
+// on any request, so a state-changing one also has to echo the `X-CSRF-Token` of the session,
+// which a cross-site page cannot read.
+#[derive(Debug)]
+pub struct LoginSessionUser {
+    pub session: LoginSession,
+}
+
+impl<S> FromRequestParts<S> for LoginSessionUser
+where
+    S: Send + Sync,
+    LoginSessionService<CacheRepoMchd>: FromRef<S>,
+{
+    type Rejection = WebError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let session_id = CookieJar::from_headers(&parts.headers)
+            .get(LOGIN_SESSION_COOKIE_NAME)
+            .and_then(|cookie| Uuid::parse_str(cookie.value()).ok())
+            .ok_or_else(|| WebError::Auth("login session is not found".to_string()))?;
+        let session = LoginSessionService::<CacheRepoMchd>::from_ref(state)
+            .get(session_id)
+            .await
+            .map_err(|e| WebError::InternalServerError(e.to_string()))?
+            .ok_or_else(|| WebError::Auth("login session is not found".to_string()))?;
+
+        if !parts.method.is_safe() {
+            let csrf_token = parts
+                .headers
+                .get(CSRF_TOKEN_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default();
+            if !session.verify_csrf(csrf_token) {
+                return Err(WebError::Forbidden("csrf token mismatch".to_string()));
+            }
+        }
+        Ok(Self { session })
+    }
+}
+
+async fn authenticate<S>(
+    parts: &Parts,
+    state: &S,
+    any_audience: bool,
+) -> Result<SAUClaims, WebError>
+where
+    JwtService<SAUJwtIssuer>: FromRef<S>,
+    TokenRevocationService<CacheRepoMchd, DatabaseRepoPg>: FromRef<S>,
+{
+    let jwt = bearer_token(&parts.headers)
+        .ok_or_else(|| WebError::Auth("bearer token is not found".to_string()))?;
+
+    let jwt_service = JwtService::<SAUJwtIssuer>::from_ref(state);
+    let claims = if any_audience {
+        jwt_service.verify_any_audience(jwt)
+    } else {
+        jwt_service.verify(jwt)
+    }
+    .map_err(|e| WebError::Auth(e.to_string()))?;
+
+    let revoked = TokenRevocationService::<CacheRepoMchd, DatabaseRepoPg>::from_ref(state)
+        .is_revoked(&claims)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    if revoked {
+        return Err(WebError::Auth("token is revoked".to_string()));
+    }
+    Ok(claims)
+}
+
+// for routers whose handlers do not need the caller itself,
+// e.g. `Router::new().route(..).route_layer(require_authentication(state))`
+pub fn require_authentication(state: AppState) -> FromExtractorLayer<AuthenticatedUser, AppState> {
+    from_extractor_with_state(state)
+}
+
+// `require_authentication` that also requires the permission `P`,
+// e.g. `.route_layer(require_permission::<ReadUsers>(state))`
+pub fn require_permission<P: Permission>(
+    state: AppState,
+) -> FromExtractorLayer<PermittedUser<P>, AppState> {
+    from_extractor_with_state(state)
+}
+
+// the user a protected route acts for, a deactivated user is refused with `403`
+// whatever tokens it still holds
+pub async fn active_user(
+    user_service: &UserService<DatabaseRepoPg>,
+    user_id: Uuid,
+) -> Result<SAUUser, WebError> {
+    user_service
+        .get_active_user(user_id)
+        .await
+        .map_err(|e| match e {
+            UserServiceError::UserInactive => WebError::UserInactive,
+            UserServiceError::UserNotFound => WebError::Auth(e.to_string()),
+            _ => WebError::InternalServerError(e.to_string()),
+        })
+}
+
+// what goes into an access token for the user, read again on every issuance
+pub async fn user_grants(
+    role_service: &RoleService<DatabaseRepoPg>,
+    organization_service: &OrganizationService<DatabaseRepoPg>,
+    user_id: Uuid,
+) -> Result<UserGrants, WebError> {
+    let mut grants = role_service
+        .grants(user_id)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    grants.orgs = organization_service
+        .org_claims(user_id)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    Ok(grants)
+}
+
+// the auth scheme is case-insensitive (RFC 7235)
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let (scheme, token) = headers.get(AUTHORIZATION)?.to_str().ok()?.split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+// `Authorization: Basic base64(id:secret)`, used by clients authenticating to us
+pub fn basic_credentials(headers: &HeaderMap) -> Option<(String, String)> {
+    let (scheme, credentials) = headers.get(AUTHORIZATION)?.to_str().ok()?.split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("basic") {
+        return None;
+    }
+    let credentials = BASE64_STANDARD.decode(credentials.trim()).ok()?;
+    let credentials = String::from_utf8(credentials).ok()?;
+    let (id, secret) = credentials.split_once(':')?;
+    Some((id.to_string(), secret.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    include!("auth_test.rs");
+}
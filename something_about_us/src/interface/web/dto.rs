@@ -1,5 +1,37 @@
+pub mod admin_user_response;
+pub mod authorize_query;
 pub mod callback_param;
+pub mod client_registration_request;
+pub mod client_response;
+pub mod device_authorization_request;
+pub mod device_authorization_response;
+pub mod device_confirmation_form;
+pub mod device_verification_query;
 pub mod error_response;
 pub mod idp_path;
+pub mod idp_provider_response;
+pub mod introspect_request;
+pub mod introspection_response;
 pub mod jwks_response;
 pub mod jwt_response;
+pub mod link_query;
+pub mod link_ticket_response;
+pub mod login_query;
+pub mod login_session_response;
+pub mod logout_request;
+pub mod oauth_error_response;
+pub mod oidc_token_request;
+pub mod oidc_token_response;
+pub mod openid_configuration;
+pub mod organization_request;
+pub mod organization_response;
+pub mod refresh_token_request;
+pub mod revoke_token_request;
+pub mod role_request;
+pub mod role_response;
+pub mod session_token_response;
+pub mod update_user_request;
+pub mod user_identity_response;
+pub mod user_list_query;
+pub mod user_response;
+pub mod userinfo_response;
@@ -0,0 +1,68 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    domain::user::{sau_user::SAUUser, user_identity::UserIdentity},
+    interface::web::dto::user_identity_response::UserIdentityResponse,
+};
+
+#[derive(Serialize, ToSchema)]
+pub struct AdminUserResponse {
+    pub id: Uuid,
+    #[schema(example = "octocat")]
+    pub username: Option<String>,
+    #[schema(example = "octocat@example.com")]
+    pub email: Option<String>,
+    pub email_verified: bool,
+    #[schema(example = "The Octocat")]
+    pub display_name: Option<String>,
+    #[schema(example = "https://avatars.githubusercontent.com/u/583231?v=4")]
+    pub avatar_url: Option<String>,
+    pub is_active: bool,
+    // RFC 3339
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub created_at: String,
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub updated_at: String,
+    // only filled in when a single user is requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identities: Option<Vec<UserIdentityResponse>>,
+}
+
+impl From<&SAUUser> for AdminUserResponse {
+    fn from(value: &SAUUser) -> Self {
+        Self {
+            id: value.id,
+            username: value
+                .username
+                .as_ref()
+                .map(|username| username.as_str().to_string()),
+            email: value.email.as_ref().map(|email| email.as_str().to_string()),
+            email_verified: value.email_verified,
+            display_name: value.display_name.clone(),
+            avatar_url: value.avatar_url.clone(),
+            is_active: value.is_active,
+            created_at: value.created_at.to_rfc3339(),
+            updated_at: value.updated_at.to_rfc3339(),
+            identities: None,
+        }
+    }
+}
+
+impl AdminUserResponse {
+    pub fn with_identities(user: &SAUUser, identities: &[UserIdentity]) -> Self {
+        Self {
+            identities: Some(identities.iter().map(UserIdentityResponse::from).collect()),
+            ..Self::from(user)
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AdminUserListResponse {
+    pub users: Vec<AdminUserResponse>,
+    // pass as `cursor` to get the next page, absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<Uuid>,
+}
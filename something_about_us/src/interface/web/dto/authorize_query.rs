@@ -0,0 +1,40 @@
+use sonic_rs::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::application::service::oidc_service::AuthorizeParams;
+
+// OpenID Connect authentication request, only the authorization code flow with PKCE
+#[derive(Deserialize, Debug, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuthorizeQuery {
+    #[param(example = "code")]
+    pub response_type: Option<String>,
+    pub client_id: String,
+    pub redirect_uri: String,
+    #[param(example = "openid email profile")]
+    pub scope: Option<String>,
+    pub state: Option<String>,
+    pub nonce: Option<String>,
+    // BASE64URL(SHA256(code_verifier))
+    pub code_challenge: Option<String>,
+    #[param(example = "S256")]
+    pub code_challenge_method: Option<String>,
+    // upstream provider to log in with, the first configured one when left out
+    #[param(example = "github")]
+    pub idp: Option<String>,
+}
+
+impl From<AuthorizeQuery> for AuthorizeParams {
+    fn from(value: AuthorizeQuery) -> Self {
+        Self {
+            response_type: value.response_type,
+            client_id: value.client_id,
+            redirect_uri: value.redirect_uri,
+            scope: value.scope,
+            state: value.state,
+            nonce: value.nonce,
+            code_challenge: value.code_challenge,
+            code_challenge_method: value.code_challenge_method,
+        }
+    }
+}
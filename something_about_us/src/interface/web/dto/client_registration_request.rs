@@ -0,0 +1,58 @@
+use jsonwebtoken::jwk::JwkSet;
+use sonic_rs::Deserialize;
+use utoipa::ToSchema;
+
+use crate::{
+    domain::oauth::oidc_client::{ClientRegistration, GrantType},
+    interface::web::dto::jwks_response::JwkSetDoc,
+};
+
+#[derive(Deserialize, ToSchema)]
+pub struct ClientRegistrationRequest {
+    #[schema(example = "web-app")]
+    pub client_id: String,
+    // false registers a public client (SPA, native app) that has no secret
+    pub confidential: bool,
+    #[serde(default)]
+    #[schema(example = json!(["https://app.example.com/callback"]))]
+    pub redirect_uris: Vec<String>,
+    #[serde(default)]
+    #[schema(example = json!(["openid", "email", "profile"]))]
+    pub allowed_scopes: Vec<String>,
+    #[schema(example = json!(["authorization_code", "refresh_token"]))]
+    pub allowed_grant_types: Vec<String>,
+    // seconds, `jwt.access_token_ttl` when left out and never longer than it
+    pub access_token_ttl: Option<u64>,
+    // seconds, `jwt.refresh_token_ttl` when left out
+    pub refresh_token_ttl: Option<u64>,
+    // `aud` of the issued access tokens, the client id when left out
+    #[schema(example = "orders-api")]
+    pub audience: Option<String>,
+    // public keys of the `private_key_jwt` client assertions
+    #[schema(value_type = Option<JwkSetDoc>)]
+    pub jwks: Option<JwkSet>,
+}
+
+impl TryFrom<ClientRegistrationRequest> for ClientRegistration {
+    type Error = String;
+
+    fn try_from(value: ClientRegistrationRequest) -> Result<Self, Self::Error> {
+        let allowed_grant_types = value
+            .allowed_grant_types
+            .iter()
+            .map(|grant_type| GrantType::try_from(grant_type.as_str()))
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string())?;
+        Ok(Self {
+            client_id: value.client_id,
+            confidential: value.confidential,
+            redirect_uris: value.redirect_uris,
+            allowed_scopes: value.allowed_scopes,
+            allowed_grant_types,
+            access_token_ttl: value.access_token_ttl,
+            refresh_token_ttl: value.refresh_token_ttl,
+            audience: value.audience,
+            jwks: value.jwks,
+        })
+    }
+}
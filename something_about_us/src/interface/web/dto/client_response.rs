@@ -0,0 +1,69 @@
+use jsonwebtoken::jwk::JwkSet;
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    domain::oauth::oidc_client::{GrantType, OidcClient},
+    interface::web::dto::jwks_response::JwkSetDoc,
+};
+
+// registered client without its secret hash
+#[derive(Serialize, ToSchema)]
+pub struct ClientResponse {
+    #[schema(example = "web-app")]
+    pub client_id: String,
+    pub confidential: bool,
+    pub redirect_uris: Vec<String>,
+    pub allowed_scopes: Vec<String>,
+    #[schema(example = json!(["authorization_code", "refresh_token"]))]
+    pub allowed_grant_types: Vec<String>,
+    pub access_token_ttl: Option<u64>,
+    pub refresh_token_ttl: Option<u64>,
+    #[schema(example = "web-app")]
+    pub audience: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<JwkSetDoc>)]
+    pub jwks: Option<JwkSet>,
+    pub is_active: bool,
+    // RFC 3339
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub created_at: String,
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub updated_at: String,
+}
+
+impl From<&OidcClient> for ClientResponse {
+    fn from(value: &OidcClient) -> Self {
+        Self {
+            client_id: value.client_id.clone(),
+            confidential: value.is_confidential(),
+            redirect_uris: value.redirect_uris.clone(),
+            allowed_scopes: value.allowed_scopes.clone(),
+            allowed_grant_types: value
+                .allowed_grant_types
+                .iter()
+                .map(|grant_type| GrantType::as_str(grant_type).to_string())
+                .collect(),
+            access_token_ttl: value.access_token_ttl,
+            refresh_token_ttl: value.refresh_token_ttl,
+            audience: value.audience.clone(),
+            jwks: value.jwks.clone(),
+            is_active: value.is_active,
+            created_at: value.created_at.to_rfc3339(),
+            updated_at: value.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ClientListResponse {
+    pub clients: Vec<ClientResponse>,
+}
+
+// the plain secret is only returned on registration and rotation
+#[derive(Serialize, ToSchema)]
+pub struct ClientSecretResponse {
+    pub client: ClientResponse,
+    #[schema(example = "J3tW0n4kq7bN0Yk8o3J1m2xQyVwZcR5sD6fG7hJ8kL9")]
+    pub client_secret: Option<String>,
+}
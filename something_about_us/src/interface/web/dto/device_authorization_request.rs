@@ -0,0 +1,13 @@
+use sonic_rs::Deserialize;
+use utoipa::ToSchema;
+
+// RFC 8628 device authorization request, sent as `application/x-www-form-urlencoded`.
+// public clients only send their `client_id`, confidential clients may use HTTP Basic instead.
+#[derive(Deserialize, ToSchema)]
+pub struct DeviceAuthorizationRequest {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    // all scopes allowed for the client when left out
+    #[schema(example = "openid email")]
+    pub scope: Option<String>,
+}
@@ -0,0 +1,18 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct DeviceAuthorizationResponse {
+    // polled at the token endpoint with the device code grant, never shown to the user
+    pub device_code: String,
+    #[schema(example = "BCDF-GHJK")]
+    pub user_code: String,
+    #[schema(example = "https://auth.example.com/api/v1/oidc/device")]
+    pub verification_uri: String,
+    // verification uri with the user code filled in, e.g. for a QR code
+    pub verification_uri_complete: String,
+    // lifetime of the device code in seconds
+    pub expires_in: u64,
+    // minimum seconds between two polls
+    pub interval: u64,
+}
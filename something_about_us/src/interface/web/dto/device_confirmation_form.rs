@@ -0,0 +1,14 @@
+use sonic_rs::Deserialize;
+use utoipa::ToSchema;
+
+// posted by the confirmation page of `/api/v1/oidc/device`, as `application/x-www-form-urlencoded`
+#[derive(Deserialize, ToSchema)]
+pub struct DeviceConfirmationForm {
+    #[schema(example = "BCDF-GHJK")]
+    pub user_code: String,
+    // upstream provider to log in with, the first configured one when left out
+    #[schema(example = "github")]
+    pub idp: Option<String>,
+    // the value of the confirmation cookie set along with the page
+    pub confirmation: String,
+}
@@ -0,0 +1,14 @@
+use sonic_rs::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, Debug, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeviceVerificationQuery {
+    // code shown by the device, a form asking for it is returned when left out
+    // and a page to confirm the device with otherwise
+    #[param(example = "BCDF-GHJK")]
+    pub user_code: Option<String>,
+    // upstream provider to log in with, the first configured one when left out
+    #[param(example = "github")]
+    pub idp: Option<String>,
+}
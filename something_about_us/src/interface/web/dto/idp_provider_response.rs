@@ -0,0 +1,20 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct IdpProviderList {
+    pub providers: Vec<IdpProvider>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct IdpProvider {
+    // name used as the `{idp}` path segment.
+    #[schema(example = "github-enterprise")]
+    pub name: String,
+
+    #[schema(example = "github")]
+    pub kind: String,
+
+    #[schema(example = "/api/v1/oauth/github-enterprise/login")]
+    pub login_url: String,
+}
@@ -0,0 +1,11 @@
+use sonic_rs::Deserialize;
+use utoipa::ToSchema;
+
+// RFC 7662 introspection request, sent as `application/x-www-form-urlencoded`
+#[derive(Deserialize, ToSchema)]
+pub struct IntrospectRequest {
+    pub token: String,
+    // only access tokens are introspected, the hint is accepted and ignored
+    #[schema(example = "access_token")]
+    pub token_type_hint: Option<String>,
+}
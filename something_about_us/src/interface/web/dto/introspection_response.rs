@@ -0,0 +1,118 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::{
+    oauth::sau_jwt::{OrgClaim, SAUClaims, ServiceClaims},
+    user::sau_user::SAUUser,
+};
+
+// RFC 7662 introspection response, every field but `active` is left out for inactive tokens
+#[derive(Serialize, ToSchema, Default)]
+pub struct IntrospectionResponse {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_type: Option<String>,
+    // the user id, or the client id for service tokens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    // only set for service tokens of the client credentials grant
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    // role names of the user token, as in its `roles` claim
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
+    // organizations of the user token, as in its `orgs` claim
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub orgs: Vec<IntrospectionOrg>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jti: Option<Uuid>,
+}
+
+#[derive(Serialize, ToSchema, Debug, PartialEq, Eq)]
+pub struct IntrospectionOrg {
+    pub id: Uuid,
+    pub slug: String,
+    #[schema(example = "member")]
+    pub role: String,
+}
+
+impl From<OrgClaim> for IntrospectionOrg {
+    fn from(value: OrgClaim) -> Self {
+        Self {
+            id: value.id,
+            slug: value.slug,
+            role: value.role.as_str().to_string(),
+        }
+    }
+}
+
+impl IntrospectionResponse {
+    pub fn inactive() -> Self {
+        Self::default()
+    }
+
+    pub fn active(claims: SAUClaims, user: &SAUUser) -> Self {
+        Self {
+            active: true,
+            token_type: Some("Bearer".to_string()),
+            sub: Some(claims.sub.to_string()),
+            username: user
+                .username
+                .as_ref()
+                .map(|username| username.as_str().to_string()),
+            client_id: None,
+            scope: claims.scope,
+            roles: claims.roles,
+            orgs: claims
+                .orgs
+                .into_iter()
+                .map(IntrospectionOrg::from)
+                .collect(),
+            aud: Some(claims.aud),
+            iss: Some(claims.iss),
+            exp: Some(claims.exp),
+            iat: Some(claims.iat),
+            nbf: Some(claims.nbf),
+            jti: Some(claims.jti),
+        }
+    }
+
+    pub fn active_service(claims: ServiceClaims) -> Self {
+        Self {
+            active: true,
+            token_type: Some("Bearer".to_string()),
+            client_id: Some(claims.sub.clone()),
+            sub: Some(claims.sub),
+            username: None,
+            scope: Some(claims.scope),
+            roles: vec![],
+            orgs: vec![],
+            aud: Some(claims.aud),
+            iss: Some(claims.iss),
+            exp: Some(claims.exp),
+            iat: Some(claims.iat),
+            nbf: Some(claims.nbf),
+            jti: Some(claims.jti),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("introspection_response_test.rs");
+}
@@ -0,0 +1,81 @@
+use super::{IntrospectionOrg, IntrospectionResponse};
+use crate::domain::{
+    oauth::sau_jwt::{OrgClaim, SAUClaims},
+    organization::sau_organization::MemberRole,
+    user::sau_user::{SAUUser, Username},
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+fn user() -> SAUUser {
+    SAUUser {
+        id: Uuid::now_v7(),
+        username: Some(Username::new("alice".to_string()).unwrap()),
+        email: None,
+        email_verified: false,
+        display_name: None,
+        avatar_url: None,
+        is_active: true,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn claims(user: &SAUUser) -> SAUClaims {
+    SAUClaims {
+        aud: "sau".to_string(),
+        iss: "https://auth.example.com".to_string(),
+        sub: user.id,
+        exp: 0,
+        jti: Uuid::now_v7(),
+        iat: 0,
+        nbf: 0,
+        roles: vec![],
+        scope: None,
+        orgs: vec![],
+    }
+}
+
+#[test]
+fn test_active_carries_scope_roles_and_orgs() {
+    let user = user();
+    let org_id = Uuid::now_v7();
+    let claims = SAUClaims {
+        roles: vec!["support".to_string()],
+        scope: Some("users:read roles:read".to_string()),
+        orgs: vec![OrgClaim {
+            id: org_id,
+            slug: "acme".to_string(),
+            role: MemberRole::Admin,
+        }],
+        ..claims(&user)
+    };
+
+    let response = IntrospectionResponse::active(claims, &user);
+    assert!(response.active);
+    assert_eq!(response.username.as_deref(), Some("alice"));
+    assert_eq!(response.scope.as_deref(), Some("users:read roles:read"));
+    assert_eq!(response.roles, vec!["support".to_string()]);
+    assert_eq!(
+        response.orgs,
+        vec![IntrospectionOrg {
+            id: org_id,
+            slug: "acme".to_string(),
+            role: "admin".to_string(),
+        }]
+    );
+
+    let json = sonic_rs::to_string(&response).unwrap();
+    assert!(json.contains(r#""roles":["support"]"#));
+    assert!(json.contains(r#""slug":"acme""#));
+}
+
+#[test]
+fn test_active_without_grants_leaves_them_out() {
+    let user = user();
+
+    let json = sonic_rs::to_string(&IntrospectionResponse::active(claims(&user), &user)).unwrap();
+    assert!(!json.contains("scope"));
+    assert!(!json.contains("roles"));
+    assert!(!json.contains("orgs"));
+}
@@ -7,7 +7,7 @@ pub struct JwkSetDoc {
 }
 
 // JSON Web Key (JWK) document model for documentation purposes.
-// represents Ed25519 OKP public keys exposed by this service.
+// Ed25519 keys carry `crv`/`x`, P-256 keys `crv`/`x`/`y` and RSA keys `n`/`e`.
 #[derive(ToSchema)]
 pub struct JwkDoc {
     #[schema(example = "OKP")]
@@ -16,10 +16,20 @@ pub struct JwkDoc {
     #[schema(example = "Ed25519")]
     pub crv: Option<String>,
 
-    // the public key, base64url encoded.
+    // the public key or its x coordinate, base64url encoded.
     #[schema(example = "VGhpc0lzTm90QVNlY3JldEtleQ")]
     pub x: Option<String>,
 
+    // y coordinate of a P-256 key, base64url encoded.
+    pub y: Option<String>,
+
+    // modulus of an RSA key, base64url encoded.
+    pub n: Option<String>,
+
+    // public exponent of an RSA key, base64url encoded.
+    #[schema(example = "AQAB")]
+    pub e: Option<String>,
+
     // intended use for the key. Commonly "sig" (signature).
     #[schema(example = "sig")]
     pub r#use: Option<String>,
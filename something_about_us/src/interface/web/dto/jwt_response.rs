@@ -4,4 +4,6 @@ use utoipa::ToSchema;
 #[derive(Serialize, ToSchema)]
 pub struct Token {
     pub access_token: String,
+    // single use, exchange it at `/api/v1/token/refresh` for a new pair.
+    pub refresh_token: String,
 }
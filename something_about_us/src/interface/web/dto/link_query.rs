@@ -0,0 +1,9 @@
+use sonic_rs::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, Debug, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LinkQuery {
+    // one-time ticket from `POST /api/v1/oauth/{idp}/link`
+    pub ticket: String,
+}
@@ -0,0 +1,11 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct LinkTicketResponse {
+    // navigate the browser here, it redirects to the IdP
+    #[schema(example = "https://auth.example.com/api/v1/oauth/github/link?ticket=...")]
+    pub link_url: String,
+    // seconds until the ticket expires
+    pub expires_in: u64,
+}
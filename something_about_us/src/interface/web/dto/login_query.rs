@@ -0,0 +1,11 @@
+use sonic_rs::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, Debug, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LoginQuery {
+    // frontend url on one of `security.login_redirect.allowed_origins` to send the tokens to,
+    // the callback answers with JSON when left out
+    #[param(example = "https://app.example.com/dashboard")]
+    pub return_to: Option<String>,
+}
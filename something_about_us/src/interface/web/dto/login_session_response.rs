@@ -0,0 +1,12 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Serialize, ToSchema)]
+pub struct LoginSessionResponse {
+    pub user_id: Uuid,
+    // send it back as `X-CSRF-Token` on every POST to `/api/v1/session`
+    pub csrf_token: String,
+    // seconds until the session ends
+    pub expires_in: u64,
+}
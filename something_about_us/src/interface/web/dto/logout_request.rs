@@ -0,0 +1,9 @@
+use sonic_rs::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, ToSchema)]
+pub struct LogoutRequest {
+    // the refresh token family of this session is revoked together with the access token
+    #[schema(example = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk")]
+    pub refresh_token: Option<String>,
+}
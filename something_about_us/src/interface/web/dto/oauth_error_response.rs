@@ -0,0 +1,11 @@
+use sonic_rs::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// RFC 6749 error body, what OAuth and OpenID client libraries expect from a token endpoint
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
+pub struct OAuthErrorResponse {
+    #[schema(example = "invalid_grant")]
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_description: Option<String>,
+}
@@ -0,0 +1,26 @@
+use sonic_rs::Deserialize;
+use utoipa::ToSchema;
+
+// token request of the authorization code, refresh token, client credentials and device code grants,
+// sent as `application/x-www-form-urlencoded`.
+// confidential clients authenticate with HTTP Basic or `client_id` + `client_secret` in the form,
+// the client credentials grant also accepts a `private_key_jwt` client assertion.
+#[derive(Deserialize, ToSchema)]
+pub struct OidcTokenRequest {
+    #[schema(example = "authorization_code")]
+    pub grant_type: String,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub code_verifier: Option<String>,
+    pub refresh_token: Option<String>,
+    // device code grant, polled until the user approved the device
+    pub device_code: Option<String>,
+    // client credentials grant, all scopes allowed for the client when left out
+    #[schema(example = "orders:read")]
+    pub scope: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    #[schema(example = "urn:ietf:params:oauth:client-assertion-type:jwt-bearer")]
+    pub client_assertion_type: Option<String>,
+    pub client_assertion: Option<String>,
+}
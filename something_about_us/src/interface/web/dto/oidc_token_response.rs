@@ -0,0 +1,20 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct OidcTokenResponse {
+    pub access_token: String,
+    #[schema(example = "Bearer")]
+    pub token_type: String,
+    // lifetime of the access token in seconds
+    pub expires_in: u64,
+    // single use, bound to the client, exchange it with the `refresh_token` grant.
+    // only issued to clients allowed that grant, never by the client credentials grant
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    // only returned by the authorization code grant
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+    #[schema(example = "openid email")]
+    pub scope: String,
+}
@@ -0,0 +1,86 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::oauth::{
+    authorization_code::OPENID_SCOPE,
+    client_assertion::ASSERTION_ALGORITHMS,
+    device_authorization::DEVICE_CODE_GRANT_TYPE,
+    id_token::{EMAIL_SCOPE, PROFILE_SCOPE},
+};
+
+// OpenID Provider Metadata served at `/.well-known/openid-configuration`
+#[derive(Serialize, ToSchema)]
+pub struct OpenIdConfiguration {
+    #[schema(example = "https://auth.example.com")]
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub userinfo_endpoint: String,
+    // RFC 8628 device authorization endpoint
+    pub device_authorization_endpoint: String,
+    pub jwks_uri: String,
+    pub response_types_supported: Vec<String>,
+    pub grant_types_supported: Vec<String>,
+    pub subject_types_supported: Vec<String>,
+    // algorithms of the keys in the JWKS
+    pub id_token_signing_alg_values_supported: Vec<String>,
+    pub scopes_supported: Vec<String>,
+    pub token_endpoint_auth_methods_supported: Vec<String>,
+    // algorithms accepted for `private_key_jwt` client assertions
+    pub token_endpoint_auth_signing_alg_values_supported: Vec<String>,
+    pub code_challenge_methods_supported: Vec<String>,
+    pub claims_supported: Vec<String>,
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+impl OpenIdConfiguration {
+    pub fn new(issuer: &str, signing_algorithms: Vec<String>) -> Self {
+        let base = issuer.trim_end_matches('/');
+        Self {
+            issuer: issuer.to_string(),
+            authorization_endpoint: format!("{}/api/v1/oidc/authorize", base),
+            token_endpoint: format!("{}/api/v1/oidc/token", base),
+            userinfo_endpoint: format!("{}/api/v1/oidc/userinfo", base),
+            device_authorization_endpoint: format!("{}/api/v1/oidc/device/code", base),
+            jwks_uri: format!("{}/api/v1/jwks", base),
+            response_types_supported: strings(&["code"]),
+            grant_types_supported: strings(&[
+                "authorization_code",
+                "refresh_token",
+                "client_credentials",
+                DEVICE_CODE_GRANT_TYPE,
+            ]),
+            subject_types_supported: strings(&["public"]),
+            id_token_signing_alg_values_supported: signing_algorithms,
+            scopes_supported: strings(&[OPENID_SCOPE, EMAIL_SCOPE, PROFILE_SCOPE]),
+            token_endpoint_auth_methods_supported: strings(&[
+                "client_secret_basic",
+                "client_secret_post",
+                "private_key_jwt",
+                "none",
+            ]),
+            token_endpoint_auth_signing_alg_values_supported: ASSERTION_ALGORITHMS
+                .iter()
+                .map(|algorithm| format!("{:?}", algorithm))
+                .collect(),
+            code_challenge_methods_supported: strings(&["S256"]),
+            claims_supported: strings(&[
+                "iss",
+                "sub",
+                "aud",
+                "exp",
+                "iat",
+                "auth_time",
+                "nonce",
+                "email",
+                "email_verified",
+                "preferred_username",
+                "name",
+                "picture",
+            ]),
+        }
+    }
+}
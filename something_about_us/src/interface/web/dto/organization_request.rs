@@ -0,0 +1,54 @@
+use sonic_rs::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateOrganizationRequest {
+    // lowercase letters, digits and `-`, up to 50 bytes, never changes
+    #[schema(example = "acme")]
+    pub slug: String,
+    #[schema(example = "Acme Inc.")]
+    pub name: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateOrganizationRequest {
+    #[schema(example = "Acme Corporation")]
+    pub name: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateMemberRoleRequest {
+    // `owner`, `admin` or `member`
+    #[schema(example = "admin")]
+    pub role: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateTeamRequest {
+    // unique in the organization, same rules as the organization slug
+    #[schema(example = "backend")]
+    pub slug: String,
+    #[schema(example = "Backend")]
+    pub name: String,
+}
+
+// either `email`, or `idp` with `username`
+#[derive(Deserialize, ToSchema)]
+pub struct CreateInvitationRequest {
+    // accepted by the user with this verified email
+    #[schema(example = "octocat@example.com")]
+    pub email: Option<String>,
+    #[schema(example = "github")]
+    pub idp: Option<String>,
+    // accepted by the user with this username and an identity of `idp`
+    #[schema(example = "octocat")]
+    pub username: Option<String>,
+    // `member` when left out
+    #[schema(example = "member")]
+    pub role: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct AcceptInvitationRequest {
+    pub token: String,
+}
@@ -0,0 +1,155 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::organization::{
+    invitation::{Invitation, InvitationTarget},
+    sau_organization::{MemberRole, Organization, OrganizationMember, Team, TeamMember},
+};
+
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationResponse {
+    pub id: Uuid,
+    #[schema(example = "acme")]
+    pub slug: String,
+    #[schema(example = "Acme Inc.")]
+    pub name: String,
+    // role of the caller in the organization
+    #[schema(example = "owner")]
+    pub role: String,
+    // RFC 3339
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub created_at: String,
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub updated_at: String,
+}
+
+impl OrganizationResponse {
+    pub fn new(organization: &Organization, role: MemberRole) -> Self {
+        Self {
+            id: organization.id,
+            slug: organization.slug.clone(),
+            name: organization.name.clone(),
+            role: role.as_str().to_string(),
+            created_at: organization.created_at.to_rfc3339(),
+            updated_at: organization.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationListResponse {
+    pub organizations: Vec<OrganizationResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MemberResponse {
+    pub user_id: Uuid,
+    #[schema(example = "member")]
+    pub role: String,
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub created_at: String,
+}
+
+impl From<&OrganizationMember> for MemberResponse {
+    fn from(value: &OrganizationMember) -> Self {
+        Self {
+            user_id: value.user_id,
+            role: value.role.as_str().to_string(),
+            created_at: value.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MemberListResponse {
+    pub members: Vec<MemberResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TeamResponse {
+    pub id: Uuid,
+    #[schema(example = "backend")]
+    pub slug: String,
+    #[schema(example = "Backend")]
+    pub name: String,
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub created_at: String,
+    // only filled in when a single team is requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<Uuid>>,
+}
+
+impl From<&Team> for TeamResponse {
+    fn from(value: &Team) -> Self {
+        Self {
+            id: value.id,
+            slug: value.slug.clone(),
+            name: value.name.clone(),
+            created_at: value.created_at.to_rfc3339(),
+            members: None,
+        }
+    }
+}
+
+impl TeamResponse {
+    pub fn with_members(team: &Team, members: &[TeamMember]) -> Self {
+        Self {
+            members: Some(members.iter().map(|member| member.user_id).collect()),
+            ..Self::from(team)
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TeamListResponse {
+    pub teams: Vec<TeamResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct InvitationResponse {
+    pub id: Uuid,
+    #[schema(example = "octocat@example.com")]
+    pub email: Option<String>,
+    #[schema(example = "github")]
+    pub idp: Option<String>,
+    #[schema(example = "octocat")]
+    pub username: Option<String>,
+    #[schema(example = "member")]
+    pub role: String,
+    pub invited_by: Uuid,
+    #[schema(example = "2026-10-25T09:00:00+00:00")]
+    pub expires_at: String,
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub created_at: String,
+    // only returned when the invitation is created, hand it to the invitee
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl From<&Invitation> for InvitationResponse {
+    fn from(value: &Invitation) -> Self {
+        let (email, idp, username) = match &value.target {
+            InvitationTarget::Email(email) => (Some(email.as_str().to_string()), None, None),
+            InvitationTarget::IdpUsername { idp, username } => {
+                (None, Some(idp.as_str().to_string()), Some(username.clone()))
+            }
+        };
+        Self {
+            id: value.id,
+            email,
+            idp,
+            username,
+            role: value.role.as_str().to_string(),
+            invited_by: value.invited_by,
+            expires_at: value.expires_at.to_rfc3339(),
+            created_at: value.created_at.to_rfc3339(),
+            token: None,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct InvitationListResponse {
+    pub invitations: Vec<InvitationResponse>,
+}
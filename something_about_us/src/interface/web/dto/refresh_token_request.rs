@@ -0,0 +1,8 @@
+use sonic_rs::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, ToSchema)]
+pub struct RefreshTokenRequest {
+    #[schema(example = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk")]
+    pub refresh_token: String,
+}
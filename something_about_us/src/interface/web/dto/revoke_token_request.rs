@@ -0,0 +1,11 @@
+use sonic_rs::Deserialize;
+use utoipa::ToSchema;
+
+// RFC 7009 revocation request, sent as `application/x-www-form-urlencoded`
+#[derive(Deserialize, ToSchema)]
+pub struct RevokeTokenRequest {
+    pub token: String,
+    // `access_token` or `refresh_token`, only decides which kind is looked up first
+    #[schema(example = "refresh_token")]
+    pub token_type_hint: Option<String>,
+}
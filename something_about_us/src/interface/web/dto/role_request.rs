@@ -0,0 +1,25 @@
+use sonic_rs::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateRoleRequest {
+    // lowercase letters, digits, `-` and `_`, up to 50 bytes
+    #[schema(example = "support")]
+    pub name: String,
+    #[schema(example = "First line support")]
+    pub description: Option<String>,
+    // `admin` grants every permission and the admin api
+    #[serde(default)]
+    #[schema(example = json!(["users:read"]))]
+    pub permissions: Vec<String>,
+}
+
+// replaces the description and permissions of the role
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateRoleRequest {
+    #[schema(example = "First line support")]
+    pub description: Option<String>,
+    #[serde(default)]
+    #[schema(example = json!(["users:read", "users:write"]))]
+    pub permissions: Vec<String>,
+}
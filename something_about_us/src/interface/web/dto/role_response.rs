@@ -0,0 +1,33 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::user::role::Role;
+
+#[derive(Serialize, ToSchema)]
+pub struct RoleResponse {
+    #[schema(example = "support")]
+    pub name: String,
+    #[schema(example = "First line support")]
+    pub description: Option<String>,
+    #[schema(example = json!(["users:read"]))]
+    pub permissions: Vec<String>,
+    // RFC 3339
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub created_at: String,
+}
+
+impl From<&Role> for RoleResponse {
+    fn from(value: &Role) -> Self {
+        Self {
+            name: value.name.clone(),
+            description: value.description.clone(),
+            permissions: value.permissions.clone(),
+            created_at: value.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RoleListResponse {
+    pub roles: Vec<RoleResponse>,
+}
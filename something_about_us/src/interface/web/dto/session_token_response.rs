@@ -0,0 +1,10 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct SessionTokenResponse {
+    pub access_token: String,
+    // always `Bearer`
+    pub token_type: String,
+    pub expires_in: u64,
+}
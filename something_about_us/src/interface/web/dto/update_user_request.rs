@@ -0,0 +1,13 @@
+use sonic_rs::Deserialize;
+use utoipa::ToSchema;
+
+// fields left out are kept as they are
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateUserRequest {
+    // 1 to 50 bytes, unique among users
+    #[schema(example = "octocat")]
+    pub username: Option<String>,
+    // unique among users, a changed email is no longer verified
+    #[schema(example = "octocat@example.com")]
+    pub email: Option<String>,
+}
@@ -0,0 +1,37 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::user::user_identity::UserIdentity;
+
+#[derive(Serialize, ToSchema)]
+pub struct UserIdentityResponse {
+    pub id: Uuid,
+    #[schema(example = "github")]
+    pub idp: String,
+    // id of the account at the idp
+    #[schema(example = "583231")]
+    pub idp_uid: String,
+    // login at the idp as of the last sign-in
+    #[schema(example = "octocat")]
+    pub idp_username: Option<String>,
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub created_at: String,
+}
+
+impl From<&UserIdentity> for UserIdentityResponse {
+    fn from(value: &UserIdentity) -> Self {
+        Self {
+            id: value.id,
+            idp: value.idp.as_str().to_string(),
+            idp_uid: value.idp_uid.clone(),
+            idp_username: value.idp_username.clone(),
+            created_at: value.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UserIdentityListResponse {
+    pub identities: Vec<UserIdentityResponse>,
+}
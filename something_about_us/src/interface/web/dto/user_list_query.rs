@@ -0,0 +1,26 @@
+use sonic_rs::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Deserialize, Debug, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UserListQuery {
+    // `next_cursor` of the previous page
+    pub cursor: Option<Uuid>,
+    // 1 to 100, 50 when left out
+    #[param(example = 50)]
+    pub limit: Option<u64>,
+    // users with an identity of this idp
+    #[param(example = "github")]
+    pub idp: Option<String>,
+    pub is_active: Option<bool>,
+    // RFC 3339, inclusive
+    #[param(example = "2026-10-01T00:00:00Z")]
+    pub created_from: Option<String>,
+    // RFC 3339, exclusive
+    #[param(example = "2026-11-01T00:00:00Z")]
+    pub created_to: Option<String>,
+    // case-insensitive prefix of the username or the email
+    #[param(example = "octo")]
+    pub q: Option<String>,
+}
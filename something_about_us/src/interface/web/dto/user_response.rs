@@ -0,0 +1,42 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::user::sau_user::SAUUser;
+
+#[derive(Serialize, ToSchema)]
+pub struct UserResponse {
+    pub id: Uuid,
+    #[schema(example = "octocat")]
+    pub username: Option<String>,
+    #[schema(example = "octocat@example.com")]
+    pub email: Option<String>,
+    pub email_verified: bool,
+    #[schema(example = "The Octocat")]
+    pub display_name: Option<String>,
+    #[schema(example = "https://avatars.githubusercontent.com/u/583231?v=4")]
+    pub avatar_url: Option<String>,
+    // RFC 3339
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub created_at: String,
+    #[schema(example = "2026-10-18T09:00:00+00:00")]
+    pub updated_at: String,
+}
+
+impl From<&SAUUser> for UserResponse {
+    fn from(value: &SAUUser) -> Self {
+        Self {
+            id: value.id,
+            username: value
+                .username
+                .as_ref()
+                .map(|username| username.as_str().to_string()),
+            email: value.email.as_ref().map(|email| email.as_str().to_string()),
+            email_verified: value.email_verified,
+            display_name: value.display_name.clone(),
+            avatar_url: value.avatar_url.clone(),
+            created_at: value.created_at.to_rfc3339(),
+            updated_at: value.updated_at.to_rfc3339(),
+        }
+    }
+}
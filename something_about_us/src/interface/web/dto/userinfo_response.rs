@@ -0,0 +1,37 @@
+use sonic_rs::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::user::sau_user::SAUUser;
+
+// OpenID Connect UserInfo response, claims without a value are left out
+#[derive(Serialize, ToSchema)]
+pub struct UserInfoResponse {
+    pub sub: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub picture: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_verified: Option<bool>,
+}
+
+impl From<&SAUUser> for UserInfoResponse {
+    fn from(value: &SAUUser) -> Self {
+        Self {
+            sub: value.id,
+            preferred_username: value
+                .username
+                .as_ref()
+                .map(|username| username.as_str().to_string()),
+            name: value.display_name.clone(),
+            picture: value.avatar_url.clone(),
+            email: value.email.as_ref().map(|email| email.as_str().to_string()),
+            email_verified: value.email.as_ref().map(|_| value.email_verified),
+        }
+    }
+}
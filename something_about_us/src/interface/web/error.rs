@@ -1,12 +1,14 @@
 use axum::{
-    extract::rejection::{JsonRejection, PathRejection, QueryRejection},
-    http::StatusCode,
+    extract::rejection::{FormRejection, JsonRejection, PathRejection, QueryRejection},
+    http::{header::CACHE_CONTROL, StatusCode},
     response::IntoResponse,
     Json,
 };
 use tracing::{info, warn};
 
-use crate::interface::web::dto::error_response::ErrorResponse;
+use crate::interface::web::dto::{
+    error_response::ErrorResponse, oauth_error_response::OAuthErrorResponse,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum WebError {
@@ -16,14 +18,48 @@ pub enum WebError {
     Query(#[from] QueryRejection),
     #[error("json extraction error")]
     Json(#[from] JsonRejection),
+    #[error("form extraction error")]
+    Form(#[from] FormRejection),
+
+    #[error("invalid request")]
+    BadRequest(String),
 
     #[error("auth error")]
     Auth(String),
 
+    #[error("forbidden")]
+    Forbidden(String),
+
+    #[error("not found")]
+    NotFound(String),
+
+    #[error("conflict")]
+    Conflict(String),
+
+    #[error("too many requests")]
+    TooManyRequests(String),
+
+    // the user was deactivated, it can not sign in or get tokens until reactivated
+    #[error("user is not active")]
+    UserInactive,
+
+    // RFC 6749 errors of the OpenID provider endpoints
+    #[error("oauth error")]
+    OAuth(OAuthErrorResponse),
+
     #[error("internal server error")]
     InternalServerError(String),
 }
 
+impl WebError {
+    pub fn oauth(error: &str, description: impl ToString) -> Self {
+        WebError::OAuth(OAuthErrorResponse {
+            error: error.to_string(),
+            error_description: Some(description.to_string()),
+        })
+    }
+}
+
 impl IntoResponse for WebError {
     fn into_response(self) -> axum::response::Response {
         match &self {
@@ -60,6 +96,28 @@ impl IntoResponse for WebError {
                     }),
                 )
             }
+            WebError::Form(inner_error) => {
+                info!("{:?} : {:?}", self, inner_error);
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse {
+                        code: "INVALID INPUT".to_string(),
+                        message: self.to_string(),
+                        details: Some(inner_error.to_string()),
+                    }),
+                )
+            }
+            WebError::BadRequest(inner_error) => {
+                info!("{:?} : {:?}", self, inner_error);
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse {
+                        code: "INVALID INPUT".to_string(),
+                        message: self.to_string(),
+                        details: Some(inner_error.to_string()),
+                    }),
+                )
+            }
             WebError::Auth(inner_error) => {
                 info!("{:?} : {:?}", self, inner_error);
                 (
@@ -71,6 +129,71 @@ impl IntoResponse for WebError {
                     }),
                 )
             }
+            WebError::Forbidden(inner_error) => {
+                info!("{:?} : {:?}", self, inner_error);
+                (
+                    StatusCode::FORBIDDEN,
+                    Json(ErrorResponse {
+                        code: "FORBIDDEN".to_string(),
+                        message: self.to_string(),
+                        details: Some(inner_error.to_string()),
+                    }),
+                )
+            }
+            WebError::NotFound(inner_error) => {
+                info!("{:?} : {:?}", self, inner_error);
+                (
+                    StatusCode::NOT_FOUND,
+                    Json(ErrorResponse {
+                        code: "NOT FOUND".to_string(),
+                        message: self.to_string(),
+                        details: Some(inner_error.to_string()),
+                    }),
+                )
+            }
+            WebError::Conflict(inner_error) => {
+                info!("{:?} : {:?}", self, inner_error);
+                (
+                    StatusCode::CONFLICT,
+                    Json(ErrorResponse {
+                        code: "CONFLICT".to_string(),
+                        message: self.to_string(),
+                        details: Some(inner_error.to_string()),
+                    }),
+                )
+            }
+            WebError::TooManyRequests(inner_error) => {
+                info!("{:?} : {:?}", self, inner_error);
+                (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    Json(ErrorResponse {
+                        code: "TOO MANY REQUESTS".to_string(),
+                        message: self.to_string(),
+                        details: Some(inner_error.to_string()),
+                    }),
+                )
+            }
+            WebError::UserInactive => {
+                info!("{:?}", self);
+                (
+                    StatusCode::FORBIDDEN,
+                    Json(ErrorResponse {
+                        code: "USER INACTIVE".to_string(),
+                        message: self.to_string(),
+                        details: None,
+                    }),
+                )
+            }
+            WebError::OAuth(inner_error) => {
+                info!("{:?} : {:?}", self, inner_error);
+                // a client that failed to authenticate gets 401, every other error 400
+                let status = if inner_error.error == "invalid_client" {
+                    StatusCode::UNAUTHORIZED
+                } else {
+                    StatusCode::BAD_REQUEST
+                };
+                return (status, [(CACHE_CONTROL, "no-store")], Json(inner_error)).into_response();
+            }
             WebError::InternalServerError(inner_error) => {
                 warn!("{:?} : {:?}", self, inner_error);
                 (
@@ -1,14 +1,40 @@
 #![allow(dead_code)]
 
-use utoipa::OpenApi;
+use utoipa::{
+    openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
 
+use crate::domain::oauth::login_session::LOGIN_SESSION_COOKIE_NAME;
 use crate::interface::web::{
     dto::error_response::ErrorResponse,
     v1::{
+        admin::{
+            clients::gen_openapi_admin_clients, roles::gen_openapi_admin_roles,
+            users::gen_openapi_admin_users,
+        },
         health::gen_openapi_health,
         jwks::gen_openapi_jwks,
-        oauth::{callback::gen_openapi_callback, login::gen_openapi_login},
+        oauth::{
+            callback::gen_openapi_callback, link::gen_openapi_link, login::gen_openapi_login,
+            logout::gen_openapi_logout, providers::gen_openapi_providers,
+        },
+        oidc::{
+            authorize::gen_openapi_authorize, device::gen_openapi_device,
+            token::gen_openapi_oidc_token, userinfo::gen_openapi_userinfo,
+        },
+        orgs::{
+            invitations::gen_openapi_invitations, members::gen_openapi_organization_members,
+            organizations::gen_openapi_organizations, teams::gen_openapi_teams,
+        },
+        session::gen_openapi_session,
+        token::{
+            introspect::gen_openapi_introspect, refresh::gen_openapi_refresh,
+            revoke::gen_openapi_revoke,
+        },
+        users::{identities::gen_openapi_user_identities, me::gen_openapi_users_me},
     },
+    well_known::gen_openapi_well_known,
 };
 
 #[derive(OpenApi)]
@@ -23,21 +49,76 @@ use crate::interface::web::{
             ErrorResponse,
         ),
     ),
+    modifiers(&SecuritySchemes),
     tags(
         (name = "Heartbeat", description = "Health check endpoints"),
         (name = "OAuth", description = "OAuth 2.0 login flow"),
-        (name = "JWKS", description = "JSON Web Key Set endpoints")
+        (name = "OIDC", description = "OpenID Connect provider for relying-party applications"),
+        (name = "Token", description = "Access token renewal, revocation and introspection"),
+        (name = "Users", description = "Profile of the signed-in user"),
+        (name = "Organizations", description = "Organizations, their teams, members and invitations"),
+        (name = "Session", description = "Backend-for-frontend sessions held in an HttpOnly cookie"),
+        (name = "JWKS", description = "JSON Web Key Set endpoints"),
+        (name = "Admin", description = "Administration of the OAuth client registry, of users and of their roles")
     )
 )]
 pub struct ApiDoc;
 
+// SAU access tokens sent as `Authorization: Bearer <jwt>`,
+// the HTTP Basic credentials of introspection clients and the backend-for-frontend session cookie
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "introspection_basic",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Basic).build()),
+        );
+        components.add_security_scheme(
+            "session_cookie",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new(LOGIN_SESSION_COOKIE_NAME))),
+        );
+    }
+}
+
 pub fn gen_openapi() -> utoipa::openapi::OpenApi {
     let mut docs = ApiDoc::openapi();
 
     docs.merge(gen_openapi_health());
     docs.merge(gen_openapi_callback());
     docs.merge(gen_openapi_login());
+    docs.merge(gen_openapi_link());
+    docs.merge(gen_openapi_logout());
+    docs.merge(gen_openapi_providers());
+    docs.merge(gen_openapi_refresh());
+    docs.merge(gen_openapi_revoke());
+    docs.merge(gen_openapi_introspect());
+    docs.merge(gen_openapi_users_me());
+    docs.merge(gen_openapi_user_identities());
+    docs.merge(gen_openapi_organizations());
+    docs.merge(gen_openapi_organization_members());
+    docs.merge(gen_openapi_teams());
+    docs.merge(gen_openapi_invitations());
+    docs.merge(gen_openapi_session());
     docs.merge(gen_openapi_jwks());
+    docs.merge(gen_openapi_authorize());
+    docs.merge(gen_openapi_oidc_token());
+    docs.merge(gen_openapi_device());
+    docs.merge(gen_openapi_userinfo());
+    docs.merge(gen_openapi_well_known());
+    docs.merge(gen_openapi_admin_clients());
+    docs.merge(gen_openapi_admin_roles());
+    docs.merge(gen_openapi_admin_users());
 
     docs
 }
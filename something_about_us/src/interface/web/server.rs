@@ -3,7 +3,7 @@ use axum::Router;
 use tokio::net::TcpListener;
 use tracing::info;
 
-use crate::interface::web::{openapi::gen_openapi, state::AppState, v1};
+use crate::interface::web::{openapi::gen_openapi, state::AppState, v1, well_known};
 use utoipa_swagger_ui::SwaggerUi;
 
 pub async fn server_run(addr: String, port: u16, state: AppState) -> Result<()> {
@@ -17,13 +17,14 @@ pub async fn server_run(addr: String, port: u16, state: AppState) -> Result<()>
         "server is running; Swagger UI: http://{}/swagger-ui",
         addr_str
     );
-    Ok(axum::serve(listener, router.into_make_service())
+    axum::serve(listener, router.into_make_service())
         .await
-        .context("fail to run server")?)
+        .context("fail to run server")
 }
 
 pub async fn make_router(state: AppState) -> Router {
     Router::new()
-        .nest("/api", v1::router(state).await)
+        .nest("/api", v1::router(state.clone()).await)
+        .nest("/.well-known", well_known::router(state).await)
         .merge(SwaggerUi::new("/swagger-ui").url("/api-doc/openapi.json", gen_openapi()))
 }
@@ -1,17 +1,29 @@
 use crate::{
     application::service::{
-        jwt_service::JwtService, oauth_service::OAuthService, user_service::UserService,
+        jwt_service::JwtService, login_session_service::LoginSessionService,
+        oauth_service::OAuthService, oidc_client_service::OidcClientService,
+        oidc_service::OidcService, organization_service::OrganizationService,
+        refresh_token_service::RefreshTokenService, role_service::RoleService,
+        token_revocation_service::TokenRevocationService, user_service::UserService,
     },
     domain::oauth::sau_jwt_issuer::SAUJwtIssuer,
     infrastructure::{
         cache::memcached::repository::CacheRepoMchd,
         persistence::postgres::repository::DatabaseRepoPg,
     },
-    interface::web::state::auth_session_cookie::AuthSessionCookieManager,
+    interface::web::state::{
+        admin_access::AdminAccess, auth_session_cookie::AuthSessionCookieManager,
+        introspection_client::IntrospectionClientAuth, login_redirect::LoginRedirect,
+        login_session_cookie::LoginSessionCookieManager,
+    },
 };
 
+pub mod admin_access;
 pub mod auth_session_cookie;
 pub mod from_part;
+pub mod introspection_client;
+pub mod login_redirect;
+pub mod login_session_cookie;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -19,6 +31,17 @@ pub struct AppState {
     pub cache_repo: CacheRepoMchd,
     pub user_service: UserService<DatabaseRepoPg>,
     pub oauth_service: OAuthService,
+    pub oidc_service: OidcService<CacheRepoMchd, DatabaseRepoPg>,
+    pub oidc_client_service: OidcClientService<DatabaseRepoPg>,
     pub jwt_service: JwtService<SAUJwtIssuer>,
+    pub refresh_token_service: RefreshTokenService<DatabaseRepoPg>,
+    pub token_revocation_service: TokenRevocationService<CacheRepoMchd, DatabaseRepoPg>,
     pub auth_cookie_manager: AuthSessionCookieManager,
+    pub introspection_client_auth: IntrospectionClientAuth,
+    pub admin_access: AdminAccess,
+    pub login_redirect: LoginRedirect,
+    pub login_session_service: LoginSessionService<CacheRepoMchd>,
+    pub login_session_cookie_manager: LoginSessionCookieManager,
+    pub role_service: RoleService<DatabaseRepoPg>,
+    pub organization_service: OrganizationService<DatabaseRepoPg>,
 }
@@ -0,0 +1,25 @@
+use std::{collections::HashSet, sync::Arc};
+
+use uuid::Uuid;
+
+use crate::infrastructure::config::types::AdminSecurityConfig;
+
+// users listed in `security.admin.user_ids`, the only callers of `/api/v1/admin`
+#[derive(Clone)]
+pub struct AdminAccess {
+    user_ids: Arc<HashSet<Uuid>>,
+}
+
+impl From<&AdminSecurityConfig> for AdminAccess {
+    fn from(value: &AdminSecurityConfig) -> Self {
+        Self {
+            user_ids: Arc::new(value.user_ids.iter().copied().collect()),
+        }
+    }
+}
+
+impl AdminAccess {
+    pub fn is_admin(&self, user_id: Uuid) -> bool {
+        self.user_ids.contains(&user_id)
+    }
+}
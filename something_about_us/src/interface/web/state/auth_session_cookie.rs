@@ -3,32 +3,64 @@ use uuid::Uuid;
 
 use crate::infrastructure::cookie::AuthSessionCookieIssuer;
 use crate::{
-    domain::oauth::auth_session::AUTH_SESSION_COOKIE_NAME,
+    domain::oauth::{
+        auth_session::AUTH_SESSION_COOKIE_NAME,
+        device_authorization::DEVICE_CONFIRMATION_COOKIE_NAME,
+    },
     infrastructure::config::types::SessionSecurityConfig,
 };
 
 #[derive(Clone)]
 pub struct AuthSessionCookieManager {
     builder: CookieBuilder<'static>,
+    device_confirmation_builder: CookieBuilder<'static>,
+    ttl: u64,
+}
+
+impl AuthSessionCookieManager {
+    // seconds, the auth session is kept in the cache for as long as its cookie lives
+    pub fn ttl(&self) -> u64 {
+        self.ttl
+    }
+
+    // only sent back by the confirmation form posted from our own page, also used to remove it
+    pub fn issue_device_confirmation_cookie(&self, token: String) -> Cookie<'static> {
+        let mut builder = self.device_confirmation_builder.clone();
+        builder.inner_mut().set_value(token);
+        builder.build()
+    }
+}
+
+// `security.session.same_site`, shared by every cookie the server sets but the strict device confirmation
+pub fn same_site(value: &SessionSecurityConfig) -> SameSite {
+    match value.same_site.to_lowercase().as_str() {
+        "strict" => SameSite::Strict,
+        "lax" => SameSite::Lax,
+        "none" => SameSite::None,
+        _ => SameSite::Lax, // default
+    }
 }
 
 impl From<&SessionSecurityConfig> for AuthSessionCookieManager {
     fn from(value: &SessionSecurityConfig) -> Self {
-        let same_site = match value.same_site.to_lowercase().as_str() {
-            "strict" => SameSite::Strict,
-            "lax" => SameSite::Lax,
-            "none" => SameSite::None,
-            _ => SameSite::Lax, // default
-        };
-
         let builder = Cookie::build((AUTH_SESSION_COOKIE_NAME, ""))
             .http_only(value.http_only)
             .secure(value.secure_cookies)
-            .same_site(same_site)
+            .same_site(same_site(value))
             .max_age(cookie::time::Duration::seconds(value.cookie_ttl as i64))
             .path("/");
+        let device_confirmation_builder = Cookie::build((DEVICE_CONFIRMATION_COOKIE_NAME, ""))
+            .http_only(true)
+            .secure(value.secure_cookies)
+            .same_site(SameSite::Strict)
+            .max_age(cookie::time::Duration::seconds(value.cookie_ttl as i64))
+            .path("/api/v1/oidc/device");
 
-        Self { builder }
+        Self {
+            builder,
+            device_confirmation_builder,
+            ttl: value.cookie_ttl,
+        }
     }
 }
 
@@ -2,14 +2,22 @@ use axum::extract::FromRef;
 
 use crate::{
     application::service::{
-        jwt_service::JwtService, oauth_service::OAuthService, user_service::UserService,
+        jwt_service::JwtService, login_session_service::LoginSessionService,
+        oauth_service::OAuthService, oidc_client_service::OidcClientService,
+        oidc_service::OidcService, organization_service::OrganizationService,
+        refresh_token_service::RefreshTokenService, role_service::RoleService,
+        token_revocation_service::TokenRevocationService, user_service::UserService,
     },
     domain::oauth::sau_jwt_issuer::SAUJwtIssuer,
     infrastructure::{
         cache::memcached::repository::CacheRepoMchd,
         persistence::postgres::repository::DatabaseRepoPg,
     },
-    interface::web::state::{auth_session_cookie::AuthSessionCookieManager, AppState},
+    interface::web::state::{
+        admin_access::AdminAccess, auth_session_cookie::AuthSessionCookieManager,
+        introspection_client::IntrospectionClientAuth, login_redirect::LoginRedirect,
+        login_session_cookie::LoginSessionCookieManager, AppState,
+    },
 };
 
 impl FromRef<AppState> for DatabaseRepoPg {
@@ -36,14 +44,80 @@ impl FromRef<AppState> for OAuthService {
     }
 }
 
+impl FromRef<AppState> for OidcService<CacheRepoMchd, DatabaseRepoPg> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.oidc_service.clone()
+    }
+}
+
+impl FromRef<AppState> for OidcClientService<DatabaseRepoPg> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.oidc_client_service.clone()
+    }
+}
+
 impl FromRef<AppState> for JwtService<SAUJwtIssuer> {
     fn from_ref(app_state: &AppState) -> Self {
         app_state.jwt_service.clone()
     }
 }
 
+impl FromRef<AppState> for RefreshTokenService<DatabaseRepoPg> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.refresh_token_service.clone()
+    }
+}
+
+impl FromRef<AppState> for TokenRevocationService<CacheRepoMchd, DatabaseRepoPg> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.token_revocation_service.clone()
+    }
+}
+
 impl FromRef<AppState> for AuthSessionCookieManager {
     fn from_ref(app_state: &AppState) -> Self {
         app_state.auth_cookie_manager.clone()
     }
 }
+
+impl FromRef<AppState> for IntrospectionClientAuth {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.introspection_client_auth.clone()
+    }
+}
+
+impl FromRef<AppState> for AdminAccess {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.admin_access.clone()
+    }
+}
+
+impl FromRef<AppState> for LoginRedirect {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.login_redirect.clone()
+    }
+}
+
+impl FromRef<AppState> for LoginSessionService<CacheRepoMchd> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.login_session_service.clone()
+    }
+}
+
+impl FromRef<AppState> for LoginSessionCookieManager {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.login_session_cookie_manager.clone()
+    }
+}
+
+impl FromRef<AppState> for RoleService<DatabaseRepoPg> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.role_service.clone()
+    }
+}
+
+impl FromRef<AppState> for OrganizationService<DatabaseRepoPg> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.organization_service.clone()
+    }
+}
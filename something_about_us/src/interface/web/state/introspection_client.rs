@@ -0,0 +1,53 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::http::HeaderMap;
+use ring::digest::{digest, SHA256};
+
+use crate::{
+    domain::oauth::constant_time::constant_time_eq,
+    infrastructure::config::types::IntrospectionSecurityConfig,
+    interface::web::auth::basic_credentials,
+};
+
+// resource servers authenticate to the introspection endpoint with HTTP Basic,
+// only the SHA-256 digest of each secret is kept in memory.
+#[derive(Clone)]
+pub struct IntrospectionClientAuth {
+    clients: Arc<HashMap<String, Vec<u8>>>,
+}
+
+impl From<&IntrospectionSecurityConfig> for IntrospectionClientAuth {
+    fn from(value: &IntrospectionSecurityConfig) -> Self {
+        let clients = value
+            .clients
+            .iter()
+            .map(|client| {
+                (
+                    client.client_id.clone(),
+                    digest(&SHA256, client.client_secret.as_bytes())
+                        .as_ref()
+                        .to_vec(),
+                )
+            })
+            .collect();
+        Self {
+            clients: Arc::new(clients),
+        }
+    }
+}
+
+impl IntrospectionClientAuth {
+    // returns the authenticated client id
+    pub fn authenticate(&self, headers: &HeaderMap) -> Option<String> {
+        let (client_id, client_secret) = basic_credentials(headers)?;
+
+        let expected = self.clients.get(&client_id)?;
+        let actual = digest(&SHA256, client_secret.as_bytes());
+        constant_time_eq(expected, actual.as_ref()).then_some(client_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("introspection_client_test.rs");
+}
@@ -0,0 +1,69 @@
+use super::IntrospectionClientAuth;
+use crate::infrastructure::config::types::{
+    IntrospectionClientConfig, IntrospectionSecurityConfig,
+};
+use axum::http::{header::AUTHORIZATION, HeaderMap, HeaderValue};
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+fn client_auth() -> IntrospectionClientAuth {
+    IntrospectionClientAuth::from(&IntrospectionSecurityConfig {
+        clients: vec![IntrospectionClientConfig {
+            client_id: "resource-server".to_string(),
+            client_secret: "s3cret".to_string(),
+        }],
+    })
+}
+
+fn basic(credentials: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let value = format!("Basic {}", BASE64_STANDARD.encode(credentials));
+    headers.insert(AUTHORIZATION, HeaderValue::from_str(&value).unwrap());
+    headers
+}
+
+#[test]
+fn test_authenticate_registered_client() {
+    let client_id = client_auth().authenticate(&basic("resource-server:s3cret"));
+    assert_eq!(client_id.as_deref(), Some("resource-server"));
+}
+
+#[test]
+fn test_authenticate_wrong_secret() {
+    assert!(client_auth()
+        .authenticate(&basic("resource-server:s3cret2"))
+        .is_none());
+    assert!(client_auth()
+        .authenticate(&basic("resource-server:"))
+        .is_none());
+}
+
+#[test]
+fn test_authenticate_unknown_client() {
+    assert!(client_auth()
+        .authenticate(&basic("other-server:s3cret"))
+        .is_none());
+}
+
+#[test]
+fn test_authenticate_missing_or_malformed_header() {
+    let auth = client_auth();
+    assert!(auth.authenticate(&HeaderMap::new()).is_none());
+
+    let mut bearer = HeaderMap::new();
+    bearer.insert(AUTHORIZATION, HeaderValue::from_static("Bearer s3cret"));
+    assert!(auth.authenticate(&bearer).is_none());
+
+    let mut not_base64 = HeaderMap::new();
+    not_base64.insert(AUTHORIZATION, HeaderValue::from_static("Basic !!!"));
+    assert!(auth.authenticate(&not_base64).is_none());
+
+    assert!(auth.authenticate(&basic("no-separator")).is_none());
+}
+
+#[test]
+fn test_no_clients_configured() {
+    let auth = IntrospectionClientAuth::from(&IntrospectionSecurityConfig::default());
+    assert!(auth
+        .authenticate(&basic("resource-server:s3cret"))
+        .is_none());
+}
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use axum::response::{IntoResponse, Redirect, Response};
+use axum_extra::extract::CookieJar;
+use cookie::{Cookie, CookieBuilder};
+use url::{form_urlencoded, Origin, Url};
+
+use crate::{
+    infrastructure::config::types::{
+        JwtConfig, LoginRedirectSecurityConfig, SessionSecurityConfig, TokenDelivery,
+    },
+    interface::web::{dto::jwt_response::Token, state::auth_session_cookie::same_site},
+};
+
+pub const ACCESS_TOKEN_COOKIE_NAME: &str = "something_about_us_access_token";
+pub const REFRESH_TOKEN_COOKIE_NAME: &str = "something_about_us_refresh_token";
+
+// sends a finished browser login back to an allowed frontend with its tokens,
+// instead of answering the IdP redirect with JSON.
+#[derive(Clone)]
+pub struct LoginRedirect {
+    allowed_origins: Arc<Vec<Origin>>,
+    token_delivery: TokenDelivery,
+    access_token_ttl: u64,
+    access_token_cookie: CookieBuilder<'static>,
+    refresh_token_cookie: CookieBuilder<'static>,
+}
+
+impl LoginRedirect {
+    pub fn new(
+        login_redirect: &LoginRedirectSecurityConfig,
+        session: &SessionSecurityConfig,
+        jwt: &JwtConfig,
+    ) -> Self {
+        let cookie = |name: &'static str, path: &'static str, ttl: u64| {
+            Cookie::build((name, ""))
+                .http_only(true)
+                .secure(session.secure_cookies)
+                .same_site(same_site(session))
+                .max_age(cookie::time::Duration::seconds(ttl as i64))
+                .path(path)
+        };
+
+        Self {
+            allowed_origins: Arc::new(
+                login_redirect
+                    .allowed_origins
+                    .iter()
+                    .map(Url::origin)
+                    .collect(),
+            ),
+            token_delivery: login_redirect.token_delivery,
+            access_token_ttl: jwt.access_token_ttl,
+            access_token_cookie: cookie(ACCESS_TOKEN_COOKIE_NAME, "/", jwt.access_token_ttl),
+            // only sent to the refresh and revocation endpoints
+            refresh_token_cookie: cookie(
+                REFRESH_TOKEN_COOKIE_NAME,
+                "/api/v1/token",
+                jwt.refresh_token_ttl,
+            ),
+        }
+    }
+
+    // `None` unless `return_to` is an absolute http(s) url on one of the allowed origins
+    pub fn check_return_to(&self, return_to: &str) -> Option<Url> {
+        let mut url = Url::parse(return_to).ok()?;
+        let allowed = matches!(url.scheme(), "http" | "https")
+            && url.username().is_empty()
+            && url.password().is_none()
+            && self.allowed_origins.contains(&url.origin());
+        if !allowed {
+            return None;
+        }
+        url.set_fragment(None);
+        Some(url)
+    }
+
+    pub fn redirect(&self, mut return_to: Url, token: Token, cookie_jar: CookieJar) -> Response {
+        match self.token_delivery {
+            TokenDelivery::Cookie => {
+                let cookie_jar = cookie_jar
+                    .add(self.build_cookie(&self.access_token_cookie, token.access_token))
+                    .add(self.build_cookie(&self.refresh_token_cookie, token.refresh_token));
+                (cookie_jar, Redirect::to(return_to.as_str())).into_response()
+            }
+            // a fragment never reaches the frontend server or its logs
+            TokenDelivery::Fragment => {
+                let fragment = form_urlencoded::Serializer::new(String::new())
+                    .append_pair("access_token", &token.access_token)
+                    .append_pair("token_type", "Bearer")
+                    .append_pair("expires_in", &self.access_token_ttl.to_string())
+                    .append_pair("refresh_token", &token.refresh_token)
+                    .finish();
+                return_to.set_fragment(Some(&fragment));
+                (cookie_jar, Redirect::to(return_to.as_str())).into_response()
+            }
+        }
+    }
+
+    fn build_cookie(&self, builder: &CookieBuilder<'static>, value: String) -> Cookie<'static> {
+        let mut builder = builder.clone();
+        builder.inner_mut().set_value(value);
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("login_redirect_test.rs");
+}
@@ -0,0 +1,139 @@
+use super::{LoginRedirect, ACCESS_TOKEN_COOKIE_NAME, REFRESH_TOKEN_COOKIE_NAME};
+use crate::{
+    infrastructure::config::types::{
+        JwtConfig, LoginRedirectSecurityConfig, SessionSecurityConfig, TokenDelivery,
+    },
+    interface::web::dto::jwt_response::Token,
+};
+use axum::http::{
+    header::{LOCATION, SET_COOKIE},
+    StatusCode,
+};
+use axum_extra::extract::CookieJar;
+use url::Url;
+
+fn login_redirect(token_delivery: TokenDelivery) -> LoginRedirect {
+    let jwt: JwtConfig = toml::from_str(
+        r#"
+iss = "https://auth.example.com"
+aud = "sau"
+keys_path = "./jwks"
+keys = []
+access_token_ttl = 3600
+"#,
+    )
+    .unwrap();
+    LoginRedirect::new(
+        &LoginRedirectSecurityConfig {
+            allowed_origins: vec![
+                Url::parse("https://app.example.com").unwrap(),
+                Url::parse("http://localhost:5173").unwrap(),
+            ],
+            token_delivery,
+        },
+        &SessionSecurityConfig {
+            cookie_ttl: 300,
+            secure_cookies: true,
+            same_site: "Lax".to_string(),
+            http_only: true,
+        },
+        &jwt,
+    )
+}
+
+fn token() -> Token {
+    Token {
+        access_token: "header.payload.signature".to_string(),
+        refresh_token: "refresh-token".to_string(),
+    }
+}
+
+#[test]
+fn test_return_to_on_allowed_origin() {
+    let login_redirect = login_redirect(TokenDelivery::Cookie);
+
+    let url = login_redirect
+        .check_return_to("https://app.example.com/dashboard?tab=1#stale")
+        .unwrap();
+    assert_eq!(url.as_str(), "https://app.example.com/dashboard?tab=1");
+    assert!(login_redirect
+        .check_return_to("http://localhost:5173/")
+        .is_some());
+}
+
+#[test]
+fn test_return_to_on_other_origin() {
+    let login_redirect = login_redirect(TokenDelivery::Cookie);
+
+    for return_to in [
+        "/dashboard",
+        "//evil.example.com/",
+        "http://app.example.com/",
+        "https://app.example.com:8443/",
+        "https://app.example.com.evil.com/",
+        "https://evil.com@app.example.com/",
+        "http://localhost:3000/",
+        "javascript:alert(1)",
+    ] {
+        assert!(
+            login_redirect.check_return_to(return_to).is_none(),
+            "{}",
+            return_to
+        );
+    }
+}
+
+#[test]
+fn test_redirect_with_cookies() {
+    let login_redirect = login_redirect(TokenDelivery::Cookie);
+    let return_to = login_redirect
+        .check_return_to("https://app.example.com/dashboard")
+        .unwrap();
+
+    let response = login_redirect.redirect(return_to, token(), CookieJar::new());
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(
+        response.headers()[LOCATION],
+        "https://app.example.com/dashboard"
+    );
+
+    let cookies: Vec<_> = response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .map(|value| value.to_str().unwrap().to_string())
+        .collect();
+    let access = cookies
+        .iter()
+        .find(|cookie| cookie.starts_with(ACCESS_TOKEN_COOKIE_NAME))
+        .unwrap();
+    assert!(access.contains("header.payload.signature"));
+    assert!(access.contains("HttpOnly"));
+    assert!(access.contains("Secure"));
+    assert!(access.contains("SameSite=Lax"));
+    let refresh = cookies
+        .iter()
+        .find(|cookie| cookie.starts_with(REFRESH_TOKEN_COOKIE_NAME))
+        .unwrap();
+    assert!(refresh.contains("Path=/api/v1/token"));
+}
+
+#[test]
+fn test_redirect_with_fragment() {
+    let login_redirect = login_redirect(TokenDelivery::Fragment);
+    let return_to = login_redirect
+        .check_return_to("https://app.example.com/callback")
+        .unwrap();
+
+    let response = login_redirect.redirect(return_to, token(), CookieJar::new());
+    assert!(response.headers().get(SET_COOKIE).is_none());
+
+    let location = Url::parse(response.headers()[LOCATION].to_str().unwrap()).unwrap();
+    assert_eq!(location.path(), "/callback");
+    assert_eq!(
+        location.fragment(),
+        Some(
+            "access_token=header.payload.signature&token_type=Bearer&expires_in=3600&refresh_token=refresh-token"
+        )
+    );
+}
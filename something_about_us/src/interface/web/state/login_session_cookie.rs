@@ -0,0 +1,48 @@
+use cookie::{Cookie, CookieBuilder};
+use uuid::Uuid;
+
+use crate::{
+    domain::oauth::login_session::LOGIN_SESSION_COOKIE_NAME,
+    infrastructure::{
+        config::types::{LoginSessionSecurityConfig, SessionSecurityConfig},
+        cookie::LoginSessionCookieIssuer,
+    },
+    interface::web::state::auth_session_cookie::same_site,
+};
+
+// the session cookie is a bearer credential, it is HttpOnly whatever `security.session.http_only` says
+#[derive(Clone)]
+pub struct LoginSessionCookieManager {
+    builder: CookieBuilder<'static>,
+}
+
+impl LoginSessionCookieManager {
+    pub fn new(
+        session: &SessionSecurityConfig,
+        login_session: &LoginSessionSecurityConfig,
+    ) -> Self {
+        let builder = Cookie::build((LOGIN_SESSION_COOKIE_NAME, ""))
+            .http_only(true)
+            .secure(session.secure_cookies)
+            .same_site(same_site(session))
+            .max_age(cookie::time::Duration::seconds(login_session.ttl as i64))
+            .path("/");
+
+        Self { builder }
+    }
+
+    // matches the attributes of the issued cookie so browsers drop it
+    pub fn removal_cookie(&self) -> Cookie<'static> {
+        let mut cookie = self.builder.clone().build();
+        cookie.make_removal();
+        cookie
+    }
+}
+
+impl LoginSessionCookieIssuer for LoginSessionCookieManager {
+    fn issue_login_session_cookie(&self, session_id: Uuid) -> Cookie<'static> {
+        let mut builder = self.builder.clone();
+        builder.inner_mut().set_value(session_id.to_string());
+        builder.build()
+    }
+}
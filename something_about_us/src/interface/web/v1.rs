@@ -2,9 +2,15 @@ use axum::Router;
 
 use crate::interface::web::state::AppState;
 
+pub mod admin;
 pub mod health;
 pub mod jwks;
 pub mod oauth;
+pub mod oidc;
+pub mod orgs;
+pub mod session;
+pub mod token;
+pub mod users;
 
 pub mod health_test;
 
@@ -14,6 +20,12 @@ pub async fn router(state: AppState) -> Router {
         Router::new()
             .nest("/heartbeat", health::router().await)
             .nest("/oauth", oauth::router(state.clone()).await)
-            .nest("/jwks", jwks::router(state.clone()).await),
+            .nest("/oidc", oidc::router(state.clone()).await)
+            .nest("/token", token::router(state.clone()).await)
+            .nest("/session", session::router(state.clone()).await)
+            .nest("/users", users::router(state.clone()).await)
+            .nest("/orgs", orgs::router(state.clone()).await)
+            .nest("/jwks", jwks::router(state.clone()).await)
+            .nest("/admin", admin::router(state.clone()).await),
     )
 }
@@ -0,0 +1,45 @@
+use axum::{
+    routing::{get, post, put},
+    Router,
+};
+
+use crate::interface::web::{
+    state::AppState,
+    v1::admin::clients::{
+        create_client, disable_client, enable_client, get_client, list_clients,
+        rotate_client_secret,
+    },
+    v1::admin::roles::{
+        assign_role, create_role, delete_role, get_role, list_roles, list_user_roles,
+        unassign_role, update_role,
+    },
+    v1::admin::users::{deactivate_user, delete_user, get_user, list_users, reactivate_user},
+};
+
+pub mod clients;
+pub mod roles;
+pub mod users;
+
+pub async fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/clients", get(list_clients).post(create_client))
+        .route("/clients/{client_id}", get(get_client))
+        .route("/clients/{client_id}/secret", post(rotate_client_secret))
+        .route("/clients/{client_id}/disable", post(disable_client))
+        .route("/clients/{client_id}/enable", post(enable_client))
+        .route("/roles", get(list_roles).post(create_role))
+        .route(
+            "/roles/{name}",
+            get(get_role).put(update_role).delete(delete_role),
+        )
+        .route("/users", get(list_users))
+        .route("/users/{user_id}", get(get_user).delete(delete_user))
+        .route("/users/{user_id}/deactivate", post(deactivate_user))
+        .route("/users/{user_id}/reactivate", post(reactivate_user))
+        .route("/users/{user_id}/roles", get(list_user_roles))
+        .route(
+            "/users/{user_id}/roles/{name}",
+            put(assign_role).delete(unassign_role),
+        )
+        .with_state(state)
+}
@@ -0,0 +1,245 @@
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection},
+        Path, State,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::oidc_client_service::{OidcClientService, OidcClientServiceError},
+    domain::oauth::oidc_client::ClientRegistration,
+    infrastructure::persistence::postgres::repository::DatabaseRepoPg,
+    interface::web::{
+        auth::AdminUser,
+        dto::{
+            client_registration_request::ClientRegistrationRequest,
+            client_response::{ClientListResponse, ClientResponse, ClientSecretResponse},
+            error_response::ErrorResponse,
+        },
+        error::WebError,
+    },
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/clients",
+    tag = "Admin",
+    operation_id = "adminCreateClient",
+    request_body = ClientRegistrationRequest,
+    responses(
+        (status = 201, description = "Client registered. The secret of a confidential client is only shown here", body = ClientSecretResponse),
+        (status = 400, description = "Invalid client registration", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 409, description = "Client id is already registered", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_client(
+    _admin: AdminUser,
+    State(oidc_client_service): State<OidcClientService<DatabaseRepoPg>>,
+    body: Result<Json<ClientRegistrationRequest>, JsonRejection>,
+) -> Result<Response, WebError> {
+    let Json(request) = body?;
+    let registration = ClientRegistration::try_from(request).map_err(WebError::BadRequest)?;
+
+    let (client, client_secret) = oidc_client_service
+        .register(registration)
+        .await
+        .map_err(client_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ClientSecretResponse {
+            client: ClientResponse::from(&client),
+            client_secret,
+        }),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/clients",
+    tag = "Admin",
+    operation_id = "adminListClients",
+    responses(
+        (status = 200, description = "Registered clients", body = ClientListResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_clients(
+    _admin: AdminUser,
+    State(oidc_client_service): State<OidcClientService<DatabaseRepoPg>>,
+) -> Result<Response, WebError> {
+    let clients = oidc_client_service.list().await.map_err(client_error)?;
+
+    Ok(Json(ClientListResponse {
+        clients: clients.iter().map(ClientResponse::from).collect(),
+    })
+    .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/clients/{client_id}",
+    tag = "Admin",
+    operation_id = "adminGetClient",
+    params(("client_id" = String, Path, description = "Client id")),
+    responses(
+        (status = 200, description = "Registered client", body = ClientResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 404, description = "Client is not registered", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_client(
+    _admin: AdminUser,
+    State(oidc_client_service): State<OidcClientService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(client_id) = path?;
+    let client = oidc_client_service
+        .get(&client_id)
+        .await
+        .map_err(client_error)?;
+
+    Ok(Json(ClientResponse::from(&client)).into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/clients/{client_id}/secret",
+    tag = "Admin",
+    operation_id = "adminRotateClientSecret",
+    params(("client_id" = String, Path, description = "Client id")),
+    responses(
+        (status = 200, description = "New secret issued, the previous one stops working right away", body = ClientSecretResponse),
+        (status = 400, description = "Public clients have no secret", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 404, description = "Client is not registered", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn rotate_client_secret(
+    _admin: AdminUser,
+    State(oidc_client_service): State<OidcClientService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(client_id) = path?;
+    let (client, client_secret) = oidc_client_service
+        .rotate_secret(&client_id)
+        .await
+        .map_err(client_error)?;
+
+    Ok(Json(ClientSecretResponse {
+        client: ClientResponse::from(&client),
+        client_secret: Some(client_secret),
+    })
+    .into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/clients/{client_id}/disable",
+    tag = "Admin",
+    operation_id = "adminDisableClient",
+    params(("client_id" = String, Path, description = "Client id")),
+    responses(
+        (status = 200, description = "Client disabled, its codes and refresh tokens stop working", body = ClientResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 404, description = "Client is not registered", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn disable_client(
+    _admin: AdminUser,
+    State(oidc_client_service): State<OidcClientService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(client_id) = path?;
+    set_active(&oidc_client_service, &client_id, false).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/clients/{client_id}/enable",
+    tag = "Admin",
+    operation_id = "adminEnableClient",
+    params(("client_id" = String, Path, description = "Client id")),
+    responses(
+        (status = 200, description = "Client enabled", body = ClientResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 404, description = "Client is not registered", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn enable_client(
+    _admin: AdminUser,
+    State(oidc_client_service): State<OidcClientService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(client_id) = path?;
+    set_active(&oidc_client_service, &client_id, true).await
+}
+
+async fn set_active(
+    oidc_client_service: &OidcClientService<DatabaseRepoPg>,
+    client_id: &str,
+    is_active: bool,
+) -> Result<Response, WebError> {
+    let client = oidc_client_service
+        .set_active(client_id, is_active)
+        .await
+        .map_err(client_error)?;
+
+    Ok(Json(ClientResponse::from(&client)).into_response())
+}
+
+fn client_error(e: OidcClientServiceError) -> WebError {
+    match e {
+        OidcClientServiceError::InvalidClient(message) => WebError::BadRequest(message),
+        OidcClientServiceError::ClientAlreadyExists => WebError::Conflict(e.to_string()),
+        OidcClientServiceError::ClientNotFound => WebError::NotFound(e.to_string()),
+        OidcClientServiceError::Repository(_) => WebError::InternalServerError(e.to_string()),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        create_client,
+        list_clients,
+        get_client,
+        rotate_client_secret,
+        disable_client,
+        enable_client
+    ),
+    components(schemas(
+        ClientRegistrationRequest,
+        ClientResponse,
+        ClientListResponse,
+        ClientSecretResponse
+    ))
+)]
+struct ClientsOpenApi;
+
+pub fn gen_openapi_admin_clients() -> utoipa::openapi::OpenApi {
+    ClientsOpenApi::openapi()
+}
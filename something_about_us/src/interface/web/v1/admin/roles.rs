@@ -0,0 +1,319 @@
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection},
+        Path, State,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::{
+    application::service::role_service::{RoleService, RoleServiceError},
+    domain::user::role::ADMIN_PERMISSION,
+    infrastructure::persistence::postgres::repository::DatabaseRepoPg,
+    interface::web::{
+        auth::{AdminUser, AssignRoles, PermittedUser, ReadRoles},
+        dto::{
+            error_response::ErrorResponse,
+            role_request::{CreateRoleRequest, UpdateRoleRequest},
+            role_response::{RoleListResponse, RoleResponse},
+        },
+        error::WebError,
+    },
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/roles",
+    tag = "Admin",
+    operation_id = "adminCreateRole",
+    request_body = CreateRoleRequest,
+    responses(
+        (status = 201, description = "Role created", body = RoleResponse),
+        (status = 400, description = "Invalid role name or permission", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 409, description = "Role name is already taken", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_role(
+    _admin: AdminUser,
+    State(role_service): State<RoleService<DatabaseRepoPg>>,
+    body: Result<Json<CreateRoleRequest>, JsonRejection>,
+) -> Result<Response, WebError> {
+    let Json(request) = body?;
+    let role = role_service
+        .create_role(request.name, request.description, request.permissions)
+        .await
+        .map_err(role_error)?;
+
+    Ok((StatusCode::CREATED, Json(RoleResponse::from(&role))).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/roles",
+    tag = "Admin",
+    operation_id = "adminListRoles",
+    responses(
+        (status = 200, description = "Roles", body = RoleListResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is neither an admin nor granted `roles:read`", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_roles(
+    _caller: PermittedUser<ReadRoles>,
+    State(role_service): State<RoleService<DatabaseRepoPg>>,
+) -> Result<Response, WebError> {
+    let roles = role_service.list_roles().await.map_err(role_error)?;
+
+    Ok(Json(RoleListResponse {
+        roles: roles.iter().map(RoleResponse::from).collect(),
+    })
+    .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/roles/{name}",
+    tag = "Admin",
+    operation_id = "adminGetRole",
+    params(("name" = String, Path, description = "Role name")),
+    responses(
+        (status = 200, description = "Role", body = RoleResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is neither an admin nor granted `roles:read`", body = ErrorResponse),
+        (status = 404, description = "Role not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_role(
+    _caller: PermittedUser<ReadRoles>,
+    State(role_service): State<RoleService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(name) = path?;
+    let role = role_service.get_role(&name).await.map_err(role_error)?;
+
+    Ok(Json(RoleResponse::from(&role)).into_response())
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/roles/{name}",
+    tag = "Admin",
+    operation_id = "adminUpdateRole",
+    params(("name" = String, Path, description = "Role name")),
+    request_body = UpdateRoleRequest,
+    responses(
+        (status = 200, description = "Role updated, users get the new permissions with their next access token", body = RoleResponse),
+        (status = 400, description = "Invalid permission", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 404, description = "Role not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_role(
+    _admin: AdminUser,
+    State(role_service): State<RoleService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+    body: Result<Json<UpdateRoleRequest>, JsonRejection>,
+) -> Result<Response, WebError> {
+    let Path(name) = path?;
+    let Json(request) = body?;
+    let role = role_service
+        .update_role(&name, request.description, request.permissions)
+        .await
+        .map_err(role_error)?;
+
+    Ok(Json(RoleResponse::from(&role)).into_response())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/roles/{name}",
+    tag = "Admin",
+    operation_id = "adminDeleteRole",
+    params(("name" = String, Path, description = "Role name")),
+    responses(
+        (status = 204, description = "Role deleted and unassigned from every user"),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 404, description = "Role not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_role(
+    _admin: AdminUser,
+    State(role_service): State<RoleService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(name) = path?;
+    role_service.delete_role(&name).await.map_err(role_error)?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/users/{user_id}/roles",
+    tag = "Admin",
+    operation_id = "adminListUserRoles",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "Roles assigned to the user", body = RoleListResponse),
+        (status = 400, description = "Invalid user id", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is neither an admin nor granted `roles:read`", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_user_roles(
+    _caller: PermittedUser<ReadRoles>,
+    State(role_service): State<RoleService<DatabaseRepoPg>>,
+    path: Result<Path<Uuid>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(user_id) = path?;
+    let roles = role_service
+        .get_user_roles(user_id)
+        .await
+        .map_err(role_error)?;
+
+    Ok(Json(RoleListResponse {
+        roles: roles.iter().map(RoleResponse::from).collect(),
+    })
+    .into_response())
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/users/{user_id}/roles/{name}",
+    tag = "Admin",
+    operation_id = "adminAssignRole",
+    params(
+        ("user_id" = Uuid, Path, description = "User id"),
+        ("name" = String, Path, description = "Role name")
+    ),
+    responses(
+        (status = 204, description = "Role assigned, assigning it again changes nothing"),
+        (status = 400, description = "Invalid user id", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is neither an admin nor granted `roles:assign`, or only the latter and the role grants `admin`", body = ErrorResponse),
+        (status = 404, description = "User or role not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn assign_role(
+    caller: PermittedUser<AssignRoles>,
+    State(role_service): State<RoleService<DatabaseRepoPg>>,
+    path: Result<Path<(Uuid, String)>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path((user_id, name)) = path?;
+    check_admin_role(&caller, &role_service, &name).await?;
+    role_service
+        .assign_role(user_id, &name)
+        .await
+        .map_err(role_error)?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/users/{user_id}/roles/{name}",
+    tag = "Admin",
+    operation_id = "adminUnassignRole",
+    params(
+        ("user_id" = Uuid, Path, description = "User id"),
+        ("name" = String, Path, description = "Role name")
+    ),
+    responses(
+        (status = 204, description = "Role unassigned, tokens already issued keep it until they expire"),
+        (status = 400, description = "Invalid user id", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is neither an admin nor granted `roles:assign`, or only the latter and the role grants `admin`", body = ErrorResponse),
+        (status = 404, description = "Role not found or not assigned to the user", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn unassign_role(
+    caller: PermittedUser<AssignRoles>,
+    State(role_service): State<RoleService<DatabaseRepoPg>>,
+    path: Result<Path<(Uuid, String)>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path((user_id, name)) = path?;
+    check_admin_role(&caller, &role_service, &name).await?;
+    role_service
+        .unassign_role(user_id, &name)
+        .await
+        .map_err(role_error)?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+// `roles:assign` alone does not hand out or take away `admin`
+async fn check_admin_role(
+    caller: &PermittedUser<AssignRoles>,
+    role_service: &RoleService<DatabaseRepoPg>,
+    name: &str,
+) -> Result<(), WebError> {
+    if caller.is_admin() {
+        return Ok(());
+    }
+    let role = role_service.get_role(name).await.map_err(role_error)?;
+    if role
+        .permissions
+        .iter()
+        .any(|permission| permission == ADMIN_PERMISSION)
+    {
+        return Err(WebError::Forbidden(
+            "only admins assign a role granting admin".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn role_error(e: RoleServiceError) -> WebError {
+    match e {
+        RoleServiceError::InvalidRole(message) => WebError::BadRequest(message),
+        RoleServiceError::RoleAlreadyExists => WebError::Conflict(e.to_string()),
+        RoleServiceError::RoleNotFound
+        | RoleServiceError::RoleNotAssigned
+        | RoleServiceError::UserNotFound => WebError::NotFound(e.to_string()),
+        RoleServiceError::Repository(_) => WebError::InternalServerError(e.to_string()),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        create_role,
+        list_roles,
+        get_role,
+        update_role,
+        delete_role,
+        list_user_roles,
+        assign_role,
+        unassign_role
+    ),
+    components(schemas(CreateRoleRequest, UpdateRoleRequest, RoleResponse, RoleListResponse))
+)]
+struct RolesOpenApi;
+
+pub fn gen_openapi_admin_roles() -> utoipa::openapi::OpenApi {
+    RolesOpenApi::openapi()
+}
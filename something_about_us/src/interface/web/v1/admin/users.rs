@@ -0,0 +1,244 @@
+use axum::{
+    extract::{
+        rejection::{PathRejection, QueryRejection},
+        Path, Query, State,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::{
+    application::service::{
+        token_revocation_service::TokenRevocationService,
+        user_service::{UserService, UserServiceError},
+    },
+    domain::{idp::supported_idp::SupportIdp, user::user_filter::UserFilter},
+    infrastructure::{
+        cache::memcached::repository::CacheRepoMchd,
+        persistence::postgres::repository::DatabaseRepoPg,
+    },
+    interface::web::{
+        auth::AdminUser,
+        dto::{
+            admin_user_response::{AdminUserListResponse, AdminUserResponse},
+            error_response::ErrorResponse,
+            user_identity_response::UserIdentityResponse,
+            user_list_query::UserListQuery,
+        },
+        error::WebError,
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/users",
+    tag = "Admin",
+    operation_id = "adminListUsers",
+    params(UserListQuery),
+    responses(
+        (status = 200, description = "One page of the matching users, oldest first", body = AdminUserListResponse),
+        (status = 400, description = "Invalid idp or date", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_users(
+    _admin: AdminUser,
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    query: Result<Query<UserListQuery>, QueryRejection>,
+) -> Result<Response, WebError> {
+    let Query(query) = query?;
+    let filter = UserFilter {
+        idp: query
+            .idp
+            .map(SupportIdp::try_from)
+            .transpose()
+            .map_err(|e| WebError::BadRequest(e.to_string()))?,
+        is_active: query.is_active,
+        created_from: query.created_from.as_deref().map(rfc3339).transpose()?,
+        created_to: query.created_to.as_deref().map(rfc3339).transpose()?,
+        search: query.q.filter(|q| !q.is_empty()),
+    };
+
+    let page = user_service
+        .list_users(&filter, query.cursor, query.limit)
+        .await
+        .map_err(user_error)?;
+
+    Ok(Json(AdminUserListResponse {
+        users: page.users.iter().map(AdminUserResponse::from).collect(),
+        next_cursor: page.next_cursor,
+    })
+    .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/users/{user_id}",
+    tag = "Admin",
+    operation_id = "adminGetUser",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "User with its linked identities", body = AdminUserResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_user(
+    _admin: AdminUser,
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    path: Result<Path<Uuid>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(user_id) = path?;
+    let user = user_service
+        .get_user_by_id(user_id)
+        .await
+        .map_err(user_error)?
+        .ok_or_else(|| WebError::NotFound(UserServiceError::UserNotFound.to_string()))?;
+    let identities = user_service
+        .get_identities(user_id)
+        .await
+        .map_err(user_error)?;
+
+    Ok(Json(AdminUserResponse::with_identities(&user, &identities)).into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/users/{user_id}/deactivate",
+    tag = "Admin",
+    operation_id = "adminDeactivateUser",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "User deactivated, it can no longer sign in and its tokens are revoked", body = AdminUserResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn deactivate_user(
+    _admin: AdminUser,
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    State(token_revocation_service): State<TokenRevocationService<CacheRepoMchd, DatabaseRepoPg>>,
+    path: Result<Path<Uuid>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(user_id) = path?;
+    let response = set_active(&user_service, user_id, false).await?;
+
+    // access tokens already handed out stop working now, not when they expire
+    token_revocation_service
+        .revoke_all_for_user(user_id)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+
+    Ok(response)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/users/{user_id}/reactivate",
+    tag = "Admin",
+    operation_id = "adminReactivateUser",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "User reactivated", body = AdminUserResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn reactivate_user(
+    _admin: AdminUser,
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    path: Result<Path<Uuid>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(user_id) = path?;
+    set_active(&user_service, user_id, true).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/users/{user_id}",
+    tag = "Admin",
+    operation_id = "adminDeleteUser",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 204, description = "User deleted with its identities, refresh tokens, roles and memberships, its access tokens are revoked"),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 409, description = "User is the last owner of an organization", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_user(
+    _admin: AdminUser,
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    State(token_revocation_service): State<TokenRevocationService<CacheRepoMchd, DatabaseRepoPg>>,
+    path: Result<Path<Uuid>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(user_id) = path?;
+    // revoked first, access tokens already handed out must not outlive the user
+    token_revocation_service
+        .revoke_all_for_user(user_id)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    user_service
+        .delete_user(user_id)
+        .await
+        .map_err(user_error)?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn set_active(
+    user_service: &UserService<DatabaseRepoPg>,
+    user_id: Uuid,
+    is_active: bool,
+) -> Result<Response, WebError> {
+    let user = user_service
+        .set_active(user_id, is_active)
+        .await
+        .map_err(user_error)?;
+
+    Ok(Json(AdminUserResponse::from(&user)).into_response())
+}
+
+fn rfc3339(value: &str) -> Result<DateTime<Utc>, WebError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|e| WebError::BadRequest(format!("invalid date {} : {}", value, e)))
+}
+
+fn user_error(e: UserServiceError) -> WebError {
+    match e {
+        UserServiceError::UserNotFound => WebError::NotFound(e.to_string()),
+        UserServiceError::LastOrganizationOwner => WebError::Conflict(e.to_string()),
+        _ => WebError::InternalServerError(e.to_string()),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_users, get_user, deactivate_user, reactivate_user, delete_user),
+    components(schemas(AdminUserResponse, AdminUserListResponse, UserIdentityResponse))
+)]
+struct UsersOpenApi;
+
+pub fn gen_openapi_admin_users() -> utoipa::openapi::OpenApi {
+    UsersOpenApi::openapi()
+}
@@ -1,16 +1,31 @@
-use axum::{routing::get, Router};
+use axum::{
+    routing::{get, post},
+    Router,
+};
 
 use crate::interface::web::{
     state::AppState,
-    v1::oauth::{callback::callback, login::login},
+    v1::oauth::{
+        callback::callback,
+        link::{create_link_ticket, link},
+        login::login,
+        logout::logout,
+        providers::providers,
+    },
 };
 
 pub mod callback;
+pub mod link;
 pub mod login;
+pub mod logout;
+pub mod providers;
 
 pub async fn router(state: AppState) -> Router {
     Router::new()
+        .route("/providers", get(providers))
+        .route("/logout", post(logout))
         .route("/{idp}/login", get(login))
+        .route("/{idp}/link", get(link).post(create_link_ticket))
         .route("/{idp}/callback", get(callback))
         .with_state(state)
 }
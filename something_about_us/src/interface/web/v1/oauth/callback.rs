@@ -3,33 +3,50 @@ use axum::{
         rejection::{PathRejection, QueryRejection},
         Path, Query, State,
     },
-    response::{IntoResponse, Response},
+    response::{IntoResponse, Redirect, Response},
+    Json,
 };
 use axum_extra::extract::CookieJar;
+use url::Url;
 use utoipa::OpenApi;
 use uuid::Uuid;
 
 use crate::{
     application::{
-        port::auth_session_repository::AuthSessionCacheRepo,
+        port::auth_session_repository::{AuthSessionCacheRepo, AuthSessionCacheRepoError},
         service::{
-            jwt_service::JwtService, oauth_service::OAuthService, user_service::UserService,
+            jwt_service::JwtService,
+            login_session_service::LoginSessionService,
+            oauth_service::OAuthService,
+            oidc_service::{OidcService, OidcServiceError},
+            organization_service::OrganizationService,
+            refresh_token_service::RefreshTokenService,
+            role_service::RoleService,
+            user_service::{UserService, UserServiceError},
         },
     },
     domain::{
         idp::supported_idp::SupportIdp,
-        oauth::{auth_session::AUTH_SESSION_COOKIE_NAME, sau_jwt_issuer::SAUJwtIssuer},
+        oauth::{
+            auth_session::AUTH_SESSION_COOKIE_NAME, authorization_code::AuthorizationRequest,
+            oauth_provider::IdpIdentity, sau_jwt_issuer::SAUJwtIssuer,
+        },
+        user::user_identity::UserIdentity,
     },
     infrastructure::{
-        cache::memcached::repository::CacheRepoMchd,
+        cache::memcached::repository::CacheRepoMchd, cookie::LoginSessionCookieIssuer,
         persistence::postgres::repository::DatabaseRepoPg,
     },
     interface::web::{
+        auth::user_grants,
         dto::{
             callback_param::OAuthCallbackQuery, error_response::ErrorResponse,
             idp_path::IdpPathParam, jwt_response::Token,
+            user_identity_response::UserIdentityResponse,
         },
         error::WebError,
+        state::{login_redirect::LoginRedirect, login_session_cookie::LoginSessionCookieManager},
+        v1::oidc::device::device_approved_page,
     },
 };
 
@@ -43,12 +60,26 @@ use crate::{
         OAuthCallbackQuery
     ),
     responses(
-        (status = 200, description = "Login complete. JWT access token issued", body = Token),
+        (status = 200, description = "Login complete. JWT access token and refresh token issued, an HTML page when the login approved a device at `/api/v1/oidc/device`, or the identity linked by a login started at `/api/v1/oauth/{idp}/link`", body = Token),
+        (status = 302, description = "Login started at `/api/v1/oidc/authorize` is complete, redirect back to the client with an authorization code",
+            headers(
+                ("Location" = String, description = "Registered redirect uri with `code` and `state`")
+            )
+        ),
+        (status = 303, description = "Login started with `return_to` is complete, redirect back to the frontend with the tokens in HttpOnly cookies or the url fragment, with a session cookie when `security.login_session` is enabled, or with nothing after a link",
+            headers(
+                ("Set-Cookie" = String, description = "Access and refresh token cookies when `token_delivery` is `cookie`, the session cookie in session mode"),
+                ("Location" = String, description = "`return_to`, with `#access_token=...` when `token_delivery` is `fragment`")
+            )
+        ),
         (status = 400, description = "Invalid input", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "The user has been deactivated", body = ErrorResponse),
+        (status = 409, description = "The identity is already linked to another user", body = ErrorResponse),
         (status = 500, description = "Internal Server Error", body = ErrorResponse)
     )
 )]
+#[allow(clippy::too_many_arguments)]
 pub async fn callback(
     path: Result<Path<SupportIdp>, PathRejection>,
     query: Result<Query<OAuthCallbackQuery>, QueryRejection>,
@@ -56,6 +87,13 @@ pub async fn callback(
     State(cache_service): State<CacheRepoMchd>,
     State(user_service): State<UserService<DatabaseRepoPg>>,
     State(jwt_issuer): State<JwtService<SAUJwtIssuer>>,
+    State(refresh_token_service): State<RefreshTokenService<DatabaseRepoPg>>,
+    State(oidc_service): State<OidcService<CacheRepoMchd, DatabaseRepoPg>>,
+    State(login_redirect): State<LoginRedirect>,
+    State(login_session_service): State<LoginSessionService<CacheRepoMchd>>,
+    State(login_session_cookie_manager): State<LoginSessionCookieManager>,
+    State(role_service): State<RoleService<DatabaseRepoPg>>,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
     cookie_jar: CookieJar,
 ) -> Result<Response, WebError> {
     let Path(idp) = path?;
@@ -67,53 +105,164 @@ pub async fn callback(
     let session_id = session_cookie
         .value()
         .parse::<Uuid>()
-        .map_err(|e| WebError::Auth(format!("invalid session_id format : {}", e.to_string())))?;
+        .map_err(|e| WebError::Auth(format!("invalid session_id format : {}", e)))?;
     let cookie_jar = cookie_jar.clone().remove(session_cookie.clone());
 
-    let auth_session_info = cache_service
-        .get_auth_session(session_id)
-        .await
-        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
-    if auth_session_info.csrf_token != callback_params.state {
+    // consumed before anything is checked, a replayed callback finds no session
+    let auth_session_info =
+        cache_service
+            .take_auth_session(session_id)
+            .await
+            .map_err(|e| match e {
+                AuthSessionCacheRepoError::SessionNotFound(_) => WebError::Auth(e.to_string()),
+                _ => WebError::InternalServerError(e.to_string()),
+            })?;
+    if !auth_session_info.verify_csrf(&callback_params.state) {
         return Err(WebError::Auth("csrf token is invalid".to_string()));
     }
+    if !auth_session_info.is_for_idp(&idp) {
+        return Err(WebError::Auth(
+            "session was started for another idp".to_string(),
+        ));
+    }
 
-    let access_token = oauth_service
-        .callback_call(
-            idp.clone(),
-            callback_params.code,
-            auth_session_info.pkce_verifier,
-        )
-        .await
-        .map_err(|e| WebError::Auth(e.to_string()))?;
-    let idp_user_id = oauth_service
-        .get_user_id_call(idp.clone(), access_token)
+    let idp_identity = oauth_service
+        .authenticate_call(idp.clone(), callback_params.code, &auth_session_info)
         .await
         .map_err(|e| WebError::Auth(e.to_string()))?;
 
+    // a link started at `/api/v1/oauth/{idp}/link` adds the identity to that user, nobody signs in
+    if let Some(user_id) = auth_session_info.link_user_id {
+        let identity = link_identity(&user_service, user_id, idp, idp_identity).await?;
+        let return_to = allowed_return_to(&login_redirect, auth_session_info.return_to)?;
+        if let Some(return_to) = return_to {
+            return Ok((cookie_jar, Redirect::to(return_to.as_str())).into_response());
+        }
+        return Ok((cookie_jar, Json(UserIdentityResponse::from(&identity))).into_response());
+    }
+
     let user = user_service
-        .get_or_create_user_from_callback(idp, idp_user_id)
+        .get_or_create_user_from_callback(idp, idp_identity)
         .await
-        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+        .map_err(|e| match e {
+            UserServiceError::UserInactive => WebError::UserInactive,
+            _ => WebError::InternalServerError(e.to_string()),
+        })?;
+
+    // the device polling `/api/v1/oidc/token` gets its tokens once the user is bound to it
+    if let Some(user_code) = auth_session_info.device_user_code {
+        let authorization = oidc_service
+            .approve_device(&user_code, user.id)
+            .await
+            .map_err(|e| match e {
+                OidcServiceError::InvalidUserCode => WebError::BadRequest(e.to_string()),
+                _ => WebError::InternalServerError(e.to_string()),
+            })?;
+        return Ok((cookie_jar, device_approved_page(&authorization.client_id)).into_response());
+    }
+
+    // the relying party gets a code and exchanges it at `/api/v1/oidc/token` itself
+    if let Some(authorization) = auth_session_info.authorization {
+        let redirect = authorization_redirect(&oidc_service, user.id, authorization).await?;
+        return Ok((cookie_jar, redirect).into_response());
+    }
+
+    let return_to = allowed_return_to(&login_redirect, auth_session_info.return_to)?;
+
+    // backend-for-frontend mode, the browser only gets the session cookie and no token at all
+    if let Some(return_to) = &return_to {
+        if login_session_service.enabled() {
+            let session = login_session_service
+                .create(user.id)
+                .await
+                .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+            let cookie_jar =
+                cookie_jar.add(login_session_cookie_manager.issue_login_session_cookie(session.id));
+            return Ok((cookie_jar, Redirect::to(return_to.as_str())).into_response());
+        }
+    }
 
-    let jwt = jwt_issuer.issue_with_id(&user.id).map_err(|e| {
-        WebError::InternalServerError(format!("fail to issue jwt: {}", e.to_string()))
+    let grants = user_grants(&role_service, &organization_service, user.id).await?;
+    let jwt = jwt_issuer
+        .issue_with_id(&user.id, &grants)
+        .map_err(|e| WebError::InternalServerError(format!("fail to issue jwt: {}", e)))?;
+    let refresh_token = refresh_token_service.issue(user.id).await.map_err(|e| {
+        WebError::InternalServerError(format!("fail to issue refresh token: {}", e))
     })?;
 
-    Ok((
-        cookie_jar,
-        (
-            axum::http::StatusCode::OK,
-            axum::Json(Token { access_token: jwt }),
-        ),
-    )
-        .into_response())
+    let token = Token {
+        access_token: jwt,
+        refresh_token,
+    };
+
+    if let Some(return_to) = return_to {
+        return Ok(login_redirect.redirect(return_to, token, cookie_jar));
+    }
+
+    Ok((cookie_jar, (axum::http::StatusCode::OK, Json(token))).into_response())
+}
+
+// checked again, the allowed origins may have changed since the login started
+fn allowed_return_to(
+    login_redirect: &LoginRedirect,
+    return_to: Option<String>,
+) -> Result<Option<Url>, WebError> {
+    return_to
+        .map(|return_to| {
+            login_redirect
+                .check_return_to(&return_to)
+                .ok_or_else(|| WebError::BadRequest("return_to is not allowed".to_string()))
+        })
+        .transpose()
+}
+
+async fn link_identity(
+    user_service: &UserService<DatabaseRepoPg>,
+    user_id: Uuid,
+    idp: SupportIdp,
+    idp_identity: IdpIdentity,
+) -> Result<UserIdentity, WebError> {
+    // the user may have been deactivated since the link started
+    user_service
+        .link_identity(user_id, idp, idp_identity)
+        .await
+        .map_err(|e| match e {
+            UserServiceError::UserNotFound => WebError::Auth(e.to_string()),
+            UserServiceError::UserInactive => WebError::UserInactive,
+            UserServiceError::IdentityAlreadyLinked => WebError::Conflict(e.to_string()),
+            _ => WebError::InternalServerError(e.to_string()),
+        })
+}
+
+async fn authorization_redirect(
+    oidc_service: &OidcService<CacheRepoMchd, DatabaseRepoPg>,
+    user_id: Uuid,
+    authorization: AuthorizationRequest,
+) -> Result<Redirect, WebError> {
+    // registered redirect uris are checked to be absolute urls on startup
+    let mut redirect_uri = Url::parse(&authorization.redirect_uri)
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    let state = authorization.state.clone();
+
+    let code = oidc_service
+        .issue_code(user_id, authorization)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+
+    {
+        let mut query = redirect_uri.query_pairs_mut();
+        query.append_pair("code", &code);
+        if let Some(state) = &state {
+            query.append_pair("state", state);
+        }
+    }
+    Ok(Redirect::to(redirect_uri.as_str()))
 }
 
 #[derive(OpenApi)]
 #[openapi(
     paths(callback),
-    components(schemas(Token, IdpPathParam, OAuthCallbackQuery))
+    components(schemas(Token, UserIdentityResponse, IdpPathParam, OAuthCallbackQuery))
 )]
 struct CallbackOpenApi;
 
@@ -0,0 +1,160 @@
+use axum::{
+    extract::{
+        rejection::{PathRejection, QueryRejection},
+        Path, Query, State,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use axum_extra::extract::CookieJar;
+use utoipa::OpenApi;
+
+use crate::{
+    application::{
+        port::link_ticket_repository::LinkTicketCacheRepo,
+        service::{
+            oauth_service::OAuthService, oidc_service::OidcService, user_service::UserService,
+        },
+    },
+    domain::{
+        idp::supported_idp::SupportIdp,
+        oauth::link_ticket::{LinkTicket, LINK_TICKET_TTL},
+    },
+    infrastructure::{
+        cache::memcached::repository::CacheRepoMchd,
+        persistence::postgres::repository::DatabaseRepoPg,
+    },
+    interface::web::{
+        auth::{active_user, AuthenticatedUser},
+        dto::{
+            error_response::ErrorResponse, idp_path::IdpPathParam, link_query::LinkQuery,
+            link_ticket_response::LinkTicketResponse, login_query::LoginQuery,
+        },
+        error::WebError,
+        state::{auth_session_cookie::AuthSessionCookieManager, login_redirect::LoginRedirect},
+        v1::oauth::login::{start_login, LoginPurpose},
+    },
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/oauth/{idp}/link",
+    tag = "OAuth",
+    operation_id = "oauthLinkTicket",
+    params(IdpPathParam, LoginQuery),
+    responses(
+        (status = 200, description = "One-time url for the browser to navigate to, it starts linking an identity of the IdP to the caller", body = LinkTicketResponse),
+        (status = 400, description = "`return_to` is not on an allowed origin", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "The user has been deactivated", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_link_ticket(
+    path: Result<Path<SupportIdp>, PathRejection>,
+    query: Result<Query<LoginQuery>, QueryRejection>,
+    user: AuthenticatedUser,
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    State(oidc_service): State<OidcService<CacheRepoMchd, DatabaseRepoPg>>,
+    State(cache_service): State<CacheRepoMchd>,
+    State(login_redirect): State<LoginRedirect>,
+) -> Result<Response, WebError> {
+    let Path(idp) = path?;
+    let Query(query) = query?;
+    let user = active_user(&user_service, user.user_id()).await?;
+    let return_to = query
+        .return_to
+        .map(|return_to| {
+            login_redirect
+                .check_return_to(&return_to)
+                .ok_or_else(|| WebError::BadRequest("return_to is not allowed".to_string()))
+        })
+        .transpose()?;
+
+    let ticket = LinkTicket::new(user.id, idp.clone(), return_to.map(String::from))
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    cache_service
+        .set_link_ticket(&ticket, LINK_TICKET_TTL)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(LinkTicketResponse {
+        link_url: format!(
+            "{}/api/v1/oauth/{}/link?ticket={}",
+            oidc_service.issuer().trim_end_matches('/'),
+            idp.as_str(),
+            ticket.ticket
+        ),
+        expires_in: LINK_TICKET_TTL,
+    })
+    .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/oauth/{idp}/link",
+    tag = "OAuth",
+    operation_id = "oauthLink",
+    params(IdpPathParam, LinkQuery),
+    responses(
+        (status = 302, description = "Redirect to Identity Provider, the callback links the identity signed in with to the user of the ticket",
+            headers(
+                ("Set-Cookie" = String, description = "Auth session cookie"),
+                ("Location" = String, description = "Redirect target URL to the IdP")
+            )
+        ),
+        (status = 400, description = "The ticket is unknown, expired, already used or for another IdP", body = ErrorResponse),
+        (status = 403, description = "The user has been deactivated", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn link(
+    path: Result<Path<SupportIdp>, PathRejection>,
+    query: Result<Query<LinkQuery>, QueryRejection>,
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    State(oauth_service): State<OAuthService>,
+    State(cache_service): State<CacheRepoMchd>,
+    State(auth_cookie_manager): State<AuthSessionCookieManager>,
+    State(login_redirect): State<LoginRedirect>,
+    cookie_jar: CookieJar,
+) -> Result<Response, WebError> {
+    let Path(idp) = path?;
+    let Query(query) = query?;
+    let ticket = cache_service
+        .take_link_ticket(&query.ticket)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?
+        .filter(|ticket| ticket.idp == idp)
+        .ok_or_else(|| WebError::BadRequest("invalid or expired link ticket".to_string()))?;
+    let user = active_user(&user_service, ticket.user_id).await?;
+    let return_to = ticket
+        .return_to
+        .as_deref()
+        .and_then(|return_to| login_redirect.check_return_to(return_to));
+
+    start_login(
+        idp,
+        LoginPurpose::Link {
+            user_id: user.id,
+            return_to,
+        },
+        &oauth_service,
+        &cache_service,
+        &auth_cookie_manager,
+        cookie_jar,
+    )
+    .await
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(create_link_ticket, link),
+    components(schemas(IdpPathParam, LoginQuery, LinkQuery, LinkTicketResponse))
+)]
+struct LinkOpenApi;
+
+pub fn gen_openapi_link() -> utoipa::openapi::OpenApi {
+    LinkOpenApi::openapi()
+}
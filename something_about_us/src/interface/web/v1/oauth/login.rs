@@ -1,21 +1,27 @@
 use axum::{
-    extract::{rejection::PathRejection, Path, State},
+    extract::{
+        rejection::{PathRejection, QueryRejection},
+        Path, Query, State,
+    },
     response::{IntoResponse, Redirect, Response},
 };
 use axum_extra::extract::CookieJar;
+use url::Url;
 use utoipa::OpenApi;
+use uuid::Uuid;
 
 use crate::{
     application::{
         port::auth_session_repository::AuthSessionCacheRepo, service::oauth_service::OAuthService,
     },
-    domain::idp::supported_idp::SupportIdp,
+    domain::{idp::supported_idp::SupportIdp, oauth::authorization_code::AuthorizationRequest},
     infrastructure::{
         cache::memcached::repository::CacheRepoMchd, cookie::AuthSessionCookieIssuer,
     },
     interface::web::{
-        dto::idp_path::IdpPathParam, error::WebError,
-        state::auth_session_cookie::AuthSessionCookieManager,
+        dto::{error_response::ErrorResponse, idp_path::IdpPathParam, login_query::LoginQuery},
+        error::WebError,
+        state::{auth_session_cookie::AuthSessionCookieManager, login_redirect::LoginRedirect},
     },
 };
 
@@ -24,7 +30,7 @@ use crate::{
     path = "/api/v1/oauth/{idp}/login",
     tag = "OAuth",
     operation_id = "oauthLogin",
-    params(IdpPathParam),
+    params(IdpPathParam, LoginQuery),
     responses(
         (status = 302, description = "Redirect to Identity Provider for authentication",
             headers(
@@ -32,25 +38,92 @@ use crate::{
                 ("Location" = String, description = "Redirect target URL to the IdP")
             )
         ),
-        (status = 500, description = "Internal Server Error", body = crate::interface::web::dto::error_response::ErrorResponse)
+        (status = 400, description = "`return_to` is not on an allowed origin", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
     )
 )]
 pub async fn login(
     path: Result<Path<SupportIdp>, PathRejection>,
+    query: Result<Query<LoginQuery>, QueryRejection>,
     State(oauth_service): State<OAuthService>,
     State(cache_service): State<CacheRepoMchd>,
     State(auth_cookie_manager): State<AuthSessionCookieManager>,
+    State(login_redirect): State<LoginRedirect>,
     cookie_jar: CookieJar,
 ) -> Result<Response, WebError> {
     let Path(idp) = path?;
+    let Query(query) = query?;
+    let return_to = query
+        .return_to
+        .map(|return_to| {
+            login_redirect
+                .check_return_to(&return_to)
+                .ok_or_else(|| WebError::BadRequest("return_to is not allowed".to_string()))
+        })
+        .transpose()?;
+
+    start_login(
+        idp,
+        LoginPurpose::Browser { return_to },
+        &oauth_service,
+        &cache_service,
+        &auth_cookie_manager,
+        cookie_jar,
+    )
+    .await
+}
+
+// what the callback does once the user is logged in, kept in the `AuthSession`
+pub enum LoginPurpose {
+    // tokens for the user, sent to `return_to` or answered as JSON
+    Browser {
+        return_to: Option<Url>,
+    },
+    // a code for the relying party that started the login at `/oidc/authorize`
+    Authorization(AuthorizationRequest),
+    // approval of the device whose user code was entered at `/oidc/device`
+    Device {
+        user_code: String,
+    },
+    // an identity for the signed-in user, who is sent back to `return_to` or answered with JSON
+    Link {
+        user_id: Uuid,
+        return_to: Option<Url>,
+    },
+}
 
-    let (authenticate_redirect_url, auth_session_info) = oauth_service
-        .login_call(idp)
+// redirects to the IdP
+pub async fn start_login(
+    idp: SupportIdp,
+    purpose: LoginPurpose,
+    oauth_service: &OAuthService,
+    cache_service: &CacheRepoMchd,
+    auth_cookie_manager: &AuthSessionCookieManager,
+    cookie_jar: CookieJar,
+) -> Result<Response, WebError> {
+    let (authenticate_redirect_url, mut auth_session_info) = oauth_service
+        .login_call(idp.clone())
         .await
         .map_err(|e| WebError::Auth(e.to_string()))?;
+    auth_session_info.idp = Some(idp);
+    match purpose {
+        LoginPurpose::Browser { return_to } => {
+            auth_session_info.return_to = return_to.map(String::from);
+        }
+        LoginPurpose::Authorization(authorization) => {
+            auth_session_info.authorization = Some(authorization);
+        }
+        LoginPurpose::Device { user_code } => {
+            auth_session_info.device_user_code = Some(user_code);
+        }
+        LoginPurpose::Link { user_id, return_to } => {
+            auth_session_info.link_user_id = Some(user_id);
+            auth_session_info.return_to = return_to.map(String::from);
+        }
+    }
 
     cache_service
-        .set_auth_session(&auth_session_info)
+        .set_auth_session(&auth_session_info, auth_cookie_manager.ttl())
         .await
         .map_err(|e| WebError::InternalServerError(e.to_string()))?;
 
@@ -65,7 +138,7 @@ pub async fn login(
 }
 
 #[derive(OpenApi)]
-#[openapi(paths(login), components(schemas(IdpPathParam)))]
+#[openapi(paths(login), components(schemas(IdpPathParam, LoginQuery)))]
 struct LoginOpenApi;
 
 pub fn gen_openapi_login() -> utoipa::openapi::OpenApi {
@@ -0,0 +1,63 @@
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::token_revocation_service::TokenRevocationService,
+    infrastructure::{
+        cache::memcached::repository::CacheRepoMchd,
+        persistence::postgres::repository::DatabaseRepoPg,
+    },
+    interface::web::{
+        auth::AuthenticatedUser,
+        dto::{error_response::ErrorResponse, logout_request::LogoutRequest},
+        error::WebError,
+    },
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/oauth/logout",
+    tag = "OAuth",
+    operation_id = "oauthLogout",
+    request_body(content = Option<LogoutRequest>, description = "Refresh token of the session to revoke as well"),
+    responses(
+        (status = 204, description = "Logged out. The access token and the given refresh token family are revoked"),
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn logout(
+    State(token_revocation_service): State<TokenRevocationService<CacheRepoMchd, DatabaseRepoPg>>,
+    user: AuthenticatedUser,
+    body: Result<Option<Json<LogoutRequest>>, JsonRejection>,
+) -> Result<Response, WebError> {
+    let body = body?;
+
+    token_revocation_service
+        .revoke_access_token(&user.claims)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    if let Some(refresh_token) = body.and_then(|Json(request)| request.refresh_token) {
+        token_revocation_service
+            .revoke_refresh_token(&refresh_token)
+            .await
+            .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    }
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(logout), components(schemas(LogoutRequest)))]
+struct LogoutOpenApi;
+
+pub fn gen_openapi_logout() -> utoipa::openapi::OpenApi {
+    LogoutOpenApi::openapi()
+}
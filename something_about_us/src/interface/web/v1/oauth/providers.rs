@@ -0,0 +1,45 @@
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::oauth_service::OAuthService,
+    interface::web::{
+        dto::idp_provider_response::{IdpProvider, IdpProviderList},
+        error::WebError,
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/oauth/providers",
+    tag = "OAuth",
+    operation_id = "oauthProviders",
+    responses(
+        (status = 200, description = "Identity providers configured in `[[providers]]`", body = IdpProviderList)
+    )
+)]
+pub async fn providers(State(oauth_service): State<OAuthService>) -> Result<Response, WebError> {
+    let providers = oauth_service
+        .registered_idps()
+        .iter()
+        .map(|registered| IdpProvider {
+            name: registered.idp.as_str().to_string(),
+            kind: registered.kind.to_string(),
+            login_url: format!("/api/v1/oauth/{}/login", registered.idp.as_str()),
+        })
+        .collect();
+
+    Ok(Json(IdpProviderList { providers }).into_response())
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(providers), components(schemas(IdpProviderList, IdpProvider)))]
+struct ProvidersOpenApi;
+
+pub fn gen_openapi_providers() -> utoipa::openapi::OpenApi {
+    ProvidersOpenApi::openapi()
+}
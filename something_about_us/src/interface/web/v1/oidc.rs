@@ -0,0 +1,29 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::interface::web::{
+    state::AppState,
+    v1::oidc::{
+        authorize::authorize,
+        device::{confirm_device, device_authorization, device_verification},
+        token::token,
+        userinfo::userinfo,
+    },
+};
+
+pub mod authorize;
+pub mod device;
+pub mod token;
+pub mod userinfo;
+
+pub async fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/authorize", get(authorize))
+        .route("/device", get(device_verification).post(confirm_device))
+        .route("/device/code", post(device_authorization))
+        .route("/token", post(token))
+        .route("/userinfo", get(userinfo))
+        .with_state(state)
+}
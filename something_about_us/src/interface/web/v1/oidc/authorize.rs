@@ -0,0 +1,118 @@
+use axum::{
+    extract::{rejection::QueryRejection, Query, State},
+    response::{IntoResponse, Redirect, Response},
+};
+use axum_extra::extract::CookieJar;
+use url::Url;
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::{
+        oauth_service::OAuthService,
+        oidc_service::{OidcService, OidcServiceError},
+    },
+    domain::idp::supported_idp::SupportIdp,
+    infrastructure::{
+        cache::memcached::repository::CacheRepoMchd,
+        persistence::postgres::repository::DatabaseRepoPg,
+    },
+    interface::web::{
+        dto::{authorize_query::AuthorizeQuery, error_response::ErrorResponse},
+        error::WebError,
+        state::auth_session_cookie::AuthSessionCookieManager,
+        v1::oauth::login::{start_login, LoginPurpose},
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/oidc/authorize",
+    tag = "OIDC",
+    operation_id = "oidcAuthorize",
+    params(AuthorizeQuery),
+    responses(
+        (status = 302, description = "Redirect to the Identity Provider, or back to the client with `error` when the request is invalid",
+            headers(
+                ("Set-Cookie" = String, description = "Auth session cookie"),
+                ("Location" = String, description = "Redirect target URL")
+            )
+        ),
+        (status = 400, description = "Unknown or disabled client, unregistered redirect uri or unknown idp", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    )
+)]
+pub async fn authorize(
+    query: Result<Query<AuthorizeQuery>, QueryRejection>,
+    State(oidc_service): State<OidcService<CacheRepoMchd, DatabaseRepoPg>>,
+    State(oauth_service): State<OAuthService>,
+    State(cache_service): State<CacheRepoMchd>,
+    State(auth_cookie_manager): State<AuthSessionCookieManager>,
+    cookie_jar: CookieJar,
+) -> Result<Response, WebError> {
+    let Query(query) = query?;
+    let idp = match query.idp.as_deref() {
+        Some(idp) => SupportIdp::try_from(idp).ok(),
+        None => oauth_service
+            .registered_idps()
+            .first()
+            .map(|registered| registered.idp.clone()),
+    };
+    let redirect_uri = query.redirect_uri.clone();
+    let state = query.state.clone();
+
+    let request = match oidc_service.authorize(query.into()).await {
+        Ok(request) => request,
+        // the redirect uri is not trusted, the error is shown to the user instead
+        Err(e @ (OidcServiceError::UnknownClient | OidcServiceError::InvalidRedirectUri)) => {
+            return Err(WebError::BadRequest(e.to_string()));
+        }
+        Err(e @ OidcServiceError::Repository(_)) => {
+            return Err(WebError::InternalServerError(e.to_string()));
+        }
+        Err(e) => return Ok(error_redirect(&redirect_uri, &e, state.as_deref())?.into_response()),
+    };
+    let idp = idp.ok_or_else(|| WebError::BadRequest("unknown idp".to_string()))?;
+
+    start_login(
+        idp,
+        LoginPurpose::Authorization(request),
+        &oauth_service,
+        &cache_service,
+        &auth_cookie_manager,
+        cookie_jar,
+    )
+    .await
+}
+
+// RFC 6749 4.1.2.1, the error goes back to the already checked redirect uri
+fn error_redirect(
+    redirect_uri: &str,
+    error: &OidcServiceError,
+    state: Option<&str>,
+) -> Result<Redirect, WebError> {
+    let code = match error {
+        OidcServiceError::UnsupportedResponseType => "unsupported_response_type",
+        OidcServiceError::InvalidScope => "invalid_scope",
+        OidcServiceError::UnauthorizedClient => "unauthorized_client",
+        _ => "invalid_request",
+    };
+    let mut redirect_uri =
+        Url::parse(redirect_uri).map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    {
+        let mut query = redirect_uri.query_pairs_mut();
+        query.append_pair("error", code);
+        query.append_pair("error_description", &error.to_string());
+        if let Some(state) = state {
+            query.append_pair("state", state);
+        }
+    }
+    Ok(Redirect::to(redirect_uri.as_str()))
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(authorize), components(schemas(AuthorizeQuery)))]
+struct AuthorizeOpenApi;
+
+pub fn gen_openapi_authorize() -> utoipa::openapi::OpenApi {
+    AuthorizeOpenApi::openapi()
+}
@@ -0,0 +1,321 @@
+use axum::{
+    extract::{
+        rejection::{FormRejection, QueryRejection},
+        Query, State,
+    },
+    http::{
+        header::{CACHE_CONTROL, X_FRAME_OPTIONS},
+        HeaderMap,
+    },
+    response::{Html, IntoResponse, Response},
+    Form, Json,
+};
+use axum_extra::extract::CookieJar;
+use url::Url;
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::{
+        oauth_service::OAuthService,
+        oidc_service::{OidcService, OidcServiceError},
+    },
+    domain::{
+        idp::supported_idp::SupportIdp,
+        oauth::{
+            constant_time::constant_time_eq,
+            device_authorization::{DeviceAuthorization, DEVICE_CONFIRMATION_COOKIE_NAME},
+        },
+    },
+    infrastructure::{
+        cache::memcached::repository::CacheRepoMchd,
+        persistence::postgres::repository::DatabaseRepoPg,
+    },
+    interface::web::{
+        auth::basic_credentials,
+        dto::{
+            device_authorization_request::DeviceAuthorizationRequest,
+            device_authorization_response::DeviceAuthorizationResponse,
+            device_confirmation_form::DeviceConfirmationForm,
+            device_verification_query::DeviceVerificationQuery, error_response::ErrorResponse,
+            oauth_error_response::OAuthErrorResponse,
+        },
+        error::WebError,
+        state::auth_session_cookie::AuthSessionCookieManager,
+        v1::oauth::login::{start_login, LoginPurpose},
+    },
+};
+
+// asks for the user code when the user opened the bare verification uri
+const USER_CODE_FORM_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>Connect a device</title></head>
+<body>
+<form method="get">
+<label for="user_code">Enter the code shown on your device</label>
+<input id="user_code" name="user_code" autocomplete="off" autofocus required>
+<button type="submit">Continue</button>
+</form>
+</body>
+</html>"#;
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/oidc/device/code",
+    tag = "OIDC",
+    operation_id = "oidcDeviceAuthorization",
+    request_body(content = DeviceAuthorizationRequest, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Device code to poll `/api/v1/oidc/token` with and the user code to show to the user", body = DeviceAuthorizationResponse),
+        (status = 400, description = "RFC 6749 error, e.g. `unauthorized_client` when the client may not use the device code grant", body = OAuthErrorResponse),
+        (status = 401, description = "`invalid_client`, the client is unknown or disabled or its credentials are wrong", body = OAuthErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security((), ("introspection_basic" = []))
+)]
+pub async fn device_authorization(
+    State(oidc_service): State<OidcService<CacheRepoMchd, DatabaseRepoPg>>,
+    headers: HeaderMap,
+    form: Result<Form<DeviceAuthorizationRequest>, FormRejection>,
+) -> Result<Response, WebError> {
+    let Form(request) = form.map_err(|e| WebError::oauth("invalid_request", e))?;
+    let (client_id, client_secret) = match basic_credentials(&headers) {
+        Some((client_id, client_secret)) => (Some(client_id), Some(client_secret)),
+        None => (request.client_id, request.client_secret),
+    };
+    let client_id =
+        client_id.ok_or_else(|| WebError::oauth("invalid_client", "client_id is required"))?;
+
+    let (device_code, authorization) = oidc_service
+        .start_device_authorization(
+            &client_id,
+            client_secret.as_deref(),
+            request.scope.as_deref(),
+        )
+        .await
+        .map_err(|e| match e {
+            OidcServiceError::InvalidClient => WebError::oauth("invalid_client", e),
+            OidcServiceError::UnauthorizedClient => WebError::oauth("unauthorized_client", e),
+            OidcServiceError::InvalidScope => WebError::oauth("invalid_scope", e),
+            _ => WebError::InternalServerError(e.to_string()),
+        })?;
+
+    let verification_uri = oidc_service.device_verification_uri();
+    let mut verification_uri_complete =
+        Url::parse(&verification_uri).map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    verification_uri_complete
+        .query_pairs_mut()
+        .append_pair("user_code", &authorization.user_code);
+
+    let response = DeviceAuthorizationResponse {
+        device_code,
+        expires_in: authorization.remaining_ttl().unwrap_or_default(),
+        interval: authorization.interval,
+        user_code: authorization.user_code,
+        verification_uri,
+        verification_uri_complete: verification_uri_complete.to_string(),
+    };
+    Ok(([(CACHE_CONTROL, "no-store")], Json(response)).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/oidc/device",
+    tag = "OIDC",
+    operation_id = "oidcDeviceVerification",
+    params(DeviceVerificationQuery),
+    responses(
+        (status = 200, description = "HTML form asking for the user code, or with `user_code` a page showing the client and the code that asks to confirm the device before logging in",
+            content_type = "text/html", body = String,
+            headers(
+                ("Set-Cookie" = String, description = "Device confirmation cookie, with `user_code`")
+            )
+        ),
+        (status = 400, description = "The user code is invalid, expired or already used, or the idp is unknown", body = ErrorResponse),
+        (status = 429, description = "Too many wrong user codes were entered lately, every user code is refused for up to a minute", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    )
+)]
+pub async fn device_verification(
+    query: Result<Query<DeviceVerificationQuery>, QueryRejection>,
+    State(oidc_service): State<OidcService<CacheRepoMchd, DatabaseRepoPg>>,
+    State(oauth_service): State<OAuthService>,
+    State(auth_cookie_manager): State<AuthSessionCookieManager>,
+    cookie_jar: CookieJar,
+) -> Result<Response, WebError> {
+    let Query(query) = query?;
+    let Some(user_code) = query.user_code.filter(|code| !code.trim().is_empty()) else {
+        return Ok(Html(USER_CODE_FORM_PAGE).into_response());
+    };
+
+    // RFC 8628 5.4, a link with the code of someone else's device must not lead straight to the login
+    let authorization = pending_authorization(&oidc_service, &user_code).await?;
+    let idp = device_idp(&oauth_service, query.idp.as_deref())?;
+    let confirmation = DeviceAuthorization::generate_confirmation_token()
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    let page = device_confirmation_page(&authorization, &idp, &confirmation);
+    let cookie_jar =
+        cookie_jar.add(auth_cookie_manager.issue_device_confirmation_cookie(confirmation));
+
+    Ok((
+        cookie_jar,
+        [(CACHE_CONTROL, "no-store"), (X_FRAME_OPTIONS, "DENY")],
+        page,
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/oidc/device",
+    tag = "OIDC",
+    operation_id = "oidcDeviceConfirmation",
+    request_body(content = DeviceConfirmationForm, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 302, description = "Redirect to the Identity Provider, the device is approved for the user once the login completes",
+            headers(
+                ("Set-Cookie" = String, description = "Auth session cookie"),
+                ("Location" = String, description = "Redirect target URL to the IdP")
+            )
+        ),
+        (status = 400, description = "The user code is invalid, expired or already used, or the idp is unknown", body = ErrorResponse),
+        (status = 429, description = "Too many wrong user codes were entered lately, every user code is refused for up to a minute", body = ErrorResponse),
+        (status = 403, description = "The form was not posted from the confirmation page in this browser", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    )
+)]
+pub async fn confirm_device(
+    State(oidc_service): State<OidcService<CacheRepoMchd, DatabaseRepoPg>>,
+    State(oauth_service): State<OAuthService>,
+    State(cache_service): State<CacheRepoMchd>,
+    State(auth_cookie_manager): State<AuthSessionCookieManager>,
+    cookie_jar: CookieJar,
+    form: Result<Form<DeviceConfirmationForm>, FormRejection>,
+) -> Result<Response, WebError> {
+    let Form(form) = form?;
+
+    // a cross-site page can post the form but neither send nor read the strict cookie
+    let confirmation_cookie = cookie_jar
+        .get(DEVICE_CONFIRMATION_COOKIE_NAME)
+        .ok_or_else(|| WebError::Forbidden("device confirmation is not found".to_string()))?;
+    if !constant_time_eq(
+        confirmation_cookie.value().as_bytes(),
+        form.confirmation.as_bytes(),
+    ) {
+        return Err(WebError::Forbidden(
+            "device confirmation is invalid".to_string(),
+        ));
+    }
+    let cookie_jar =
+        cookie_jar.remove(auth_cookie_manager.issue_device_confirmation_cookie(String::new()));
+
+    let authorization = pending_authorization(&oidc_service, &form.user_code).await?;
+    let idp = device_idp(&oauth_service, form.idp.as_deref())?;
+
+    start_login(
+        idp,
+        LoginPurpose::Device {
+            user_code: authorization.user_code,
+        },
+        &oauth_service,
+        &cache_service,
+        &auth_cookie_manager,
+        cookie_jar,
+    )
+    .await
+}
+
+async fn pending_authorization(
+    oidc_service: &OidcService<CacheRepoMchd, DatabaseRepoPg>,
+    user_code: &str,
+) -> Result<DeviceAuthorization, WebError> {
+    oidc_service
+        .find_device_authorization(user_code)
+        .await
+        .map_err(|e| match e {
+            OidcServiceError::InvalidUserCode => WebError::BadRequest(e.to_string()),
+            OidcServiceError::TooManyUserCodeAttempts => WebError::TooManyRequests(e.to_string()),
+            _ => WebError::InternalServerError(e.to_string()),
+        })
+}
+
+// `idp` picks the upstream provider like at `/authorize`, the first configured one otherwise
+fn device_idp(oauth_service: &OAuthService, idp: Option<&str>) -> Result<SupportIdp, WebError> {
+    match idp {
+        Some(idp) => SupportIdp::try_from(idp).ok(),
+        None => oauth_service
+            .registered_idps()
+            .first()
+            .map(|registered| registered.idp.clone()),
+    }
+    .ok_or_else(|| WebError::BadRequest("unknown idp".to_string()))
+}
+
+// the client id is restricted to `[A-Za-z0-9._-]` and the user code is normalized,
+// the scope and idp are escaped
+fn device_confirmation_page(
+    authorization: &DeviceAuthorization,
+    idp: &SupportIdp,
+    confirmation: &str,
+) -> Html<String> {
+    Html(format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>Connect a device</title></head>
+<body>
+<p><code>{client_id}</code> asks to sign in as you on a device showing the code <strong>{user_code}</strong> with the scope <code>{scope}</code>.</p>
+<p>Only continue if you started this yourself and your device shows this code.</p>
+<form method="post">
+<input type="hidden" name="user_code" value="{user_code}">
+<input type="hidden" name="idp" value="{idp}">
+<input type="hidden" name="confirmation" value="{confirmation}">
+<button type="submit">Connect the device</button>
+</form>
+</body>
+</html>"#,
+        client_id = authorization.client_id,
+        user_code = authorization.user_code,
+        scope = escape_html(&authorization.scope),
+        idp = escape_html(idp.as_str()),
+        confirmation = confirmation,
+    ))
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// shown at the end of the login, the client id is restricted to `[A-Za-z0-9._-]` and needs no escaping
+pub fn device_approved_page(client_id: &str) -> Html<String> {
+    Html(format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>Device connected</title></head>
+<body>
+<p>You are signed in on the device of <code>{}</code>. You can close this page and return to it.</p>
+</body>
+</html>"#,
+        client_id
+    ))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(device_authorization, device_verification, confirm_device),
+    components(schemas(
+        DeviceAuthorizationRequest,
+        DeviceAuthorizationResponse,
+        DeviceConfirmationForm,
+        DeviceVerificationQuery,
+        OAuthErrorResponse
+    ))
+)]
+struct DeviceOpenApi;
+
+pub fn gen_openapi_device() -> utoipa::openapi::OpenApi {
+    DeviceOpenApi::openapi()
+}
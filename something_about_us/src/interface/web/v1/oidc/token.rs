@@ -0,0 +1,385 @@
+use axum::{
+    extract::{rejection::FormRejection, State},
+    http::{header::CACHE_CONTROL, HeaderMap},
+    response::{IntoResponse, Response},
+    Form, Json,
+};
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::{
+        jwt_service::JwtService,
+        oidc_service::{OidcService, OidcServiceError},
+        organization_service::OrganizationService,
+        refresh_token_service::{RefreshTokenService, RefreshTokenServiceError},
+        role_service::RoleService,
+        user_service::UserService,
+    },
+    domain::{
+        oauth::{
+            id_token::IdToken,
+            oidc_client::{GrantType, OidcClient},
+            sau_jwt_issuer::SAUJwtIssuer,
+        },
+        user::{role::UserGrants, sau_user::SAUUser},
+    },
+    infrastructure::{
+        cache::memcached::repository::CacheRepoMchd,
+        persistence::postgres::repository::DatabaseRepoPg,
+    },
+    interface::web::{
+        auth::{active_user, basic_credentials, user_grants},
+        dto::{
+            error_response::ErrorResponse, oauth_error_response::OAuthErrorResponse,
+            oidc_token_request::OidcTokenRequest, oidc_token_response::OidcTokenResponse,
+        },
+        error::WebError,
+    },
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/oidc/token",
+    tag = "OIDC",
+    operation_id = "oidcToken",
+    request_body(content = OidcTokenRequest, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Tokens issued for the client. `id_token` is only returned by the authorization code grant, `refresh_token` only to clients allowed the `refresh_token` grant and the client credentials grant returns a service token without it", body = OidcTokenResponse),
+        (status = 400, description = "RFC 6749 error, e.g. `invalid_grant` for an unknown, used or expired code or refresh token. The device code grant answers `authorization_pending` or `slow_down` until the user approved the device and `expired_token` once the device code is gone", body = OAuthErrorResponse),
+        (status = 401, description = "`invalid_client`, the client credentials or assertion are missing or wrong or the client is disabled", body = OAuthErrorResponse),
+        (status = 403, description = "The user the grant was issued to has been deactivated", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security((), ("introspection_basic" = []))
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn token(
+    State(oidc_service): State<OidcService<CacheRepoMchd, DatabaseRepoPg>>,
+    State(jwt_service): State<JwtService<SAUJwtIssuer>>,
+    State(refresh_token_service): State<RefreshTokenService<DatabaseRepoPg>>,
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    State(role_service): State<RoleService<DatabaseRepoPg>>,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    headers: HeaderMap,
+    form: Result<Form<OidcTokenRequest>, FormRejection>,
+) -> Result<Response, WebError> {
+    let Form(request) = form.map_err(|e| WebError::oauth("invalid_request", e))?;
+    let grant_type = GrantType::try_from(request.grant_type.as_str())
+        .map_err(|e| WebError::oauth("unsupported_grant_type", e))?;
+
+    // HTTP Basic takes precedence, public clients only send their `client_id`
+    let (client_id, client_secret) = match basic_credentials(&headers) {
+        Some((client_id, client_secret)) => (Some(client_id), Some(client_secret)),
+        None => (request.client_id.clone(), request.client_secret.clone()),
+    };
+    let client_id = client_id.as_deref();
+
+    let response = match grant_type {
+        GrantType::AuthorizationCode => {
+            authorization_code_grant(
+                request,
+                required_client_id(client_id)?,
+                client_secret.as_deref(),
+                &oidc_service,
+                &jwt_service,
+                &refresh_token_service,
+                &user_service,
+                &role_service,
+                &organization_service,
+            )
+            .await?
+        }
+        GrantType::RefreshToken => {
+            refresh_token_grant(
+                request,
+                required_client_id(client_id)?,
+                client_secret.as_deref(),
+                &oidc_service,
+                &jwt_service,
+                &refresh_token_service,
+                &user_service,
+                &role_service,
+                &organization_service,
+            )
+            .await?
+        }
+        GrantType::DeviceCode => {
+            device_code_grant(
+                request,
+                required_client_id(client_id)?,
+                client_secret.as_deref(),
+                &oidc_service,
+                &jwt_service,
+                &refresh_token_service,
+                &user_service,
+                &role_service,
+                &organization_service,
+            )
+            .await?
+        }
+        GrantType::ClientCredentials => {
+            client_credentials_grant(
+                request,
+                client_id,
+                client_secret.as_deref(),
+                &oidc_service,
+                &jwt_service,
+            )
+            .await?
+        }
+    };
+
+    Ok(([(CACHE_CONTROL, "no-store")], Json(response)).into_response())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn authorization_code_grant(
+    request: OidcTokenRequest,
+    client_id: &str,
+    client_secret: Option<&str>,
+    oidc_service: &OidcService<CacheRepoMchd, DatabaseRepoPg>,
+    jwt_service: &JwtService<SAUJwtIssuer>,
+    refresh_token_service: &RefreshTokenService<DatabaseRepoPg>,
+    user_service: &UserService<DatabaseRepoPg>,
+    role_service: &RoleService<DatabaseRepoPg>,
+    organization_service: &OrganizationService<DatabaseRepoPg>,
+) -> Result<OidcTokenResponse, WebError> {
+    let (Some(code), Some(redirect_uri), Some(code_verifier)) =
+        (request.code, request.redirect_uri, request.code_verifier)
+    else {
+        return Err(WebError::oauth(
+            "invalid_request",
+            "code, redirect_uri and code_verifier are required",
+        ));
+    };
+
+    let (authorization_code, client) = oidc_service
+        .exchange_code(
+            &code,
+            client_id,
+            client_secret,
+            &redirect_uri,
+            &code_verifier,
+        )
+        .await
+        .map_err(oauth_error)?;
+
+    let user = active_user(user_service, authorization_code.user_id).await?;
+    let grants = user_grants(role_service, organization_service, user.id).await?;
+    let id_token = jwt_service
+        .issue_id_token(&IdToken::new(&user, &authorization_code))
+        .map_err(|e| WebError::InternalServerError(format!("fail to issue id token: {}", e)))?;
+    let refresh_token = refresh_token_service
+        .issue_for_client(user.id, &client, &authorization_code.request.scope)
+        .await
+        .map_err(|e| {
+            WebError::InternalServerError(format!("fail to issue refresh token: {}", e))
+        })?;
+
+    token_response(
+        oidc_service,
+        jwt_service,
+        &client,
+        &user,
+        &grants,
+        refresh_token,
+        Some(id_token),
+        authorization_code.request.scope,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn refresh_token_grant(
+    request: OidcTokenRequest,
+    client_id: &str,
+    client_secret: Option<&str>,
+    oidc_service: &OidcService<CacheRepoMchd, DatabaseRepoPg>,
+    jwt_service: &JwtService<SAUJwtIssuer>,
+    refresh_token_service: &RefreshTokenService<DatabaseRepoPg>,
+    user_service: &UserService<DatabaseRepoPg>,
+    role_service: &RoleService<DatabaseRepoPg>,
+    organization_service: &OrganizationService<DatabaseRepoPg>,
+) -> Result<OidcTokenResponse, WebError> {
+    let refresh_token = request
+        .refresh_token
+        .ok_or_else(|| WebError::oauth("invalid_request", "refresh_token is required"))?;
+
+    let client = oidc_service
+        .authenticate_client(client_id, client_secret, GrantType::RefreshToken)
+        .await
+        .map_err(oauth_error)?;
+    let (successor, refresh_token) = refresh_token_service
+        .rotate(&refresh_token, Some(&client.client_id))
+        .await
+        .map_err(|e| match e {
+            RefreshTokenServiceError::Issue(_) | RefreshTokenServiceError::Repository(_) => {
+                WebError::InternalServerError(e.to_string())
+            }
+            _ => WebError::oauth("invalid_grant", e),
+        })?;
+
+    let user = active_user(user_service, successor.user_id).await?;
+    let grants = user_grants(role_service, organization_service, user.id).await?;
+    // families issued before the scope was stored answer what the client may be granted
+    let scope = successor
+        .scope
+        .unwrap_or_else(|| client.allowed_scopes.join(" "));
+    token_response(
+        oidc_service,
+        jwt_service,
+        &client,
+        &user,
+        &grants,
+        Some(refresh_token),
+        None,
+        scope,
+    )
+}
+
+// RFC 8628 3.4, polled by the device until the user approved it at the verification uri
+#[allow(clippy::too_many_arguments)]
+async fn device_code_grant(
+    request: OidcTokenRequest,
+    client_id: &str,
+    client_secret: Option<&str>,
+    oidc_service: &OidcService<CacheRepoMchd, DatabaseRepoPg>,
+    jwt_service: &JwtService<SAUJwtIssuer>,
+    refresh_token_service: &RefreshTokenService<DatabaseRepoPg>,
+    user_service: &UserService<DatabaseRepoPg>,
+    role_service: &RoleService<DatabaseRepoPg>,
+    organization_service: &OrganizationService<DatabaseRepoPg>,
+) -> Result<OidcTokenResponse, WebError> {
+    let device_code = request
+        .device_code
+        .ok_or_else(|| WebError::oauth("invalid_request", "device_code is required"))?;
+
+    let (user_id, authorization, client) = oidc_service
+        .exchange_device_code(&device_code, client_id, client_secret)
+        .await
+        .map_err(oauth_error)?;
+
+    let user = active_user(user_service, user_id).await?;
+    let grants = user_grants(role_service, organization_service, user.id).await?;
+    let refresh_token = refresh_token_service
+        .issue_for_client(user.id, &client, &authorization.scope)
+        .await
+        .map_err(|e| {
+            WebError::InternalServerError(format!("fail to issue refresh token: {}", e))
+        })?;
+
+    token_response(
+        oidc_service,
+        jwt_service,
+        &client,
+        &user,
+        &grants,
+        refresh_token,
+        None,
+        authorization.scope,
+    )
+}
+
+// service token of the client itself, `sub` is the client id and no user is involved.
+// a `private_key_jwt` assertion names the client, so `client_id` is optional then.
+async fn client_credentials_grant(
+    request: OidcTokenRequest,
+    client_id: Option<&str>,
+    client_secret: Option<&str>,
+    oidc_service: &OidcService<CacheRepoMchd, DatabaseRepoPg>,
+    jwt_service: &JwtService<SAUJwtIssuer>,
+) -> Result<OidcTokenResponse, WebError> {
+    let client = match request.client_assertion.as_deref() {
+        Some(assertion) => {
+            oidc_service
+                .authenticate_client_assertion(
+                    client_id,
+                    request.client_assertion_type.as_deref().unwrap_or_default(),
+                    assertion,
+                    GrantType::ClientCredentials,
+                )
+                .await
+        }
+        None => {
+            oidc_service
+                .authenticate_client(
+                    required_client_id(client_id)?,
+                    client_secret,
+                    GrantType::ClientCredentials,
+                )
+                .await
+        }
+    }
+    .map_err(oauth_error)?;
+    let scope = oidc_service
+        .client_credentials_scope(&client, request.scope.as_deref())
+        .map_err(oauth_error)?;
+
+    let expires_in = oidc_service.client_credentials_ttl();
+    let access_token = jwt_service
+        .issue_service_token(&client.client_id, &client.audience, &scope, expires_in)
+        .map_err(|e| WebError::InternalServerError(format!("fail to issue jwt: {}", e)))?;
+
+    Ok(OidcTokenResponse {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in,
+        refresh_token: None,
+        id_token: None,
+        scope,
+    })
+}
+
+fn required_client_id(client_id: Option<&str>) -> Result<&str, WebError> {
+    client_id.ok_or_else(|| WebError::oauth("invalid_client", "client_id is required"))
+}
+
+// access tokens carry the `aud` and lifetime registered for the client
+#[allow(clippy::too_many_arguments)]
+fn token_response(
+    oidc_service: &OidcService<CacheRepoMchd, DatabaseRepoPg>,
+    jwt_service: &JwtService<SAUJwtIssuer>,
+    client: &OidcClient,
+    user: &SAUUser,
+    grants: &UserGrants,
+    refresh_token: Option<String>,
+    id_token: Option<String>,
+    scope: String,
+) -> Result<OidcTokenResponse, WebError> {
+    let expires_in = oidc_service.access_token_ttl(client);
+    let access_token = jwt_service
+        .issue_for_audience(&user.id, &client.audience, expires_in, grants)
+        .map_err(|e| WebError::InternalServerError(format!("fail to issue jwt: {}", e)))?;
+
+    Ok(OidcTokenResponse {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in,
+        refresh_token,
+        id_token,
+        scope,
+    })
+}
+
+fn oauth_error(e: OidcServiceError) -> WebError {
+    match e {
+        OidcServiceError::InvalidClient => WebError::oauth("invalid_client", e),
+        OidcServiceError::UnauthorizedClient => WebError::oauth("unauthorized_client", e),
+        OidcServiceError::InvalidScope => WebError::oauth("invalid_scope", e),
+        OidcServiceError::InvalidGrant(_) => WebError::oauth("invalid_grant", e),
+        OidcServiceError::AuthorizationPending => WebError::oauth("authorization_pending", e),
+        OidcServiceError::SlowDown => WebError::oauth("slow_down", e),
+        OidcServiceError::ExpiredToken => WebError::oauth("expired_token", e),
+        _ => WebError::InternalServerError(e.to_string()),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(token),
+    components(schemas(OidcTokenRequest, OidcTokenResponse, OAuthErrorResponse))
+)]
+struct TokenOpenApi;
+
+pub fn gen_openapi_oidc_token() -> utoipa::openapi::OpenApi {
+    TokenOpenApi::openapi()
+}
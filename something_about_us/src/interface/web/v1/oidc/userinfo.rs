@@ -0,0 +1,46 @@
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::user_service::UserService,
+    infrastructure::persistence::postgres::repository::DatabaseRepoPg,
+    interface::web::{
+        auth::{active_user, AnyAudienceUser},
+        dto::{error_response::ErrorResponse, userinfo_response::UserInfoResponse},
+        error::WebError,
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/oidc/userinfo",
+    tag = "OIDC",
+    operation_id = "oidcUserInfo",
+    responses(
+        (status = 200, description = "Claims of the user the access token was issued to", body = UserInfoResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "The user has been deactivated", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn userinfo(
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    AnyAudienceUser(user): AnyAudienceUser,
+) -> Result<Response, WebError> {
+    let user = active_user(&user_service, user.user_id()).await?;
+
+    Ok(Json(UserInfoResponse::from(&user)).into_response())
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(userinfo), components(schemas(UserInfoResponse)))]
+struct UserInfoOpenApi;
+
+pub fn gen_openapi_userinfo() -> utoipa::openapi::OpenApi {
+    UserInfoOpenApi::openapi()
+}
@@ -0,0 +1,80 @@
+use axum::{
+    routing::{delete, get, post, put},
+    Router,
+};
+
+use crate::{
+    application::service::organization_service::OrganizationServiceError,
+    interface::web::{
+        error::WebError,
+        state::AppState,
+        v1::orgs::{
+            invitations::{
+                accept_invitation, create_invitation, list_invitations, revoke_invitation,
+            },
+            members::{list_members, remove_member, update_member_role},
+            organizations::{
+                create_organization, delete_organization, get_organization, list_organizations,
+                update_organization,
+            },
+            teams::{
+                add_team_member, create_team, delete_team, get_team, list_teams, remove_team_member,
+            },
+        },
+    },
+};
+
+pub mod invitations;
+pub mod members;
+pub mod organizations;
+pub mod teams;
+
+pub async fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/", get(list_organizations).post(create_organization))
+        .route("/invitations/accept", post(accept_invitation))
+        .route(
+            "/{slug}",
+            get(get_organization)
+                .patch(update_organization)
+                .delete(delete_organization),
+        )
+        .route("/{slug}/members", get(list_members))
+        .route(
+            "/{slug}/members/{user_id}",
+            put(update_member_role).delete(remove_member),
+        )
+        .route("/{slug}/teams", get(list_teams).post(create_team))
+        .route("/{slug}/teams/{team}", get(get_team).delete(delete_team))
+        .route(
+            "/{slug}/teams/{team}/members/{user_id}",
+            put(add_team_member).delete(remove_team_member),
+        )
+        .route(
+            "/{slug}/invitations",
+            get(list_invitations).post(create_invitation),
+        )
+        .route(
+            "/{slug}/invitations/{invitation_id}",
+            delete(revoke_invitation),
+        )
+        .with_state(state)
+}
+
+// organizations the caller is not a member of are reported as not found
+fn organization_error(e: OrganizationServiceError) -> WebError {
+    match e {
+        OrganizationServiceError::InvalidOrganization(message) => WebError::BadRequest(message),
+        OrganizationServiceError::SlugTaken
+        | OrganizationServiceError::TeamSlugTaken
+        | OrganizationServiceError::LastOwner => WebError::Conflict(e.to_string()),
+        OrganizationServiceError::OrganizationNotFound
+        | OrganizationServiceError::TeamNotFound
+        | OrganizationServiceError::MemberNotFound
+        | OrganizationServiceError::InvitationNotFound => WebError::NotFound(e.to_string()),
+        OrganizationServiceError::Forbidden | OrganizationServiceError::InvitationNotForUser => {
+            WebError::Forbidden(e.to_string())
+        }
+        OrganizationServiceError::Repository(_) => WebError::InternalServerError(e.to_string()),
+    }
+}
@@ -0,0 +1,215 @@
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection},
+        Path, State,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::{
+    application::service::{organization_service::OrganizationService, user_service::UserService},
+    domain::{
+        idp::supported_idp::SupportIdp,
+        organization::{invitation::InvitationTarget, sau_organization::MemberRole},
+        user::sau_user::Email,
+    },
+    infrastructure::persistence::postgres::repository::DatabaseRepoPg,
+    interface::web::{
+        auth::{active_user, AuthenticatedUser},
+        dto::{
+            error_response::ErrorResponse,
+            organization_request::{AcceptInvitationRequest, CreateInvitationRequest},
+            organization_response::{
+                InvitationListResponse, InvitationResponse, OrganizationResponse,
+            },
+        },
+        error::WebError,
+        v1::orgs::organization_error,
+    },
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/orgs/{slug}/invitations",
+    tag = "Organizations",
+    operation_id = "createInvitation",
+    params(("slug" = String, Path, description = "Organization slug")),
+    request_body = CreateInvitationRequest,
+    responses(
+        (status = 201, description = "Invitation created, valid for 7 days. Its token is only returned here", body = InvitationResponse),
+        (status = 400, description = "Neither an email nor an idp username, or an unknown role", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin, or not an owner when inviting an owner", body = ErrorResponse),
+        (status = 404, description = "Organization not found or the caller is not a member", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_invitation(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+    body: Result<Json<CreateInvitationRequest>, JsonRejection>,
+) -> Result<Response, WebError> {
+    let Path(slug) = path?;
+    let Json(request) = body?;
+    let role = match request.role {
+        Some(role) => {
+            MemberRole::try_from(role).map_err(|e| WebError::BadRequest(e.to_string()))?
+        }
+        None => MemberRole::Member,
+    };
+    let target = match (request.email, request.idp, request.username) {
+        (Some(email), None, None) => InvitationTarget::Email(
+            Email::new(email).map_err(|e| WebError::BadRequest(e.to_string()))?,
+        ),
+        (None, Some(idp), Some(username)) => InvitationTarget::IdpUsername {
+            idp: SupportIdp::try_from(idp).map_err(|e| WebError::BadRequest(e.to_string()))?,
+            username,
+        },
+        _ => {
+            return Err(WebError::BadRequest(
+                "invite either by email or by idp and username".to_string(),
+            ))
+        }
+    };
+
+    let (token, invitation) = organization_service
+        .invite(user.user_id(), &slug, target, role)
+        .await
+        .map_err(organization_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(InvitationResponse {
+            token: Some(token),
+            ..InvitationResponse::from(&invitation)
+        }),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{slug}/invitations",
+    tag = "Organizations",
+    operation_id = "listInvitations",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Pending invitations, without their tokens", body = InvitationListResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin of the organization", body = ErrorResponse),
+        (status = 404, description = "Organization not found or the caller is not a member", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_invitations(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(slug) = path?;
+    let invitations = organization_service
+        .list_invitations(user.user_id(), &slug)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(Json(InvitationListResponse {
+        invitations: invitations.iter().map(InvitationResponse::from).collect(),
+    })
+    .into_response())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/orgs/{slug}/invitations/{invitation_id}",
+    tag = "Organizations",
+    operation_id = "revokeInvitation",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        ("invitation_id" = Uuid, Path, description = "Invitation id")
+    ),
+    responses(
+        (status = 204, description = "Invitation revoked"),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin of the organization", body = ErrorResponse),
+        (status = 404, description = "Organization or invitation not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn revoke_invitation(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<(String, Uuid)>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path((slug, invitation_id)) = path?;
+    organization_service
+        .revoke_invitation(user.user_id(), &slug, invitation_id)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/orgs/invitations/accept",
+    tag = "Organizations",
+    operation_id = "acceptInvitation",
+    request_body = AcceptInvitationRequest,
+    responses(
+        (status = 200, description = "Caller joined the organization, it shows up in the orgs claim of its next access token", body = OrganizationResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Invitation is for another email or idp username, or the user has been deactivated", body = ErrorResponse),
+        (status = 404, description = "Invitation is invalid, expired or already used", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn accept_invitation(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    body: Result<Json<AcceptInvitationRequest>, JsonRejection>,
+) -> Result<Response, WebError> {
+    let Json(request) = body?;
+    let user = active_user(&user_service, user.user_id()).await?;
+    let identities = user_service
+        .get_identities(user.id)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+
+    let (organization, role) = organization_service
+        .accept_invitation(&request.token, &user, &identities)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(Json(OrganizationResponse::new(&organization, role)).into_response())
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        create_invitation,
+        list_invitations,
+        revoke_invitation,
+        accept_invitation
+    ),
+    components(schemas(
+        CreateInvitationRequest,
+        AcceptInvitationRequest,
+        InvitationResponse,
+        InvitationListResponse
+    ))
+)]
+struct InvitationsOpenApi;
+
+pub fn gen_openapi_invitations() -> utoipa::openapi::OpenApi {
+    InvitationsOpenApi::openapi()
+}
@@ -0,0 +1,141 @@
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection},
+        Path, State,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::{
+    application::service::organization_service::OrganizationService,
+    domain::organization::sau_organization::MemberRole,
+    infrastructure::persistence::postgres::repository::DatabaseRepoPg,
+    interface::web::{
+        auth::AuthenticatedUser,
+        dto::{
+            error_response::ErrorResponse,
+            organization_request::UpdateMemberRoleRequest,
+            organization_response::{MemberListResponse, MemberResponse},
+        },
+        error::WebError,
+        v1::orgs::organization_error,
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{slug}/members",
+    tag = "Organizations",
+    operation_id = "listOrganizationMembers",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Members of the organization", body = MemberListResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 404, description = "Organization not found or the caller is not a member", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_members(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(slug) = path?;
+    let members = organization_service
+        .list_members(user.user_id(), &slug)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(Json(MemberListResponse {
+        members: members.iter().map(MemberResponse::from).collect(),
+    })
+    .into_response())
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/orgs/{slug}/members/{user_id}",
+    tag = "Organizations",
+    operation_id = "updateOrganizationMemberRole",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        ("user_id" = Uuid, Path, description = "User id of the member")
+    ),
+    request_body = UpdateMemberRoleRequest,
+    responses(
+        (status = 200, description = "Role changed, the member gets it with its next access token", body = MemberResponse),
+        (status = 400, description = "Unknown role", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin, or not an owner when ownership is involved", body = ErrorResponse),
+        (status = 404, description = "Organization or member not found", body = ErrorResponse),
+        (status = 409, description = "The organization would be left without an owner", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_member_role(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<(String, Uuid)>, PathRejection>,
+    body: Result<Json<UpdateMemberRoleRequest>, JsonRejection>,
+) -> Result<Response, WebError> {
+    let Path((slug, user_id)) = path?;
+    let Json(request) = body?;
+    let role =
+        MemberRole::try_from(request.role).map_err(|e| WebError::BadRequest(e.to_string()))?;
+    let member = organization_service
+        .change_member_role(user.user_id(), &slug, user_id, role)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(Json(MemberResponse::from(&member)).into_response())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/orgs/{slug}/members/{user_id}",
+    tag = "Organizations",
+    operation_id = "removeOrganizationMember",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        ("user_id" = Uuid, Path, description = "User id of the member, the caller's own id to leave")
+    ),
+    responses(
+        (status = 204, description = "Member removed from the organization and its teams"),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin, or not an owner when removing an owner", body = ErrorResponse),
+        (status = 404, description = "Organization or member not found", body = ErrorResponse),
+        (status = 409, description = "The organization would be left without an owner", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn remove_member(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<(String, Uuid)>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path((slug, user_id)) = path?;
+    organization_service
+        .remove_member(user.user_id(), &slug, user_id)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_members, update_member_role, remove_member),
+    components(schemas(UpdateMemberRoleRequest, MemberResponse, MemberListResponse))
+)]
+struct MembersOpenApi;
+
+pub fn gen_openapi_organization_members() -> utoipa::openapi::OpenApi {
+    MembersOpenApi::openapi()
+}
@@ -0,0 +1,201 @@
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection},
+        Path, State,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::organization_service::OrganizationService,
+    domain::organization::sau_organization::MemberRole,
+    infrastructure::persistence::postgres::repository::DatabaseRepoPg,
+    interface::web::{
+        auth::AuthenticatedUser,
+        dto::{
+            error_response::ErrorResponse,
+            organization_request::{CreateOrganizationRequest, UpdateOrganizationRequest},
+            organization_response::{OrganizationListResponse, OrganizationResponse},
+        },
+        error::WebError,
+        v1::orgs::organization_error,
+    },
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/orgs",
+    tag = "Organizations",
+    operation_id = "createOrganization",
+    request_body = CreateOrganizationRequest,
+    responses(
+        (status = 201, description = "Organization created, the caller is its owner", body = OrganizationResponse),
+        (status = 400, description = "Invalid slug or name", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 409, description = "Slug is already taken", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_organization(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    body: Result<Json<CreateOrganizationRequest>, JsonRejection>,
+) -> Result<Response, WebError> {
+    let Json(request) = body?;
+    let organization = organization_service
+        .create_organization(user.user_id(), request.slug, request.name)
+        .await
+        .map_err(organization_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(OrganizationResponse::new(&organization, MemberRole::Owner)),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs",
+    tag = "Organizations",
+    operation_id = "listOrganizations",
+    responses(
+        (status = 200, description = "Organizations the caller is a member of", body = OrganizationListResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_organizations(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+) -> Result<Response, WebError> {
+    let organizations = organization_service
+        .list_organizations(user.user_id())
+        .await
+        .map_err(organization_error)?;
+
+    Ok(Json(OrganizationListResponse {
+        organizations: organizations
+            .iter()
+            .map(|(organization, role)| OrganizationResponse::new(organization, *role))
+            .collect(),
+    })
+    .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{slug}",
+    tag = "Organizations",
+    operation_id = "getOrganization",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Organization", body = OrganizationResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 404, description = "Organization not found or the caller is not a member", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_organization(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(slug) = path?;
+    let (organization, role) = organization_service
+        .get_organization(user.user_id(), &slug)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(Json(OrganizationResponse::new(&organization, role)).into_response())
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/orgs/{slug}",
+    tag = "Organizations",
+    operation_id = "updateOrganization",
+    params(("slug" = String, Path, description = "Organization slug")),
+    request_body = UpdateOrganizationRequest,
+    responses(
+        (status = 200, description = "Organization renamed", body = OrganizationResponse),
+        (status = 400, description = "Invalid name", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin of the organization", body = ErrorResponse),
+        (status = 404, description = "Organization not found or the caller is not a member", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_organization(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+    body: Result<Json<UpdateOrganizationRequest>, JsonRejection>,
+) -> Result<Response, WebError> {
+    let Path(slug) = path?;
+    let Json(request) = body?;
+    let (organization, role) = organization_service
+        .rename_organization(user.user_id(), &slug, request.name)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(Json(OrganizationResponse::new(&organization, role)).into_response())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/orgs/{slug}",
+    tag = "Organizations",
+    operation_id = "deleteOrganization",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 204, description = "Organization deleted with its members, teams and invitations"),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an owner of the organization", body = ErrorResponse),
+        (status = 404, description = "Organization not found or the caller is not a member", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_organization(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(slug) = path?;
+    organization_service
+        .delete_organization(user.user_id(), &slug)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        create_organization,
+        list_organizations,
+        get_organization,
+        update_organization,
+        delete_organization
+    ),
+    components(schemas(
+        CreateOrganizationRequest,
+        UpdateOrganizationRequest,
+        OrganizationResponse,
+        OrganizationListResponse
+    ))
+)]
+struct OrganizationsOpenApi;
+
+pub fn gen_openapi_organizations() -> utoipa::openapi::OpenApi {
+    OrganizationsOpenApi::openapi()
+}
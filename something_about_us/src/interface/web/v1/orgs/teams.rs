@@ -0,0 +1,238 @@
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection},
+        Path, State,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::{
+    application::service::organization_service::OrganizationService,
+    infrastructure::persistence::postgres::repository::DatabaseRepoPg,
+    interface::web::{
+        auth::AuthenticatedUser,
+        dto::{
+            error_response::ErrorResponse,
+            organization_request::CreateTeamRequest,
+            organization_response::{TeamListResponse, TeamResponse},
+        },
+        error::WebError,
+        v1::orgs::organization_error,
+    },
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/orgs/{slug}/teams",
+    tag = "Organizations",
+    operation_id = "createTeam",
+    params(("slug" = String, Path, description = "Organization slug")),
+    request_body = CreateTeamRequest,
+    responses(
+        (status = 201, description = "Team created", body = TeamResponse),
+        (status = 400, description = "Invalid slug or name", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin of the organization", body = ErrorResponse),
+        (status = 404, description = "Organization not found or the caller is not a member", body = ErrorResponse),
+        (status = 409, description = "Team slug is already taken in the organization", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_team(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+    body: Result<Json<CreateTeamRequest>, JsonRejection>,
+) -> Result<Response, WebError> {
+    let Path(slug) = path?;
+    let Json(request) = body?;
+    let team = organization_service
+        .create_team(user.user_id(), &slug, request.slug, request.name)
+        .await
+        .map_err(organization_error)?;
+
+    Ok((StatusCode::CREATED, Json(TeamResponse::from(&team))).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{slug}/teams",
+    tag = "Organizations",
+    operation_id = "listTeams",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Teams of the organization", body = TeamListResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 404, description = "Organization not found or the caller is not a member", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_teams(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<String>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(slug) = path?;
+    let teams = organization_service
+        .list_teams(user.user_id(), &slug)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(Json(TeamListResponse {
+        teams: teams.iter().map(TeamResponse::from).collect(),
+    })
+    .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{slug}/teams/{team}",
+    tag = "Organizations",
+    operation_id = "getTeam",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        ("team" = String, Path, description = "Team slug")
+    ),
+    responses(
+        (status = 200, description = "Team with the user ids of its members", body = TeamResponse),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 404, description = "Organization or team not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_team(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<(String, String)>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path((slug, team)) = path?;
+    let (team, members) = organization_service
+        .get_team(user.user_id(), &slug, &team)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(Json(TeamResponse::with_members(&team, &members)).into_response())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/orgs/{slug}/teams/{team}",
+    tag = "Organizations",
+    operation_id = "deleteTeam",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        ("team" = String, Path, description = "Team slug")
+    ),
+    responses(
+        (status = 204, description = "Team deleted"),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin of the organization", body = ErrorResponse),
+        (status = 404, description = "Organization or team not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_team(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<(String, String)>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path((slug, team)) = path?;
+    organization_service
+        .delete_team(user.user_id(), &slug, &team)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/orgs/{slug}/teams/{team}/members/{user_id}",
+    tag = "Organizations",
+    operation_id = "addTeamMember",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        ("team" = String, Path, description = "Team slug"),
+        ("user_id" = Uuid, Path, description = "User id of a member of the organization")
+    ),
+    responses(
+        (status = 204, description = "User is in the team"),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin of the organization", body = ErrorResponse),
+        (status = 404, description = "Organization, team or member not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn add_team_member(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<(String, String, Uuid)>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path((slug, team, user_id)) = path?;
+    organization_service
+        .add_team_member(user.user_id(), &slug, &team, user_id)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/orgs/{slug}/teams/{team}/members/{user_id}",
+    tag = "Organizations",
+    operation_id = "removeTeamMember",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        ("team" = String, Path, description = "Team slug"),
+        ("user_id" = Uuid, Path, description = "User id of the team member")
+    ),
+    responses(
+        (status = 204, description = "User removed from the team"),
+        (status = 401, description = "Missing or invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin of the organization", body = ErrorResponse),
+        (status = 404, description = "Organization, team or team member not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn remove_team_member(
+    user: AuthenticatedUser,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    path: Result<Path<(String, String, Uuid)>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path((slug, team, user_id)) = path?;
+    organization_service
+        .remove_team_member(user.user_id(), &slug, &team, user_id)
+        .await
+        .map_err(organization_error)?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        create_team,
+        list_teams,
+        get_team,
+        delete_team,
+        add_team_member,
+        remove_team_member
+    ),
+    components(schemas(CreateTeamRequest, TeamResponse, TeamListResponse))
+)]
+struct TeamsOpenApi;
+
+pub fn gen_openapi_teams() -> utoipa::openapi::OpenApi {
+    TeamsOpenApi::openapi()
+}
@@ -0,0 +1,153 @@
+use axum::{
+    extract::State,
+    http::{header::CACHE_CONTROL, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use axum_extra::extract::CookieJar;
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::{
+        jwt_service::JwtService, login_session_service::LoginSessionService,
+        organization_service::OrganizationService, role_service::RoleService,
+        user_service::UserService,
+    },
+    domain::oauth::sau_jwt_issuer::SAUJwtIssuer,
+    infrastructure::{
+        cache::memcached::repository::CacheRepoMchd,
+        persistence::postgres::repository::DatabaseRepoPg,
+    },
+    interface::web::{
+        auth::{active_user, user_grants, LoginSessionUser},
+        dto::{
+            error_response::ErrorResponse, login_session_response::LoginSessionResponse,
+            session_token_response::SessionTokenResponse,
+        },
+        error::WebError,
+        state::{login_session_cookie::LoginSessionCookieManager, AppState},
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/session",
+    tag = "Session",
+    operation_id = "getLoginSession",
+    responses(
+        (status = 200, description = "The session of the cookie with its CSRF token", body = LoginSessionResponse),
+        (status = 401, description = "No session cookie, or the session has ended", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("session_cookie" = []))
+)]
+async fn session(LoginSessionUser { session }: LoginSessionUser) -> Result<Response, WebError> {
+    let response = LoginSessionResponse {
+        user_id: session.user_id,
+        expires_in: session.remaining_ttl().unwrap_or_default(),
+        csrf_token: session.csrf_token,
+    };
+    Ok(([(CACHE_CONTROL, "no-store")], Json(response)).into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/session/token",
+    tag = "Session",
+    operation_id = "issueSessionToken",
+    params(("X-CSRF-Token" = String, Header, description = "`csrf_token` of the session")),
+    responses(
+        (status = 200, description = "Short-lived access token of the session user, nothing to refresh it with", body = SessionTokenResponse),
+        (status = 401, description = "No session cookie, or the session has ended", body = ErrorResponse),
+        (status = 403, description = "The CSRF token is missing or wrong, or the user has been deactivated", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("session_cookie" = []))
+)]
+async fn session_token(
+    State(login_session_service): State<LoginSessionService<CacheRepoMchd>>,
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    State(jwt_service): State<JwtService<SAUJwtIssuer>>,
+    State(role_service): State<RoleService<DatabaseRepoPg>>,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    LoginSessionUser { session }: LoginSessionUser,
+) -> Result<Response, WebError> {
+    // the session of a deactivated or deleted user ends here
+    let user = active_user(&user_service, session.user_id).await;
+    if let Err(WebError::UserInactive | WebError::Auth(_)) = user {
+        login_session_service
+            .delete(session.id)
+            .await
+            .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    }
+    user?;
+
+    let grants = user_grants(&role_service, &organization_service, session.user_id).await?;
+    let token_ttl = login_session_service.token_ttl();
+    let access_token = jwt_service
+        .issue_for_audience(
+            &session.user_id,
+            login_session_service.token_audience(),
+            token_ttl,
+            &grants,
+        )
+        .map_err(|e| WebError::InternalServerError(format!("fail to issue jwt: {}", e)))?;
+
+    let response = SessionTokenResponse {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in: token_ttl,
+    };
+    Ok(([(CACHE_CONTROL, "no-store")], Json(response)).into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/session/logout",
+    tag = "Session",
+    operation_id = "logoutSession",
+    params(("X-CSRF-Token" = String, Header, description = "`csrf_token` of the session")),
+    responses(
+        (status = 204, description = "The session is ended and its cookie removed",
+            headers(("Set-Cookie" = String, description = "Expired session cookie"))
+        ),
+        (status = 401, description = "No session cookie, or the session has ended", body = ErrorResponse),
+        (status = 403, description = "The CSRF token is missing or wrong", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("session_cookie" = []))
+)]
+async fn session_logout(
+    State(login_session_service): State<LoginSessionService<CacheRepoMchd>>,
+    State(login_session_cookie_manager): State<LoginSessionCookieManager>,
+    LoginSessionUser { session }: LoginSessionUser,
+    cookie_jar: CookieJar,
+) -> Result<Response, WebError> {
+    login_session_service
+        .delete(session.id)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+
+    let cookie_jar = cookie_jar.add(login_session_cookie_manager.removal_cookie());
+    Ok((cookie_jar, StatusCode::NO_CONTENT).into_response())
+}
+
+pub async fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/", get(session))
+        .route("/token", post(session_token))
+        .route("/logout", post(session_logout))
+        .with_state(state)
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(session, session_token, session_logout),
+    components(schemas(LoginSessionResponse, SessionTokenResponse))
+)]
+struct SessionOpenApi;
+
+pub fn gen_openapi_session() -> utoipa::openapi::OpenApi {
+    SessionOpenApi::openapi()
+}
@@ -0,0 +1,18 @@
+use axum::{routing::post, Router};
+
+use crate::interface::web::{
+    state::AppState,
+    v1::token::{introspect::introspect, refresh::refresh, revoke::revoke},
+};
+
+pub mod introspect;
+pub mod refresh;
+pub mod revoke;
+
+pub async fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/refresh", post(refresh))
+        .route("/revoke", post(revoke))
+        .route("/introspect", post(introspect))
+        .with_state(state)
+}
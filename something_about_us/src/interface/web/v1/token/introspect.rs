@@ -0,0 +1,111 @@
+use axum::{
+    extract::{rejection::FormRejection, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    Form, Json,
+};
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::{
+        jwt_service::JwtService,
+        oidc_client_service::{OidcClientService, OidcClientServiceError},
+        token_revocation_service::TokenRevocationService,
+        user_service::{UserService, UserServiceError},
+    },
+    domain::oauth::sau_jwt_issuer::SAUJwtIssuer,
+    infrastructure::{
+        cache::memcached::repository::CacheRepoMchd,
+        persistence::postgres::repository::DatabaseRepoPg,
+    },
+    interface::web::{
+        dto::{
+            error_response::ErrorResponse,
+            introspect_request::IntrospectRequest,
+            introspection_response::{IntrospectionOrg, IntrospectionResponse},
+        },
+        error::WebError,
+        state::introspection_client::IntrospectionClientAuth,
+    },
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/token/introspect",
+    tag = "Token",
+    operation_id = "tokenIntrospect",
+    request_body(content = IntrospectRequest, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Token state (RFC 7662), `active` is false for invalid, expired or revoked tokens, inactive users and disabled clients", body = IntrospectionResponse),
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+        (status = 401, description = "Client credentials of `[[security.introspection.clients]]` are missing or wrong", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("introspection_basic" = []))
+)]
+pub async fn introspect(
+    State(client_auth): State<IntrospectionClientAuth>,
+    State(jwt_service): State<JwtService<SAUJwtIssuer>>,
+    State(token_revocation_service): State<TokenRevocationService<CacheRepoMchd, DatabaseRepoPg>>,
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    State(oidc_client_service): State<OidcClientService<DatabaseRepoPg>>,
+    headers: HeaderMap,
+    form: Result<Form<IntrospectRequest>, FormRejection>,
+) -> Result<Response, WebError> {
+    client_auth
+        .authenticate(&headers)
+        .ok_or_else(|| WebError::Auth("invalid introspection client credentials".to_string()))?;
+    let Form(request) = form?;
+
+    // signature, `exp`, `nbf` and `iss`, tokens of every client audience are reported
+    let Ok(claims) = jwt_service.verify_any_audience(&request.token) else {
+        return introspect_service_token(&jwt_service, &oidc_client_service, &request.token).await;
+    };
+
+    let revoked = token_revocation_service
+        .is_revoked(&claims)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+    if revoked {
+        return Ok(Json(IntrospectionResponse::inactive()).into_response());
+    }
+
+    let response = match user_service.get_active_user(claims.sub).await {
+        Ok(user) => IntrospectionResponse::active(claims, &user),
+        Err(UserServiceError::UserInactive | UserServiceError::UserNotFound) => {
+            IntrospectionResponse::inactive()
+        }
+        Err(e) => return Err(WebError::InternalServerError(e.to_string())),
+    };
+
+    Ok(Json(response).into_response())
+}
+
+// service tokens of the client credentials grant stay active as long as their client is enabled
+async fn introspect_service_token(
+    jwt_service: &JwtService<SAUJwtIssuer>,
+    oidc_client_service: &OidcClientService<DatabaseRepoPg>,
+    token: &str,
+) -> Result<Response, WebError> {
+    let Ok(claims) = jwt_service.verify_service_token(token) else {
+        return Ok(Json(IntrospectionResponse::inactive()).into_response());
+    };
+
+    let response = match oidc_client_service.get(&claims.sub).await {
+        Ok(client) if client.is_active => IntrospectionResponse::active_service(claims),
+        Ok(_) | Err(OidcClientServiceError::ClientNotFound) => IntrospectionResponse::inactive(),
+        Err(e) => return Err(WebError::InternalServerError(e.to_string())),
+    };
+    Ok(Json(response).into_response())
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(introspect),
+    components(schemas(IntrospectRequest, IntrospectionResponse, IntrospectionOrg))
+)]
+struct IntrospectOpenApi;
+
+pub fn gen_openapi_introspect() -> utoipa::openapi::OpenApi {
+    IntrospectOpenApi::openapi()
+}
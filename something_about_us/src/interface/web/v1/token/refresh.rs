@@ -0,0 +1,85 @@
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::{
+        jwt_service::JwtService,
+        organization_service::OrganizationService,
+        refresh_token_service::{RefreshTokenService, RefreshTokenServiceError},
+        role_service::RoleService,
+        user_service::UserService,
+    },
+    domain::oauth::sau_jwt_issuer::SAUJwtIssuer,
+    infrastructure::persistence::postgres::repository::DatabaseRepoPg,
+    interface::web::{
+        auth::{active_user, user_grants},
+        dto::{
+            error_response::ErrorResponse, jwt_response::Token,
+            refresh_token_request::RefreshTokenRequest,
+        },
+        error::WebError,
+    },
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/token/refresh",
+    tag = "Token",
+    operation_id = "tokenRefresh",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "Refresh token rotated. New JWT access token and refresh token issued", body = Token),
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+        (status = 401, description = "Refresh token is invalid, expired, revoked or already used", body = ErrorResponse),
+        (status = 403, description = "The user has been deactivated", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    )
+)]
+pub async fn refresh(
+    State(jwt_issuer): State<JwtService<SAUJwtIssuer>>,
+    State(refresh_token_service): State<RefreshTokenService<DatabaseRepoPg>>,
+    State(role_service): State<RoleService<DatabaseRepoPg>>,
+    State(organization_service): State<OrganizationService<DatabaseRepoPg>>,
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    body: Result<Json<RefreshTokenRequest>, JsonRejection>,
+) -> Result<Response, WebError> {
+    let Json(request) = body?;
+
+    let (successor, refresh_token) = refresh_token_service
+        .rotate(&request.refresh_token, None)
+        .await
+        .map_err(|e| match e {
+            RefreshTokenServiceError::Issue(_) | RefreshTokenServiceError::Repository(_) => {
+                WebError::InternalServerError(e.to_string())
+            }
+            _ => WebError::Auth(e.to_string()),
+        })?;
+    let user_id = successor.user_id;
+
+    // deactivation revokes the refresh tokens, this also covers one that slipped through
+    active_user(&user_service, user_id).await?;
+
+    // roles are read again, a changed assignment reaches the user with the next refresh
+    let grants = user_grants(&role_service, &organization_service, user_id).await?;
+    let jwt = jwt_issuer
+        .issue_with_id(&user_id, &grants)
+        .map_err(|e| WebError::InternalServerError(format!("fail to issue jwt: {}", e)))?;
+
+    Ok(Json(Token {
+        access_token: jwt,
+        refresh_token,
+    })
+    .into_response())
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(refresh), components(schemas(Token, RefreshTokenRequest)))]
+struct RefreshOpenApi;
+
+pub fn gen_openapi_refresh() -> utoipa::openapi::OpenApi {
+    RefreshOpenApi::openapi()
+}
@@ -0,0 +1,78 @@
+use axum::{
+    extract::{rejection::FormRejection, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Form,
+};
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::{
+        jwt_service::JwtService, token_revocation_service::TokenRevocationService,
+    },
+    domain::oauth::sau_jwt_issuer::SAUJwtIssuer,
+    infrastructure::{
+        cache::memcached::repository::CacheRepoMchd,
+        persistence::postgres::repository::DatabaseRepoPg,
+    },
+    interface::web::{
+        dto::{error_response::ErrorResponse, revoke_token_request::RevokeTokenRequest},
+        error::WebError,
+    },
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/token/revoke",
+    tag = "Token",
+    operation_id = "tokenRevoke",
+    request_body(content = RevokeTokenRequest, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Token revoked, also returned for unknown or expired tokens (RFC 7009)"),
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    )
+)]
+pub async fn revoke(
+    State(jwt_service): State<JwtService<SAUJwtIssuer>>,
+    State(token_revocation_service): State<TokenRevocationService<CacheRepoMchd, DatabaseRepoPg>>,
+    form: Result<Form<RevokeTokenRequest>, FormRejection>,
+) -> Result<Response, WebError> {
+    let Form(request) = form?;
+
+    if request.token_type_hint.as_deref() == Some("refresh_token")
+        && revoke_refresh_token(&token_revocation_service, &request.token).await?
+    {
+        return Ok(StatusCode::OK.into_response());
+    }
+
+    // a token that does not verify is not one of our access tokens
+    if let Ok(claims) = jwt_service.verify_any_audience(&request.token) {
+        token_revocation_service
+            .revoke_access_token(&claims)
+            .await
+            .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+        return Ok(StatusCode::OK.into_response());
+    }
+
+    revoke_refresh_token(&token_revocation_service, &request.token).await?;
+    Ok(StatusCode::OK.into_response())
+}
+
+async fn revoke_refresh_token(
+    token_revocation_service: &TokenRevocationService<CacheRepoMchd, DatabaseRepoPg>,
+    token: &str,
+) -> Result<bool, WebError> {
+    token_revocation_service
+        .revoke_refresh_token(token)
+        .await
+        .map_err(|e| WebError::InternalServerError(e.to_string()))
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(revoke), components(schemas(RevokeTokenRequest)))]
+struct RevokeOpenApi;
+
+pub fn gen_openapi_revoke() -> utoipa::openapi::OpenApi {
+    RevokeOpenApi::openapi()
+}
@@ -0,0 +1,23 @@
+use axum::{
+    routing::{delete, get},
+    Router,
+};
+
+use crate::interface::web::{
+    state::AppState,
+    v1::users::{
+        identities::{list_identities, unlink_identity},
+        me::{get_me, update_me},
+    },
+};
+
+pub mod identities;
+pub mod me;
+
+pub async fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/me", get(get_me).patch(update_me))
+        .route("/me/identities", get(list_identities))
+        .route("/me/identities/{identity_id}", delete(unlink_identity))
+        .with_state(state)
+}
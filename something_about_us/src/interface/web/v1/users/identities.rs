@@ -0,0 +1,100 @@
+use axum::{
+    extract::{rejection::PathRejection, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::{
+    application::service::user_service::{UserService, UserServiceError},
+    infrastructure::persistence::postgres::repository::DatabaseRepoPg,
+    interface::web::{
+        auth::{active_user, AuthenticatedUser},
+        dto::{
+            error_response::ErrorResponse,
+            user_identity_response::{UserIdentityListResponse, UserIdentityResponse},
+        },
+        error::WebError,
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/identities",
+    tag = "Users",
+    operation_id = "listMyIdentities",
+    responses(
+        (status = 200, description = "Identities linked to the caller, oldest first", body = UserIdentityListResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "The user has been deactivated", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_identities(
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    user: AuthenticatedUser,
+) -> Result<Response, WebError> {
+    let user = active_user(&user_service, user.user_id()).await?;
+    let identities = user_service
+        .get_identities(user.id)
+        .await
+        .map_err(identity_error)?;
+
+    Ok(Json(UserIdentityListResponse {
+        identities: identities.iter().map(UserIdentityResponse::from).collect(),
+    })
+    .into_response())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/me/identities/{identity_id}",
+    tag = "Users",
+    operation_id = "unlinkMyIdentity",
+    params(("identity_id" = Uuid, Path, description = "Identity id")),
+    responses(
+        (status = 204, description = "Identity unlinked, signing in with it creates a new user"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "The user has been deactivated", body = ErrorResponse),
+        (status = 404, description = "The caller has no such identity", body = ErrorResponse),
+        (status = 409, description = "The last identity of a user can not be unlinked", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn unlink_identity(
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    user: AuthenticatedUser,
+    path: Result<Path<Uuid>, PathRejection>,
+) -> Result<Response, WebError> {
+    let Path(identity_id) = path?;
+    let user = active_user(&user_service, user.user_id()).await?;
+    user_service
+        .unlink_identity(user.id, identity_id)
+        .await
+        .map_err(identity_error)?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+fn identity_error(e: UserServiceError) -> WebError {
+    match e {
+        UserServiceError::IdentityNotFound => WebError::NotFound(e.to_string()),
+        UserServiceError::LastIdentity => WebError::Conflict(e.to_string()),
+        _ => WebError::InternalServerError(e.to_string()),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_identities, unlink_identity),
+    components(schemas(UserIdentityListResponse, UserIdentityResponse))
+)]
+struct UserIdentitiesOpenApi;
+
+pub fn gen_openapi_user_identities() -> utoipa::openapi::OpenApi {
+    UserIdentitiesOpenApi::openapi()
+}
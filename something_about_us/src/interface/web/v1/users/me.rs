@@ -0,0 +1,89 @@
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::user_service::{UserService, UserServiceError},
+    infrastructure::persistence::postgres::repository::DatabaseRepoPg,
+    interface::web::{
+        auth::{active_user, AuthenticatedUser},
+        dto::{
+            error_response::ErrorResponse, update_user_request::UpdateUserRequest,
+            user_response::UserResponse,
+        },
+        error::WebError,
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me",
+    tag = "Users",
+    operation_id = "getMe",
+    responses(
+        (status = 200, description = "Profile of the user the access token was issued to", body = UserResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "The user has been deactivated", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_me(
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    user: AuthenticatedUser,
+) -> Result<Response, WebError> {
+    let user = active_user(&user_service, user.user_id()).await?;
+    Ok(Json(UserResponse::from(&user)).into_response())
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/users/me",
+    tag = "Users",
+    operation_id = "updateMe",
+    request_body = UpdateUserRequest,
+    responses(
+        (status = 200, description = "Updated profile", body = UserResponse),
+        (status = 400, description = "Invalid username or email", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "The user has been deactivated", body = ErrorResponse),
+        (status = 409, description = "The username or email is already used by another user", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_me(
+    State(user_service): State<UserService<DatabaseRepoPg>>,
+    user: AuthenticatedUser,
+    body: Result<Json<UpdateUserRequest>, JsonRejection>,
+) -> Result<Response, WebError> {
+    let Json(request) = body?;
+    let user = active_user(&user_service, user.user_id()).await?;
+
+    let user = user_service
+        .update_profile(user.id, request.username, request.email)
+        .await
+        .map_err(|e| match e {
+            UserServiceError::InvalidProfile(_) => WebError::BadRequest(e.to_string()),
+            UserServiceError::UsernameTaken | UserServiceError::EmailTaken => {
+                WebError::Conflict(e.to_string())
+            }
+            UserServiceError::UserNotFound => WebError::Auth(e.to_string()),
+            _ => WebError::InternalServerError(e.to_string()),
+        })?;
+    Ok(Json(UserResponse::from(&user)).into_response())
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_me, update_me),
+    components(schemas(UserResponse, UpdateUserRequest))
+)]
+struct UsersMeOpenApi;
+
+pub fn gen_openapi_users_me() -> utoipa::openapi::OpenApi {
+    UsersMeOpenApi::openapi()
+}
@@ -0,0 +1,59 @@
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use utoipa::OpenApi;
+
+use crate::{
+    application::service::{jwt_service::JwtService, oidc_service::OidcService},
+    domain::oauth::sau_jwt_issuer::SAUJwtIssuer,
+    infrastructure::{
+        cache::memcached::repository::CacheRepoMchd,
+        persistence::postgres::repository::DatabaseRepoPg,
+    },
+    interface::web::{
+        dto::openid_configuration::OpenIdConfiguration, error::WebError, state::AppState,
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/.well-known/openid-configuration",
+    tag = "OIDC",
+    operation_id = "getOpenIdConfiguration",
+    responses(
+        (status = 200, description = "OpenID Provider Metadata", body = OpenIdConfiguration)
+    )
+)]
+async fn openid_configuration(
+    State(oidc_service): State<OidcService<CacheRepoMchd, DatabaseRepoPg>>,
+    State(jwt_service): State<JwtService<SAUJwtIssuer>>,
+) -> Result<Response, WebError> {
+    // every published key can sign an id token during rotation
+    let mut algorithms = Vec::new();
+    for jwk in jwt_service.get_jwks().keys {
+        if let Some(algorithm) = jwk.common.key_algorithm.map(|alg| alg.to_string()) {
+            if !algorithms.contains(&algorithm) {
+                algorithms.push(algorithm);
+            }
+        }
+    }
+
+    Ok(Json(OpenIdConfiguration::new(oidc_service.issuer(), algorithms)).into_response())
+}
+
+pub async fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/openid-configuration", get(openid_configuration))
+        .with_state(state)
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(openid_configuration), components(schemas(OpenIdConfiguration)))]
+struct WellKnownOpenApi;
+
+pub fn gen_openapi_well_known() -> utoipa::openapi::OpenApi {
+    WellKnownOpenApi::openapi()
+}
@@ -4,7 +4,11 @@ use axum::Router;
 
 use crate::{
     application::service::{
-        jwt_service::JwtService, oauth_service::OAuthService, user_service::UserService,
+        jwt_service::JwtService, login_session_service::LoginSessionService,
+        oauth_service::OAuthService, oidc_client_service::OidcClientService,
+        oidc_service::OidcService, organization_service::OrganizationService,
+        refresh_token_service::RefreshTokenService, role_service::RoleService,
+        token_revocation_service::TokenRevocationService, user_service::UserService,
     },
     infrastructure::{
         self,
@@ -15,7 +19,11 @@ use crate::{
     },
     interface::web::{
         server::make_router,
-        state::{auth_session_cookie::AuthSessionCookieManager, AppState},
+        state::{
+            admin_access::AdminAccess, auth_session_cookie::AuthSessionCookieManager,
+            introspection_client::IntrospectionClientAuth, login_redirect::LoginRedirect,
+            login_session_cookie::LoginSessionCookieManager, AppState,
+        },
     },
 };
 
@@ -33,13 +41,48 @@ pub async fn test_make_route() -> Router {
     let cache_repo = CacheRepoMchd::new(memcached_connection_pool);
 
     // service
-    let jwt_issuer = Arc::new(JwtIssuerHelper::make_jwtissuer(&cfg.jwt).await);
-    let jwt_service = JwtService::new(jwt_issuer, cfg.jwt.keys[0].kid.clone());
-    let user_service = UserService::new(database_repo.clone());
-    let oauth_service = OAuthService::new(&cfg);
+    let (jwt_issuer, signing_kid) = JwtIssuerHelper::make_jwtissuer(&cfg.jwt).await;
+    let jwt_service = JwtService::new(Arc::new(jwt_issuer), signing_kid);
+    let user_service = UserService::new(database_repo.clone(), cfg.users.refresh_profile_on_login);
+    let role_service = RoleService::new(database_repo.clone());
+    let organization_service = OrganizationService::new(database_repo.clone(), cfg.jwt.orgs_claim);
+    let refresh_token_service =
+        RefreshTokenService::new(database_repo.clone(), cfg.jwt.refresh_token_ttl);
+    let token_revocation_service = TokenRevocationService::new(
+        cache_repo.clone(),
+        database_repo.clone(),
+        cfg.jwt.access_token_ttl,
+        cfg.jwt.leeway,
+    );
+    let oauth_service = OAuthService::new(&cfg.providers).await.unwrap();
+    let oidc_service = OidcService::new(
+        cache_repo.clone(),
+        database_repo.clone(),
+        cfg.jwt.iss.clone(),
+        cfg.jwt.access_token_ttl,
+        cfg.jwt.client_credentials_ttl,
+    );
+    let oidc_client_service =
+        OidcClientService::new(database_repo.clone(), cfg.jwt.access_token_ttl);
+    let login_session_service = LoginSessionService::new(
+        cache_repo.clone(),
+        cfg.security.login_session.enabled,
+        cfg.security.login_session.ttl,
+        cfg.security.login_session.token_ttl,
+        cfg.jwt.aud.clone(),
+    );
 
     // http cookie
     let auth_cookie_manager = AuthSessionCookieManager::from(&cfg.security.session);
+    let introspection_client_auth = IntrospectionClientAuth::from(&cfg.security.introspection);
+    let admin_access = AdminAccess::from(&cfg.security.admin);
+    let login_redirect = LoginRedirect::new(
+        &cfg.security.login_redirect,
+        &cfg.security.session,
+        &cfg.jwt,
+    );
+    let login_session_cookie_manager =
+        LoginSessionCookieManager::new(&cfg.security.session, &cfg.security.login_session);
 
     // http server state
     let http_server_state = AppState {
@@ -47,8 +90,19 @@ pub async fn test_make_route() -> Router {
         cache_repo,
         user_service,
         oauth_service,
+        oidc_service,
+        oidc_client_service,
         jwt_service,
+        refresh_token_service,
+        token_revocation_service,
         auth_cookie_manager,
+        introspection_client_auth,
+        admin_access,
+        login_redirect,
+        login_session_service,
+        login_session_cookie_manager,
+        role_service,
+        organization_service,
     };
 
     make_router(http_server_state).await